@@ -3,6 +3,7 @@
 //! Benchmarks cover:
 //!   - Tool dispatch (XML parsing, native parsing)
 //!   - Memory store/recall cycles (SQLite backend)
+//!   - Vector recall: exact cosine scan vs HNSW index
 //!   - Agent turn cycle (full orchestration loop)
 //!
 //! Run: `cargo bench`
@@ -17,6 +18,8 @@ use zeroclaw::agent::agent::Agent;
 use zeroclaw::agent::dispatcher::{NativeToolDispatcher, ToolDispatcher, XmlToolDispatcher};
 use zeroclaw::config::MemoryConfig;
 use zeroclaw::memory;
+use zeroclaw::memory::hnsw::HnswIndex;
+use zeroclaw::memory::vector::cosine_similarity;
 use zeroclaw::memory::{Memory, MemoryCategory};
 use zeroclaw::observability::{NoopObserver, Observer};
 use zeroclaw::providers::{ChatRequest, ChatResponse, Provider, ToolCall};
@@ -275,6 +278,57 @@ fn bench_memory_operations(c: &mut Criterion) {
     });
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Benchmark: Vector recall (exact scan vs HNSW)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Deterministic pseudo-random embeddings so runs are comparable.
+fn bench_vectors(count: usize, dims: usize, seed: u32) -> Vec<Vec<f32>> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            (0..dims)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    f32::from(u16::try_from(state % 1000).unwrap()) / 1000.0
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn bench_vector_recall(c: &mut Criterion) {
+    const ENTRIES: usize = 10_000;
+    const DIMS: usize = 384;
+
+    let data = bench_vectors(ENTRIES, DIMS, 0x1234_5678);
+    let query = bench_vectors(1, DIMS, 0x8765_4321).remove(0);
+
+    let mut index = HnswIndex::new();
+    for (i, v) in data.iter().enumerate() {
+        index.insert(&format!("m{i}"), "t", v);
+    }
+
+    c.bench_function("vector_recall_top10_exact_10k", |b| {
+        b.iter(|| {
+            let mut scored: Vec<(usize, f32)> = data
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i, cosine_similarity(black_box(&query), v)))
+                .collect();
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+            scored.truncate(10);
+            scored
+        });
+    });
+
+    c.bench_function("vector_recall_top10_hnsw_10k", |b| {
+        b.iter(|| index.search(black_box(&query), 10, 64));
+    });
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Benchmark: Full agent turn cycle
 // ─────────────────────────────────────────────────────────────────────────────
@@ -324,6 +378,7 @@ criterion_group!(
     bench_xml_parsing,
     bench_native_parsing,
     bench_memory_operations,
+    bench_vector_recall,
     bench_agent_turn,
 );
 criterion_main!(benches);
@@ -9,6 +9,7 @@ use crate::observability::{self, Observer, ObserverEvent};
 use crate::providers::{self, ChatMessage, ChatRequest, ConversationMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::sessions::{Transcript, TranscriptEvent};
 use crate::tools::{self, Tool, ToolSpec};
 use anyhow::Result;
 use std::collections::HashMap;
@@ -26,6 +27,7 @@ pub struct Agent {
     tool_dispatcher: Box<dyn ToolDispatcher>,
     memory_loader: Box<dyn MemoryLoader>,
     config: crate::config::AgentConfig,
+    provider_name: String,
     model_name: String,
     temperature: f64,
     workspace_dir: std::path::PathBuf,
@@ -37,6 +39,7 @@ pub struct Agent {
     classification_config: crate::config::QueryClassificationConfig,
     available_hints: Vec<String>,
     route_model_by_hint: HashMap<String, String>,
+    transcript: Option<Arc<Transcript>>,
 }
 
 pub struct AgentBuilder {
@@ -48,6 +51,7 @@ pub struct AgentBuilder {
     tool_dispatcher: Option<Box<dyn ToolDispatcher>>,
     memory_loader: Option<Box<dyn MemoryLoader>>,
     config: Option<crate::config::AgentConfig>,
+    provider_name: Option<String>,
     model_name: Option<String>,
     temperature: Option<f64>,
     workspace_dir: Option<std::path::PathBuf>,
@@ -71,6 +75,7 @@ impl AgentBuilder {
             tool_dispatcher: None,
             memory_loader: None,
             config: None,
+            provider_name: None,
             model_name: None,
             temperature: None,
             workspace_dir: None,
@@ -124,6 +129,11 @@ impl AgentBuilder {
         self
     }
 
+    pub fn provider_name(mut self, provider_name: String) -> Self {
+        self.provider_name = Some(provider_name);
+        self
+    }
+
     pub fn model_name(mut self, model_name: String) -> Self {
         self.model_name = Some(model_name);
         self
@@ -208,6 +218,7 @@ impl AgentBuilder {
                 .memory_loader
                 .unwrap_or_else(|| Box::new(DefaultMemoryLoader::default())),
             config: self.config.unwrap_or_default(),
+            provider_name: self.provider_name.unwrap_or_else(|| "openrouter".into()),
             model_name: self
                 .model_name
                 .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into()),
@@ -223,6 +234,7 @@ impl AgentBuilder {
             classification_config: self.classification_config.unwrap_or_default(),
             available_hints: self.available_hints.unwrap_or_default(),
             route_model_by_hint: self.route_model_by_hint.unwrap_or_default(),
+            transcript: None,
         })
     }
 }
@@ -240,6 +252,23 @@ impl Agent {
         self.history.clear();
     }
 
+    /// Replace the history with the system prompt followed by `messages`,
+    /// e.g. the turns of a resumed session transcript.
+    pub fn seed_history(&mut self, messages: Vec<ChatMessage>) -> Result<()> {
+        let system_prompt = self.build_system_prompt()?;
+        self.history = std::iter::once(ChatMessage::system(system_prompt))
+            .chain(messages)
+            .map(ConversationMessage::Chat)
+            .collect();
+        self.trim_history();
+        Ok(())
+    }
+
+    /// Record every subsequent turn into `transcript`.
+    pub fn set_transcript(&mut self, transcript: Arc<Transcript>) {
+        self.transcript = Some(transcript);
+    }
+
     pub fn from_config(config: &Config) -> Result<Self> {
         let observer: Arc<dyn Observer> =
             Arc::from(observability::create_observer(&config.observability));
@@ -293,14 +322,19 @@ impl Agent {
             .unwrap_or("anthropic/claude-sonnet-4-20250514")
             .to_string();
 
-        let provider: Box<dyn Provider> = providers::create_routed_provider(
-            provider_name,
-            config.api_key.as_deref(),
-            config.api_url.as_deref(),
-            &config.reliability,
-            &config.model_routes,
-            &model_name,
-        )?;
+        let provider: Box<dyn Provider> = providers::with_recording(
+            providers::create_routed_provider(
+                provider_name,
+                config.api_key.as_deref(),
+                config.api_url.as_deref(),
+                &config.reliability,
+                &config.model_routes,
+                &config.routing,
+                &model_name,
+            )?,
+            &config.observability,
+            &config.workspace_dir,
+        );
 
         let dispatcher_choice = config.agent.tool_dispatcher.as_str();
         let tool_dispatcher: Box<dyn ToolDispatcher> = match dispatcher_choice {
@@ -329,6 +363,7 @@ impl Agent {
             )))
             .prompt_builder(SystemPromptBuilder::with_defaults())
             .config(config.agent.clone())
+            .provider_name(provider_name.to_string())
             .model_name(model_name)
             .temperature(config.default_temperature)
             .workspace_dir(config.workspace_dir.clone())
@@ -389,32 +424,43 @@ impl Agent {
     async fn execute_tool_call(&self, call: &ParsedToolCall) -> ToolExecutionResult {
         let start = Instant::now();
 
-        let result = if let Some(tool) = self.tools.iter().find(|t| t.name() == call.name) {
-            match tool.execute(call.arguments.clone()).await {
-                Ok(r) => {
-                    self.observer.record_event(&ObserverEvent::ToolCall {
-                        tool: call.name.clone(),
-                        duration: start.elapsed(),
-                        success: r.success,
-                    });
-                    if r.success {
-                        r.output
-                    } else {
-                        format!("Error: {}", r.error.unwrap_or(r.output))
+        let (result, succeeded) =
+            if let Some(tool) = self.tools.iter().find(|t| t.name() == call.name) {
+                match tool.execute(call.arguments.clone()).await {
+                    Ok(r) => {
+                        self.observer.record_event(&ObserverEvent::ToolCall {
+                            tool: call.name.clone(),
+                            duration: start.elapsed(),
+                            success: r.success,
+                        });
+                        if r.success {
+                            (r.output, true)
+                        } else {
+                            (format!("Error: {}", r.error.unwrap_or(r.output)), false)
+                        }
+                    }
+                    Err(e) => {
+                        self.observer.record_event(&ObserverEvent::ToolCall {
+                            tool: call.name.clone(),
+                            duration: start.elapsed(),
+                            success: false,
+                        });
+                        (format!("Error executing {}: {e}", call.name), false)
                     }
                 }
-                Err(e) => {
-                    self.observer.record_event(&ObserverEvent::ToolCall {
-                        tool: call.name.clone(),
-                        duration: start.elapsed(),
-                        success: false,
-                    });
-                    format!("Error executing {}: {e}", call.name)
-                }
-            }
-        } else {
-            format!("Unknown tool: {}", call.name)
-        };
+            } else {
+                (format!("Unknown tool: {}", call.name), false)
+            };
+
+        if let Some(transcript) = &self.transcript {
+            transcript.record_tool_call(
+                &call.name,
+                &call.arguments,
+                &result,
+                succeeded,
+                start.elapsed(),
+            );
+        }
 
         ToolExecutionResult {
             name: call.name.clone(),
@@ -487,19 +533,25 @@ impl Agent {
             .unwrap_or_default();
 
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
-        let enriched = if context.is_empty() {
+        let mut enriched = if context.is_empty() {
             format!("[{now}] {user_message}")
         } else {
             format!("{context}[{now}] {user_message}")
         };
+        // History holds the screened text, so later iterations reuse it
+        super::loop_::screen_provider_content(&mut enriched, self.observer.as_ref())?;
 
         self.history
             .push(ConversationMessage::Chat(ChatMessage::user(enriched)));
+        if let Some(transcript) = &self.transcript {
+            transcript.record_message("user", user_message);
+        }
 
         let effective_model = self.classify_model(user_message);
 
         for _ in 0..self.config.max_tool_iterations {
             let messages = self.tool_dispatcher.to_provider_messages(&self.history);
+            let started = Instant::now();
             let response = match self
                 .provider
                 .chat(
@@ -517,10 +569,26 @@ impl Agent {
                 .await
             {
                 Ok(resp) => resp,
-                Err(err) => return Err(err),
+                Err(err) => {
+                    if let Some(transcript) = &self.transcript {
+                        transcript.record_error(&providers::sanitize_api_error(&err.to_string()));
+                    }
+                    return Err(err);
+                }
             };
 
             let (text, calls) = self.tool_dispatcher.parse_response(&response);
+            if let Some(transcript) = &self.transcript {
+                transcript.record(TranscriptEvent::ModelResponse {
+                    provider: self.provider_name.clone(),
+                    model: effective_model.clone(),
+                    text: response.text.clone().unwrap_or_default(),
+                    tool_calls: calls.len(),
+                    input_tokens: response.usage.as_ref().and_then(|u| u.input_tokens),
+                    output_tokens: response.usage.as_ref().and_then(|u| u.output_tokens),
+                    duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+                });
+            }
             if calls.is_empty() {
                 let final_text = if text.is_empty() {
                     response.text.unwrap_or_default()
@@ -533,6 +601,9 @@ impl Agent {
                         final_text.clone(),
                     )));
                 self.trim_history();
+                if let Some(transcript) = &self.transcript {
+                    transcript.record_message("assistant", &final_text);
+                }
 
                 return Ok(final_text);
             }
@@ -552,7 +623,10 @@ impl Agent {
                 reasoning_content: response.reasoning_content.clone(),
             });
 
-            let results = self.execute_tools(&calls).await;
+            let mut results = self.execute_tools(&calls).await;
+            for result in &mut results {
+                super::loop_::check_provider_tool_output(&result.output, self.observer.as_ref())?;
+            }
             let formatted = self.tool_dispatcher.format_results(&results);
             self.history.push(formatted);
             self.trim_history();
@@ -788,6 +862,60 @@ mod tests {
         assert_eq!(response, "hello");
     }
 
+    #[tokio::test]
+    async fn resumed_history_is_sent_and_new_turns_are_recorded() {
+        let provider = Box::new(MockProvider {
+            responses: Mutex::new(vec![crate::providers::ChatResponse {
+                text: Some("welcome back".into()),
+                tool_calls: vec![],
+                usage: None,
+                reasoning_content: None,
+            }]),
+        });
+        let memory_cfg = crate::config::MemoryConfig {
+            backend: "none".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> = Arc::from(
+            crate::memory::create_memory(&memory_cfg, std::path::Path::new("/tmp"), None)
+                .expect("memory creation should succeed with valid config"),
+        );
+        let observer: Arc<dyn Observer> = Arc::from(crate::observability::NoopObserver {});
+        let mut agent = Agent::builder()
+            .provider(provider)
+            .tools(vec![Box::new(MockTool)])
+            .memory(mem)
+            .observer(observer)
+            .tool_dispatcher(Box::new(XmlToolDispatcher))
+            .workspace_dir(std::path::PathBuf::from("/tmp"))
+            .build()
+            .expect("agent builder should succeed with valid config");
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = crate::sessions::SessionStore::new(tmp.path().to_path_buf(), 0);
+        let transcript = store.start("cli", None, None, "mock", "model").unwrap();
+        let id = transcript.id().to_string();
+
+        agent
+            .seed_history(vec![
+                ChatMessage::user("remember the number 7"),
+                ChatMessage::assistant("noted"),
+            ])
+            .unwrap();
+        agent.set_transcript(Arc::new(transcript));
+        assert_eq!(agent.history().len(), 3);
+        assert!(matches!(
+            &agent.history()[0],
+            ConversationMessage::Chat(msg) if msg.role == "system"
+        ));
+
+        assert_eq!(agent.turn("what was it?").await.unwrap(), "welcome back");
+        let history = crate::sessions::history_from_records(&store.load(&id).unwrap());
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].content, "what was it?");
+        assert_eq!(history[1].content, "welcome back");
+    }
+
     #[tokio::test]
     async fn turn_with_native_dispatcher_handles_tool_results_variant() {
         let provider = Box::new(MockProvider {
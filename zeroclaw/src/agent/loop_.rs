@@ -4,10 +4,11 @@ use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
 use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
 use crate::providers::{
-    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
+    self, ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilityError, ToolCall,
 };
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::sessions::{SessionStore, Transcript, TranscriptEvent};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
@@ -145,7 +146,7 @@ fn autosave_memory_key(prefix: &str) -> String {
 
 /// Trim conversation history to prevent unbounded growth.
 /// Preserves the system prompt (first message if role=system) and the most recent messages.
-fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
+pub(crate) fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
     // Nothing to trim if within limit
     let has_system = history.first().map_or(false, |m| m.role == "system");
     let non_system_count = if has_system {
@@ -187,7 +188,7 @@ fn apply_compaction_summary(
     history.splice(start..compact_end, std::iter::once(summary_msg));
 }
 
-async fn auto_compact_history(
+pub(crate) async fn auto_compact_history(
     history: &mut Vec<ChatMessage>,
     provider: &dyn Provider,
     model: &str,
@@ -1738,6 +1739,27 @@ fn parse_structured_tool_calls(tool_calls: &[ToolCall]) -> Vec<ParsedToolCall> {
         .collect()
 }
 
+/// Parse a provider response the way the tool loop does: native tool calls
+/// first, then the text dialects. Returns `(name, arguments)` pairs and the
+/// parse issue the loop would trace, if any.
+pub(crate) fn inspect_tool_calls(
+    response: &ChatResponse,
+) -> (Vec<(String, serde_json::Value)>, Option<String>) {
+    let text = response.text_or_empty();
+    let mut calls = parse_structured_tool_calls(&response.tool_calls);
+    if calls.is_empty() {
+        calls = parse_tool_calls(text).1;
+    }
+    let issue = detect_tool_call_parse_issue(text, &calls);
+    (
+        calls
+            .into_iter()
+            .map(|call| (call.name, call.arguments))
+            .collect(),
+        issue,
+    )
+}
+
 /// Build assistant history entry in JSON format for native tool-call APIs.
 /// `convert_messages` in the OpenRouter provider parses this JSON to reconstruct
 /// the proper `NativeMessage` with structured `tool_calls`.
@@ -1858,6 +1880,68 @@ pub(crate) fn is_tool_loop_cancelled(err: &anyhow::Error) -> bool {
     err.chain().any(|source| source.is::<ToolLoopCancelled>())
 }
 
+/// Run provider-bound messages through the outbound leak guard, redacting in
+/// place so later iterations and saved transcripts carry the redacted text.
+/// The system prompt is ours; user and tool content is what can carry secrets
+/// picked up from files or command output.
+fn screen_provider_bound<'a>(
+    messages: impl Iterator<Item = &'a mut ChatMessage>,
+    observer: &dyn Observer,
+) -> Result<()> {
+    if let Some(guard) = crate::security::outbound::global_guard() {
+        screen_messages(&guard, messages, observer)?;
+    }
+    Ok(())
+}
+
+/// Tool output is only checked, never rewritten: the model quotes it back in
+/// file edits, and a redacted copy would no longer match the file on disk.
+fn screen_messages<'a>(
+    guard: &crate::security::OutboundGuard,
+    messages: impl Iterator<Item = &'a mut ChatMessage>,
+    observer: &dyn Observer,
+) -> Result<()> {
+    let surface = crate::security::OutboundSurface::Provider;
+    for message in messages.filter(|message| message.role != "system") {
+        let screened = guard.screen(surface, &message.content, observer)?;
+        if let std::borrow::Cow::Owned(redacted) = screened {
+            if !is_tool_output(message) {
+                message.content = redacted;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_tool_output(message: &ChatMessage) -> bool {
+    message.role == "tool"
+        || (message.role == "user" && message.content.starts_with("[Tool results]"))
+}
+
+/// Screen one piece of provider-bound content, redacting it in place.
+pub(crate) fn screen_provider_content(content: &mut String, observer: &dyn Observer) -> Result<()> {
+    let screened = crate::security::outbound::screen(
+        crate::security::OutboundSurface::Provider,
+        content,
+        observer,
+    )?;
+    if let std::borrow::Cow::Owned(redacted) = screened {
+        *content = redacted;
+    }
+    Ok(())
+}
+
+/// Check tool output bound for the provider without rewriting it; only a
+/// `block` match stops the turn.
+pub(crate) fn check_provider_tool_output(output: &str, observer: &dyn Observer) -> Result<()> {
+    crate::security::outbound::screen(
+        crate::security::OutboundSurface::Provider,
+        output,
+        observer,
+    )?;
+    Ok(())
+}
+
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
 /// When `silent` is true, suppresses stdout (for channel use).
@@ -1873,6 +1957,7 @@ pub(crate) async fn agent_turn(
     silent: bool,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
+    transcript: Option<&Transcript>,
 ) -> Result<String> {
     run_tool_call_loop(
         provider,
@@ -1891,6 +1976,7 @@ pub(crate) async fn agent_turn(
         None,
         None,
         &[],
+        transcript,
     )
     .await
 }
@@ -2081,6 +2167,7 @@ pub(crate) async fn run_tool_call_loop(
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
     excluded_tools: &[String],
+    transcript: Option<&Transcript>,
 ) -> Result<String> {
     let max_iterations = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
@@ -2096,6 +2183,9 @@ pub(crate) async fn run_tool_call_loop(
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
     let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
+    let mut budget_notices: Vec<String> = Vec::new();
+
+    let mut screened_messages = 0;
 
     for iteration in 0..max_iterations {
         if cancellation_token
@@ -2105,6 +2195,9 @@ pub(crate) async fn run_tool_call_loop(
             return Err(ToolLoopCancelled.into());
         }
 
+        screen_provider_bound(history.iter_mut().skip(screened_messages), observer)?;
+        screened_messages = history.len();
+
         let image_marker_count = multimodal::count_image_markers(history);
         if image_marker_count > 0 && !provider.supports_vision() {
             return Err(ProviderCapabilityError {
@@ -2120,6 +2213,15 @@ pub(crate) async fn run_tool_call_loop(
         let prepared_messages =
             multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
 
+        // ── Budget pre-check ────────────────────────────────
+        if let Some(notice) = crate::cost::budget::precheck(
+            provider_name,
+            model,
+            crate::cost::budget::estimate_input_tokens(&prepared_messages.messages),
+        )? {
+            budget_notices.push(notice);
+        }
+
         // ── Progress: LLM thinking ────────────────────────────
         if let Some(ref tx) = on_delta {
             let phase = if iteration == 0 {
@@ -2190,7 +2292,12 @@ pub(crate) async fn run_tool_call_loop(
                         .as_ref()
                         .map(|u| (u.input_tokens, u.output_tokens))
                         .unwrap_or((None, None));
-
+                    crate::cost::budget::record(
+                        channel_name,
+                        provider_name,
+                        model,
+                        resp.usage.as_ref(),
+                    );
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
                         model: model.to_string(),
@@ -2217,6 +2324,19 @@ pub(crate) async fn run_tool_call_loop(
                         calls = fallback_calls;
                     }
 
+                    if let Some(transcript) = transcript {
+                        transcript.record(TranscriptEvent::ModelResponse {
+                            provider: provider_name.to_string(),
+                            model: model.to_string(),
+                            text: response_text.clone(),
+                            tool_calls: calls.len(),
+                            input_tokens: resp_input_tokens,
+                            output_tokens: resp_output_tokens,
+                            duration_ms: u64::try_from(llm_started_at.elapsed().as_millis())
+                                .unwrap_or(u64::MAX),
+                        });
+                    }
+
                     if let Some(parse_issue) = detect_tool_call_parse_issue(&response_text, &calls)
                     {
                         runtime_trace::record_event(
@@ -2289,6 +2409,9 @@ pub(crate) async fn run_tool_call_loop(
                 }
                 Err(e) => {
                     let safe_error = crate::providers::sanitize_api_error(&e.to_string());
+                    if let Some(transcript) = transcript {
+                        transcript.record_error(&safe_error);
+                    }
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
                         model: model.to_string(),
@@ -2376,6 +2499,13 @@ pub(crate) async fn run_tool_call_loop(
                 }
             }
             history.push(ChatMessage::assistant(response_text.clone()));
+            if let Some(transcript) = transcript {
+                transcript.record_message("assistant", &display_text);
+            }
+            if !budget_notices.is_empty() {
+                // Budget notices go to the user only, never into history.
+                return Ok(format!("{}\n\n{display_text}", budget_notices.join("\n")));
+            }
             return Ok(display_text);
         }
 
@@ -2451,16 +2581,13 @@ pub(crate) async fn run_tool_call_loop(
                         arguments: tool_args.clone(),
                     };
 
-                    // Only prompt interactively on CLI; auto-approve on other channels.
-                    let decision = if channel_name == "cli" {
-                        mgr.prompt_cli(&request)
-                    } else {
-                        ApprovalResponse::Yes
-                    };
+                    // Prompt via the attached prompter or the CLI; other
+                    // channels auto-approve.
+                    let decision = mgr.request_approval(&request, channel_name).await;
 
-                    mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
+                    mgr.record_decision_by(&tool_name, &tool_args, &decision, channel_name);
 
-                    if decision == ApprovalResponse::No {
+                    if decision.response == ApprovalResponse::No {
                         let denied = "Denied by user.".to_string();
                         runtime_trace::record_event(
                             "tool_call_result",
@@ -2625,8 +2752,17 @@ pub(crate) async fn run_tool_call_loop(
             ordered_results[*idx] = Some((call.name.clone(), call.tool_call_id.clone(), outcome));
         }
 
-        for entry in ordered_results {
+        for (idx, entry) in ordered_results.into_iter().enumerate() {
             if let Some((tool_name, tool_call_id, outcome)) = entry {
+                if let Some(transcript) = transcript {
+                    transcript.record_tool_call(
+                        &tool_name,
+                        &tool_calls[idx].arguments,
+                        &outcome.output,
+                        outcome.success,
+                        outcome.duration,
+                    );
+                }
                 individual_results.push((tool_call_id, outcome.output.clone()));
                 let _ = writeln!(
                     tool_results,
@@ -2671,6 +2807,11 @@ pub(crate) async fn run_tool_call_loop(
         }
     }
 
+    if let Some(transcript) = transcript {
+        transcript.record_error(&format!(
+            "Agent exceeded maximum tool iterations ({max_iterations})"
+        ));
+    }
     runtime_trace::record_event(
         "tool_loop_exhausted",
         Some(channel_name),
@@ -2722,7 +2863,6 @@ pub(crate) fn build_tool_instructions(tools_registry: &[Box<dyn Tool>]) -> Strin
 // interactive REPL mode. The interactive loop manages history compaction
 // and hard trimming to keep the context window bounded.
 
-#[allow(clippy::too_many_lines)]
 pub async fn run(
     config: Config,
     message: Option<String>,
@@ -2731,6 +2871,65 @@ pub async fn run(
     temperature: f64,
     peripheral_overrides: Vec<String>,
     interactive: bool,
+) -> Result<String> {
+    // ── Approval manager (supervised mode) ───────────────────────
+    let approval_manager = interactive.then(|| ApprovalManager::from_config(&config.autonomy));
+    run_session(
+        config,
+        message,
+        provider_override,
+        model_override,
+        temperature,
+        peripheral_overrides,
+        interactive,
+        approval_manager,
+        None,
+    )
+    .await
+}
+
+/// A recorded session picked up again by `zeroclaw sessions resume`.
+pub struct ResumedSession {
+    /// Earlier user and assistant messages, oldest first.
+    pub history: Vec<ChatMessage>,
+    /// Transcript the resumed turns are appended to.
+    pub transcript: Transcript,
+}
+
+/// Continue `session` on the CLI, with the same approvals, cost budget and
+/// hooks as `zeroclaw agent`.
+pub async fn resume(
+    config: Config,
+    session: ResumedSession,
+    message: Option<String>,
+) -> Result<String> {
+    let approval_manager = Some(ApprovalManager::from_config(&config.autonomy));
+    let temperature = config.default_temperature;
+    run_session(
+        config,
+        message,
+        None,
+        None,
+        temperature,
+        Vec::new(),
+        true,
+        approval_manager,
+        Some(session),
+    )
+    .await
+}
+
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
+async fn run_session(
+    config: Config,
+    message: Option<String>,
+    provider_override: Option<String>,
+    model_override: Option<String>,
+    temperature: f64,
+    peripheral_overrides: Vec<String>,
+    interactive: bool,
+    approval_manager: Option<ApprovalManager>,
+    resumed: Option<ResumedSession>,
 ) -> Result<String> {
     // ── Wire up agnostic subsystems ──────────────────────────────
     let base_observer = observability::create_observer(&config.observability);
@@ -2810,15 +3009,25 @@ pub async fn run(
         reasoning_enabled: config.runtime.reasoning_enabled,
     };
 
-    let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
-        provider_name,
-        config.api_key.as_deref(),
-        config.api_url.as_deref(),
-        &config.reliability,
-        &config.model_routes,
-        model_name,
-        &provider_runtime_options,
-    )?;
+    let provider: Box<dyn Provider> = providers::with_recording(
+        providers::with_response_cache(
+            providers::create_routed_provider_with_options(
+                provider_name,
+                config.api_key.as_deref(),
+                config.api_url.as_deref(),
+                &config.reliability,
+                &config.model_routes,
+                &config.routing,
+                model_name,
+                &provider_runtime_options,
+            )?,
+            &config.memory,
+            &config.workspace_dir,
+            Some(Arc::clone(&observer)),
+        ),
+        &config.observability,
+        &config.workspace_dir,
+    );
 
     observer.record_event(&ObserverEvent::AgentStart {
         provider: provider_name.to_string(),
@@ -2977,13 +3186,16 @@ pub async fn run(
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
 
-    // ── Approval manager (supervised mode) ───────────────────────
-    let approval_manager = if interactive {
-        Some(ApprovalManager::from_config(&config.autonomy))
-    } else {
-        None
-    };
     let channel_name = if interactive { "cli" } else { "daemon" };
+    let session_store = SessionStore::from_config(&config.sessions, &config.workspace_dir);
+    let hooks = config
+        .hooks
+        .enabled
+        .then(|| crate::hooks::HookRunner::from_config(&config.hooks));
+    let (earlier_messages, mut resumed_transcript) = match resumed {
+        Some(session) => (session.history, Some(session.transcript)),
+        None => (Vec::new(), None),
+    };
 
     // ── Execute ──────────────────────────────────────────────────
     let start = Instant::now();
@@ -3015,10 +3227,23 @@ pub async fn run(
             format!("{context}[{now}] {msg}")
         };
 
-        let mut history = vec![
-            ChatMessage::system(&system_prompt),
-            ChatMessage::user(&enriched),
-        ];
+        let mut history = vec![ChatMessage::system(&system_prompt)];
+        history.extend(earlier_messages);
+        trim_history(&mut history, config.agent.max_history_messages);
+        history.push(ChatMessage::user(&enriched));
+
+        let transcript = resumed_transcript.take().or_else(|| {
+            crate::sessions::start_session(
+                session_store.as_ref(),
+                channel_name,
+                None,
+                provider_name,
+                model_name,
+            )
+        });
+        if let Some(transcript) = &transcript {
+            transcript.record_message("user", &msg);
+        }
 
         let response = run_tool_call_loop(
             provider.as_ref(),
@@ -3035,8 +3260,9 @@ pub async fn run(
             config.agent.max_tool_iterations,
             None,
             None,
-            None,
+            hooks.as_ref(),
             &[],
+            transcript.as_ref(),
         )
         .await?;
         final_output = response.clone();
@@ -3049,6 +3275,10 @@ pub async fn run(
 
         // Persistent conversation history across turns
         let mut history = vec![ChatMessage::system(&system_prompt)];
+        history.extend(earlier_messages);
+        trim_history(&mut history, config.agent.max_history_messages);
+        // Started on the first message and again after `/new`.
+        let mut transcript: Option<Transcript> = resumed_transcript.take();
 
         loop {
             print!("> ");
@@ -3096,6 +3326,7 @@ pub async fn run(
 
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
+                    transcript = None;
                     // Clear conversation and daily memory
                     let mut cleared = 0;
                     for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
@@ -3142,6 +3373,19 @@ pub async fn run(
 
             history.push(ChatMessage::user(&enriched));
 
+            if transcript.is_none() {
+                transcript = crate::sessions::start_session(
+                    session_store.as_ref(),
+                    channel_name,
+                    None,
+                    provider_name,
+                    model_name,
+                );
+            }
+            if let Some(transcript) = &transcript {
+                transcript.record_message("user", &user_input);
+            }
+
             let response = match run_tool_call_loop(
                 provider.as_ref(),
                 &mut history,
@@ -3157,8 +3401,9 @@ pub async fn run(
                 config.agent.max_tool_iterations,
                 None,
                 None,
-                None,
+                hooks.as_ref(),
                 &[],
+                transcript.as_ref(),
             )
             .await
             {
@@ -3196,6 +3441,13 @@ pub async fn run(
             // Hard cap as a safety net.
             trim_history(&mut history, config.agent.max_history_messages);
         }
+
+        if let Some(transcript) = &transcript {
+            println!(
+                "Session saved as {id} (continue with `zeroclaw sessions resume {id}`)",
+                id = transcript.id()
+            );
+        }
     }
 
     let duration = start.elapsed();
@@ -3267,15 +3519,25 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
     };
-    let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
-        provider_name,
-        config.api_key.as_deref(),
-        config.api_url.as_deref(),
-        &config.reliability,
-        &config.model_routes,
-        &model_name,
-        &provider_runtime_options,
-    )?;
+    let provider: Box<dyn Provider> = providers::with_recording(
+        providers::with_response_cache(
+            providers::create_routed_provider_with_options(
+                provider_name,
+                config.api_key.as_deref(),
+                config.api_url.as_deref(),
+                &config.reliability,
+                &config.model_routes,
+                &config.routing,
+                &model_name,
+                &provider_runtime_options,
+            )?,
+            &config.memory,
+            &config.workspace_dir,
+            Some(Arc::clone(&observer)),
+        ),
+        &config.observability,
+        &config.workspace_dir,
+    );
 
     let hardware_rag: Option<crate::rag::HardwareRag> = config
         .peripherals
@@ -3379,6 +3641,17 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ChatMessage::user(&enriched),
     ];
 
+    let transcript = crate::sessions::start_session(
+        SessionStore::from_config(&config.sessions, &config.workspace_dir).as_ref(),
+        "gateway",
+        None,
+        provider_name,
+        &model_name,
+    );
+    if let Some(transcript) = &transcript {
+        transcript.record_message("user", message);
+    }
+
     agent_turn(
         provider.as_ref(),
         &mut history,
@@ -3390,6 +3663,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         true,
         &config.multimodal,
         config.agent.max_tool_iterations,
+        transcript.as_ref(),
     )
     .await
 }
@@ -3703,6 +3977,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect_err("provider without vision support should fail");
@@ -3749,6 +4024,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect_err("oversized payload must fail");
@@ -3789,6 +4065,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("valid multimodal payload should pass");
@@ -3915,6 +4192,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("parallel execution should complete");
@@ -3984,6 +4262,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("loop should finish after deduplicating repeated calls");
@@ -4003,6 +4282,73 @@ mod tests {
         assert!(tool_results.content.contains("Skipped duplicate tool call"));
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_writes_session_transcript() {
+        let provider = ScriptedProvider::from_text_responses(vec![
+            r#"<tool_call>
+{"name":"count_tool","arguments":{"value":"A"}}
+</tool_call>"#,
+            "done",
+        ]);
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path().to_path_buf(), 0);
+        let transcript = store
+            .start("cli", None, None, "mock-provider", "mock-model")
+            .unwrap();
+
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run tool calls"),
+        ];
+        run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            None,
+            &[],
+            Some(&transcript),
+        )
+        .await
+        .expect("loop should finish");
+
+        let events: Vec<TranscriptEvent> = store
+            .load(transcript.id())
+            .unwrap()
+            .into_iter()
+            .map(|record| record.event)
+            .collect();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(
+            &events[1],
+            TranscriptEvent::ModelResponse { tool_calls: 1, .. }
+        ));
+        assert!(matches!(
+            &events[2],
+            TranscriptEvent::ToolCall { name, output, success: true, .. }
+                if name == "count_tool" && output == "counted:A"
+        ));
+        assert!(matches!(
+            &events[4],
+            TranscriptEvent::Message { role, content } if role == "assistant" && content == "done"
+        ));
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_native_mode_preserves_fallback_tool_call_ids() {
         let provider = ScriptedProvider::from_text_responses(vec![
@@ -4040,6 +4386,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("native fallback id flow should complete");
@@ -5596,4 +5943,128 @@ Let me check the result."#;
         assert_eq!(parsed["content"].as_str(), Some("answer"));
         assert!(parsed.get("reasoning_content").is_none());
     }
+
+    struct DenyingPrompter {
+        asked: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl crate::approval::ApprovalPrompter for DenyingPrompter {
+        async fn prompt(&self, request: &ApprovalRequest) -> crate::approval::ApprovalDecision {
+            self.asked.lock().unwrap().push(request.tool_name.clone());
+            crate::approval::ApprovalDecision::by(ApprovalResponse::No, "test")
+        }
+    }
+
+    #[tokio::test]
+    async fn resumed_supervised_session_still_asks_for_approval() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{"message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": {
+                            "name": "shell",
+                            "arguments": "{\"command\":\"touch resumed-marker\"}"
+                        }
+                    }]
+                }}]
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{"message": {"role": "assistant", "content": "Skipped it."}}]
+            })))
+            .mount(&server)
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            default_provider: Some(format!("custom:{}/v1", server.uri())),
+            default_model: Some("test-model".into()),
+            api_key: Some("test-key".into()),
+            ..Config::default()
+        };
+        config.memory.backend = "none".into();
+        config.autonomy.level = crate::security::AutonomyLevel::Supervised;
+        config.autonomy.allowed_commands = vec!["touch".into()];
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+
+        let store = SessionStore::new(tmp.path().join("sessions"), 1000);
+        let transcript =
+            crate::sessions::start_session(Some(&store), "cli", None, "custom", "test-model")
+                .unwrap();
+        let session = ResumedSession {
+            history: vec![
+                ChatMessage::user("earlier question"),
+                ChatMessage::assistant("earlier answer"),
+            ],
+            transcript,
+        };
+        let prompter = Arc::new(DenyingPrompter {
+            asked: Mutex::new(Vec::new()),
+        });
+        let approvals =
+            ApprovalManager::from_config(&config.autonomy).with_prompter(prompter.clone());
+        let workspace = config.workspace_dir.clone();
+
+        let response = run_session(
+            config,
+            Some("make the marker".into()),
+            None,
+            None,
+            0.0,
+            Vec::new(),
+            true,
+            Some(approvals),
+            Some(session),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response, "Skipped it.");
+        assert_eq!(*prompter.asked.lock().unwrap(), vec!["shell".to_string()]);
+        assert!(!workspace.join("resumed-marker").exists());
+        let first_request = &server.received_requests().await.unwrap()[0];
+        let body = String::from_utf8_lossy(&first_request.body);
+        assert!(body.contains("earlier question"), "{body}");
+    }
+
+    #[test]
+    fn provider_screening_never_rewrites_tool_output() {
+        let guard = crate::security::OutboundGuard::new(&crate::config::OutboundGuardConfig {
+            providers: true,
+            patterns: vec![crate::config::OutboundPatternConfig {
+                name: "ticket".to_string(),
+                regex: "TICKET-[0-9]+".to_string(),
+                action: Some(crate::config::OutboundAction::Redact),
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        let mut history = [
+            ChatMessage::user("look at TICKET-1"),
+            ChatMessage::tool(r#"{"tool_call_id":"c1","content":"id = TICKET-2"}"#),
+            ChatMessage::user("[Tool results]\nid = TICKET-3"),
+        ];
+
+        screen_messages(&guard, history.iter_mut(), &NoopObserver).unwrap();
+
+        assert!(!history[0].content.contains("TICKET-1"));
+        assert!(history[1].content.contains("TICKET-2"));
+        assert!(history[2].content.contains("TICKET-3"));
+    }
 }
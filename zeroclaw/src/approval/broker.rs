@@ -0,0 +1,343 @@
+//! Process-wide registry of approval requests waiting on a human.
+//!
+//! Chat channels and the gateway WebSocket register every prompt here so a
+//! request raised on Telegram can also be answered from the dashboard via
+//! `/api/approvals`. Registrations and resolutions are broadcast as
+//! [`ApprovalEvent`]s for SSE subscribers, and requests nobody answers are
+//! denied once their timeout lapses.
+
+use super::{summarize_args, ApprovalDecision, ApprovalRequest, ApprovalResponse};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, oneshot};
+
+/// Approver recorded when a request expires unanswered.
+pub const TIMEOUT_APPROVER: &str = "timeout";
+
+const EVENT_BUFFER: usize = 64;
+
+/// Where an approval request was raised.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ApprovalOrigin {
+    /// Channel name (`telegram`, `slack`, `ws`, ...).
+    pub channel: String,
+    /// Conversation the prompt was posted to (chat id, WebSocket session id).
+    pub conversation: String,
+    /// Sender whose message led to the tool call, if known.
+    pub requested_by: Option<String>,
+}
+
+/// A request waiting for an answer, as shown to approvers.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingApproval {
+    pub id: String,
+    pub tool_name: String,
+    pub arguments: serde_json::Value,
+    pub arguments_summary: String,
+    #[serde(flatten)]
+    pub origin: ApprovalOrigin,
+    pub created_at: String,
+    pub expires_at: String,
+    pub timeout_secs: u64,
+}
+
+impl PendingApproval {
+    /// Plain-text prompt for channels without interactive widgets.
+    pub fn prompt_text(&self) -> String {
+        format!(
+            "🔧 Approval needed to run `{}`\n{}\n\nReply `yes`, `no` or `always` within {}s (request `{}`).",
+            self.tool_name, self.arguments_summary, self.timeout_secs, self.id
+        )
+    }
+}
+
+/// Broadcast to gateway SSE clients as requests are raised and settled.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApprovalEvent {
+    ApprovalRequested {
+        approval: PendingApproval,
+    },
+    ApprovalResolved {
+        id: String,
+        tool_name: String,
+        decision: ApprovalResponse,
+        approver: Option<String>,
+    },
+}
+
+struct Waiter {
+    approval: PendingApproval,
+    tx: oneshot::Sender<ApprovalDecision>,
+}
+
+/// Tracks outstanding approval requests across channels and the gateway.
+pub struct ApprovalBroker {
+    /// Oldest first, so bare `yes` replies answer the earliest prompt.
+    pending: Mutex<Vec<Waiter>>,
+    events: broadcast::Sender<ApprovalEvent>,
+}
+
+impl Default for ApprovalBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApprovalBroker {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            pending: Mutex::new(Vec::new()),
+            events,
+        }
+    }
+
+    /// Broker shared by every channel and gateway session in this process.
+    pub fn global() -> Arc<Self> {
+        static BROKER: OnceLock<Arc<ApprovalBroker>> = OnceLock::new();
+        Arc::clone(BROKER.get_or_init(|| Arc::new(Self::new())))
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ApprovalEvent> {
+        self.events.subscribe()
+    }
+
+    /// Register `request` and announce it. Pass the returned receiver to
+    /// [`Self::wait`] once the prompt has been delivered.
+    pub fn register(
+        &self,
+        request: &ApprovalRequest,
+        origin: ApprovalOrigin,
+        timeout: Duration,
+    ) -> (PendingApproval, oneshot::Receiver<ApprovalDecision>) {
+        let now = chrono::Utc::now();
+        let expires = now + chrono::Duration::from_std(timeout).unwrap_or(chrono::Duration::MAX);
+        let (tx, rx) = oneshot::channel();
+
+        let approval = {
+            let mut pending = self.pending.lock();
+            let id = loop {
+                let candidate = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+                if !pending.iter().any(|w| w.approval.id == candidate) {
+                    break candidate;
+                }
+            };
+            let approval = PendingApproval {
+                id,
+                tool_name: request.tool_name.clone(),
+                arguments: request.arguments.clone(),
+                arguments_summary: summarize_args(&request.arguments),
+                origin,
+                created_at: now.to_rfc3339(),
+                expires_at: expires.to_rfc3339(),
+                timeout_secs: timeout.as_secs(),
+            };
+            pending.push(Waiter {
+                approval: approval.clone(),
+                tx,
+            });
+            approval
+        };
+
+        let _ = self.events.send(ApprovalEvent::ApprovalRequested {
+            approval: approval.clone(),
+        });
+        (approval, rx)
+    }
+
+    /// Wait for an answer to `approval`, denying it when `timeout` elapses.
+    pub async fn wait(
+        &self,
+        approval: &PendingApproval,
+        mut rx: oneshot::Receiver<ApprovalDecision>,
+        timeout: Duration,
+    ) -> ApprovalDecision {
+        match tokio::time::timeout(timeout, &mut rx).await {
+            Ok(Ok(decision)) => decision,
+            Ok(Err(_)) => ApprovalDecision::automatic(ApprovalResponse::No),
+            Err(_) => {
+                if self
+                    .resolve(&approval.id, ApprovalResponse::No, TIMEOUT_APPROVER)
+                    .is_none()
+                {
+                    // Answered in the instant the timer fired.
+                    if let Ok(decision) = rx.try_recv() {
+                        return decision;
+                    }
+                }
+                ApprovalDecision::by(ApprovalResponse::No, TIMEOUT_APPROVER)
+            }
+        }
+    }
+
+    /// Answer a pending request. Returns it, or `None` for unknown or
+    /// already-settled ids.
+    pub fn resolve(
+        &self,
+        id: &str,
+        response: ApprovalResponse,
+        approver: &str,
+    ) -> Option<PendingApproval> {
+        let waiter = {
+            let mut pending = self.pending.lock();
+            let index = pending.iter().position(|w| w.approval.id == id)?;
+            pending.remove(index)
+        };
+        Some(self.settle(waiter, ApprovalDecision::by(response, approver)))
+    }
+
+    /// Deny everything pending in one conversation (cancel, disconnect).
+    pub fn deny_conversation(&self, channel: &str, conversation: &str, approver: &str) -> usize {
+        let denied: Vec<Waiter> = {
+            let mut pending = self.pending.lock();
+            let (denied, kept) = std::mem::take(&mut *pending).into_iter().partition(|w| {
+                w.approval.origin.channel == channel
+                    && w.approval.origin.conversation == conversation
+            });
+            *pending = kept;
+            denied
+        };
+        let count = denied.len();
+        for waiter in denied {
+            self.settle(waiter, ApprovalDecision::by(ApprovalResponse::No, approver));
+        }
+        count
+    }
+
+    /// Snapshot of all pending requests, oldest first.
+    pub fn list(&self) -> Vec<PendingApproval> {
+        self.pending
+            .lock()
+            .iter()
+            .map(|w| w.approval.clone())
+            .collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<PendingApproval> {
+        self.pending
+            .lock()
+            .iter()
+            .find(|w| w.approval.id == id)
+            .map(|w| w.approval.clone())
+    }
+
+    /// Oldest pending request raised in `conversation` on `channel`.
+    pub fn oldest_for(&self, channel: &str, conversation: &str) -> Option<PendingApproval> {
+        self.pending
+            .lock()
+            .iter()
+            .find(|w| {
+                w.approval.origin.channel == channel
+                    && w.approval.origin.conversation == conversation
+            })
+            .map(|w| w.approval.clone())
+    }
+
+    fn settle(&self, waiter: Waiter, decision: ApprovalDecision) -> PendingApproval {
+        let _ = self.events.send(ApprovalEvent::ApprovalResolved {
+            id: waiter.approval.id.clone(),
+            tool_name: waiter.approval.tool_name.clone(),
+            decision: decision.response,
+            approver: decision.approver.clone(),
+        });
+        // The requester may have given up (cancelled turn); that's fine.
+        let _ = waiter.tx.send(decision);
+        waiter.approval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        }
+    }
+
+    fn origin(conversation: &str) -> ApprovalOrigin {
+        ApprovalOrigin {
+            channel: "telegram".into(),
+            conversation: conversation.into(),
+            requested_by: Some("alice".into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_delivers_decision_with_approver() {
+        let broker = ApprovalBroker::new();
+        let mut events = broker.subscribe();
+        let (approval, rx) = broker.register(&request(), origin("chat1"), Duration::from_secs(60));
+        assert_eq!(broker.list().len(), 1);
+        assert_eq!(approval.arguments_summary, "command: ls");
+
+        assert!(broker
+            .resolve(&approval.id, ApprovalResponse::Yes, "gateway:token:ops")
+            .is_some());
+        let decision = broker.wait(&approval, rx, Duration::from_secs(60)).await;
+        assert_eq!(
+            decision,
+            ApprovalDecision::by(ApprovalResponse::Yes, "gateway:token:ops")
+        );
+        assert!(broker.list().is_empty());
+        assert!(broker
+            .resolve(&approval.id, ApprovalResponse::No, "x")
+            .is_none());
+
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            ApprovalEvent::ApprovalRequested { .. }
+        ));
+        let ApprovalEvent::ApprovalResolved {
+            decision, approver, ..
+        } = events.recv().await.unwrap()
+        else {
+            panic!("expected approval_resolved");
+        };
+        assert_eq!(decision, ApprovalResponse::Yes);
+        assert_eq!(approver.as_deref(), Some("gateway:token:ops"));
+    }
+
+    #[tokio::test]
+    async fn unanswered_requests_time_out_to_deny() {
+        let broker = ApprovalBroker::new();
+        let (approval, rx) =
+            broker.register(&request(), origin("chat1"), Duration::from_millis(20));
+        let decision = broker.wait(&approval, rx, Duration::from_millis(20)).await;
+        assert_eq!(decision.response, ApprovalResponse::No);
+        assert_eq!(decision.approver.as_deref(), Some(TIMEOUT_APPROVER));
+        assert!(broker.get(&approval.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn deny_conversation_only_touches_that_conversation() {
+        let broker = ApprovalBroker::new();
+        let (first, rx1) = broker.register(&request(), origin("chat1"), Duration::from_secs(60));
+        let (second, _rx2) = broker.register(&request(), origin("chat2"), Duration::from_secs(60));
+
+        assert_eq!(broker.oldest_for("telegram", "chat1").unwrap().id, first.id);
+        assert_eq!(
+            broker.deny_conversation("telegram", "chat1", "cancelled"),
+            1
+        );
+        assert_eq!(rx1.await.unwrap().response, ApprovalResponse::No);
+        assert!(broker.oldest_for("telegram", "chat1").is_none());
+        assert_eq!(broker.list()[0].id, second.id);
+    }
+
+    #[test]
+    fn approval_event_serializes_with_type_tag() {
+        let broker = ApprovalBroker::new();
+        let (approval, _rx) = broker.register(&request(), origin("chat1"), Duration::from_secs(60));
+        let json = serde_json::to_value(ApprovalEvent::ApprovalRequested { approval }).unwrap();
+        assert_eq!(json["type"], "approval_requested");
+        assert_eq!(json["approval"]["tool_name"], "shell");
+        assert_eq!(json["approval"]["channel"], "telegram");
+        assert_eq!(json["approval"]["conversation"], "chat1");
+    }
+}
@@ -1,15 +1,25 @@
 //! Interactive approval workflow for supervised mode.
 //!
 //! Provides a pre-execution hook that prompts the user before tool calls,
-//! with session-scoped "Always" allowlists and audit logging.
+//! with session-scoped "Always" allowlists and audit logging. Prompts that
+//! leave the terminal (chat channels, the gateway) are tracked by the
+//! process-wide [`ApprovalBroker`] so any surface can answer them.
+
+mod broker;
+
+pub use broker::{
+    ApprovalBroker, ApprovalEvent, ApprovalOrigin, PendingApproval, TIMEOUT_APPROVER,
+};
 
 use crate::config::AutonomyConfig;
 use crate::security::AutonomyLevel;
+use async_trait::async_trait;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::{self, BufRead, Write};
+use std::sync::Arc;
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -32,6 +42,32 @@ pub enum ApprovalResponse {
     Always,
 }
 
+/// A resolved approval: the answer and who gave it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalDecision {
+    pub response: ApprovalResponse,
+    /// Identity of whoever answered (e.g. `telegram:alice`, `gateway:token:ops`,
+    /// `timeout`). `None` when nobody was asked.
+    pub approver: Option<String>,
+}
+
+impl ApprovalDecision {
+    pub fn by(response: ApprovalResponse, approver: impl Into<String>) -> Self {
+        Self {
+            response,
+            approver: Some(approver.into()),
+        }
+    }
+
+    /// A decision made without asking anyone.
+    pub fn automatic(response: ApprovalResponse) -> Self {
+        Self {
+            response,
+            approver: None,
+        }
+    }
+}
+
 /// A single audit log entry for an approval decision.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalLogEntry {
@@ -40,6 +76,17 @@ pub struct ApprovalLogEntry {
     pub arguments_summary: String,
     pub decision: ApprovalResponse,
     pub channel: String,
+    /// Who made the decision, when known.
+    #[serde(default)]
+    pub approver: Option<String>,
+}
+
+/// Interactive approval front-end for non-CLI surfaces (chat channels, the
+/// gateway WebSocket). Implementations must resolve to `No` when the user
+/// cannot be reached so an unanswered prompt never executes a tool.
+#[async_trait]
+pub trait ApprovalPrompter: Send + Sync {
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalDecision;
 }
 
 // ── ApprovalManager ──────────────────────────────────────────────
@@ -60,6 +107,8 @@ pub struct ApprovalManager {
     session_allowlist: Mutex<HashSet<String>>,
     /// Audit trail of approval decisions.
     audit_log: Mutex<Vec<ApprovalLogEntry>>,
+    /// Optional non-CLI prompter; takes precedence over the CLI prompt.
+    prompter: Option<Arc<dyn ApprovalPrompter>>,
 }
 
 impl ApprovalManager {
@@ -71,9 +120,25 @@ impl ApprovalManager {
             autonomy_level: config.level,
             session_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
+            prompter: None,
         }
     }
 
+    /// Route approval prompts through `prompter` instead of stdin.
+    #[must_use]
+    pub fn with_prompter(mut self, prompter: Arc<dyn ApprovalPrompter>) -> Self {
+        self.prompter = Some(prompter);
+        self
+    }
+
+    /// Seed the session allowlist, e.g. with "Always" answers remembered
+    /// from earlier messages in the same conversation.
+    #[must_use]
+    pub fn with_session_allowlist(self, tools: impl IntoIterator<Item = String>) -> Self {
+        self.session_allowlist.lock().extend(tools);
+        self
+    }
+
     /// Check whether a tool call requires interactive approval.
     ///
     /// Returns `true` if the call needs a prompt, `false` if it can proceed.
@@ -116,6 +181,24 @@ impl ApprovalManager {
         decision: ApprovalResponse,
         channel: &str,
     ) {
+        self.record_decision_by(
+            tool_name,
+            args,
+            &ApprovalDecision::automatic(decision),
+            channel,
+        );
+    }
+
+    /// Record an approval decision together with the approver's identity.
+    pub fn record_decision_by(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        decision: &ApprovalDecision,
+        channel: &str,
+    ) {
+        let approver = decision.approver.clone();
+        let decision = decision.response;
         // If "Always", add to session allowlist.
         if decision == ApprovalResponse::Always {
             let mut allowlist = self.session_allowlist.lock();
@@ -124,12 +207,29 @@ impl ApprovalManager {
 
         // Append to audit log.
         let summary = summarize_args(args);
+        let verdict = serde_json::to_value(decision)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        crate::security::audit::record(
+            crate::security::audit::AuditEvent::new(
+                crate::security::audit::AuditEventType::ApprovalDecision,
+            )
+            .with_actor(channel.to_string(), None, approver.clone())
+            .with_action(
+                format!("{tool_name}: {summary} => {verdict}"),
+                "approval".into(),
+                decision != ApprovalResponse::No,
+                decision != ApprovalResponse::No,
+            ),
+        );
         let entry = ApprovalLogEntry {
             timestamp: Utc::now().to_rfc3339(),
             tool_name: tool_name.to_string(),
             arguments_summary: summary,
             decision,
             channel: channel.to_string(),
+            approver,
         };
         let mut log = self.audit_log.lock();
         log.push(entry);
@@ -152,6 +252,33 @@ impl ApprovalManager {
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
+
+    /// Ask for a decision on `request` using the best front-end available.
+    ///
+    /// A configured prompter wins; otherwise the CLI channel prompts on
+    /// stdin and every other channel auto-approves.
+    pub async fn request_approval(
+        &self,
+        request: &ApprovalRequest,
+        channel: &str,
+    ) -> ApprovalDecision {
+        if let Some(prompter) = &self.prompter {
+            return prompter.prompt(request).await;
+        }
+        if channel == "cli" {
+            ApprovalDecision::by(self.prompt_cli(request), cli_approver())
+        } else {
+            ApprovalDecision::automatic(ApprovalResponse::Yes)
+        }
+    }
+}
+
+/// `cli:<login>` for decisions typed at the terminal.
+fn cli_approver() -> String {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "local".to_string());
+    format!("cli:{user}")
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
@@ -179,7 +306,7 @@ fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
 }
 
 /// Produce a short human-readable summary of tool arguments.
-fn summarize_args(args: &serde_json::Value) -> String {
+pub(crate) fn summarize_args(args: &serde_json::Value) -> String {
     match args {
         serde_json::Value::Object(map) => {
             let parts: Vec<String> = map
@@ -364,6 +491,32 @@ mod tests {
         assert_eq!(log.len(), 1);
         assert!(!log[0].timestamp.is_empty());
         assert_eq!(log[0].channel, "telegram");
+        assert!(log[0].approver.is_none());
+    }
+
+    #[test]
+    fn audit_log_records_approver_identity() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        mgr.record_decision_by(
+            "file_write",
+            &serde_json::json!({"path": "a.txt"}),
+            &ApprovalDecision::by(ApprovalResponse::Always, "telegram:alice"),
+            "telegram",
+        );
+
+        let log = mgr.audit_log();
+        assert_eq!(log[0].approver.as_deref(), Some("telegram:alice"));
+        assert_eq!(log[0].decision, ApprovalResponse::Always);
+        assert!(!mgr.needs_approval("file_write"));
+    }
+
+    #[test]
+    fn seeded_session_allowlist_skips_prompt() {
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_session_allowlist(["file_write".to_string(), "shell".to_string()]);
+        assert!(!mgr.needs_approval("file_write"));
+        // always_ask still wins over remembered answers.
+        assert!(mgr.needs_approval("shell"));
     }
 
     // ── summarize_args ───────────────────────────────────────
@@ -423,4 +576,46 @@ mod tests {
         let parsed: ApprovalRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.tool_name, "shell");
     }
+
+    // ── request_approval ─────────────────────────────────────
+
+    struct FixedPrompter(ApprovalResponse);
+
+    #[async_trait]
+    impl ApprovalPrompter for FixedPrompter {
+        async fn prompt(&self, _request: &ApprovalRequest) -> ApprovalDecision {
+            ApprovalDecision::by(self.0, "fixed")
+        }
+    }
+
+    #[tokio::test]
+    async fn request_approval_auto_approves_non_cli_without_prompter() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let req = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({}),
+        };
+        assert_eq!(
+            mgr.request_approval(&req, "telegram").await,
+            ApprovalDecision::automatic(ApprovalResponse::Yes)
+        );
+    }
+
+    #[tokio::test]
+    async fn request_approval_prefers_prompter() {
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_prompter(Arc::new(FixedPrompter(ApprovalResponse::No)));
+        let req = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({}),
+        };
+        assert_eq!(
+            mgr.request_approval(&req, "ws").await,
+            ApprovalDecision::by(ApprovalResponse::No, "fixed")
+        );
+        assert_eq!(
+            mgr.request_approval(&req, "cli").await.response,
+            ApprovalResponse::No
+        );
+    }
 }
@@ -0,0 +1,383 @@
+//! Supervised-mode tool approvals on chat channels.
+//!
+//! With `[autonomy].channel_approvals = true`, tool calls that need approval
+//! are posted back to the conversation that triggered them (inline buttons
+//! where the channel has them) and answered with `yes`, `no` or `always`.
+//! Prompts are registered with the shared [`ApprovalBroker`], so they can
+//! also be answered from the gateway's `/api/approvals`.
+
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::approval::{
+    ApprovalBroker, ApprovalDecision, ApprovalManager, ApprovalOrigin, ApprovalPrompter,
+    ApprovalRequest, ApprovalResponse,
+};
+use crate::config::AutonomyConfig;
+use crate::security::AutonomyLevel;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Per-runtime approval state for channel conversations.
+pub(crate) struct ChannelApprovals {
+    autonomy: AutonomyConfig,
+    broker: Arc<ApprovalBroker>,
+    /// "Always" answers per conversation, kept across messages.
+    allowlists: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+impl ChannelApprovals {
+    /// `None` unless channel approvals are enabled in supervised mode.
+    pub(crate) fn from_config(autonomy: &AutonomyConfig) -> Option<Self> {
+        (autonomy.channel_approvals && autonomy.level == AutonomyLevel::Supervised).then(|| Self {
+            autonomy: autonomy.clone(),
+            broker: ApprovalBroker::global(),
+            allowlists: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Approval manager that prompts in `msg`'s conversation.
+    pub(crate) fn manager_for(
+        &self,
+        channel: Arc<dyn Channel>,
+        msg: &ChannelMessage,
+        session_key: &str,
+    ) -> ApprovalManager {
+        let remembered = self
+            .allowlists
+            .lock()
+            .get(session_key)
+            .cloned()
+            .unwrap_or_default();
+        ApprovalManager::from_config(&self.autonomy)
+            .with_session_allowlist(remembered)
+            .with_prompter(Arc::new(ChannelApprovalPrompter {
+                broker: Arc::clone(&self.broker),
+                channel,
+                recipient: msg.reply_target.clone(),
+                thread_ts: msg.thread_ts.clone(),
+                origin: ApprovalOrigin {
+                    channel: msg.channel.clone(),
+                    conversation: msg.reply_target.clone(),
+                    requested_by: Some(msg.sender.clone()),
+                },
+                timeout: Duration::from_secs(self.autonomy.approval_timeout_secs),
+            }))
+    }
+
+    /// Keep `manager`'s "Always" answers for the next message in the session.
+    pub(crate) fn remember(&self, session_key: &str, manager: &ApprovalManager) {
+        let allowlist = manager.session_allowlist();
+        if !allowlist.is_empty() {
+            self.allowlists
+                .lock()
+                .insert(session_key.to_string(), allowlist);
+        }
+    }
+}
+
+/// Posts the prompt into the conversation and waits on the broker.
+struct ChannelApprovalPrompter {
+    broker: Arc<ApprovalBroker>,
+    channel: Arc<dyn Channel>,
+    recipient: String,
+    thread_ts: Option<String>,
+    origin: ApprovalOrigin,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApprovalPrompter for ChannelApprovalPrompter {
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalDecision {
+        let (approval, rx) = self
+            .broker
+            .register(request, self.origin.clone(), self.timeout);
+
+        if let Err(e) = self
+            .channel
+            .send_approval_prompt(&approval, &self.recipient, self.thread_ts.clone())
+            .await
+        {
+            tracing::warn!(
+                channel = %self.origin.channel,
+                "Failed to deliver approval prompt, denying: {e}"
+            );
+            self.broker
+                .resolve(&approval.id, ApprovalResponse::No, "undeliverable");
+            return ApprovalDecision::by(ApprovalResponse::No, "undeliverable");
+        }
+
+        let decision = self.broker.wait(&approval, rx, self.timeout).await;
+        if decision.approver.as_deref() == Some(crate::approval::TIMEOUT_APPROVER) {
+            let notice = format!(
+                "⏱️ Approval for `{}` timed out — denied.",
+                approval.tool_name
+            );
+            let _ = self
+                .channel
+                .send(&SendMessage::new(notice, &self.recipient).in_thread(self.thread_ts.clone()))
+                .await;
+        }
+        decision
+    }
+}
+
+/// A `yes` / `no` / `always` answer typed (or clicked) in a conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApprovalReply {
+    /// Explicit request id from `/approve <id> <decision>`.
+    pub id: Option<String>,
+    pub response: ApprovalResponse,
+}
+
+fn parse_decision_word(word: &str) -> Option<ApprovalResponse> {
+    match word {
+        "yes" | "y" | "approve" => Some(ApprovalResponse::Yes),
+        "no" | "n" | "deny" => Some(ApprovalResponse::No),
+        "always" | "a" => Some(ApprovalResponse::Always),
+        _ => None,
+    }
+}
+
+/// Parse `yes`/`no`/`always` or `/approve <id> [yes|no|always]`.
+pub(crate) fn parse_approval_reply(content: &str) -> Option<ApprovalReply> {
+    let normalized = content
+        .trim()
+        .trim_end_matches(['.', '!'])
+        .to_ascii_lowercase();
+    let mut parts = normalized.split_whitespace();
+    let first = parts.next()?;
+
+    if let Some(command) = first.strip_prefix('/') {
+        // Strip Telegram-style `/approve@botname`.
+        let command = command.split('@').next().unwrap_or(command);
+        let response = match command {
+            "approve" => ApprovalResponse::Yes,
+            "deny" => ApprovalResponse::No,
+            _ => return None,
+        };
+        let id = parts.next()?.to_string();
+        let response = match parts.next() {
+            Some(word) => parse_decision_word(word)?,
+            None => response,
+        };
+        return Some(ApprovalReply {
+            id: Some(id),
+            response,
+        });
+    }
+
+    if parts.next().is_some() {
+        return None;
+    }
+    parse_decision_word(first).map(|response| ApprovalReply { id: None, response })
+}
+
+/// Answer a pending approval from an incoming channel message.
+///
+/// Returns `Some(ack)` when the message was consumed as an approval reply;
+/// bare `yes`/`no` with nothing pending falls through as a normal message.
+pub(crate) fn answer_pending_approval(
+    broker: &ApprovalBroker,
+    msg: &ChannelMessage,
+) -> Option<String> {
+    let reply = parse_approval_reply(&msg.content)?;
+    let pending = match &reply.id {
+        Some(id) => match broker.get(id) {
+            Some(approval)
+                if approval.origin.channel == msg.channel
+                    && approval.origin.conversation == msg.reply_target =>
+            {
+                approval
+            }
+            _ => return Some(format!("⚠️ Unknown or expired approval request `{id}`.")),
+        },
+        None => broker.oldest_for(&msg.channel, &msg.reply_target)?,
+    };
+
+    let approver = format!("{}:{}", msg.channel, msg.sender);
+    if broker
+        .resolve(&pending.id, reply.response, &approver)
+        .is_none()
+    {
+        return Some(format!(
+            "⚠️ Approval request `{}` was already answered.",
+            pending.id
+        ));
+    }
+
+    Some(match reply.response {
+        ApprovalResponse::Yes => format!("✅ Approved `{}`.", pending.tool_name),
+        ApprovalResponse::Always => format!(
+            "✅ Approved `{}` for the rest of this conversation.",
+            pending.tool_name
+        ),
+        ApprovalResponse::No => format!("🚫 Denied `{}`.", pending.tool_name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "m1".into(),
+            sender: "alice".into(),
+            reply_target: "chat-1".into(),
+            content: content.into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            thread_ts: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    fn register(broker: &ApprovalBroker, conversation: &str) -> crate::approval::PendingApproval {
+        let (approval, _rx) = broker.register(
+            &ApprovalRequest {
+                tool_name: "shell".into(),
+                arguments: serde_json::json!({"command": "ls"}),
+            },
+            ApprovalOrigin {
+                channel: "telegram".into(),
+                conversation: conversation.into(),
+                requested_by: Some("alice".into()),
+            },
+            Duration::from_secs(60),
+        );
+        approval
+    }
+
+    #[test]
+    fn parse_approval_reply_accepts_words_and_commands() {
+        assert_eq!(
+            parse_approval_reply(" Yes! "),
+            Some(ApprovalReply {
+                id: None,
+                response: ApprovalResponse::Yes
+            })
+        );
+        assert_eq!(
+            parse_approval_reply("always").unwrap().response,
+            ApprovalResponse::Always
+        );
+        assert_eq!(
+            parse_approval_reply("/approve ab12cd34 no"),
+            Some(ApprovalReply {
+                id: Some("ab12cd34".into()),
+                response: ApprovalResponse::No
+            })
+        );
+        assert_eq!(
+            parse_approval_reply("/deny@zeroclaw_bot ab12cd34")
+                .unwrap()
+                .response,
+            ApprovalResponse::No
+        );
+        assert!(parse_approval_reply("yes please do it").is_none());
+        assert!(parse_approval_reply("/approve").is_none());
+        assert!(parse_approval_reply("/status").is_none());
+    }
+
+    #[tokio::test]
+    async fn bare_reply_answers_oldest_prompt_in_conversation() {
+        let broker = ApprovalBroker::new();
+        let first = register(&broker, "chat-1");
+        let other = register(&broker, "chat-2");
+
+        let ack = answer_pending_approval(&broker, &message("yes")).unwrap();
+        assert!(ack.contains("Approved `shell`"));
+        assert!(broker.get(&first.id).is_none());
+        assert!(broker.get(&other.id).is_some());
+
+        // Nothing left in chat-1: a plain "yes" is an ordinary message again.
+        assert!(answer_pending_approval(&broker, &message("yes")).is_none());
+    }
+
+    #[tokio::test]
+    async fn explicit_id_must_belong_to_the_conversation() {
+        let broker = ApprovalBroker::new();
+        let elsewhere = register(&broker, "chat-2");
+
+        let ack =
+            answer_pending_approval(&broker, &message(&format!("/approve {} yes", elsewhere.id)))
+                .unwrap();
+        assert!(ack.contains("Unknown or expired"));
+        assert!(broker.get(&elsewhere.id).is_some());
+    }
+
+    #[tokio::test]
+    async fn prompter_records_channel_approver() {
+        use crate::channels::traits::ChannelMessage as Msg;
+
+        struct Sink(tokio::sync::mpsc::UnboundedSender<String>);
+
+        #[async_trait]
+        impl Channel for Sink {
+            fn name(&self) -> &str {
+                "telegram"
+            }
+            async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+                let _ = self.0.send(message.content.clone());
+                Ok(())
+            }
+            async fn listen(&self, _tx: tokio::sync::mpsc::Sender<Msg>) -> anyhow::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (tx, mut sent) = tokio::sync::mpsc::unbounded_channel();
+        let autonomy = AutonomyConfig {
+            channel_approvals: true,
+            ..AutonomyConfig::default()
+        };
+        let approvals = ChannelApprovals::from_config(&autonomy).unwrap();
+        let broker = Arc::clone(&approvals.broker);
+        let mut msg = message("run ls");
+        msg.reply_target = format!("chat-{}", uuid::Uuid::new_v4());
+        let manager = approvals.manager_for(Arc::new(Sink(tx)), &msg, "session");
+
+        let request = ApprovalRequest {
+            tool_name: "file_write".into(),
+            arguments: serde_json::json!({"path": "a.txt"}),
+        };
+        let waiter = tokio::spawn(async move {
+            let decision = manager.request_approval(&request, "telegram").await;
+            (manager, decision)
+        });
+
+        let prompt = sent.recv().await.unwrap();
+        assert!(prompt.contains("`file_write`"));
+        let mut reply = msg.clone();
+        reply.content = "always".into();
+        reply.sender = "bob".into();
+        assert!(answer_pending_approval(&broker, &reply).is_some());
+
+        let (manager, decision) = waiter.await.unwrap();
+        assert_eq!(
+            decision,
+            ApprovalDecision::by(ApprovalResponse::Always, "telegram:bob")
+        );
+        manager.record_decision_by("file_write", &serde_json::json!({}), &decision, "telegram");
+        approvals.remember("session", &manager);
+        let next = approvals.manager_for(
+            Arc::new(Sink(tokio::sync::mpsc::unbounded_channel().0)),
+            &msg,
+            "session",
+        );
+        assert!(!next.needs_approval("file_write"));
+    }
+
+    #[test]
+    fn disabled_unless_supervised_and_opted_in() {
+        assert!(ChannelApprovals::from_config(&AutonomyConfig::default()).is_none());
+        let full = AutonomyConfig {
+            channel_approvals: true,
+            level: AutonomyLevel::Full,
+            ..AutonomyConfig::default()
+        };
+        assert!(ChannelApprovals::from_config(&full).is_none());
+    }
+}
@@ -0,0 +1,516 @@
+//! Typed file attachments shared by every channel.
+//!
+//! Inbound messages carry the files a user sent as [`Attachment`]s on
+//! [`ChannelMessage::attachments`](super::traits::ChannelMessage), and
+//! outbound replies carry generated reports and images on
+//! [`SendMessage::attachments`](super::traits::SendMessage). The agent
+//! references outbound files with `[KIND:target]` markers (for example
+//! `[IMAGE:/tmp/chart.png]` or `[DOCUMENT:https://example.com/report.pdf]`),
+//! which [`extract_attachment_markers`] turns into attachments.
+
+use crate::security::SecurityPolicy;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Broad attachment category; channels pick their upload API from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentKind {
+    Image,
+    Audio,
+    Voice,
+    Video,
+    Document,
+}
+
+impl AttachmentKind {
+    /// Parse the kind part of a `[KIND:target]` marker.
+    pub fn from_marker(marker: &str) -> Option<Self> {
+        match marker.trim().to_ascii_uppercase().as_str() {
+            "IMAGE" | "PHOTO" => Some(Self::Image),
+            "DOCUMENT" | "FILE" => Some(Self::Document),
+            "VIDEO" => Some(Self::Video),
+            "AUDIO" => Some(Self::Audio),
+            "VOICE" => Some(Self::Voice),
+            _ => None,
+        }
+    }
+
+    pub fn marker_name(self) -> &'static str {
+        match self {
+            Self::Image => "IMAGE",
+            Self::Audio => "AUDIO",
+            Self::Voice => "VOICE",
+            Self::Video => "VIDEO",
+            Self::Document => "DOCUMENT",
+        }
+    }
+
+    /// Classify a MIME type; anything unrecognised is a document.
+    pub fn from_mime(mime_type: &str) -> Self {
+        let essence = mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if essence == "audio/ogg" || essence == "audio/opus" {
+            Self::Voice
+        } else if essence.starts_with("image/") {
+            Self::Image
+        } else if essence.starts_with("audio/") {
+            Self::Audio
+        } else if essence.starts_with("video/") {
+            Self::Video
+        } else {
+            Self::Document
+        }
+    }
+}
+
+/// Where an attachment's content lives.
+#[derive(Clone, PartialEq, Eq)]
+pub enum AttachmentSource {
+    /// A file on the local filesystem (e.g. a download in the workspace).
+    Path(PathBuf),
+    /// A remote URL the platform (or recipient) can fetch.
+    Url(String),
+    /// In-memory content.
+    Bytes(Vec<u8>),
+}
+
+impl std::fmt::Debug for AttachmentSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            Self::Url(url) => f.debug_tuple("Url").field(url).finish(),
+            Self::Bytes(bytes) => write!(f, "Bytes(<{} bytes>)", bytes.len()),
+        }
+    }
+}
+
+/// A file attached to an inbound or outbound channel message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    pub kind: AttachmentKind,
+    pub mime_type: String,
+    pub filename: String,
+    pub source: AttachmentSource,
+    /// Size in bytes, when known.
+    pub size: Option<u64>,
+}
+
+impl Attachment {
+    /// Attach a local file. MIME type and kind are guessed from the extension.
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let mime_type = guess_mime(&path.to_string_lossy());
+        let filename = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("attachment")
+            .to_string();
+        let size = std::fs::metadata(&path).ok().map(|meta| meta.len());
+        Self {
+            kind: AttachmentKind::from_mime(&mime_type),
+            mime_type,
+            filename,
+            source: AttachmentSource::Path(path),
+            size,
+        }
+    }
+
+    /// Attach a remote file by URL.
+    pub fn from_url(url: impl Into<String>) -> Self {
+        let url = url.into();
+        let without_query = url.split(['?', '#']).next().unwrap_or_default();
+        let filename = without_query
+            .rsplit('/')
+            .find(|segment| !segment.is_empty())
+            .filter(|segment| !segment.contains(':'))
+            .unwrap_or("attachment")
+            .to_string();
+        let mime_type = guess_mime(without_query);
+        Self {
+            kind: AttachmentKind::from_mime(&mime_type),
+            mime_type,
+            filename,
+            source: AttachmentSource::Url(url),
+            size: None,
+        }
+    }
+
+    /// Attach in-memory content under `filename`.
+    pub fn from_bytes(filename: impl Into<String>, bytes: Vec<u8>) -> Self {
+        let filename = filename.into();
+        let mime_type = guess_mime(&filename);
+        Self {
+            kind: AttachmentKind::from_mime(&mime_type),
+            mime_type,
+            filename,
+            size: Some(bytes.len() as u64),
+            source: AttachmentSource::Bytes(bytes),
+        }
+    }
+
+    pub fn with_kind(mut self, kind: AttachmentKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = mime_type.into();
+        self
+    }
+
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match &self.source {
+            AttachmentSource::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        match &self.source {
+            AttachmentSource::Url(url) => Some(url),
+            _ => None,
+        }
+    }
+
+    /// Check a local file against `policy` before it is uploaded.
+    ///
+    /// The path must pass [`SecurityPolicy::is_path_allowed`] and resolve
+    /// inside the workspace; relative paths resolve from the workspace. On
+    /// success the attachment points at the canonical path. URL and in-memory
+    /// attachments pass through unchanged.
+    pub fn confined_to(mut self, policy: &SecurityPolicy) -> Result<Self> {
+        let AttachmentSource::Path(path) = &self.source else {
+            return Ok(self);
+        };
+        let workspace = policy
+            .workspace_dir
+            .canonicalize()
+            .unwrap_or_else(|_| policy.workspace_dir.clone());
+        // Absolute paths inside the workspace are checked in their relative form
+        let relative = path
+            .strip_prefix(&policy.workspace_dir)
+            .or_else(|_| path.strip_prefix(&workspace))
+            .unwrap_or(path);
+        if !policy.is_path_allowed(&relative.to_string_lossy()) {
+            anyhow::bail!(
+                "Attachment path '{}' is not allowed by security policy",
+                path.display()
+            );
+        }
+        let resolved = workspace.join(relative).canonicalize().map_err(|e| {
+            anyhow::anyhow!("Failed to resolve attachment '{}': {e}", path.display())
+        })?;
+        if !resolved.starts_with(&workspace) || !policy.is_resolved_path_allowed(&resolved) {
+            anyhow::bail!(
+                "Attachment path '{}' resolves outside the workspace",
+                path.display()
+            );
+        }
+        if self.size.is_none() {
+            self.size = std::fs::metadata(&resolved).ok().map(|meta| meta.len());
+        }
+        self.source = AttachmentSource::Path(resolved);
+        Ok(self)
+    }
+
+    /// Load the content. URLs are not fetched; callers that can upload by
+    /// link should check [`Attachment::url`] first.
+    pub async fn read_bytes(&self) -> Result<Vec<u8>> {
+        match &self.source {
+            AttachmentSource::Bytes(bytes) => Ok(bytes.clone()),
+            AttachmentSource::Path(path) => tokio::fs::read(path).await.map_err(|e| {
+                anyhow::anyhow!("Failed to read attachment '{}': {e}", path.display())
+            }),
+            AttachmentSource::Url(url) => {
+                anyhow::bail!("Attachment '{}' is a remote URL ({url})", self.filename)
+            }
+        }
+    }
+
+    /// One-line summary for the agent's context, e.g.
+    /// `[DOCUMENT] report.pdf (application/pdf, 2048 bytes) at /path/report.pdf`.
+    pub fn describe(&self) -> String {
+        let size = self
+            .size
+            .map(|size| format!(", {size} bytes"))
+            .unwrap_or_default();
+        let location = match &self.source {
+            AttachmentSource::Path(path) => format!(" at {}", path.display()),
+            AttachmentSource::Url(url) => format!(" at {url}"),
+            AttachmentSource::Bytes(_) => String::new(),
+        };
+        format!(
+            "[{}] {} ({}{size}){location}",
+            self.kind.marker_name(),
+            self.filename,
+            self.mime_type
+        )
+    }
+}
+
+/// Largest attachment a channel downloads (the Whisper upload limit).
+pub const MAX_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+
+/// Read a download response into memory, refusing bodies over
+/// [`MAX_ATTACHMENT_BYTES`]: up front from `Content-Length`, and while
+/// streaming for responses that omit or understate it.
+pub async fn read_capped_body(resp: reqwest::Response) -> Result<Vec<u8>> {
+    use futures_util::StreamExt;
+
+    let too_large = || {
+        anyhow::anyhow!(
+            "Attachment exceeds the {} MB download limit",
+            MAX_ATTACHMENT_BYTES / (1024 * 1024)
+        )
+    };
+    let declared = resp.content_length().unwrap_or(0);
+    if declared > MAX_ATTACHMENT_BYTES as u64 {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::with_capacity(usize::try_from(declared).unwrap_or(0));
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if body.len() + chunk.len() > MAX_ATTACHMENT_BYTES {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+fn guess_mime(name: &str) -> String {
+    mime_guess::from_path(name)
+        .first()
+        .map_or_else(|| "application/octet-stream".to_string(), |m| m.to_string())
+}
+
+/// Pull `[KIND:target]` markers out of `text`.
+///
+/// `http(s)` targets become URL attachments and anything else a local path.
+/// Markers with an unknown kind, an empty target or an inline `data:` URI are
+/// left in the text untouched.
+pub fn extract_attachment_markers(text: &str) -> (String, Vec<Attachment>) {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut attachments = Vec::new();
+    let mut cursor = 0;
+
+    while cursor < text.len() {
+        let Some(open_rel) = text[cursor..].find('[') else {
+            cleaned.push_str(&text[cursor..]);
+            break;
+        };
+        let open = cursor + open_rel;
+        cleaned.push_str(&text[cursor..open]);
+
+        let Some(close_rel) = text[open..].find(']') else {
+            cleaned.push_str(&text[open..]);
+            break;
+        };
+        let close = open + close_rel;
+
+        let parsed = text[open + 1..close]
+            .split_once(':')
+            .and_then(|(kind, target)| {
+                let kind = AttachmentKind::from_marker(kind)?;
+                let target = target.trim();
+                if target.is_empty() || target.starts_with("data:") {
+                    return None;
+                }
+                let attachment = if target.starts_with("https://") || target.starts_with("http://")
+                {
+                    Attachment::from_url(target)
+                } else {
+                    Attachment::from_path(target)
+                };
+                Some(attachment.with_kind(kind))
+            });
+
+        match parsed {
+            Some(attachment) => attachments.push(attachment),
+            None => cleaned.push_str(&text[open..=close]),
+        }
+        cursor = close + 1;
+    }
+
+    (cleaned.trim().to_string(), attachments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_from_mime_covers_common_types() {
+        assert_eq!(
+            AttachmentKind::from_mime("image/png"),
+            AttachmentKind::Image
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("audio/ogg; codecs=opus"),
+            AttachmentKind::Voice
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("audio/mpeg"),
+            AttachmentKind::Audio
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("video/mp4"),
+            AttachmentKind::Video
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("application/pdf"),
+            AttachmentKind::Document
+        );
+    }
+
+    #[test]
+    fn from_path_guesses_mime_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chart.png");
+        std::fs::write(&path, b"12345").unwrap();
+
+        let attachment = Attachment::from_path(&path);
+        assert_eq!(attachment.kind, AttachmentKind::Image);
+        assert_eq!(attachment.mime_type, "image/png");
+        assert_eq!(attachment.filename, "chart.png");
+        assert_eq!(attachment.size, Some(5));
+        assert_eq!(attachment.path(), Some(path.as_path()));
+    }
+
+    #[test]
+    fn from_url_ignores_query_string() {
+        let attachment = Attachment::from_url("https://example.com/files/report.pdf?sig=abc");
+        assert_eq!(attachment.filename, "report.pdf");
+        assert_eq!(attachment.mime_type, "application/pdf");
+        assert_eq!(attachment.kind, AttachmentKind::Document);
+        assert_eq!(
+            attachment.url(),
+            Some("https://example.com/files/report.pdf?sig=abc")
+        );
+    }
+
+    #[tokio::test]
+    async fn read_bytes_loads_paths_and_rejects_urls() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, b"hello").unwrap();
+
+        assert_eq!(
+            Attachment::from_path(&path).read_bytes().await.unwrap(),
+            b"hello"
+        );
+        assert_eq!(
+            Attachment::from_bytes("a.txt", b"hi".to_vec())
+                .read_bytes()
+                .await
+                .unwrap(),
+            b"hi"
+        );
+        assert!(Attachment::from_url("https://example.com/a.txt")
+            .read_bytes()
+            .await
+            .is_err());
+    }
+
+    fn workspace_policy(workspace: &Path) -> SecurityPolicy {
+        SecurityPolicy {
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        }
+    }
+
+    #[test]
+    fn confined_to_accepts_workspace_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("report.pdf"), b"%PDF").unwrap();
+        let policy = workspace_policy(dir.path());
+        let canonical = dir.path().canonicalize().unwrap().join("report.pdf");
+
+        let relative = Attachment::from_path("report.pdf")
+            .confined_to(&policy)
+            .unwrap();
+        assert_eq!(relative.path(), Some(canonical.as_path()));
+        assert_eq!(relative.size, Some(4));
+
+        let absolute = Attachment::from_path(dir.path().join("report.pdf"))
+            .confined_to(&policy)
+            .unwrap();
+        assert_eq!(absolute.path(), Some(canonical.as_path()));
+    }
+
+    #[test]
+    fn confined_to_rejects_paths_outside_the_workspace() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let secret = outside.path().join("secret.txt");
+        std::fs::write(&secret, b"hunter2").unwrap();
+        let policy = workspace_policy(workspace.path());
+
+        let (_, attachments) =
+            extract_attachment_markers(&format!("[DOCUMENT:{}]", secret.display()));
+        let err = attachments
+            .into_iter()
+            .next()
+            .unwrap()
+            .confined_to(&policy)
+            .unwrap_err();
+        assert!(err.to_string().contains("not allowed"), "{err}");
+
+        assert!(Attachment::from_path("../secret.txt")
+            .confined_to(&policy)
+            .is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&secret, workspace.path().join("link.txt")).unwrap();
+            let err = Attachment::from_path("link.txt")
+                .confined_to(&policy)
+                .unwrap_err();
+            assert!(err.to_string().contains("outside the workspace"), "{err}");
+        }
+
+        let url = Attachment::from_url("https://example.com/r.pdf");
+        assert!(url.confined_to(&policy).is_ok());
+    }
+
+    #[test]
+    fn extract_markers_builds_typed_attachments() {
+        let (cleaned, attachments) = extract_attachment_markers(
+            "Done [IMAGE:/tmp/chart.png] and [DOCUMENT:https://example.com/r.pdf]",
+        );
+        assert_eq!(cleaned, "Done  and");
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0].kind, AttachmentKind::Image);
+        assert_eq!(attachments[0].path(), Some(Path::new("/tmp/chart.png")));
+        assert_eq!(attachments[1].kind, AttachmentKind::Document);
+        assert_eq!(attachments[1].url(), Some("https://example.com/r.pdf"));
+    }
+
+    #[test]
+    fn extract_markers_keeps_unknown_and_data_uri_markers() {
+        let text = "See [UNKNOWN:/tmp/a] [IMAGE:data:image/png;base64,AAAA] [link]";
+        let (cleaned, attachments) = extract_attachment_markers(text);
+        assert_eq!(cleaned, text);
+        assert!(attachments.is_empty());
+    }
+
+    #[test]
+    fn describe_includes_location_and_size() {
+        let attachment = Attachment::from_bytes("report.csv", b"a,b\n".to_vec());
+        assert_eq!(
+            attachment.describe(),
+            "[DOCUMENT] report.csv (text/csv, 4 bytes)"
+        );
+    }
+}
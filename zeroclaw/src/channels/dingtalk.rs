@@ -111,6 +111,10 @@ impl Channel for DingTalkChannel {
         "dingtalk"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let webhooks = self.session_webhooks.read().await;
         let webhook_url = webhooks.get(&message.recipient).ok_or_else(|| {
@@ -275,6 +279,7 @@ impl Channel for DingTalkChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        attachments: Vec::new(),
                     };
 
                     if tx.send(channel_msg).await.is_err() {
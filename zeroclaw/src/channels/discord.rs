@@ -1,3 +1,4 @@
+use super::attachment::{Attachment, AttachmentKind, AttachmentSource};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
@@ -103,6 +104,29 @@ async fn process_attachments(
     parts.join("\n---\n")
 }
 
+/// Typed view of Discord message attachments; files stay on the CDN.
+fn inbound_attachments(attachments: &[serde_json::Value]) -> Vec<Attachment> {
+    attachments
+        .iter()
+        .filter_map(|att| {
+            let url = att.get("url").and_then(|v| v.as_str())?;
+            let mut attachment = Attachment::from_url(url);
+            if let Some(name) = att.get("filename").and_then(|v| v.as_str()) {
+                attachment.filename = name.to_string();
+            }
+            if let Some(ct) = att.get("content_type").and_then(|v| v.as_str()) {
+                attachment = attachment
+                    .with_kind(AttachmentKind::from_mime(ct))
+                    .with_mime_type(ct);
+            }
+            if let Some(size) = att.get("size").and_then(serde_json::Value::as_u64) {
+                attachment = attachment.with_size(size);
+            }
+            Some(attachment)
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum DiscordAttachmentKind {
     Image,
@@ -306,6 +330,40 @@ async fn send_discord_message_with_files(
     Ok(())
 }
 
+async fn send_discord_file(
+    client: &reqwest::Client,
+    bot_token: &str,
+    recipient: &str,
+    filename: &str,
+    bytes: Vec<u8>,
+) -> anyhow::Result<()> {
+    let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
+    let form = Form::new()
+        .text("payload_json", json!({ "content": "" }).to_string())
+        .part(
+            "files[0]",
+            Part::bytes(bytes).file_name(filename.to_string()),
+        );
+
+    let resp = client
+        .post(&url)
+        .header("Authorization", format!("Bot {bot_token}"))
+        .multipart(form)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let err = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        anyhow::bail!("Discord file upload failed ({status}): {err}");
+    }
+
+    Ok(())
+}
+
 const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
 
 /// Discord's maximum message length for regular messages.
@@ -413,6 +471,16 @@ fn contains_bot_mention(content: &str, bot_user_id: &str) -> bool {
     content.contains(&tags[0]) || content.contains(&tags[1])
 }
 
+/// Discord message flag set on voice messages.
+const DISCORD_VOICE_MESSAGE_FLAG: u64 = 1 << 13;
+
+fn is_voice_message(message: &serde_json::Value) -> bool {
+    message
+        .get("flags")
+        .and_then(serde_json::Value::as_u64)
+        .is_some_and(|flags| flags & DISCORD_VOICE_MESSAGE_FLAG != 0)
+}
+
 fn normalize_incoming_content(
     content: &str,
     mention_only: bool,
@@ -485,6 +553,10 @@ impl Channel for DiscordChannel {
         "discord"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let raw_content = super::strip_tool_call_tags(&message.content);
         let (cleaned_content, parsed_attachments) = parse_attachment_markers(&raw_content);
@@ -535,6 +607,28 @@ impl Channel for DiscordChannel {
         Ok(())
     }
 
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        recipient: &str,
+        _thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let client = self.http_client();
+        // Discord unfurls links, so remote files are posted as their URL.
+        if let AttachmentSource::Url(url) = &attachment.source {
+            return send_discord_message_json(&client, &self.bot_token, recipient, url).await;
+        }
+        let bytes = attachment.read_bytes().await?;
+        send_discord_file(
+            &client,
+            &self.bot_token,
+            recipient,
+            &attachment.filename,
+            bytes,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_lines)]
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let bot_user_id = Self::bot_user_id_from_token(&self.bot_token).unwrap_or_default();
@@ -698,20 +792,21 @@ impl Channel for DiscordChannel {
                     }
 
                     let content = d.get("content").and_then(|c| c.as_str()).unwrap_or("");
-                    let Some(clean_content) =
-                        normalize_incoming_content(content, self.mention_only, &bot_user_id)
-                    else {
-                        continue;
-                    };
-
-                    let attachment_text = {
-                        let atts = d
-                            .get("attachments")
-                            .and_then(|a| a.as_array())
-                            .cloned()
-                            .unwrap_or_default();
-                        process_attachments(&atts, &self.http_client()).await
-                    };
+                    // Voice messages carry no text and cannot mention the bot.
+                    let clean_content =
+                        match normalize_incoming_content(content, self.mention_only, &bot_user_id)
+                        {
+                            Some(clean) => clean,
+                            None if is_voice_message(d) && !self.mention_only => String::new(),
+                            None => continue,
+                        };
+
+                    let atts = d
+                        .get("attachments")
+                        .and_then(|a| a.as_array())
+                        .cloned()
+                        .unwrap_or_default();
+                    let attachment_text = process_attachments(&atts, &self.http_client()).await;
                     let final_content = if attachment_text.is_empty() {
                         clean_content
                     } else {
@@ -771,6 +866,7 @@ impl Channel for DiscordChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        attachments: inbound_attachments(&atts),
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -1415,8 +1511,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn voice_message_flag_detected() {
+        let voice = serde_json::json!({
+            "content": "",
+            "flags": 8192,
+            "attachments": [{
+                "url": "https://cdn.discordapp.com/attachments/1/2/voice-message.ogg",
+                "filename": "voice-message.ogg",
+                "content_type": "audio/ogg"
+            }]
+        });
+        assert!(is_voice_message(&voice));
+        assert!(!is_voice_message(&serde_json::json!({ "flags": 4 })));
+        assert!(!is_voice_message(&serde_json::json!({})));
+
+        let atts = voice["attachments"].as_array().unwrap();
+        assert_eq!(inbound_attachments(atts)[0].kind, AttachmentKind::Voice);
+    }
+
     // process_attachments tests
 
+    #[test]
+    fn inbound_attachments_keep_discord_metadata() {
+        let atts = vec![
+            serde_json::json!({
+                "url": "https://cdn.discordapp.com/attachments/1/2/chart.png",
+                "filename": "chart.png",
+                "content_type": "image/png",
+                "size": 512
+            }),
+            serde_json::json!({ "filename": "missing-url.txt" }),
+        ];
+        let parsed = inbound_attachments(&atts);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].kind, AttachmentKind::Image);
+        assert_eq!(parsed[0].filename, "chart.png");
+        assert_eq!(parsed[0].size, Some(512));
+    }
+
     #[tokio::test]
     async fn process_attachments_empty_list_returns_empty() {
         let client = reqwest::Client::new();
@@ -14,7 +14,8 @@ use async_imap::types::Fetch;
 use async_imap::Session;
 use async_trait::async_trait;
 use futures_util::TryStreamExt;
-use lettre::message::SinglePart;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment as MailAttachment, MultiPart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
@@ -22,7 +23,9 @@ use rustls::{ClientConfig, RootCertStore};
 use rustls_pki_types::DnsName;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::net::TcpStream;
@@ -33,6 +36,7 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::attachment::{Attachment, AttachmentKind, AttachmentSource};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 
 /// Email channel configuration
@@ -114,10 +118,26 @@ impl Default for EmailConfig {
 
 type ImapSession = Session<TlsStream<TcpStream>>;
 
+/// Inbound attachments larger than this are dropped (25 MB, a common SMTP cap).
+const EMAIL_MAX_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+
 /// Email channel — IMAP IDLE for instant push notifications, SMTP for outbound
 pub struct EmailChannel {
     pub config: EmailConfig,
     seen_messages: Arc<Mutex<HashSet<String>>>,
+    /// Latest message per thread root, used to build reply headers.
+    threads: Arc<Mutex<HashMap<String, ThreadContext>>>,
+    workspace_dir: Option<PathBuf>,
+}
+
+/// What a reply needs to stay in the sender's mail-client thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ThreadContext {
+    /// Message-ID of the latest inbound message in the thread.
+    message_id: String,
+    /// Full `References` chain for a reply, ending with `message_id`.
+    references: Vec<String>,
+    subject: String,
 }
 
 impl EmailChannel {
@@ -125,9 +145,17 @@ impl EmailChannel {
         Self {
             config,
             seen_messages: Arc::new(Mutex::new(HashSet::new())),
+            threads: Arc::new(Mutex::new(HashMap::new())),
+            workspace_dir: None,
         }
     }
 
+    /// Save inbound attachments under `{dir}/email_files/`.
+    pub fn with_workspace_dir(mut self, dir: PathBuf) -> Self {
+        self.workspace_dir = Some(dir);
+        self
+    }
+
     /// Check if a sender email is in the allowlist
     pub fn is_sender_allowed(&self, email: &str) -> bool {
         if self.config.allowed_senders.is_empty() {
@@ -260,50 +288,8 @@ impl EmailChannel {
 
         for msg in messages {
             let uid = msg.uid.unwrap_or(0);
-            if let Some(body) = msg.body() {
-                if let Some(parsed) = MessageParser::default().parse(body) {
-                    let sender = Self::extract_sender(&parsed);
-                    let subject = parsed.subject().unwrap_or("(no subject)").to_string();
-                    let body_text = Self::extract_text(&parsed);
-                    let content = format!("Subject: {}\n\n{}", subject, body_text);
-                    let msg_id = parsed
-                        .message_id()
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
-
-                    #[allow(clippy::cast_sign_loss)]
-                    let ts = parsed
-                        .date()
-                        .map(|d| {
-                            let naive = chrono::NaiveDate::from_ymd_opt(
-                                d.year as i32,
-                                u32::from(d.month),
-                                u32::from(d.day),
-                            )
-                            .and_then(|date| {
-                                date.and_hms_opt(
-                                    u32::from(d.hour),
-                                    u32::from(d.minute),
-                                    u32::from(d.second),
-                                )
-                            });
-                            naive.map_or(0, |n| n.and_utc().timestamp() as u64)
-                        })
-                        .unwrap_or_else(|| {
-                            SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .map(|d| d.as_secs())
-                                .unwrap_or(0)
-                        });
-
-                    results.push(ParsedEmail {
-                        _uid: uid,
-                        msg_id,
-                        sender,
-                        content,
-                        timestamp: ts,
-                    });
-                }
+            if let Some(email) = msg.body().and_then(|body| Self::parse_email(uid, body)) {
+                results.push(email);
             }
         }
 
@@ -319,6 +305,168 @@ impl EmailChannel {
         Ok(results)
     }
 
+    /// Parse a raw RFC 822 message into its text, threading headers and attachments.
+    fn parse_email(uid: u32, raw: &[u8]) -> Option<ParsedEmail> {
+        let parsed = MessageParser::default().parse(raw)?;
+        let sender = Self::extract_sender(&parsed);
+        let subject = parsed.subject().unwrap_or("(no subject)").to_string();
+        let body_text = Self::extract_text(&parsed);
+        let content = format!("Subject: {}\n\n{}", subject, body_text);
+        let msg_id = parsed
+            .message_id()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
+
+        // Thread root: the first `References` entry, else `In-Reply-To`, else
+        // this message starts a new thread.
+        let header_ids = |value: &mail_parser::HeaderValue| -> Vec<String> {
+            value
+                .as_text_list()
+                .unwrap_or_default()
+                .iter()
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .collect()
+        };
+        let mut references = header_ids(parsed.references());
+        if references.is_empty() {
+            references = header_ids(parsed.in_reply_to());
+        }
+        let thread_root = references
+            .first()
+            .cloned()
+            .unwrap_or_else(|| msg_id.clone());
+        references.push(msg_id.clone());
+
+        #[allow(clippy::cast_sign_loss)]
+        let ts = parsed
+            .date()
+            .map(|d| {
+                let naive = chrono::NaiveDate::from_ymd_opt(
+                    d.year as i32,
+                    u32::from(d.month),
+                    u32::from(d.day),
+                )
+                .and_then(|date| {
+                    date.and_hms_opt(u32::from(d.hour), u32::from(d.minute), u32::from(d.second))
+                });
+                naive.map_or(0, |n| n.and_utc().timestamp() as u64)
+            })
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0)
+            });
+
+        Some(ParsedEmail {
+            _uid: uid,
+            msg_id,
+            sender,
+            subject,
+            content,
+            timestamp: ts,
+            thread_root,
+            references,
+            attachments: Self::extract_attachments(&parsed),
+        })
+    }
+
+    /// Collect attachment parts, skipping any over [`EMAIL_MAX_ATTACHMENT_BYTES`].
+    fn extract_attachments(parsed: &mail_parser::Message) -> Vec<RawAttachment> {
+        parsed
+            .attachments()
+            .enumerate()
+            .filter_map(|(idx, part)| {
+                let data = part.contents();
+                let filename = MimeHeaders::attachment_name(part)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("attachment-{}", idx + 1));
+                if data.len() > EMAIL_MAX_ATTACHMENT_BYTES {
+                    info!(
+                        "Skipping email attachment {filename}: {} bytes exceeds {} MB limit",
+                        data.len(),
+                        EMAIL_MAX_ATTACHMENT_BYTES / (1024 * 1024)
+                    );
+                    return None;
+                }
+                let mime_type = MimeHeaders::content_type(part)
+                    .map(|ct| match ct.subtype() {
+                        Some(sub) => format!("{}/{}", ct.ctype(), sub),
+                        None => ct.ctype().to_string(),
+                    })
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                Some(RawAttachment {
+                    filename,
+                    mime_type,
+                    data: data.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Write attachments to `{workspace}/email_files/` and append a marker per
+    /// file to `content`: `[IMAGE:path]` for images (multimodal input) and
+    /// `[Document: name] path` for everything else (e.g. PDFs for `pdf_read`).
+    async fn save_attachments(
+        &self,
+        attachments: &[RawAttachment],
+        content: &mut String,
+    ) -> Vec<Attachment> {
+        if attachments.is_empty() {
+            return Vec::new();
+        }
+        let Some(workspace) = self.workspace_dir.as_ref() else {
+            warn!("Cannot save email attachments: workspace_dir not configured");
+            return Vec::new();
+        };
+        let save_dir = workspace.join("email_files");
+        if let Err(e) = tokio::fs::create_dir_all(&save_dir).await {
+            warn!("Failed to create email_files directory: {e}");
+            return Vec::new();
+        }
+
+        let mut saved = Vec::new();
+        for raw in attachments {
+            let safe_name: String = raw
+                .filename
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let unique = Uuid::new_v4().simple().to_string();
+            let path = save_dir.join(format!("{}_{}", &unique[..8], safe_name));
+            if let Err(e) = tokio::fs::write(&path, &raw.data).await {
+                warn!("Failed to save email attachment to {}: {e}", path.display());
+                continue;
+            }
+
+            let attachment = Attachment::from_path(&path)
+                .with_mime_type(raw.mime_type.clone())
+                .with_kind(AttachmentKind::from_mime(&raw.mime_type));
+            if attachment.kind == AttachmentKind::Image {
+                let _ = write!(content, "\n\n[IMAGE:{}]", path.display());
+            } else {
+                let _ = write!(
+                    content,
+                    "\n\n[Document: {}] {}",
+                    raw.filename,
+                    path.display()
+                );
+            }
+            saved.push(Attachment {
+                filename: raw.filename.clone(),
+                ..attachment
+            });
+        }
+        saved
+    }
+
     /// Run the IDLE loop, returning when a new message arrives or timeout
     /// Note: IDLE consumes the session and returns it via done()
     async fn wait_for_changes(
@@ -452,14 +600,29 @@ impl EmailChannel {
                 continue;
             }
 
+            let mut content = email.content;
+            let attachments = self
+                .save_attachments(&email.attachments, &mut content)
+                .await;
+
+            self.threads.lock().await.insert(
+                email.thread_root.clone(),
+                ThreadContext {
+                    message_id: email.msg_id.clone(),
+                    references: email.references,
+                    subject: email.subject,
+                },
+            );
+
             let msg = ChannelMessage {
                 id: email.msg_id,
                 reply_target: email.sender.clone(),
                 sender: email.sender,
-                content: email.content,
+                content,
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
-                thread_ts: None,
+                thread_ts: Some(email.thread_root),
+                attachments,
             };
 
             if tx.send(msg).await.is_err() {
@@ -471,6 +634,85 @@ impl EmailChannel {
         Ok(())
     }
 
+    /// Build the outgoing message. Replies within a known thread get
+    /// `In-Reply-To`/`References` headers and a `Re:` subject; the body is
+    /// sent as plain text plus an HTML rendering of its Markdown.
+    fn build_email(
+        &self,
+        message: &SendMessage,
+        thread: Option<&ThreadContext>,
+        files: &[(&Attachment, Vec<u8>)],
+    ) -> Result<Message> {
+        let reply_subject = thread.map(|t| {
+            if t.subject.to_ascii_lowercase().starts_with("re:") {
+                t.subject.clone()
+            } else {
+                format!("Re: {}", t.subject)
+            }
+        });
+        let fallback_subject = reply_subject.as_deref().unwrap_or("ZeroClaw Message");
+
+        // Use explicit subject if provided, otherwise fall back to legacy parsing or default
+        let (subject, body) = if let Some(ref subj) = message.subject {
+            (subj.as_str(), message.content.as_str())
+        } else if message.content.starts_with("Subject: ") {
+            if let Some(pos) = message.content.find('\n') {
+                (&message.content[9..pos], message.content[pos + 1..].trim())
+            } else {
+                (fallback_subject, message.content.as_str())
+            }
+        } else {
+            (fallback_subject, message.content.as_str())
+        };
+
+        let mut builder = Message::builder()
+            .from(self.config.from_address.parse()?)
+            .to(message.recipient.parse()?)
+            .subject(subject);
+        if let Some(thread) = thread {
+            let references: Vec<String> = thread
+                .references
+                .iter()
+                .map(|id| format!("<{id}>"))
+                .collect();
+            builder = builder
+                .in_reply_to(format!("<{}>", thread.message_id))
+                .references(references.join(" "));
+        }
+
+        let alternative =
+            MultiPart::alternative_plain_html(body.to_string(), markdown_to_html(body));
+        if files.is_empty() {
+            return Ok(builder.multipart(alternative)?);
+        }
+
+        let mut parts = MultiPart::mixed().multipart(alternative);
+        for (attachment, bytes) in files {
+            let content_type = ContentType::parse(&attachment.mime_type)
+                .or_else(|_| ContentType::parse("application/octet-stream"))?;
+            parts = parts.singlepart(
+                MailAttachment::new(attachment.filename.clone()).body(bytes.clone(), content_type),
+            );
+        }
+        Ok(builder.multipart(parts)?)
+    }
+
+    async fn deliver(&self, message: &SendMessage, files: &[(&Attachment, Vec<u8>)]) -> Result<()> {
+        let thread = match message.thread_ts.as_deref() {
+            Some(root) => self.threads.lock().await.get(root).cloned(),
+            None => None,
+        };
+        let email = self.build_email(message, thread.as_ref(), files)?;
+        let transport = self.create_smtp_transport()?;
+        transport.send(&email)?;
+        info!(
+            "Email sent to {} with {} attachment(s)",
+            message.recipient,
+            files.len()
+        );
+        Ok(())
+    }
+
     fn create_smtp_transport(&self) -> Result<SmtpTransport> {
         let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
         let transport = if self.config.smtp_tls {
@@ -493,8 +735,157 @@ struct ParsedEmail {
     _uid: u32,
     msg_id: String,
     sender: String,
+    subject: String,
     content: String,
     timestamp: u64,
+    /// Message-ID of the first message in the thread.
+    thread_root: String,
+    /// `References` chain for a reply to this message.
+    references: Vec<String>,
+    attachments: Vec<RawAttachment>,
+}
+
+/// Attachment bytes parsed from a message, saved only once the sender passes
+/// the allowlist.
+struct RawAttachment {
+    filename: String,
+    mime_type: String,
+    data: Vec<u8>,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render inline Markdown: `code`, **bold**, *italic* and http(s) links.
+fn inline_markdown_to_html(text: &str) -> String {
+    let escaped = escape_html(text);
+    let mut out = String::with_capacity(escaped.len());
+    let mut rest = escaped.as_str();
+
+    while let Some(ch) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                let _ = write!(out, "<code>{}</code>", &after[..end]);
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            if let Some(end) = after.find("**") {
+                let _ = write!(out, "<strong>{}</strong>", &after[..end]);
+                rest = &after[end + 2..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('*') {
+            if let Some(end) = after.find('*').filter(|&end| end > 0) {
+                let _ = write!(out, "<em>{}</em>", &after[..end]);
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('[') {
+            if let Some(close) = after.find("](") {
+                let target = &after[close + 2..];
+                if let Some(end) = target.find(')') {
+                    let url = &target[..end];
+                    if url.starts_with("http://") || url.starts_with("https://") {
+                        let _ = write!(out, "<a href=\"{url}\">{}</a>", &after[..close]);
+                        rest = &target[end + 1..];
+                        continue;
+                    }
+                }
+            }
+        }
+        out.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    out
+}
+
+/// Render a reply's Markdown as a small HTML document: headings, paragraphs,
+/// bullet and numbered lists, fenced code blocks and inline formatting.
+fn markdown_to_html(markdown: &str) -> String {
+    fn flush(html: &mut String, paragraph: &mut Vec<String>, list: &mut Option<&'static str>) {
+        if !paragraph.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", paragraph.join("<br>\n"));
+            paragraph.clear();
+        }
+        if let Some(tag) = list.take() {
+            let _ = writeln!(html, "</{tag}>");
+        }
+    }
+
+    let mut html = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut list: Option<&'static str> = None;
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            flush(&mut html, &mut paragraph, &mut list);
+            html.push_str(if in_code {
+                "</code></pre>\n"
+            } else {
+                "<pre><code>"
+            });
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            html.push_str(&escape_html(line));
+            html.push('\n');
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush(&mut html, &mut paragraph, &mut list);
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            flush(&mut html, &mut paragraph, &mut list);
+            let _ = writeln!(
+                html,
+                "<h{hashes}>{}</h{hashes}>",
+                inline_markdown_to_html(trimmed[hashes..].trim())
+            );
+            continue;
+        }
+
+        let bullet = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .map(|item| ("ul", item));
+        let numbered = trimmed.split_once(". ").and_then(|(n, item)| {
+            (!n.is_empty() && n.chars().all(|c| c.is_ascii_digit())).then_some(("ol", item))
+        });
+        if let Some((tag, item)) = bullet.or(numbered) {
+            if list != Some(tag) {
+                flush(&mut html, &mut paragraph, &mut list);
+                let _ = writeln!(html, "<{tag}>");
+                list = Some(tag);
+            }
+            let _ = writeln!(html, "<li>{}</li>", inline_markdown_to_html(item));
+            continue;
+        }
+
+        if let Some(tag) = list.take() {
+            let _ = writeln!(html, "</{tag}>");
+        }
+        paragraph.push(inline_markdown_to_html(trimmed));
+    }
+
+    if in_code {
+        html.push_str("</code></pre>\n");
+    }
+    flush(&mut html, &mut paragraph, &mut list);
+    format!("<html><body>\n{html}</body></html>")
 }
 
 /// Result from waiting on IDLE
@@ -510,30 +901,48 @@ impl Channel for EmailChannel {
         "email"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_sender_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> Result<()> {
-        // Use explicit subject if provided, otherwise fall back to legacy parsing or default
-        let (subject, body) = if let Some(ref subj) = message.subject {
-            (subj.as_str(), message.content.as_str())
-        } else if message.content.starts_with("Subject: ") {
-            if let Some(pos) = message.content.find('\n') {
-                (&message.content[9..pos], message.content[pos + 1..].trim())
-            } else {
-                ("ZeroClaw Message", message.content.as_str())
-            }
-        } else {
-            ("ZeroClaw Message", message.content.as_str())
-        };
+        self.deliver(message, &[]).await
+    }
 
-        let email = Message::builder()
-            .from(self.config.from_address.parse()?)
-            .to(message.recipient.parse()?)
-            .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        recipient: &str,
+        thread_ts: Option<&str>,
+    ) -> Result<()> {
+        self.send_with_attachments(
+            &SendMessage::new("", recipient)
+                .in_thread(thread_ts.map(str::to_string))
+                .with_attachments(vec![attachment.clone()]),
+        )
+        .await
+    }
 
-        let transport = self.create_smtp_transport()?;
-        transport.send(&email)?;
-        info!("Email sent to {}", message.recipient);
-        Ok(())
+    /// Send the reply and its attachments as a single multipart email.
+    /// Remote files are linked in the body rather than fetched.
+    async fn send_with_attachments(&self, message: &SendMessage) -> Result<()> {
+        let mut message = message.clone();
+        let mut files = Vec::new();
+        for attachment in &message.attachments {
+            match &attachment.source {
+                AttachmentSource::Url(url) => {
+                    message.content = format!(
+                        "{}\n\n📎 {}: {url}",
+                        message.content.trim_end(),
+                        attachment.filename
+                    );
+                }
+                AttachmentSource::Path(_) | AttachmentSource::Bytes(_) => {
+                    files.push((attachment, attachment.read_bytes().await?));
+                }
+            }
+        }
+        self.deliver(&message, &files).await
     }
 
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
@@ -674,6 +1083,145 @@ mod tests {
         assert_eq!(seen_guard.len(), 0);
     }
 
+    #[test]
+    fn build_email_attaches_files_as_multipart() {
+        let channel = EmailChannel::new(EmailConfig {
+            from_address: "bot@example.com".into(),
+            ..EmailConfig::default()
+        });
+        let attachment = Attachment::from_bytes("report.csv", b"a,b\n1,2\n".to_vec());
+        let bytes = b"a,b\n1,2\n".to_vec();
+        let message = SendMessage::with_subject("See attached", "user@example.com", "Report");
+
+        let email = channel
+            .build_email(&message, None, &[(&attachment, bytes)])
+            .unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+        assert!(raw.contains("multipart/mixed"));
+        assert!(raw.contains("filename=\"report.csv\""));
+        assert!(raw.contains("See attached"));
+
+        let plain = channel.build_email(&message, None, &[]).unwrap();
+        let raw = String::from_utf8(plain.formatted()).unwrap();
+        assert!(!raw.contains("multipart/mixed"));
+    }
+
+    const THREADED_EMAIL: &str = "From: Alice <alice@example.com>\r
+To: bot@example.com\r
+Subject: Re: Quarterly numbers\r
+Message-ID: <m3@example.com>\r
+In-Reply-To: <m2@example.com>\r
+References: <m1@example.com> <m2@example.com>\r
+MIME-Version: 1.0\r
+Content-Type: multipart/mixed; boundary=\"b1\"\r
+\r
+--b1\r
+Content-Type: text/plain\r
+\r
+Numbers attached.\r
+--b1\r
+Content-Type: application/pdf; name=\"q3.pdf\"\r
+Content-Disposition: attachment; filename=\"q3.pdf\"\r
+Content-Transfer-Encoding: base64\r
+\r
+JVBERi0xLjQ=\r
+--b1\r
+Content-Type: image/png\r
+Content-Disposition: attachment; filename=\"chart.png\"\r
+Content-Transfer-Encoding: base64\r
+\r
+iVBORw0KGgo=\r
+--b1--\r
+";
+
+    #[test]
+    fn parse_email_extracts_thread_and_attachments() {
+        let email = EmailChannel::parse_email(7, THREADED_EMAIL.as_bytes()).unwrap();
+        assert_eq!(email.sender, "alice@example.com");
+        assert_eq!(email.msg_id, "m3@example.com");
+        assert_eq!(email.thread_root, "m1@example.com");
+        assert_eq!(
+            email.references,
+            vec!["m1@example.com", "m2@example.com", "m3@example.com"]
+        );
+        assert!(email.content.contains("Numbers attached."));
+
+        let names: Vec<_> = email
+            .attachments
+            .iter()
+            .map(|a| a.filename.as_str())
+            .collect();
+        assert_eq!(names, vec!["q3.pdf", "chart.png"]);
+        assert_eq!(email.attachments[0].mime_type, "application/pdf");
+        assert_eq!(email.attachments[0].data, b"%PDF-1.4");
+    }
+
+    #[test]
+    fn parse_email_without_references_starts_a_thread() {
+        let raw = "From: bob@example.com\r\nSubject: Hi\r\nMessage-ID: <solo@example.com>\r\n\r\nHello\r\n";
+        let email = EmailChannel::parse_email(1, raw.as_bytes()).unwrap();
+        assert_eq!(email.thread_root, "solo@example.com");
+        assert_eq!(email.references, vec!["solo@example.com"]);
+        assert!(email.attachments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn save_attachments_writes_files_and_markers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let channel =
+            EmailChannel::new(EmailConfig::default()).with_workspace_dir(tmp.path().to_path_buf());
+        let email = EmailChannel::parse_email(7, THREADED_EMAIL.as_bytes()).unwrap();
+
+        let mut content = String::from("Subject: Re: Quarterly numbers");
+        let saved = channel
+            .save_attachments(&email.attachments, &mut content)
+            .await;
+
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[0].kind, AttachmentKind::Document);
+        assert_eq!(saved[1].kind, AttachmentKind::Image);
+        let pdf_path = saved[0].path().unwrap();
+        assert!(pdf_path.starts_with(tmp.path().join("email_files")));
+        assert_eq!(std::fs::read(pdf_path).unwrap(), b"%PDF-1.4");
+        assert!(content.contains(&format!("[Document: q3.pdf] {}", pdf_path.display())));
+        assert!(content.contains(&format!("[IMAGE:{}]", saved[1].path().unwrap().display())));
+    }
+
+    #[test]
+    fn build_email_threads_replies_and_renders_html() {
+        let channel = EmailChannel::new(EmailConfig {
+            from_address: "bot@example.com".into(),
+            ..EmailConfig::default()
+        });
+        let thread = ThreadContext {
+            message_id: "m3@example.com".into(),
+            references: vec!["m1@example.com".into(), "m3@example.com".into()],
+            subject: "Quarterly numbers".into(),
+        };
+        let message = SendMessage::new("**Revenue** is up", "alice@example.com");
+
+        let email = channel.build_email(&message, Some(&thread), &[]).unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+        assert!(raw.contains("Subject: Re: Quarterly numbers"));
+        assert!(raw.contains("In-Reply-To: <m3@example.com>"));
+        assert!(raw.contains("References: <m1@example.com> <m3@example.com>"));
+        assert!(raw.contains("multipart/alternative"));
+        assert!(raw.contains("text/html"));
+        assert!(raw.contains("<strong>Revenue</strong>"));
+    }
+
+    #[test]
+    fn markdown_to_html_renders_common_blocks() {
+        let html = markdown_to_html(
+            "# Summary\nLine one\nline <two>\n\n- a `x`\n- [docs](https://example.com)\n\n1. first\n\n```\nlet x = 1 < 2;\n```",
+        );
+        assert!(html.contains("<h1>Summary</h1>"));
+        assert!(html.contains("<p>Line one<br>\nline &lt;two&gt;</p>"));
+        assert!(html.contains("<ul>\n<li>a <code>x</code></li>\n<li><a href=\"https://example.com\">docs</a></li>\n</ul>"));
+        assert!(html.contains("<ol>\n<li>first</li>\n</ol>"));
+        assert!(html.contains("<pre><code>let x = 1 &lt; 2;\n</code></pre>"));
+    }
+
     #[test]
     fn email_channel_name() {
         let channel = EmailChannel::new(EmailConfig::default());
@@ -0,0 +1,331 @@
+//! Durable per-sender conversation history for channels.
+//!
+//! Mirrors the in-memory `conversation_histories` map into
+//! `memory/channel_history.db` (next to `brain.db`) so Telegram, Discord,
+//! Slack, etc. conversations survive daemon restarts. Each sender key stores
+//! its full (already capped and compacted) turn list as one JSON row, so the
+//! on-disk copy always matches what the runtime would send to the provider.
+
+use crate::providers::ChatMessage;
+use anyhow::Context;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// SQLite-backed store for channel conversation histories.
+pub struct ChannelHistoryStore {
+    conn: Mutex<Connection>,
+    db_path: PathBuf,
+    /// Conversations idle longer than this are dropped (`None` = keep forever).
+    ttl_secs: Option<i64>,
+    /// Latest unsaved turns per sender, written by [`Self::save_in_background`].
+    pending: Mutex<HashMap<String, Vec<ChatMessage>>>,
+    /// Held while taking and writing a pending snapshot, so a newer snapshot
+    /// is never overwritten by an older one.
+    writer: Mutex<()>,
+}
+
+impl ChannelHistoryStore {
+    /// Open (or create) `memory/channel_history.db` under `workspace_dir`.
+    ///
+    /// `ttl_hours == 0` disables expiry.
+    pub fn open(workspace_dir: &Path, ttl_hours: u64) -> anyhow::Result<Self> {
+        let db_path = workspace_dir.join("memory").join("channel_history.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             CREATE TABLE IF NOT EXISTS channel_history (
+                 sender_key TEXT PRIMARY KEY,
+                 turns      TEXT NOT NULL,
+                 updated_at INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_channel_history_updated
+                 ON channel_history(updated_at);",
+        )?;
+
+        let ttl_secs = (ttl_hours > 0)
+            .then(|| i64::try_from(ttl_hours.saturating_mul(3600)).unwrap_or(i64::MAX));
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            db_path,
+            ttl_secs,
+            pending: Mutex::new(HashMap::new()),
+            writer: Mutex::new(()),
+        })
+    }
+
+    /// Path of the backing database file.
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    /// Prune expired conversations and return everything that remains.
+    pub fn load_all(&self) -> anyhow::Result<HashMap<String, Vec<ChatMessage>>> {
+        self.prune_expired()?;
+
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT sender_key, turns FROM channel_history")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut histories = HashMap::new();
+        for row in rows {
+            let (key, turns) = row?;
+            match serde_json::from_str::<Vec<ChatMessage>>(&turns) {
+                Ok(turns) if !turns.is_empty() => {
+                    histories.insert(key, turns);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(sender_key = %key, "Skipping unreadable channel history: {e}");
+                }
+            }
+        }
+        Ok(histories)
+    }
+
+    /// Replace the stored turns for `sender_key`; an empty list deletes the row.
+    pub fn save(&self, sender_key: &str, turns: &[ChatMessage]) -> anyhow::Result<()> {
+        if turns.is_empty() {
+            return self.remove(sender_key);
+        }
+        let encoded = serde_json::to_string(turns)?;
+        self.conn.lock().execute(
+            "INSERT INTO channel_history (sender_key, turns, updated_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(sender_key) DO UPDATE SET
+                 turns = excluded.turns,
+                 updated_at = excluded.updated_at",
+            params![sender_key, encoded, now_secs()],
+        )?;
+        Ok(())
+    }
+
+    /// Like [`Self::save`], but off the async runtime: the snapshot is queued
+    /// and written on a blocking thread, where only the newest queued turns
+    /// for a sender are written. Saves inline when no runtime is running.
+    pub fn save_in_background(self: &Arc<Self>, sender_key: &str, turns: &[ChatMessage]) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            if let Err(e) = self.save(sender_key, turns) {
+                tracing::warn!(sender_key, "Failed to persist channel history: {e}");
+            }
+            return;
+        };
+        self.pending
+            .lock()
+            .insert(sender_key.to_string(), turns.to_vec());
+        let store = Arc::clone(self);
+        let sender_key = sender_key.to_string();
+        runtime.spawn_blocking(move || store.write_pending(Some(&sender_key)));
+    }
+
+    /// Write every snapshot still queued by [`Self::save_in_background`].
+    pub fn flush(&self) {
+        self.write_pending(None);
+    }
+
+    /// Write the queued snapshot for `sender_key`, or all of them for `None`.
+    fn write_pending(&self, sender_key: Option<&str>) {
+        let _writer = self.writer.lock();
+        let snapshots: Vec<(String, Vec<ChatMessage>)> = {
+            let mut pending = self.pending.lock();
+            match sender_key {
+                Some(key) => pending.remove_entry(key).into_iter().collect(),
+                None => pending.drain().collect(),
+            }
+        };
+        for (key, turns) in snapshots {
+            if let Err(e) = self.save(&key, &turns) {
+                tracing::warn!(sender_key = %key, "Failed to persist channel history: {e}");
+            }
+        }
+    }
+
+    /// Forget the stored conversation for `sender_key`.
+    pub fn remove(&self, sender_key: &str) -> anyhow::Result<()> {
+        self.conn.lock().execute(
+            "DELETE FROM channel_history WHERE sender_key = ?1",
+            params![sender_key],
+        )?;
+        Ok(())
+    }
+
+    /// Whether `sender_key` has been idle longer than the retention window.
+    ///
+    /// Unknown keys, and keys with a save still queued, are never expired.
+    pub fn is_expired(&self, sender_key: &str) -> anyhow::Result<bool> {
+        let Some(ttl) = self.ttl_secs else {
+            return Ok(false);
+        };
+        if self.pending.lock().contains_key(sender_key) {
+            return Ok(false);
+        }
+        let updated_at: Option<i64> = self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT updated_at FROM channel_history WHERE sender_key = ?1",
+                params![sender_key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(updated_at.is_some_and(|ts| now_secs().saturating_sub(ts) > ttl))
+    }
+
+    /// Delete every conversation idle longer than the retention window.
+    pub fn prune_expired(&self) -> anyhow::Result<usize> {
+        let Some(ttl) = self.ttl_secs else {
+            return Ok(0);
+        };
+        let cutoff = now_secs().saturating_sub(ttl);
+        let removed = self.conn.lock().execute(
+            "DELETE FROM channel_history WHERE updated_at < ?1",
+            params![cutoff],
+        )?;
+        Ok(removed)
+    }
+}
+
+/// Flushes queued saves when dropped, so histories survive shutdown even if
+/// the owning task is aborted mid-await.
+pub struct FlushOnDrop(pub Arc<ChannelHistoryStore>);
+
+impl Drop for FlushOnDrop {
+    fn drop(&mut self) {
+        self.0.flush();
+    }
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn turns() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::user("hello"),
+            ChatMessage::assistant("hi there"),
+        ]
+    }
+
+    #[test]
+    fn save_and_reload_across_instances() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let store = ChannelHistoryStore::open(tmp.path(), 0).unwrap();
+            store.save("telegram_alice", &turns()).unwrap();
+        }
+
+        let store = ChannelHistoryStore::open(tmp.path(), 0).unwrap();
+        let loaded = store.load_all().unwrap();
+        let alice = &loaded["telegram_alice"];
+        assert_eq!(alice.len(), 2);
+        assert_eq!(alice[1].role, "assistant");
+        assert_eq!(alice[1].content, "hi there");
+        assert!(store.db_path().ends_with("memory/channel_history.db"));
+    }
+
+    #[test]
+    fn save_replaces_and_empty_save_removes() {
+        let tmp = TempDir::new().unwrap();
+        let store = ChannelHistoryStore::open(tmp.path(), 0).unwrap();
+        store.save("k", &turns()).unwrap();
+        store.save("k", &[ChatMessage::user("only")]).unwrap();
+        assert_eq!(store.load_all().unwrap()["k"].len(), 1);
+
+        store.save("k", &[]).unwrap();
+        assert!(store.load_all().unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn background_saves_keep_the_newest_turns() {
+        let tmp = TempDir::new().unwrap();
+        let store = Arc::new(ChannelHistoryStore::open(tmp.path(), 0).unwrap());
+        let mut history = Vec::new();
+        for i in 0..50 {
+            history.push(ChatMessage::user(format!("turn {i}")));
+            store.save_in_background("k", &history);
+        }
+
+        let flushed = Arc::clone(&store);
+        tokio::task::spawn_blocking(move || flushed.flush())
+            .await
+            .unwrap();
+        let loaded = store.load_all().unwrap();
+        assert_eq!(loaded["k"].len(), 50);
+        assert_eq!(loaded["k"][49].content, "turn 49");
+    }
+
+    #[test]
+    fn expired_conversations_are_pruned() {
+        let tmp = TempDir::new().unwrap();
+        let store = ChannelHistoryStore::open(tmp.path(), 1).unwrap();
+        store.save("stale", &turns()).unwrap();
+        store.save("fresh", &turns()).unwrap();
+        store
+            .conn
+            .lock()
+            .execute(
+                "UPDATE channel_history SET updated_at = ?1 WHERE sender_key = 'stale'",
+                params![now_secs() - 7200],
+            )
+            .unwrap();
+
+        assert!(store.is_expired("stale").unwrap());
+        assert!(!store.is_expired("fresh").unwrap());
+        assert!(!store.is_expired("unknown").unwrap());
+
+        let loaded = store.load_all().unwrap();
+        assert!(!loaded.contains_key("stale"));
+        assert!(loaded.contains_key("fresh"));
+    }
+
+    #[test]
+    fn dropping_flush_guard_writes_queued_saves_and_blocks_expiry() {
+        let tmp = TempDir::new().unwrap();
+        let store = Arc::new(ChannelHistoryStore::open(tmp.path(), 1).unwrap());
+        store.save("k", &turns()).unwrap();
+        store
+            .conn
+            .lock()
+            .execute("UPDATE channel_history SET updated_at = 0", [])
+            .unwrap();
+        store.pending.lock().insert("k".into(), vec![]);
+        store.pending.lock().insert("queued".into(), turns());
+        assert!(!store.is_expired("k").unwrap());
+
+        drop(FlushOnDrop(Arc::clone(&store)));
+        assert!(store.pending.lock().is_empty());
+        let loaded = store.load_all().unwrap();
+        assert!(!loaded.contains_key("k"));
+        assert_eq!(loaded["queued"].len(), 2);
+    }
+
+    #[test]
+    fn zero_ttl_never_expires() {
+        let tmp = TempDir::new().unwrap();
+        let store = ChannelHistoryStore::open(tmp.path(), 0).unwrap();
+        store.save("k", &turns()).unwrap();
+        store
+            .conn
+            .lock()
+            .execute("UPDATE channel_history SET updated_at = 0", [])
+            .unwrap();
+        assert!(!store.is_expired("k").unwrap());
+        assert_eq!(store.prune_expired().unwrap(), 0);
+    }
+}
@@ -350,6 +350,10 @@ impl Channel for IrcChannel {
         "irc"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let mut guard = self.writer.lock().await;
         let writer = guard
@@ -580,6 +584,7 @@ impl Channel for IrcChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        attachments: Vec::new(),
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -213,6 +213,7 @@ impl LinqChannel {
             channel: "linq".to_string(),
             timestamp,
             thread_ts: None,
+            attachments: Vec::new(),
         });
 
         messages
@@ -225,6 +226,10 @@ impl Channel for LinqChannel {
         "linq"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_sender_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         // If reply_target looks like a chat_id, send to existing chat.
         // Otherwise create a new chat with the recipient phone number.
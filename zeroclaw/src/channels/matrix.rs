@@ -1,8 +1,11 @@
+use crate::channels::attachment::{Attachment, AttachmentKind, AttachmentSource};
 use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use matrix_sdk::{
+    attachment::AttachmentConfig,
     authentication::matrix::MatrixSession,
     config::SyncSettings,
+    media::{MediaFormat, MediaRequestParameters},
     ruma::{
         events::room::message::{
             MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent,
@@ -474,6 +477,29 @@ impl MatrixChannel {
         Ok(())
     }
 
+    /// The configured room, syncing once if the client has not seen it yet.
+    async fn joined_target_room(&self) -> anyhow::Result<Room> {
+        let client = self.matrix_client().await?;
+        let target_room_id = self.target_room_id().await?;
+        let target_room: OwnedRoomId = target_room_id.parse()?;
+
+        let mut room = client.get_room(&target_room);
+        if room.is_none() {
+            let _ = client.sync_once(SyncSettings::new()).await;
+            room = client.get_room(&target_room);
+        }
+
+        let Some(room) = room else {
+            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
+        };
+
+        if room.state() != RoomState::Joined {
+            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
+        }
+
+        Ok(room)
+    }
+
     fn sync_filter_for_room(room_id: &str, timeline_limit: usize) -> String {
         let timeline_limit = timeline_limit.max(1);
         serde_json::json!({
@@ -528,27 +554,47 @@ impl Channel for MatrixChannel {
         "matrix"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let client = self.matrix_client().await?;
-        let target_room_id = self.target_room_id().await?;
-        let target_room: OwnedRoomId = target_room_id.parse()?;
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
 
-        let mut room = client.get_room(&target_room);
-        if room.is_none() {
-            let _ = client.sync_once(SyncSettings::new()).await;
-            room = client.get_room(&target_room);
-        }
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let room = self.joined_target_room().await?;
+        room.send(RoomMessageEventContent::text_markdown(&message.content))
+            .await?;
 
-        let Some(room) = room else {
-            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
-        };
+        Ok(())
+    }
 
-        if room.state() != RoomState::Joined {
-            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        _recipient: &str,
+        _thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let room = self.joined_target_room().await?;
+
+        if let AttachmentSource::Url(url) = &attachment.source {
+            room.send(RoomMessageEventContent::text_markdown(format!(
+                "📎 [{}]({url})",
+                attachment.filename
+            )))
+            .await?;
+            return Ok(());
         }
 
-        room.send(RoomMessageEventContent::text_markdown(&message.content))
-            .await?;
+        let content_type: mime_guess::mime::Mime = attachment
+            .mime_type
+            .parse()
+            .unwrap_or(mime_guess::mime::APPLICATION_OCTET_STREAM);
+        let data = attachment.read_bytes().await?;
+        room.send_attachment(
+            attachment.filename.clone(),
+            &content_type,
+            data,
+            AttachmentConfig::new(),
+        )
+        .await?;
 
         Ok(())
     }
@@ -615,13 +661,44 @@ impl Channel for MatrixChannel {
                     return;
                 }
 
+                let mut attachments = Vec::new();
                 let body = match &event.content.msgtype {
                     MessageType::Text(content) => content.body.clone(),
                     MessageType::Notice(content) => content.body.clone(),
+                    MessageType::Audio(content) => {
+                        let request = MediaRequestParameters {
+                            source: content.source.clone(),
+                            format: MediaFormat::File,
+                        };
+                        match room
+                            .client()
+                            .media()
+                            .get_media_content(&request, true)
+                            .await
+                        {
+                            Ok(bytes) => {
+                                let mime_type = content
+                                    .info
+                                    .as_ref()
+                                    .and_then(|info| info.mimetype.clone())
+                                    .unwrap_or_else(|| "audio/ogg".to_string());
+                                attachments.push(
+                                    Attachment::from_bytes(content.body.clone(), bytes)
+                                        .with_kind(AttachmentKind::Voice)
+                                        .with_mime_type(mime_type),
+                                );
+                            }
+                            Err(error) => {
+                                tracing::warn!("Matrix: failed to download voice note: {error}");
+                                return;
+                            }
+                        }
+                        String::new()
+                    }
                     _ => return,
                 };
 
-                if !MatrixChannel::has_non_empty_body(&body) {
+                if attachments.is_empty() && !MatrixChannel::has_non_empty_body(&body) {
                     return;
                 }
 
@@ -645,6 +722,7 @@ impl Channel for MatrixChannel {
                         .unwrap_or_default()
                         .as_secs(),
                     thread_ts: None,
+                    attachments,
                 };
 
                 let _ = tx.send(msg).await;
@@ -89,6 +89,10 @@ impl Channel for MattermostChannel {
         "mattermost"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> Result<()> {
         // Mattermost supports threading via 'root_id'.
         // We pack 'channel_id:root_id' into recipient if it's a thread.
@@ -322,6 +326,7 @@ impl MattermostChannel {
             #[allow(clippy::cast_sign_loss)]
             timestamp: (create_at / 1000) as u64,
             thread_ts: None,
+            attachments: Vec::new(),
         })
     }
 }
@@ -14,19 +14,25 @@
 //! To add a new channel, implement [`Channel`] in a new submodule and wire it into
 //! [`start_channels`]. See `AGENTS.md` §7.2 for the full change playbook.
 
+pub mod approvals;
+pub mod attachment;
 pub mod clawdtalk;
 pub mod cli;
 pub mod dingtalk;
 pub mod discord;
 pub mod email_channel;
+pub mod history_store;
 pub mod imessage;
 pub mod irc;
 #[cfg(feature = "channel-lark")]
 pub mod lark;
+pub mod leak_guard;
 pub mod linq;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
+#[cfg(feature = "channel-mqtt")]
+pub mod mqtt;
 pub mod nextcloud_talk;
 pub mod nostr;
 pub mod qq;
@@ -35,6 +41,7 @@ pub mod slack;
 pub mod telegram;
 pub mod traits;
 pub mod transcription;
+pub mod tts;
 pub mod wati;
 pub mod whatsapp;
 #[cfg(feature = "whatsapp-web")]
@@ -42,6 +49,7 @@ pub mod whatsapp_storage;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_web;
 
+pub use attachment::{Attachment, AttachmentKind, AttachmentSource};
 pub use clawdtalk::{ClawdTalkChannel, ClawdTalkConfig};
 pub use cli::CliChannel;
 pub use dingtalk::DingTalkChannel;
@@ -74,11 +82,12 @@ use crate::memory::{self, Memory};
 use crate::observability::{self, runtime_trace, Observer};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
-use crate::security::SecurityPolicy;
+use crate::security::{OutboundSurface, SecurityPolicy};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
@@ -125,6 +134,7 @@ const CHANNEL_HOOK_MAX_OUTBOUND_CHARS: usize = 20_000;
 
 type ProviderCacheMap = Arc<Mutex<HashMap<String, Arc<dyn Provider>>>>;
 type RouteSelectionMap = Arc<Mutex<HashMap<String, ChannelRouteSelection>>>;
+type InFlightTaskMap = Arc<tokio::sync::Mutex<HashMap<String, Vec<InFlightSenderTaskState>>>>;
 
 fn effective_channel_message_timeout_secs(configured: u64) -> u64 {
     configured.max(MIN_CHANNEL_MESSAGE_TIMEOUT_SECS)
@@ -152,6 +162,16 @@ enum ChannelRuntimeCommand {
     ShowModel,
     SetModel(String),
     NewSession,
+    ShowStatus,
+    ShowCost,
+    SearchMemory(String),
+    ForgetMemory(String),
+    StopTask,
+    EngageEstop,
+    ShowEstop,
+    ShowVoice,
+    SetVoice(bool),
+    ShowHelp,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -212,6 +232,7 @@ struct ChannelRuntimeContext {
     max_tool_iterations: usize,
     min_relevance_score: f64,
     conversation_histories: ConversationHistoryMap,
+    history_store: Option<Arc<history_store::ChannelHistoryStore>>,
     provider_cache: ProviderCacheMap,
     route_overrides: RouteSelectionMap,
     api_key: Option<String>,
@@ -224,6 +245,23 @@ struct ChannelRuntimeContext {
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    /// Running requests per interruption scope, cancelled by `/stop`.
+    in_flight: InFlightTaskMap,
+    cost_tracker: Option<Arc<crate::cost::CostTracker>>,
+    /// Present only when `[transcription].enabled = true`.
+    transcriber: Option<Arc<dyn transcription::Transcriber>>,
+    /// Present only when `[tts].enabled = true`; senders opt in with `/voice on`.
+    voice_replies: Option<Arc<tts::VoiceReplies>>,
+    /// Present only when `[security.estop].enabled = true`.
+    estop_config: Option<Arc<crate::config::EstopConfig>>,
+    /// Live estop state; kill-all stops messages before they reach the LLM.
+    estop_guard: Option<Arc<crate::security::EstopGuard>>,
+    /// Present only when `[autonomy].channel_approvals` is on in supervised mode.
+    approvals: Option<Arc<approvals::ChannelApprovals>>,
+    /// Present only when `[sessions].enabled = true`.
+    transcripts: Option<Arc<crate::sessions::TranscriptRegistry>>,
+    /// Confines outbound `[KIND:path]` attachments to the workspace.
+    security: Arc<SecurityPolicy>,
 }
 
 #[derive(Clone)]
@@ -477,15 +515,7 @@ fn normalize_cached_channel_turns(turns: Vec<ChatMessage>) -> Vec<ChatMessage> {
     normalized
 }
 
-fn supports_runtime_model_switch(channel_name: &str) -> bool {
-    matches!(channel_name, "telegram" | "discord")
-}
-
-fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
-    if !supports_runtime_model_switch(channel_name) {
-        return None;
-    }
-
+fn parse_runtime_command(content: &str) -> Option<ChannelRuntimeCommand> {
     let trimmed = content.trim();
     if !trimmed.starts_with('/') {
         return None;
@@ -518,6 +548,27 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
             }
         }
         "/new" => Some(ChannelRuntimeCommand::NewSession),
+        "/status" => Some(ChannelRuntimeCommand::ShowStatus),
+        "/cost" => Some(ChannelRuntimeCommand::ShowCost),
+        "/memory" => Some(ChannelRuntimeCommand::SearchMemory(
+            parts.collect::<Vec<_>>().join(" "),
+        )),
+        "/forget" => Some(ChannelRuntimeCommand::ForgetMemory(
+            parts.collect::<Vec<_>>().join(" "),
+        )),
+        "/stop" => Some(ChannelRuntimeCommand::StopTask),
+        "/estop" => match parts.next() {
+            Some(arg) if arg.eq_ignore_ascii_case("status") => {
+                Some(ChannelRuntimeCommand::ShowEstop)
+            }
+            _ => Some(ChannelRuntimeCommand::EngageEstop),
+        },
+        "/voice" => match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            Some("on") => Some(ChannelRuntimeCommand::SetVoice(true)),
+            Some("off") => Some(ChannelRuntimeCommand::SetVoice(false)),
+            _ => Some(ChannelRuntimeCommand::ShowVoice),
+        },
+        "/help" => Some(ChannelRuntimeCommand::ShowHelp),
         _ => None,
     }
 }
@@ -738,11 +789,37 @@ fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: Chan
     }
 }
 
+/// Mirror a sender's in-memory turns to the durable store, if enabled.
+fn persist_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str, turns: &[ChatMessage]) {
+    if let Some(store) = ctx.history_store.as_ref() {
+        store.save_in_background(sender_key, turns);
+    }
+}
+
 fn clear_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
-    ctx.conversation_histories
+    let mut histories = ctx
+        .conversation_histories
         .lock()
-        .unwrap_or_else(|e| e.into_inner())
-        .remove(sender_key);
+        .unwrap_or_else(|e| e.into_inner());
+    histories.remove(sender_key);
+    persist_sender_history(ctx, sender_key, &[]);
+    if let Some(transcripts) = ctx.transcripts.as_ref() {
+        transcripts.forget(sender_key);
+    }
+}
+
+/// Drop a sender's history once it has been idle past the retention window.
+async fn expire_stale_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let Some(store) = ctx.history_store.clone() else {
+        return;
+    };
+    let key = sender_key.to_string();
+    match tokio::task::spawn_blocking(move || store.is_expired(&key)).await {
+        Ok(Ok(true)) => clear_sender_history(ctx, sender_key),
+        Ok(Ok(false)) => {}
+        Ok(Err(e)) => tracing::warn!(sender_key, "Failed to check channel history expiry: {e}"),
+        Err(e) => tracing::warn!(sender_key, "Channel history expiry check panicked: {e}"),
+    }
 }
 
 fn compact_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool {
@@ -773,10 +850,12 @@ fn compact_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool
 
     if compacted.is_empty() {
         turns.clear();
+        persist_sender_history(ctx, sender_key, &[]);
         return false;
     }
 
     *turns = compacted;
+    persist_sender_history(ctx, sender_key, turns);
     true
 }
 
@@ -790,6 +869,7 @@ fn append_sender_turn(ctx: &ChannelRuntimeContext, sender_key: &str, turn: ChatM
     while turns.len() > MAX_CHANNEL_HISTORY {
         turns.remove(0);
     }
+    persist_sender_history(ctx, sender_key, turns);
 }
 
 fn rollback_orphan_user_turn(
@@ -813,6 +893,7 @@ fn rollback_orphan_user_turn(
     }
 
     turns.pop();
+    persist_sender_history(ctx, sender_key, turns);
     if turns.is_empty() {
         histories.remove(sender_key);
     }
@@ -992,12 +1073,198 @@ fn build_providers_help_response(current: &ChannelRouteSelection) -> String {
     response
 }
 
+const RUNTIME_COMMAND_HELP: &str = "Available commands:
+/status — current provider, model and token usage
+/cost — session, daily and monthly spend
+/models [provider] — list or switch providers
+/model [model-id] — show or switch the model
+/new — clear this conversation
+/memory <query> — search long-term memory
+/forget <key> — delete a memory entry
+/stop — cancel the request currently running for you
+/estop — engage the emergency stop (kill-all); `/estop status` to inspect
+/voice on|off — also send replies as voice notes
+/help — this message";
+
+const RUNTIME_COMMAND_REFUSAL: &str =
+    "You are not on this channel's allowlist, so runtime commands are unavailable.";
+
+fn build_status_response(
+    ctx: &ChannelRuntimeContext,
+    current: &ChannelRouteSelection,
+    sender_key: &str,
+) -> String {
+    let turns = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(sender_key)
+        .map_or(0, Vec::len);
+
+    let mut response = String::new();
+    let _ = writeln!(
+        response,
+        "Provider: `{}`\nModel: `{}`\nConversation turns: {turns}",
+        current.provider, current.model
+    );
+    match ctx.cost_tracker.as_ref().map(|t| t.get_summary()) {
+        Some(Ok(summary)) => {
+            let _ = write!(
+                response,
+                "Token usage (this session): {} tokens across {} request(s)",
+                summary.total_tokens, summary.request_count
+            );
+        }
+        Some(Err(err)) => {
+            let _ = write!(response, "Token usage unavailable: {err}");
+        }
+        None => response.push_str("Token usage tracking is disabled (`[cost].enabled = false`)."),
+    }
+    response
+}
+
+fn build_cost_response(ctx: &ChannelRuntimeContext) -> String {
+    let Some(tracker) = ctx.cost_tracker.as_ref() else {
+        return "Cost tracking is disabled. Set `[cost].enabled = true` to track spend."
+            .to_string();
+    };
+    let summary = match tracker.get_summary() {
+        Ok(summary) => summary,
+        Err(err) => return format!("Failed to read cost summary: {err}"),
+    };
+
+    let mut response = String::new();
+    let _ = writeln!(
+        response,
+        "Session: ${:.4}\nToday: ${:.4}\nThis month: ${:.4}\nRequests: {} ({} tokens)",
+        summary.session_cost_usd,
+        summary.daily_cost_usd,
+        summary.monthly_cost_usd,
+        summary.request_count,
+        summary.total_tokens
+    );
+    let mut models: Vec<_> = summary.by_model.values().collect();
+    models.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+    for stats in models {
+        let _ = writeln!(
+            response,
+            "- `{}`: ${:.4} ({} requests, {} tokens)",
+            stats.model, stats.cost_usd, stats.request_count, stats.total_tokens
+        );
+    }
+    response
+}
+
+async fn build_memory_search_response(mem: &dyn Memory, query: &str) -> String {
+    if query.is_empty() {
+        return "Usage: `/memory <query>`".to_string();
+    }
+    match mem.recall(query, 5, None).await {
+        Ok(entries) if entries.is_empty() => format!("No memories match `{query}`."),
+        Ok(entries) => {
+            let mut response = String::new();
+            let _ = writeln!(response, "Memories matching `{query}`:");
+            for entry in entries {
+                let _ = writeln!(
+                    response,
+                    "- `{}` ({}): {}",
+                    entry.key,
+                    entry.category,
+                    truncate_with_ellipsis(&entry.content, 200)
+                );
+            }
+            response
+        }
+        Err(err) => format!("Memory search failed: {err}"),
+    }
+}
+
+/// Cancel every request still running for `scope_key`; returns how many were signalled.
+async fn cancel_in_flight_tasks(ctx: &ChannelRuntimeContext, scope_key: &str) -> usize {
+    let tasks = ctx
+        .in_flight
+        .lock()
+        .await
+        .remove(scope_key)
+        .unwrap_or_default();
+    for task in &tasks {
+        task.cancellation.cancel();
+    }
+    tasks.len()
+}
+
+fn load_estop_manager(
+    ctx: &ChannelRuntimeContext,
+) -> std::result::Result<crate::security::EstopManager, String> {
+    let Some(estop_config) = ctx.estop_config.as_ref() else {
+        return Err(
+            "Emergency stop is disabled. Enable `[security.estop].enabled = true` in config.toml."
+                .to_string(),
+        );
+    };
+    let Some(config_dir) = ctx.provider_runtime_options.zeroclaw_dir.as_deref() else {
+        return Err("Emergency stop is unavailable: config directory is unknown.".to_string());
+    };
+    crate::security::EstopManager::load(estop_config, config_dir)
+        .map_err(|err| format!("Failed to load emergency stop state: {err}"))
+}
+
+fn engage_estop_from_channel(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) -> String {
+    let mut manager = match load_estop_manager(ctx) {
+        Ok(manager) => manager,
+        Err(message) => return message,
+    };
+    match manager.engage(crate::security::EstopLevel::KillAll) {
+        Ok(()) => {
+            tracing::warn!(
+                channel = %msg.channel,
+                sender = %msg.sender,
+                "Emergency stop (kill-all) engaged from channel"
+            );
+            "🛑 Emergency stop engaged (kill-all). Resume with `zeroclaw estop resume`.".to_string()
+        }
+        Err(err) => format!("Failed to engage emergency stop: {err}"),
+    }
+}
+
+fn build_estop_status_response(ctx: &ChannelRuntimeContext) -> String {
+    let state = match load_estop_manager(ctx) {
+        Ok(manager) => manager.status(),
+        Err(message) => return message,
+    };
+    if !state.is_engaged() {
+        return "Emergency stop is not engaged.".to_string();
+    }
+    let mut response = String::from("Emergency stop is engaged:\n");
+    if state.kill_all {
+        response.push_str("- kill-all\n");
+    }
+    if state.network_kill {
+        response.push_str("- network kill\n");
+    }
+    if !state.blocked_domains.is_empty() {
+        let _ = writeln!(
+            response,
+            "- blocked domains: {}",
+            state.blocked_domains.join(", ")
+        );
+    }
+    if !state.frozen_tools.is_empty() {
+        let _ = writeln!(
+            response,
+            "- frozen tools: {}",
+            state.frozen_tools.join(", ")
+        );
+    }
+    response
+}
+
 async fn handle_runtime_command_if_needed(
     ctx: &ChannelRuntimeContext,
     msg: &traits::ChannelMessage,
     target_channel: Option<&Arc<dyn Channel>>,
 ) -> bool {
-    let Some(command) = parse_runtime_command(&msg.channel, &msg.content) else {
+    let Some(command) = parse_runtime_command(&msg.content) else {
         return false;
     };
 
@@ -1005,6 +1272,23 @@ async fn handle_runtime_command_if_needed(
         return true;
     };
 
+    if !channel.allows_sender(&msg.sender) {
+        tracing::warn!(
+            channel = %msg.channel,
+            sender = %msg.sender,
+            "Refusing runtime command from sender outside the channel allowlist"
+        );
+        let refusal = SendMessage::new(RUNTIME_COMMAND_REFUSAL, &msg.reply_target)
+            .in_thread(msg.thread_ts.clone());
+        if let Err(err) = channel.send(&refusal).await {
+            tracing::warn!(
+                "Failed to send runtime command refusal on {}: {err}",
+                channel.name()
+            );
+        }
+        return true;
+    }
+
     let sender_key = conversation_history_key(msg);
     let mut current = get_route_selection(ctx, &sender_key);
 
@@ -1059,6 +1343,40 @@ async fn handle_runtime_command_if_needed(
             clear_sender_history(ctx, &sender_key);
             "Conversation history cleared. Starting fresh.".to_string()
         }
+        ChannelRuntimeCommand::ShowStatus => build_status_response(ctx, &current, &sender_key),
+        ChannelRuntimeCommand::ShowCost => build_cost_response(ctx),
+        ChannelRuntimeCommand::SearchMemory(query) => {
+            build_memory_search_response(ctx.memory.as_ref(), query.trim()).await
+        }
+        ChannelRuntimeCommand::ForgetMemory(key) => {
+            let key = key.trim().trim_matches('`');
+            if key.is_empty() {
+                "Usage: `/forget <memory-key>`. Find keys with `/memory <query>`.".to_string()
+            } else {
+                match ctx.memory.forget(key).await {
+                    Ok(true) => format!("Forgot memory `{key}`."),
+                    Ok(false) => format!("No memory with key `{key}`."),
+                    Err(err) => format!("Failed to forget `{key}`: {err}"),
+                }
+            }
+        }
+        ChannelRuntimeCommand::StopTask => {
+            let stopped = cancel_in_flight_tasks(ctx, &interruption_scope_key(msg)).await;
+            if stopped == 0 {
+                "Nothing to stop.".to_string()
+            } else {
+                format!("Stopped {stopped} in-flight request(s).")
+            }
+        }
+        ChannelRuntimeCommand::EngageEstop => engage_estop_from_channel(ctx, msg),
+        ChannelRuntimeCommand::ShowEstop => build_estop_status_response(ctx),
+        ChannelRuntimeCommand::ShowVoice => {
+            build_voice_response(ctx, channel.as_ref(), &sender_key, None)
+        }
+        ChannelRuntimeCommand::SetVoice(enabled) => {
+            build_voice_response(ctx, channel.as_ref(), &sender_key, Some(enabled))
+        }
+        ChannelRuntimeCommand::ShowHelp => RUNTIME_COMMAND_HELP.to_string(),
     };
 
     if let Err(err) = channel
@@ -1074,6 +1392,69 @@ async fn handle_runtime_command_if_needed(
     true
 }
 
+/// Apply (when `set` is given) and describe the sender's `/voice` setting.
+fn build_voice_response(
+    ctx: &ChannelRuntimeContext,
+    channel: &dyn Channel,
+    sender_key: &str,
+    set: Option<bool>,
+) -> String {
+    let Some(voice) = ctx.voice_replies.as_ref() else {
+        return "Voice replies are not configured. Set `[tts].enabled = true` in config.toml."
+            .to_string();
+    };
+    if !channel.supports_voice_notes() {
+        return format!("Voice replies are not supported on {}.", channel.name());
+    }
+    if let Some(enabled) = set {
+        voice.set_enabled(sender_key, enabled);
+    }
+    if voice.is_enabled(sender_key) {
+        format!(
+            "Voice replies are on (via `{}`). Use `/voice off` to stop.",
+            voice.synthesizer_name()
+        )
+    } else {
+        "Voice replies are off. Use `/voice on` to hear replies.".to_string()
+    }
+}
+
+/// Speak `reply` when the sender turned on `/voice` and the channel can
+/// deliver voice notes. Synthesis failures only cost the voice note.
+async fn synthesize_voice_reply(
+    ctx: &ChannelRuntimeContext,
+    channel: &dyn Channel,
+    sender_key: &str,
+    reply: &str,
+) -> Option<Attachment> {
+    let voice = ctx.voice_replies.as_ref()?;
+    if !channel.supports_voice_notes() || !voice.is_enabled(sender_key) {
+        return None;
+    }
+    // The text reply is screened by the leak-guarded channel; the spoken copy
+    // is an attachment, so screen it here and stay silent when blocked.
+    let reply = match voice.outbound_guard() {
+        Some(guard) => match guard.screen(OutboundSurface::Channel, reply, ctx.observer.as_ref()) {
+            Ok(screened) => screened,
+            Err(blocked) => {
+                tracing::warn!(channel = channel.name(), "Skipping voice reply: {blocked}");
+                return None;
+            }
+        },
+        None => Cow::Borrowed(reply),
+    };
+    match voice.voice_note(&reply).await {
+        Ok(note) => note,
+        Err(e) => {
+            tracing::warn!(
+                channel = channel.name(),
+                "Voice reply synthesis failed: {e:#}"
+            );
+            None
+        }
+    }
+}
+
 async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
@@ -1505,14 +1886,52 @@ fn spawn_scoped_typing_task(
     handle
 }
 
+/// Describe inbound attachments the channel has not already referenced in
+/// the message text, so the agent and its file tools can find them.
+fn append_attachment_context(msg: &mut traits::ChannelMessage) {
+    let lines: Vec<String> = msg
+        .attachments
+        .iter()
+        .filter(|attachment| {
+            let location = match &attachment.source {
+                AttachmentSource::Path(path) => path.display().to_string(),
+                AttachmentSource::Url(url) => url.clone(),
+                AttachmentSource::Bytes(_) => return true,
+            };
+            !msg.content.contains(&location)
+        })
+        .map(Attachment::describe)
+        .collect();
+    if lines.is_empty() {
+        return;
+    }
+    if !msg.content.trim().is_empty() {
+        msg.content.push_str("\n\n");
+    }
+    msg.content.push_str("[Attachments]\n");
+    msg.content.push_str(&lines.join("\n"));
+}
+
 async fn process_channel_message(
     ctx: Arc<ChannelRuntimeContext>,
-    msg: traits::ChannelMessage,
+    mut msg: traits::ChannelMessage,
     cancellation_token: CancellationToken,
 ) {
     if cancellation_token.is_cancelled() {
         return;
     }
+    if let (Some(transcriber), Some(channel)) = (
+        ctx.transcriber.as_ref(),
+        ctx.channels_by_name.get(&msg.channel),
+    ) {
+        transcription::transcribe_voice_attachments(
+            channel.as_ref(),
+            transcriber.as_ref(),
+            &mut msg,
+        )
+        .await;
+    }
+    append_attachment_context(&mut msg);
 
     println!(
         "  💬 [{}] from {}: {}",
@@ -1556,6 +1975,19 @@ async fn process_channel_message(
     if handle_runtime_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
         return;
     }
+    if let Some(guard) = ctx.estop_guard.as_ref() {
+        if let Err(blocked) = guard.check_running(&format!("channel:{}", msg.channel)) {
+            if let Some(channel) = target_channel.as_ref() {
+                let _ = channel
+                    .send(
+                        &SendMessage::new(format!("🛑 {blocked}"), &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone()),
+                    )
+                    .await;
+            }
+            return;
+        }
+    }
 
     let history_key = conversation_history_key(&msg);
     let route = get_route_selection(ctx.as_ref(), &history_key);
@@ -1595,6 +2027,8 @@ async fn process_channel_message(
     println!("  ⏳ Processing message...");
     let started_at = Instant::now();
 
+    expire_stale_sender_history(ctx.as_ref(), &history_key).await;
+
     let had_prior_history = ctx
         .conversation_histories
         .lock()
@@ -1722,6 +2156,26 @@ async fn process_channel_message(
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
 
+    // Supervised approvals are asked in this conversation (CLI keeps stdin prompts).
+    let approval_manager = match (ctx.approvals.as_ref(), target_channel.as_ref()) {
+        (Some(approvals), Some(channel)) if msg.channel != "cli" => {
+            Some(approvals.manager_for(Arc::clone(channel), &msg, &history_key))
+        }
+        _ => None,
+    };
+
+    let transcript = ctx.transcripts.as_ref().and_then(|transcripts| {
+        transcripts.transcript_for(
+            &msg.channel,
+            &history_key,
+            route.provider.as_str(),
+            route.model.as_str(),
+        )
+    });
+    if let Some(transcript) = transcript.as_ref() {
+        transcript.record_message("user", &msg.content);
+    }
+
     enum LlmExecutionResult {
         Completed(Result<Result<String, anyhow::Error>, tokio::time::error::Elapsed>),
         Cancelled,
@@ -1742,7 +2196,7 @@ async fn process_channel_message(
                 route.model.as_str(),
                 runtime_defaults.temperature,
                 true,
-                None,
+                approval_manager.as_ref(),
                 msg.channel.as_str(),
                 &ctx.multimodal,
                 ctx.max_tool_iterations,
@@ -1754,6 +2208,7 @@ async fn process_channel_message(
                 } else {
                     ctx.non_cli_excluded_tools.as_ref()
                 },
+                transcript.as_deref(),
             ),
         ) => LlmExecutionResult::Completed(result),
     };
@@ -1762,6 +2217,10 @@ async fn process_channel_message(
         let _ = handle.await;
     }
 
+    if let (Some(approvals), Some(manager)) = (ctx.approvals.as_ref(), approval_manager.as_ref()) {
+        approvals.remember(&history_key, manager);
+    }
+
     if let Some(token) = typing_cancellation.as_ref() {
         token.cancel();
     }
@@ -1906,6 +2365,21 @@ async fn process_channel_message(
                 truncate_with_ellipsis(&delivered_response, 80)
             );
             if let Some(channel) = target_channel.as_ref() {
+                let (text, attachments) =
+                    attachment::extract_attachment_markers(&delivered_response);
+                let mut attachments: Vec<_> = attachments
+                    .into_iter()
+                    .filter_map(|item| match item.confined_to(&ctx.security) {
+                        Ok(item) => Some(item),
+                        Err(e) => {
+                            tracing::warn!("Dropping outbound attachment: {e}");
+                            None
+                        }
+                    })
+                    .collect();
+                let voice_note =
+                    synthesize_voice_reply(ctx.as_ref(), channel.as_ref(), &history_key, &text)
+                        .await;
                 if let Some(ref draft_id) = draft_message_id {
                     if let Err(e) = channel
                         .finalize_draft(&msg.reply_target, draft_id, &delivered_response)
@@ -1919,14 +2393,22 @@ async fn process_channel_message(
                             )
                             .await;
                     }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
-                {
-                    eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    if let Some(note) = voice_note {
+                        if let Err(e) = channel
+                            .send_attachment(&note, &msg.reply_target, msg.thread_ts.as_deref())
+                            .await
+                        {
+                            tracing::warn!("Failed to send voice reply: {e}");
+                        }
+                    }
+                } else {
+                    attachments.extend(voice_note);
+                    let reply = SendMessage::new(text, &msg.reply_target)
+                        .in_thread(msg.thread_ts.clone())
+                        .with_attachments(attachments);
+                    if let Err(e) = channel.send_with_attachments(&reply).await {
+                        eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    }
                 }
             }
         }
@@ -1998,6 +2480,37 @@ async fn process_channel_message(
                             .await;
                     }
                 }
+            } else if crate::cost::is_budget_exceeded(&e) {
+                let error_text = format!("⚠️ {e}");
+                runtime_trace::record_event(
+                    "channel_message_error",
+                    Some(msg.channel.as_str()),
+                    Some(route.provider.as_str()),
+                    Some(route.model.as_str()),
+                    None,
+                    Some(false),
+                    Some("cost budget exceeded"),
+                    serde_json::json!({
+                        "sender": msg.sender,
+                        "elapsed_ms": started_at.elapsed().as_millis(),
+                    }),
+                );
+                // Drop the unanswered turn so it isn't replayed once the budget resets.
+                rollback_orphan_user_turn(ctx.as_ref(), &history_key, &msg.content);
+                if let Some(channel) = target_channel.as_ref() {
+                    if let Some(ref draft_id) = draft_message_id {
+                        let _ = channel
+                            .finalize_draft(&msg.reply_target, draft_id, &error_text)
+                            .await;
+                    } else {
+                        let _ = channel
+                            .send(
+                                &SendMessage::new(error_text, &msg.reply_target)
+                                    .in_thread(msg.thread_ts.clone()),
+                            )
+                            .await;
+                    }
+                }
             } else {
                 eprintln!(
                     "  ❌ LLM error after {}ms: {e}",
@@ -2115,10 +2628,6 @@ async fn run_message_dispatch_loop(
 ) {
     let semaphore = Arc::new(tokio::sync::Semaphore::new(max_in_flight_messages));
     let mut workers = tokio::task::JoinSet::new();
-    let in_flight_by_sender = Arc::new(tokio::sync::Mutex::new(HashMap::<
-        String,
-        InFlightSenderTaskState,
-    >::new()));
     let task_sequence = Arc::new(AtomicU64::new(1));
 
     while let Some(msg) = rx.recv().await {
@@ -2128,31 +2637,51 @@ async fn run_message_dispatch_loop(
         };
 
         let worker_ctx = Arc::clone(&ctx);
-        let in_flight = Arc::clone(&in_flight_by_sender);
+        let in_flight = Arc::clone(&ctx.in_flight);
         let task_sequence = Arc::clone(&task_sequence);
         workers.spawn(async move {
             let _permit = permit;
+            // Approval replies unblock a running request, so they must never
+            // interrupt it or queue behind it.
+            if let Some(ack) =
+                approvals::answer_pending_approval(&crate::approval::ApprovalBroker::global(), &msg)
+            {
+                if let Some(channel) = worker_ctx.channels_by_name.get(&msg.channel) {
+                    let reply = SendMessage::new(ack, &msg.reply_target).in_thread(msg.thread_ts);
+                    if let Err(e) = channel.send(&reply).await {
+                        tracing::debug!("Failed to acknowledge approval on {}: {e}", msg.channel);
+                    }
+                }
+                return;
+            }
+            // Runtime commands are answered immediately and must not interrupt
+            // (or be cancellable by) the request they may be about to `/stop`.
+            let tracked = parse_runtime_command(&msg.content).is_none();
             let interrupt_enabled =
-                worker_ctx.interrupt_on_new_message && msg.channel == "telegram";
+                tracked && worker_ctx.interrupt_on_new_message && msg.channel == "telegram";
             let sender_scope_key = interruption_scope_key(&msg);
             let cancellation_token = CancellationToken::new();
             let completion = Arc::new(InFlightTaskCompletion::new());
             let task_id = task_sequence.fetch_add(1, Ordering::Relaxed);
 
-            if interrupt_enabled {
+            if tracked {
                 let previous = {
                     let mut active = in_flight.lock().await;
-                    active.insert(
-                        sender_scope_key.clone(),
-                        InFlightSenderTaskState {
-                            task_id,
-                            cancellation: cancellation_token.clone(),
-                            completion: Arc::clone(&completion),
-                        },
-                    )
+                    let tasks = active.entry(sender_scope_key.clone()).or_default();
+                    let previous = if interrupt_enabled {
+                        std::mem::take(tasks)
+                    } else {
+                        Vec::new()
+                    };
+                    tasks.push(InFlightSenderTaskState {
+                        task_id,
+                        cancellation: cancellation_token.clone(),
+                        completion: Arc::clone(&completion),
+                    });
+                    previous
                 };
 
-                if let Some(previous) = previous {
+                for previous in previous {
                     tracing::info!(
                         channel = %msg.channel,
                         sender = %msg.sender,
@@ -2165,13 +2694,13 @@ async fn run_message_dispatch_loop(
 
             process_channel_message(worker_ctx, msg, cancellation_token).await;
 
-            if interrupt_enabled {
+            if tracked {
                 let mut active = in_flight.lock().await;
-                if active
-                    .get(&sender_scope_key)
-                    .is_some_and(|state| state.task_id == task_id)
-                {
-                    active.remove(&sender_scope_key);
+                if let Some(tasks) = active.get_mut(&sender_scope_key) {
+                    tasks.retain(|state| state.task_id != task_id);
+                    if tasks.is_empty() {
+                        active.remove(&sender_scope_key);
+                    }
                 }
             }
 
@@ -2873,7 +3402,10 @@ fn collect_configured_channels(
     if let Some(ref email_cfg) = config.channels_config.email {
         channels.push(ConfiguredChannel {
             display_name: "Email",
-            channel: Arc::new(EmailChannel::new(email_cfg.clone())),
+            channel: Arc::new(
+                EmailChannel::new(email_cfg.clone())
+                    .with_workspace_dir(config.workspace_dir.clone()),
+            ),
         });
     }
 
@@ -3024,6 +3556,40 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     Ok(())
 }
 
+/// Open the durable history store when `[channels_config.history]` enables it,
+/// returning the store together with the replayed per-sender histories.
+fn open_channel_history_store(
+    config: &Config,
+) -> (
+    Option<Arc<history_store::ChannelHistoryStore>>,
+    HashMap<String, Vec<ChatMessage>>,
+) {
+    let history_config = &config.channels_config.history;
+    if !history_config.persist {
+        return (None, HashMap::new());
+    }
+
+    match history_store::ChannelHistoryStore::open(&config.workspace_dir, history_config.ttl_hours)
+    {
+        Ok(store) => {
+            let histories = store.load_all().unwrap_or_else(|e| {
+                tracing::warn!("Failed to replay channel history: {e}");
+                HashMap::new()
+            });
+            println!(
+                "  💬 History:  {} conversation(s) restored from {}",
+                histories.len(),
+                store.db_path().display()
+            );
+            (Some(Arc::new(store)), histories)
+        }
+        Err(e) => {
+            tracing::warn!("Channel history persistence disabled: {e}");
+            (None, HashMap::new())
+        }
+    }
+}
+
 /// Start all configured channels and route messages to the agent
 #[allow(clippy::too_many_lines)]
 pub async fn start_channels(config: Config) -> Result<()> {
@@ -3035,16 +3601,25 @@ pub async fn start_channels(config: Config) -> Result<()> {
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
     };
-    let provider: Arc<dyn Provider> = Arc::from(
-        create_resilient_provider_nonblocking(
-            &provider_name,
-            config.api_key.clone(),
-            config.api_url.clone(),
-            config.reliability.clone(),
-            provider_runtime_options.clone(),
-        )
-        .await?,
-    );
+    let observer: Arc<dyn Observer> =
+        Arc::from(observability::create_observer(&config.observability));
+    let provider: Arc<dyn Provider> = Arc::from(providers::with_recording(
+        providers::with_response_cache(
+            create_resilient_provider_nonblocking(
+                &provider_name,
+                config.api_key.clone(),
+                config.api_url.clone(),
+                config.reliability.clone(),
+                provider_runtime_options.clone(),
+            )
+            .await?,
+            &config.memory,
+            &config.workspace_dir,
+            Some(Arc::clone(&observer)),
+        ),
+        &config.observability,
+        &config.workspace_dir,
+    ));
 
     // Warm up the provider connection pool (TLS handshake, DNS, HTTP/2 setup)
     // so the first real message doesn't hit a cold-start timeout.
@@ -3066,8 +3641,6 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -3219,6 +3792,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
         println!("No channels configured. Run `zeroclaw onboard` to set up channels.");
         return Ok(());
     }
+    if let Some(guard) = crate::security::outbound::shared_guard(&config.security.outbound) {
+        channels = channels
+            .into_iter()
+            .map(|channel| {
+                leak_guard::LeakGuardedChannel::wrap(channel, &guard, &observer, &security)
+            })
+            .collect();
+    }
 
     println!("🦀 ZeroClaw Channel Server");
     println!("  🤖 Model:    {model}");
@@ -3289,6 +3870,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
 
+    let (history_store, replayed_histories) = open_channel_history_store(&config);
+    let _flush_history_on_shutdown = history_store.clone().map(history_store::FlushOnDrop);
+
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
@@ -3302,7 +3886,8 @@ pub async fn start_channels(config: Config) -> Result<()> {
         auto_save_memory: config.memory.auto_save,
         max_tool_iterations: config.agent.max_tool_iterations,
         min_relevance_score: config.memory.min_relevance_score,
-        conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+        conversation_histories: Arc::new(Mutex::new(replayed_histories)),
+        history_store,
         provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
         route_overrides: Arc::new(Mutex::new(HashMap::new())),
         api_key: config.api_key.clone(),
@@ -3314,15 +3899,45 @@ pub async fn start_channels(config: Config) -> Result<()> {
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
         hooks: if config.hooks.enabled {
-            let mut runner = crate::hooks::HookRunner::new();
-            if config.hooks.builtin.command_logger {
-                runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
-            }
-            Some(Arc::new(runner))
+            Some(Arc::new(crate::hooks::HookRunner::from_config(
+                &config.hooks,
+            )))
         } else {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        in_flight: Arc::default(),
+        cost_tracker: crate::cost::budget::shared_tracker(&config.cost, &config.workspace_dir),
+        transcriber: if config.transcription.enabled {
+            transcription::create_transcriber(&config.transcription)
+                .map_err(|e| tracing::warn!("Voice transcription disabled: {e}"))
+                .ok()
+        } else {
+            None
+        },
+        voice_replies: tts::VoiceReplies::from_config(&config.tts).map(|voice| {
+            Arc::new(
+                voice.with_outbound_guard(crate::security::outbound::shared_guard(
+                    &config.security.outbound,
+                )),
+            )
+        }),
+        estop_config: config
+            .security
+            .estop
+            .enabled
+            .then(|| Arc::new(config.security.estop.clone())),
+        estop_guard: config
+            .config_path
+            .parent()
+            .and_then(|dir| crate::security::estop::shared_guard(&config.security, dir)),
+        approvals: approvals::ChannelApprovals::from_config(&config.autonomy).map(Arc::new),
+        transcripts: crate::sessions::SessionStore::from_config(
+            &config.sessions,
+            &config.workspace_dir,
+        )
+        .map(|store| Arc::new(crate::sessions::TranscriptRegistry::new(store))),
+        security: security.clone(),
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
@@ -3524,6 +4139,7 @@ mod tests {
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -3536,6 +4152,15 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3573,6 +4198,7 @@ mod tests {
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -3585,6 +4211,15 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3599,6 +4234,74 @@ mod tests {
         assert_eq!(turns[0].content, "hello");
     }
 
+    #[test]
+    fn sender_history_mutations_are_mirrored_to_store() {
+        let tmp = TempDir::new().unwrap();
+        let sender = "telegram_u4".to_string();
+        let store = Arc::new(history_store::ChannelHistoryStore::open(tmp.path(), 0).unwrap());
+        let ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: Some(Arc::clone(&store)),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
+        };
+
+        append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
+        append_sender_turn(&ctx, &sender, ChatMessage::assistant("hi"));
+        append_sender_turn(&ctx, &sender, ChatMessage::user("x".repeat(1000).as_str()));
+        assert_eq!(store.load_all().unwrap()[&sender].len(), 3);
+
+        assert!(rollback_orphan_user_turn(&ctx, &sender, &"x".repeat(1000)));
+        assert_eq!(store.load_all().unwrap()[&sender].len(), 2);
+
+        assert!(compact_sender_history(&ctx, &sender));
+        let in_memory = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())[&sender]
+            .clone();
+        let persisted = &store.load_all().unwrap()[&sender];
+        assert_eq!(persisted.len(), in_memory.len());
+        for (disk, mem) in persisted.iter().zip(&in_memory) {
+            assert_eq!(disk.role, mem.role);
+            assert_eq!(disk.content, mem.content);
+        }
+
+        clear_sender_history(&ctx, &sender);
+        assert!(store.load_all().unwrap().is_empty());
+    }
+
     #[test]
     fn rollback_orphan_user_turn_removes_only_latest_matching_user_turn() {
         let sender = "telegram_u3".to_string();
@@ -3625,6 +4328,7 @@ mod tests {
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -3637,6 +4341,15 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4100,6 +4813,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4110,6 +4824,15 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4124,6 +4847,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4159,6 +4883,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4169,6 +4894,15 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4183,6 +4917,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4232,6 +4967,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4244,6 +4980,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4256,6 +5001,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4291,6 +5037,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4303,6 +5050,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4315,6 +5071,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4359,6 +5116,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4371,6 +5129,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4383,6 +5150,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4407,6 +5175,403 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(fallback_provider_impl.call_count.load(Ordering::SeqCst), 0);
     }
 
+    /// Channel with a sender allowlist, used to exercise runtime command gating.
+    struct AllowlistRecordingChannel {
+        allowed: Vec<String>,
+        sent_messages: tokio::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for AllowlistRecordingChannel {
+        fn name(&self) -> &str {
+            "slack"
+        }
+
+        fn allows_sender(&self, sender: &str) -> bool {
+            self.allowed.iter().any(|u| u == sender)
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent_messages
+                .lock()
+                .await
+                .push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn command_test_ctx(
+        channel: Arc<dyn Channel>,
+        provider: Arc<dyn Provider>,
+    ) -> Arc<ChannelRuntimeContext> {
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+        Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
+            provider_cache: Arc::new(Mutex::new(HashMap::from([(
+                "test-provider".to_string(),
+                provider,
+            )]))),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
+        })
+    }
+
+    #[test]
+    fn append_attachment_context_lists_unreferenced_files() {
+        let mut msg = slack_command("alice", "[IMAGE:/tmp/seen.png] what's this?");
+        msg.attachments = vec![
+            Attachment::from_path("/tmp/seen.png"),
+            Attachment::from_url("https://files.example.com/q3.pdf"),
+        ];
+
+        append_attachment_context(&mut msg);
+
+        assert_eq!(
+            msg.content,
+            "[IMAGE:/tmp/seen.png] what's this?\n\n[Attachments]\n\
+             [DOCUMENT] q3.pdf (application/pdf) at https://files.example.com/q3.pdf"
+        );
+
+        let mut plain = slack_command("alice", "hi");
+        append_attachment_context(&mut plain);
+        assert_eq!(plain.content, "hi");
+    }
+
+    fn slack_command(sender: &str, content: &str) -> traits::ChannelMessage {
+        traits::ChannelMessage {
+            id: format!("cmd-{content}"),
+            sender: sender.to_string(),
+            reply_target: "C123".to_string(),
+            content: content.to_string(),
+            channel: "slack".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parse_runtime_command_recognizes_control_commands() {
+        assert_eq!(
+            parse_runtime_command("/status"),
+            Some(ChannelRuntimeCommand::ShowStatus)
+        );
+        assert_eq!(
+            parse_runtime_command("/cost@zeroclaw_bot"),
+            Some(ChannelRuntimeCommand::ShowCost)
+        );
+        assert_eq!(
+            parse_runtime_command("/memory  deploy  notes "),
+            Some(ChannelRuntimeCommand::SearchMemory("deploy notes".into()))
+        );
+        assert_eq!(
+            parse_runtime_command("/forget user_lang"),
+            Some(ChannelRuntimeCommand::ForgetMemory("user_lang".into()))
+        );
+        assert_eq!(
+            parse_runtime_command("/STOP"),
+            Some(ChannelRuntimeCommand::StopTask)
+        );
+        assert_eq!(
+            parse_runtime_command("/estop"),
+            Some(ChannelRuntimeCommand::EngageEstop)
+        );
+        assert_eq!(
+            parse_runtime_command("/estop status"),
+            Some(ChannelRuntimeCommand::ShowEstop)
+        );
+        assert_eq!(
+            parse_runtime_command("/help"),
+            Some(ChannelRuntimeCommand::ShowHelp)
+        );
+        assert_eq!(
+            parse_runtime_command("/voice ON"),
+            Some(ChannelRuntimeCommand::SetVoice(true))
+        );
+        assert_eq!(
+            parse_runtime_command("/voice off"),
+            Some(ChannelRuntimeCommand::SetVoice(false))
+        );
+        assert_eq!(
+            parse_runtime_command("/voice"),
+            Some(ChannelRuntimeCommand::ShowVoice)
+        );
+        assert_eq!(parse_runtime_command("/unknown"), None);
+        assert_eq!(parse_runtime_command("status please"), None);
+    }
+
+    #[tokio::test]
+    async fn voice_command_reports_missing_config_and_unsupported_channel() {
+        let channel_impl = Arc::new(AllowlistRecordingChannel {
+            allowed: vec!["U_ALICE".to_string()],
+            sent_messages: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let provider: Arc<dyn Provider> = Arc::new(ModelCaptureProvider::default());
+        let ctx = command_test_ctx(channel_impl.clone(), provider);
+
+        process_channel_message(
+            ctx.clone(),
+            slack_command("U_ALICE", "/voice on"),
+            CancellationToken::new(),
+        )
+        .await;
+
+        let mut with_tts = (*ctx).clone();
+        let voice = Arc::new(tts::VoiceReplies::new(
+            Arc::new(tts::CommandSynthesizer::new("cat")),
+            100,
+        ));
+        with_tts.voice_replies = Some(Arc::clone(&voice));
+        let msg = slack_command("U_ALICE", "/voice on");
+        process_channel_message(Arc::new(with_tts), msg.clone(), CancellationToken::new()).await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent[0].contains("not configured"));
+        assert_eq!(sent[1], "Voice replies are not supported on slack.");
+        assert!(!voice.is_enabled(&conversation_history_key(&msg)));
+    }
+
+    /// Channel that accepts voice notes; nothing is ever sent in these tests.
+    struct VoiceNoteChannel;
+
+    #[async_trait::async_trait]
+    impl Channel for VoiceNoteChannel {
+        fn name(&self) -> &str {
+            "voice-test"
+        }
+
+        fn supports_voice_notes(&self) -> bool {
+            true
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn voice_replies_are_screened_by_the_outbound_guard() {
+        let channel_impl = Arc::new(AllowlistRecordingChannel {
+            allowed: vec!["U_ALICE".to_string()],
+            sent_messages: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let provider: Arc<dyn Provider> = Arc::new(ModelCaptureProvider::default());
+        let mut ctx = (*command_test_ctx(channel_impl, provider)).clone();
+        let guard = crate::security::OutboundGuard::new(&crate::config::OutboundGuardConfig {
+            patterns: vec![crate::config::OutboundPatternConfig {
+                name: "ticket".to_string(),
+                regex: "TICKET-[0-9]+".to_string(),
+                action: Some(crate::config::OutboundAction::Block),
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        let voice = tts::VoiceReplies::new(Arc::new(tts::CommandSynthesizer::new("cat")), 100)
+            .with_outbound_guard(Some(Arc::new(guard)));
+        voice.set_enabled("alice", true);
+        ctx.voice_replies = Some(Arc::new(voice));
+
+        let channel = VoiceNoteChannel;
+        assert!(
+            synthesize_voice_reply(&ctx, &channel, "alice", "Your ticket is TICKET-42")
+                .await
+                .is_none()
+        );
+        assert!(synthesize_voice_reply(&ctx, &channel, "alice", "All done")
+            .await
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn runtime_commands_work_on_any_channel_for_allowlisted_senders() {
+        let channel_impl = Arc::new(AllowlistRecordingChannel {
+            allowed: vec!["U_ALICE".to_string()],
+            sent_messages: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let ctx = command_test_ctx(channel_impl.clone(), provider_impl.clone());
+
+        process_channel_message(
+            ctx.clone(),
+            slack_command("U_ALICE", "/status"),
+            CancellationToken::new(),
+        )
+        .await;
+        process_channel_message(
+            ctx.clone(),
+            slack_command("U_MALLORY", "/estop"),
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 2);
+        assert!(sent[0].contains("Provider: `test-provider`"));
+        assert!(sent[0].contains("Model: `default-model`"));
+        assert!(sent[0].contains("Token usage tracking is disabled"));
+        assert_eq!(sent[1], RUNTIME_COMMAND_REFUSAL);
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn stop_command_cancels_in_flight_request_for_sender() {
+        let channel_impl = Arc::new(AllowlistRecordingChannel {
+            allowed: vec!["U_ALICE".to_string()],
+            sent_messages: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let provider: Arc<dyn Provider> = Arc::new(ModelCaptureProvider::default());
+        let ctx = command_test_ctx(channel_impl.clone(), provider);
+
+        let running = CancellationToken::new();
+        let msg = slack_command("U_ALICE", "/stop");
+        ctx.in_flight.lock().await.insert(
+            interruption_scope_key(&msg),
+            vec![InFlightSenderTaskState {
+                task_id: 1,
+                cancellation: running.clone(),
+                completion: Arc::new(InFlightTaskCompletion::new()),
+            }],
+        );
+
+        process_channel_message(ctx.clone(), msg.clone(), CancellationToken::new()).await;
+        assert!(running.is_cancelled());
+        assert!(ctx.in_flight.lock().await.is_empty());
+
+        process_channel_message(ctx.clone(), msg, CancellationToken::new()).await;
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent[0], "Stopped 1 in-flight request(s).");
+        assert_eq!(sent[1], "Nothing to stop.");
+    }
+
+    #[tokio::test]
+    async fn estop_command_engages_kill_all_when_enabled() {
+        let channel_impl = Arc::new(AllowlistRecordingChannel {
+            allowed: vec!["U_ALICE".to_string()],
+            sent_messages: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let provider: Arc<dyn Provider> = Arc::new(ModelCaptureProvider::default());
+        let disabled = command_test_ctx(channel_impl.clone(), provider.clone());
+        process_channel_message(
+            disabled,
+            slack_command("U_ALICE", "/estop"),
+            CancellationToken::new(),
+        )
+        .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut ctx = Arc::try_unwrap(command_test_ctx(channel_impl.clone(), provider))
+            .unwrap_or_else(|_| panic!("context should be uniquely owned"));
+        ctx.provider_runtime_options.zeroclaw_dir = Some(temp.path().to_path_buf());
+        ctx.estop_config = Some(Arc::new(crate::config::EstopConfig {
+            enabled: true,
+            state_file: "estop-state.json".to_string(),
+            require_otp_to_resume: false,
+        }));
+        let ctx = Arc::new(ctx);
+        process_channel_message(
+            ctx.clone(),
+            slack_command("U_ALICE", "/estop"),
+            CancellationToken::new(),
+        )
+        .await;
+        process_channel_message(
+            ctx,
+            slack_command("U_ALICE", "/estop status"),
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent[0].contains("Emergency stop is disabled"));
+        assert!(sent[1].contains("Emergency stop engaged"));
+        assert!(sent[2].contains("kill-all"));
+        assert!(temp.path().join("estop-state.json").exists());
+    }
+
+    #[tokio::test]
+    async fn engaged_kill_all_refuses_messages_before_the_llm() {
+        let channel_impl = Arc::new(AllowlistRecordingChannel {
+            allowed: vec!["U_ALICE".to_string()],
+            sent_messages: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let temp = tempfile::TempDir::new().unwrap();
+        let estop_config = crate::config::EstopConfig {
+            enabled: true,
+            state_file: "estop-state.json".to_string(),
+            require_otp_to_resume: false,
+        };
+        crate::security::EstopManager::load(&estop_config, temp.path())
+            .unwrap()
+            .engage(crate::security::EstopLevel::KillAll)
+            .unwrap();
+
+        let mut ctx = Arc::try_unwrap(command_test_ctx(channel_impl.clone(), provider))
+            .unwrap_or_else(|_| panic!("context should be uniquely owned"));
+        ctx.estop_guard = Some(Arc::new(crate::security::EstopGuard::new(
+            &estop_config,
+            temp.path(),
+        )));
+        process_channel_message(
+            Arc::new(ctx),
+            slack_command("U_ALICE", "run the deploy script"),
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("Blocked by emergency stop"), "{sent:?}");
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn process_channel_message_uses_route_override_provider_and_model() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -4448,6 +5613,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(route_overrides)),
             api_key: None,
@@ -4460,6 +5626,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4472,6 +5647,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4519,6 +5695,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4531,6 +5708,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4543,6 +5729,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4602,6 +5789,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4617,6 +5805,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4629,6 +5826,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 4,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4676,6 +5874,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 12,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4688,6 +5887,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4700,6 +5908,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4736,6 +5945,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 3,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4748,6 +5958,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -4760,6 +5979,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4907,6 +6127,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4919,6 +6140,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -4930,6 +6160,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         })
         .await
         .unwrap();
@@ -4941,6 +6172,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         })
         .await
         .unwrap();
@@ -4987,6 +6219,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -4999,6 +6232,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5011,6 +6253,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -5023,6 +6266,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -5079,6 +6323,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5091,6 +6336,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5103,6 +6357,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -5115,6 +6370,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -5153,6 +6409,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5165,6 +6422,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -5177,6 +6443,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5212,6 +6479,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5224,6 +6492,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -5236,6 +6513,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5612,6 +6890,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -5627,6 +6906,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5636,6 +6916,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         assert_ne!(
@@ -5657,6 +6938,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5666,6 +6948,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         mem.store(
@@ -5728,6 +7011,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5740,6 +7024,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -5752,6 +7045,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5767,6 +7061,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5813,6 +7108,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5825,6 +7121,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -5837,6 +7142,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5898,6 +7204,7 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -5910,6 +7217,15 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -5922,6 +7238,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6447,6 +7764,7 @@ This is an example JSON object for profile settings."#;
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6459,6 +7777,15 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6472,6 +7799,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6513,6 +7841,7 @@ This is an example JSON object for profile settings."#;
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            history_store: None,
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
             api_key: None,
@@ -6525,6 +7854,15 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            in_flight: Arc::default(),
+            cost_tracker: None,
+            transcriber: None,
+            voice_replies: None,
+            estop_config: None,
+            estop_guard: None,
+            approvals: None,
+            transcripts: None,
+            security: Arc::new(SecurityPolicy::default()),
         });
 
         process_channel_message(
@@ -6537,6 +7875,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6552,6 +7891,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -2,6 +2,8 @@
 //!
 //! This is NOT a `Channel` trait implementor — it routes MQTT messages
 //! to the SOP engine via `dispatch_sop_event`, not to the chat loop.
+//! When `response_topic` is configured, messages that match no SOP are
+//! answered by the agent and the reply is published to that topic.
 
 use std::sync::{Arc, Mutex};
 
@@ -9,17 +11,24 @@ use anyhow::Result;
 use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, Transport};
 use tracing::{info, warn};
 
-use crate::config::MqttConfig;
+use crate::config::{Config, MqttConfig};
 use crate::sop::audit::SopAuditLogger;
-use crate::sop::dispatch::{dispatch_sop_event, process_headless_results};
+use crate::sop::dispatch::{dispatch_sop_event, execute_dispatch_results, DispatchResult};
 use crate::sop::engine::{now_iso8601, SopEngine};
 use crate::sop::types::{SopEvent, SopTriggerSource};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Agent replies to unmatched messages that may run at once; publishes that
+/// arrive while all are busy get no reply.
+const MAX_CONCURRENT_AGENT_REPLIES: usize = 4;
 
 /// Run the MQTT SOP listener loop.
 ///
 /// Subscribes to configured topics and dispatches incoming publishes
-/// to the SOP engine. Blocks until disconnected or cancelled.
+/// to the SOP engine. Returns on connection errors so the daemon
+/// supervisor can reconnect with backoff.
 pub async fn run_mqtt_sop_listener(
+    root: &Config,
     config: &MqttConfig,
     engine: Arc<Mutex<SopEngine>>,
     audit: Arc<SopAuditLogger>,
@@ -44,36 +53,54 @@ pub async fn run_mqtt_sop_listener(
     }
 
     let (client, mut eventloop) = AsyncClient::new(mqtt_options, 64);
-
-    let qos = match config.qos {
-        0 => QoS::AtMostOnce,
-        1 => QoS::AtLeastOnce,
-        _ => QoS::ExactlyOnce,
-    };
+    let qos = qos_level(config.qos);
+    let reply_slots = Arc::new(Semaphore::new(MAX_CONCURRENT_AGENT_REPLIES));
 
     // Subscribe to all configured topics
-    for topic in &config.topics {
+    for topic in config.topics.iter().filter(|t| !t.trim().is_empty()) {
         client.subscribe(topic, qos).await?;
         info!("MQTT SOP listener: subscribed to '{topic}'");
     }
 
-    crate::health::mark_component_ok("mqtt");
-
     loop {
         match eventloop.poll().await {
             Ok(Event::Incoming(Packet::Publish(msg))) => {
                 let topic = msg.topic.clone();
+                // Never treat our own replies as new input.
+                if config.response_topic.as_deref() == Some(topic.as_str()) {
+                    continue;
+                }
                 let payload = String::from_utf8_lossy(&msg.payload).to_string();
 
                 let event = SopEvent {
                     source: SopTriggerSource::Mqtt,
-                    topic: Some(topic),
-                    payload: Some(payload),
+                    topic: Some(topic.clone()),
+                    payload: Some(payload.clone()),
                     timestamp: now_iso8601(),
                 };
 
                 let results = dispatch_sop_event(&engine, &audit, event).await;
-                process_headless_results(&results).await;
+                let unmatched = results.iter().all(|r| matches!(r, DispatchResult::NoMatch));
+                execute_dispatch_results(root, &audit, results);
+
+                if let (true, Some(response_topic)) = (unmatched, &config.response_topic) {
+                    let Ok(permit) = Arc::clone(&reply_slots).try_acquire_owned() else {
+                        warn!(
+                            "MQTT SOP listener: {MAX_CONCURRENT_AGENT_REPLIES} agent replies \
+                             already running, not answering message on '{topic}'"
+                        );
+                        continue;
+                    };
+                    spawn_agent_reply(
+                        root.clone(),
+                        client.clone(),
+                        response_topic.clone(),
+                        qos,
+                        topic,
+                        payload,
+                        permit,
+                    );
+                }
             }
             Ok(Event::Incoming(Packet::ConnAck(_))) => {
                 crate::health::mark_component_ok("mqtt");
@@ -83,14 +110,52 @@ pub async fn run_mqtt_sop_listener(
                 // Other events (PingResp, SubAck, etc.) — ignore
             }
             Err(e) => {
-                crate::health::mark_component_error("mqtt", e.to_string());
+                // rumqttc would retry immediately on the next poll; hand the
+                // error to the supervisor so reconnects back off instead.
                 warn!("MQTT SOP listener: connection error: {e}");
-                // rumqttc handles auto-reconnect; loop continues
+                return Err(e.into());
             }
         }
     }
 }
 
+/// Answer an unmatched MQTT message with a full agent turn and publish the
+/// reply to `response_topic`.
+fn spawn_agent_reply(
+    config: Config,
+    client: AsyncClient,
+    response_topic: String,
+    qos: QoS,
+    topic: String,
+    payload: String,
+    permit: OwnedSemaphorePermit,
+) {
+    tokio::spawn(async move {
+        let _permit = permit;
+        let prompt = format!("[MQTT message on '{topic}']\n{payload}");
+        match crate::agent::process_message(config, &prompt).await {
+            Ok(reply) => {
+                if let Err(e) = client
+                    .publish(&response_topic, qos, false, reply.into_bytes())
+                    .await
+                {
+                    warn!("MQTT SOP listener: failed to publish reply to '{response_topic}': {e}");
+                }
+            }
+            Err(e) => warn!("MQTT SOP listener: agent reply for '{topic}' failed: {e}"),
+        }
+    });
+}
+
+/// Map a validated config QoS (0–2) onto the rumqttc level.
+fn qos_level(qos: u8) -> QoS {
+    match qos {
+        0 => QoS::AtMostOnce,
+        1 => QoS::AtLeastOnce,
+        _ => QoS::ExactlyOnce,
+    }
+}
+
 /// Extract host from broker URL like "mqtt://host:port"
 fn broker_host(url: &str) -> String {
     let without_scheme = url
@@ -134,6 +199,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("qos must be 0, 1, or 2"));
@@ -150,6 +216,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("mqtt://"));
@@ -166,6 +233,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("at least one topic"));
@@ -182,6 +250,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("client_id must not be empty"));
@@ -198,6 +267,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         assert!(config.validate().is_ok());
     }
@@ -213,6 +283,7 @@ mod tests {
             password: None,
             use_tls: true,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("use_tls is true"));
@@ -229,6 +300,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("mqtts://"));
@@ -245,10 +317,72 @@ mod tests {
             password: None,
             use_tls: true,
             keep_alive_secs: 30,
+            response_topic: None,
         };
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn mqtt_config_validation_rejects_wildcard_response_topic() {
+        let config = MqttConfig {
+            broker_url: "mqtt://localhost:1883".into(),
+            client_id: "zeroclaw".into(),
+            topics: vec!["sensors/#".into()],
+            qos: 1,
+            username: None,
+            password: None,
+            use_tls: false,
+            keep_alive_secs: 30,
+            response_topic: Some("replies/#".into()),
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("response_topic"));
+    }
+
+    #[test]
+    fn mqtt_config_validation_rejects_response_topic_under_subscription() {
+        let config = MqttConfig {
+            broker_url: "mqtt://localhost:1883".into(),
+            client_id: "zeroclaw".into(),
+            topics: vec!["alerts".into(), "sensors/#".into()],
+            qos: 1,
+            username: None,
+            password: None,
+            use_tls: false,
+            keep_alive_secs: 30,
+            response_topic: Some("sensors/reply".into()),
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("sensors/#"), "{err}");
+
+        let config = MqttConfig {
+            response_topic: Some("zeroclaw/replies".into()),
+            ..config
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn topic_filters_match_mqtt_wildcards() {
+        use crate::config::schema::mqtt_topic_matches;
+
+        assert!(mqtt_topic_matches("sensors/#", "sensors/reply"));
+        assert!(mqtt_topic_matches("sensors/#", "sensors"));
+        assert!(mqtt_topic_matches("#", "anything/at/all"));
+        assert!(mqtt_topic_matches("sensors/+/temp", "sensors/kitchen/temp"));
+        assert!(mqtt_topic_matches("sensors/reply", "sensors/reply"));
+        assert!(!mqtt_topic_matches("sensors/+", "sensors/kitchen/temp"));
+        assert!(!mqtt_topic_matches("sensors/+/temp", "sensors/kitchen"));
+        assert!(!mqtt_topic_matches("sensors", "sensors/reply"));
+    }
+
+    #[test]
+    fn qos_level_maps_config_values() {
+        assert_eq!(qos_level(0), QoS::AtMostOnce);
+        assert_eq!(qos_level(1), QoS::AtLeastOnce);
+        assert_eq!(qos_level(2), QoS::ExactlyOnce);
+    }
+
     #[test]
     fn broker_host_extracts_host() {
         assert_eq!(broker_host("mqtt://myhost:1883"), "myhost");
@@ -168,6 +168,7 @@ impl NextcloudTalkChannel {
             channel: "nextcloud_talk".to_string(),
             timestamp,
             thread_ts: None,
+            attachments: Vec::new(),
         });
 
         messages
@@ -207,6 +208,10 @@ impl Channel for NextcloudTalkChannel {
         "nextcloud_talk"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         self.send_to_room(&message.recipient, &message.content)
             .await
@@ -248,6 +248,10 @@ impl Channel for QQChannel {
         "qq"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let token = self.get_token().await?;
 
@@ -461,6 +465,7 @@ impl Channel for QQChannel {
                                     .unwrap_or_default()
                                     .as_secs(),
                                 thread_ts: None,
+                                attachments: Vec::new(),
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -499,6 +504,7 @@ impl Channel for QQChannel {
                                     .unwrap_or_default()
                                     .as_secs(),
                                 thread_ts: None,
+                                attachments: Vec::new(),
                             };
 
                             if tx.send(channel_msg).await.is_err() {
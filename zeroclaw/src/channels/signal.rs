@@ -1,5 +1,7 @@
 use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::{Attachment, AttachmentKind, AttachmentSource};
 use async_trait::async_trait;
+use base64::Engine;
 use futures_util::StreamExt;
 use reqwest::Client;
 use serde::Deserialize;
@@ -232,7 +234,10 @@ impl SignalChannel {
             }
         }
 
-        let text = data_msg.message.as_deref().filter(|t| !t.is_empty())?;
+        let text = data_msg.message.as_deref().unwrap_or_default();
+        if text.is_empty() && Self::voice_notes(data_msg).next().is_none() {
+            return None;
+        }
         let sender = Self::sender(envelope)?;
 
         if !self.is_sender_allowed(&sender) {
@@ -266,30 +271,145 @@ impl SignalChannel {
             channel: "signal".to_string(),
             timestamp: timestamp / 1000, // millis → secs
             thread_ts: None,
+            attachments: Vec::new(),
         })
     }
-}
-
-#[async_trait]
-impl Channel for SignalChannel {
-    fn name(&self) -> &str {
-        "signal"
-    }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let params = match Self::parse_recipient_target(&message.recipient) {
+    /// JSON-RPC `send` parameters for a direct or group recipient.
+    fn send_params(&self, recipient: &str, message: &str) -> serde_json::Value {
+        match Self::parse_recipient_target(recipient) {
             RecipientTarget::Direct(number) => serde_json::json!({
                 "recipient": [number],
-                "message": &message.content,
+                "message": message,
                 "account": &self.account,
             }),
             RecipientTarget::Group(group_id) => serde_json::json!({
                 "groupId": group_id,
-                "message": &message.content,
+                "message": message,
                 "account": &self.account,
             }),
-        };
+        }
+    }
+
+    /// Audio attachments of a data message (Signal voice notes are AAC).
+    fn voice_notes(data_msg: &DataMessage) -> impl Iterator<Item = &serde_json::Value> {
+        data_msg.attachments.iter().flatten().filter(|att| {
+            att.get("contentType")
+                .and_then(|c| c.as_str())
+                .is_some_and(|c| c.starts_with("audio/"))
+        })
+    }
+
+    /// Download an attachment from signal-cli via the `getAttachment` RPC.
+    async fn fetch_signal_attachment(
+        &self,
+        id: &str,
+        data_msg: &DataMessage,
+        sender: &str,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut params = serde_json::json!({ "id": id, "account": &self.account });
+        match data_msg
+            .group_info
+            .as_ref()
+            .and_then(|g| g.group_id.as_deref())
+        {
+            Some(group_id) => params["groupId"] = serde_json::json!(group_id),
+            None => params["recipient"] = serde_json::json!(sender),
+        }
+
+        let result = self
+            .rpc_request("getAttachment", params)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Signal getAttachment returned no data"))?;
+        let encoded = result
+            .get("data")
+            .and_then(|d| d.as_str())
+            .or_else(|| result.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Signal getAttachment returned no data"))?;
+        Ok(base64::engine::general_purpose::STANDARD.decode(encoded)?)
+    }
+
+    /// Build the message for an envelope, downloading any voice notes.
+    async fn envelope_to_message(&self, envelope: &Envelope) -> Option<ChannelMessage> {
+        let mut msg = self.process_envelope(envelope)?;
+        let data_msg = envelope.data_message.as_ref()?;
+
+        for note in Self::voice_notes(data_msg) {
+            let Some(id) = note.get("id").and_then(|i| i.as_str()) else {
+                continue;
+            };
+            match self
+                .fetch_signal_attachment(id, data_msg, &msg.sender)
+                .await
+            {
+                Ok(bytes) => {
+                    let filename = note.get("filename").and_then(|f| f.as_str()).unwrap_or(id);
+                    let mime_type = note
+                        .get("contentType")
+                        .and_then(|c| c.as_str())
+                        .unwrap_or("audio/aac");
+                    msg.attachments.push(
+                        Attachment::from_bytes(filename, bytes)
+                            .with_kind(AttachmentKind::Voice)
+                            .with_mime_type(mime_type),
+                    );
+                }
+                Err(e) => tracing::warn!("Signal: failed to fetch voice note {id}: {e}"),
+            }
+        }
+
+        if msg.content.is_empty() && msg.attachments.is_empty() {
+            return None;
+        }
+        Some(msg)
+    }
+}
+
+#[async_trait]
+impl Channel for SignalChannel {
+    fn name(&self) -> &str {
+        "signal"
+    }
+
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_sender_allowed(sender)
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let params = self.send_params(&message.recipient, &message.content);
+        self.rpc_request("send", params).await?;
+        Ok(())
+    }
+
+    fn supports_voice_notes(&self) -> bool {
+        true
+    }
+
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        recipient: &str,
+        _thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if let AttachmentSource::Url(url) = &attachment.source {
+            return self
+                .send(&SendMessage::new(
+                    format!("📎 {}: {url}", attachment.filename),
+                    recipient,
+                ))
+                .await;
+        }
 
+        // signal-cli accepts inline attachments as data URIs.
+        let bytes = attachment.read_bytes().await?;
+        let data_uri = format!(
+            "data:{};filename={};base64,{}",
+            attachment.mime_type,
+            attachment.filename,
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        );
+        let mut params = self.send_params(recipient, "");
+        params["attachments"] = serde_json::json!([data_uri]);
         self.rpc_request("send", params).await?;
         Ok(())
     }
@@ -369,7 +489,8 @@ impl Channel for SignalChannel {
                             match serde_json::from_str::<SseEnvelope>(&current_data) {
                                 Ok(sse) => {
                                     if let Some(ref envelope) = sse.envelope {
-                                        if let Some(msg) = self.process_envelope(envelope) {
+                                        if let Some(msg) = self.envelope_to_message(envelope).await
+                                        {
                                             if tx.send(msg).await.is_err() {
                                                 return Ok(());
                                             }
@@ -396,7 +517,7 @@ impl Channel for SignalChannel {
                 match serde_json::from_str::<SseEnvelope>(&current_data) {
                     Ok(sse) => {
                         if let Some(ref envelope) = sse.envelope {
-                            if let Some(msg) = self.process_envelope(envelope) {
+                            if let Some(msg) = self.envelope_to_message(envelope).await {
                                 let _ = tx.send(msg).await;
                             }
                         }
@@ -855,6 +976,45 @@ mod tests {
         assert!(ch.process_envelope(&env).is_none());
     }
 
+    #[test]
+    fn send_params_target_direct_and_group() {
+        let ch = make_channel();
+        let direct = ch.send_params("+1111111111", "hi");
+        assert_eq!(direct["recipient"][0], "+1111111111");
+        assert_eq!(direct["message"], "hi");
+
+        let group = ch.send_params("group:abc==", "");
+        assert_eq!(group["groupId"], "abc==");
+        assert_eq!(group["account"], "+1234567890");
+    }
+
+    #[test]
+    fn process_envelope_accepts_voice_note_only() {
+        let ch = make_channel();
+        let env = Envelope {
+            source: Some("+1111111111".to_string()),
+            source_number: Some("+1111111111".to_string()),
+            data_message: Some(DataMessage {
+                message: None,
+                timestamp: Some(1_700_000_000_000),
+                group_info: None,
+                attachments: Some(vec![
+                    serde_json::json!({"contentType": "audio/aac", "id": "v1.aac"}),
+                    serde_json::json!({"contentType": "image/png", "id": "p1.png"}),
+                ]),
+            }),
+            story_message: None,
+            timestamp: Some(1_700_000_000_000),
+        };
+        let msg = ch.process_envelope(&env).unwrap();
+        assert!(msg.content.is_empty());
+
+        let data_msg = env.data_message.as_ref().unwrap();
+        let notes: Vec<_> = SignalChannel::voice_notes(data_msg).collect();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0]["id"], "v1.aac");
+    }
+
     #[test]
     fn sse_envelope_deserializes() {
         let json = r#"{
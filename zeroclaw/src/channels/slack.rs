@@ -1,3 +1,4 @@
+use super::attachment::{Attachment, AttachmentSource};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -159,6 +160,106 @@ impl SlackChannel {
         Ok(channels)
     }
 
+    /// Files shared with a message. `url_private` needs the bot token to
+    /// download, so the URL is passed through rather than fetched.
+    fn inbound_attachments(msg: &serde_json::Value) -> Vec<Attachment> {
+        msg.get("files")
+            .and_then(|f| f.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|file| {
+                let url = file.get("url_private").and_then(|u| u.as_str())?;
+                let mut attachment = Attachment::from_url(url);
+                if let Some(name) = file.get("name").and_then(|n| n.as_str()) {
+                    attachment.filename = name.to_string();
+                }
+                if let Some(mime) = file.get("mimetype").and_then(|m| m.as_str()) {
+                    attachment = attachment
+                        .with_kind(super::AttachmentKind::from_mime(mime))
+                        .with_mime_type(mime);
+                }
+                if let Some(size) = file.get("size").and_then(serde_json::Value::as_u64) {
+                    attachment = attachment.with_size(size);
+                }
+                Some(attachment)
+            })
+            .collect()
+    }
+
+    /// Parse a Web API response, failing on HTTP errors and `"ok": false`.
+    async fn api_result(
+        resp: reqwest::Response,
+        method: &str,
+    ) -> anyhow::Result<serde_json::Value> {
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+
+        if !status.is_success() {
+            anyhow::bail!("Slack {method} failed ({status}): {body}");
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack {method} failed: {err}");
+        }
+        Ok(parsed)
+    }
+
+    /// Upload via `files.getUploadURLExternal` + `files.completeUploadExternal`.
+    async fn upload_file(
+        &self,
+        filename: &str,
+        bytes: Vec<u8>,
+        channel_id: &str,
+        thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let client = self.http_client();
+        let resp = client
+            .get("https://slack.com/api/files.getUploadURLExternal")
+            .bearer_auth(&self.bot_token)
+            .query(&[
+                ("filename", filename.to_string()),
+                ("length", bytes.len().to_string()),
+            ])
+            .send()
+            .await?;
+        let ticket = Self::api_result(resp, "files.getUploadURLExternal").await?;
+        let (Some(upload_url), Some(file_id)) = (
+            ticket.get("upload_url").and_then(|u| u.as_str()),
+            ticket.get("file_id").and_then(|f| f.as_str()),
+        ) else {
+            anyhow::bail!("Slack files.getUploadURLExternal returned no upload_url/file_id");
+        };
+
+        let upload = client.post(upload_url).body(bytes).send().await?;
+        if !upload.status().is_success() {
+            anyhow::bail!("Slack file upload failed ({})", upload.status());
+        }
+
+        let mut body = serde_json::json!({
+            "files": [{ "id": file_id, "title": filename }],
+            "channel_id": channel_id,
+        });
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+        let resp = client
+            .post("https://slack.com/api/files.completeUploadExternal")
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await?;
+        Self::api_result(resp, "files.completeUploadExternal").await?;
+        Ok(())
+    }
+
     fn slack_now_ts() -> String {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -184,6 +285,10 @@ impl Channel for SlackChannel {
         "slack"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        self.is_user_allowed(sender)
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let mut body = serde_json::json!({
             "channel": message.recipient,
@@ -202,27 +307,28 @@ impl Channel for SlackChannel {
             .send()
             .await?;
 
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        // Slack returns 200 for most app-level errors; api_result checks "ok"
+        Self::api_result(resp, "chat.postMessage").await?;
+        Ok(())
+    }
 
-        if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        recipient: &str,
+        thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if let AttachmentSource::Url(url) = &attachment.source {
+            return self
+                .send(
+                    &SendMessage::new(format!("📎 <{url}|{}>", attachment.filename), recipient)
+                        .in_thread(thread_ts.map(str::to_string)),
+                )
+                .await;
         }
-
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
-        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
-            let err = parsed
-                .get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
-        }
-
-        Ok(())
+        let bytes = attachment.read_bytes().await?;
+        self.upload_file(&attachment.filename, bytes, recipient, thread_ts)
+            .await
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -351,8 +457,10 @@ impl Channel for SlackChannel {
                             continue;
                         }
 
+                        let attachments = Self::inbound_attachments(msg);
+
                         // Skip empty or already-seen
-                        if text.is_empty() || ts <= last_ts {
+                        if (text.is_empty() && attachments.is_empty()) || ts <= last_ts {
                             continue;
                         }
 
@@ -369,6 +477,7 @@ impl Channel for SlackChannel {
                                 .unwrap_or_default()
                                 .as_secs(),
                             thread_ts: Self::inbound_thread_ts(msg, ts),
+                            attachments,
                         };
 
                         if tx.send(channel_msg).await.is_err() {
@@ -407,6 +516,31 @@ mod tests {
         assert_eq!(ch.channel_id, Some("C12345".to_string()));
     }
 
+    #[test]
+    fn inbound_attachments_read_slack_file_metadata() {
+        let msg = serde_json::json!({
+            "text": "",
+            "files": [
+                {
+                    "name": "Q3 report.pdf",
+                    "mimetype": "application/pdf",
+                    "size": 2048,
+                    "url_private": "https://files.slack.com/files-pri/T1-F1/q3.pdf"
+                },
+                {"name": "no-url.txt"}
+            ]
+        });
+        let attachments = SlackChannel::inbound_attachments(&msg);
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].filename, "Q3 report.pdf");
+        assert_eq!(attachments[0].mime_type, "application/pdf");
+        assert_eq!(attachments[0].size, Some(2048));
+        assert_eq!(
+            attachments[0].url(),
+            Some("https://files.slack.com/files-pri/T1-F1/q3.pdf")
+        );
+    }
+
     #[test]
     fn normalized_channel_id_respects_wildcard_and_blank() {
         assert_eq!(SlackChannel::normalized_channel_id(None), None);
@@ -1,3 +1,4 @@
+use super::attachment::{Attachment, AttachmentKind, AttachmentSource};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
@@ -139,6 +140,18 @@ struct TelegramAttachment {
     target: String,
 }
 
+impl From<AttachmentKind> for TelegramAttachmentKind {
+    fn from(kind: AttachmentKind) -> Self {
+        match kind {
+            AttachmentKind::Image => Self::Image,
+            AttachmentKind::Audio => Self::Audio,
+            AttachmentKind::Voice => Self::Voice,
+            AttachmentKind::Video => Self::Video,
+            AttachmentKind::Document => Self::Document,
+        }
+    }
+}
+
 impl TelegramAttachmentKind {
     fn from_marker(marker: &str) -> Option<Self> {
         match marker.trim().to_ascii_uppercase().as_str() {
@@ -310,6 +323,9 @@ pub struct TelegramChannel {
     api_base: String,
     transcription: Option<crate::config::TranscriptionConfig>,
     voice_transcriptions: Mutex<std::collections::HashMap<String, String>>,
+    /// Numeric user id last seen for each admitted username, so
+    /// [`Channel::allows_sender`] can match id-only allowlist entries.
+    sender_ids: Mutex<std::collections::HashMap<String, String>>,
     workspace_dir: Option<std::path::PathBuf>,
 }
 
@@ -341,6 +357,7 @@ impl TelegramChannel {
             api_base: "https://api.telegram.org".to_string(),
             transcription: None,
             voice_transcriptions: Mutex::new(std::collections::HashMap::new()),
+            sender_ids: Mutex::new(std::collections::HashMap::new()),
             workspace_dir: None,
         }
     }
@@ -652,6 +669,22 @@ impl TelegramChannel {
         identities.into_iter().any(|id| self.is_user_allowed(id))
     }
 
+    /// Check a sender's username and numeric id against the allowlist,
+    /// remembering the id of admitted senders for [`Channel::allows_sender`].
+    fn admit_sender(&self, username: &str, sender_id: Option<&str>) -> bool {
+        let mut identities = vec![username];
+        identities.extend(sender_id);
+        if !self.is_any_user_allowed(identities) {
+            return false;
+        }
+        if let Some(id) = sender_id {
+            self.sender_ids
+                .lock()
+                .insert(username.to_string(), id.to_string());
+        }
+        true
+    }
+
     async fn handle_unauthorized_message(&self, update: &serde_json::Value) {
         let Some(message) = update.get("message") else {
             return;
@@ -928,12 +961,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
 
         let (username, sender_id, sender_identity) = Self::extract_sender_info(message);
 
-        let mut identities = vec![username.as_str()];
-        if let Some(id) = sender_id.as_deref() {
-            identities.push(id);
-        }
-
-        if !self.is_any_user_allowed(identities.iter().copied()) {
+        if !self.admit_sender(&username, sender_id.as_deref()) {
             return None;
         }
 
@@ -1032,6 +1060,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            attachments: vec![Attachment::from_path(local_path)],
         })
     }
 
@@ -1055,12 +1084,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
 
         let (username, sender_id, sender_identity) = Self::extract_sender_info(message);
 
-        let mut identities = vec![username.as_str()];
-        if let Some(id) = sender_id.as_deref() {
-            identities.push(id);
-        }
-
-        if !self.is_any_user_allowed(identities.iter().copied()) {
+        if !self.admit_sender(&username, sender_id.as_deref()) {
             return None;
         }
 
@@ -1149,6 +1173,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            attachments: Vec::new(),
         })
     }
 
@@ -1235,12 +1260,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
 
         let (username, sender_id, sender_identity) = Self::extract_sender_info(message);
 
-        let mut identities = vec![username.as_str()];
-        if let Some(id) = sender_id.as_deref() {
-            identities.push(id);
-        }
-
-        if !self.is_any_user_allowed(identities.iter().copied()) {
+        if !self.admit_sender(&username, sender_id.as_deref()) {
             return None;
         }
 
@@ -1305,9 +1325,85 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            attachments: Vec::new(),
         })
     }
 
+    /// Inline keyboard for an approval prompt. Button presses come back as
+    /// `callback_query` updates carrying `/approve <id> <decision>`.
+    fn approval_keyboard(approval_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "inline_keyboard": [[
+                {"text": "✅ Approve", "callback_data": format!("/approve {approval_id} yes")},
+                {"text": "♾️ Always", "callback_data": format!("/approve {approval_id} always")},
+                {"text": "🚫 Deny", "callback_data": format!("/approve {approval_id} no")},
+            ]]
+        })
+    }
+
+    /// Turn an approval button press into a channel message.
+    ///
+    /// Only presses on `/approve` buttons from allowed users are accepted.
+    fn parse_callback_query(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
+        let query = update.get("callback_query")?;
+        let data = query.get("data").and_then(serde_json::Value::as_str)?;
+        if !data.starts_with("/approve ") {
+            return None;
+        }
+
+        let (username, sender_id, sender_identity) = Self::extract_sender_info(query);
+        if !self.admit_sender(&username, sender_id.as_deref()) {
+            return None;
+        }
+
+        let message = query.get("message")?;
+        let chat_id = message
+            .get("chat")
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)?
+            .to_string();
+        let thread_id = message
+            .get("message_thread_id")
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string());
+        let reply_target = match &thread_id {
+            Some(tid) => format!("{chat_id}:{tid}"),
+            None => chat_id.clone(),
+        };
+        let query_id = query
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+
+        Some(ChannelMessage {
+            id: format!("telegram_{chat_id}_cb_{query_id}"),
+            sender: sender_identity,
+            reply_target,
+            content: data.to_string(),
+            channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: thread_id,
+            attachments: Vec::new(),
+        })
+    }
+
+    /// Stop the loading spinner on a pressed inline button.
+    async fn answer_callback_query(&self, query_id: &str) {
+        let body = serde_json::json!({ "callback_query_id": query_id });
+        if let Err(e) = self
+            .http_client()
+            .post(self.api_url("answerCallbackQuery"))
+            .json(&body)
+            .send()
+            .await
+        {
+            tracing::debug!("Telegram answerCallbackQuery failed: {e}");
+        }
+    }
+
     /// Download a Telegram photo by file_id, resize to fit within 1024px, and return as base64 data URI.
     async fn resolve_photo_data_uri(&self, file_id: &str) -> anyhow::Result<String> {
         use base64::Engine as _;
@@ -1641,7 +1737,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         Ok(())
     }
 
-    async fn send_attachment(
+    async fn send_marker_attachment(
         &self,
         chat_id: &str,
         thread_id: Option<&str>,
@@ -1996,6 +2092,19 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             .unwrap_or("voice.ogg");
 
         let file_bytes = tokio::fs::read(file_path).await?;
+        self.send_voice_bytes(chat_id, thread_id, file_bytes, file_name, caption)
+            .await
+    }
+
+    /// Send in-memory audio (OGG/Opus, MP3 or M4A) as a voice message
+    pub async fn send_voice_bytes(
+        &self,
+        chat_id: &str,
+        thread_id: Option<&str>,
+        file_bytes: Vec<u8>,
+        file_name: &str,
+        caption: Option<&str>,
+    ) -> anyhow::Result<()> {
         let part = Part::bytes(file_bytes).file_name(file_name.to_string());
 
         let mut form = Form::new()
@@ -2143,6 +2252,11 @@ impl Channel for TelegramChannel {
         "telegram"
     }
 
+    fn allows_sender(&self, sender: &str) -> bool {
+        let sender_id = self.sender_ids.lock().get(sender).cloned();
+        self.is_any_user_allowed(std::iter::once(sender).chain(sender_id.as_deref()))
+    }
+
     fn supports_draft_updates(&self) -> bool {
         self.stream_mode != StreamMode::Off
     }
@@ -2309,7 +2423,7 @@ impl Channel for TelegramChannel {
 
             // Send attachments
             for attachment in &attachments {
-                self.send_attachment(&chat_id, thread_id.as_deref(), attachment)
+                self.send_marker_attachment(&chat_id, thread_id.as_deref(), attachment)
                     .await?;
             }
 
@@ -2435,14 +2549,15 @@ impl Channel for TelegramChannel {
             }
 
             for attachment in &attachments {
-                self.send_attachment(chat_id, thread_id, attachment).await?;
+                self.send_marker_attachment(chat_id, thread_id, attachment)
+                    .await?;
             }
 
             return Ok(());
         }
 
         if let Some(attachment) = parse_path_only_attachment(&content) {
-            self.send_attachment(chat_id, thread_id, &attachment)
+            self.send_marker_attachment(chat_id, thread_id, &attachment)
                 .await?;
             return Ok(());
         }
@@ -2450,6 +2565,89 @@ impl Channel for TelegramChannel {
         self.send_text_chunks(&content, chat_id, thread_id).await
     }
 
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        recipient: &str,
+        _thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let (chat_id, thread_id) = Self::parse_reply_target(recipient);
+        let thread_id = thread_id.as_deref();
+        let kind = TelegramAttachmentKind::from(attachment.kind);
+
+        let target = match &attachment.source {
+            AttachmentSource::Url(url) => url.clone(),
+            AttachmentSource::Path(path) => path.to_string_lossy().into_owned(),
+            AttachmentSource::Bytes(bytes) => {
+                return if kind == TelegramAttachmentKind::Image {
+                    self.send_photo_bytes(
+                        &chat_id,
+                        thread_id,
+                        bytes.clone(),
+                        &attachment.filename,
+                        None,
+                    )
+                    .await
+                } else if kind == TelegramAttachmentKind::Voice {
+                    self.send_voice_bytes(
+                        &chat_id,
+                        thread_id,
+                        bytes.clone(),
+                        &attachment.filename,
+                        None,
+                    )
+                    .await
+                } else {
+                    self.send_document_bytes(
+                        &chat_id,
+                        thread_id,
+                        bytes.clone(),
+                        &attachment.filename,
+                        None,
+                    )
+                    .await
+                };
+            }
+        };
+
+        self.send_marker_attachment(&chat_id, thread_id, &TelegramAttachment { kind, target })
+            .await
+    }
+
+    fn supports_voice_notes(&self) -> bool {
+        true
+    }
+
+    async fn send_approval_prompt(
+        &self,
+        approval: &crate::approval::PendingApproval,
+        recipient: &str,
+        _thread_ts: Option<String>,
+    ) -> anyhow::Result<()> {
+        let (chat_id, thread_id) = Self::parse_reply_target(recipient);
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": approval.prompt_text(),
+            "reply_markup": Self::approval_keyboard(&approval.id),
+        });
+        if let Some(tid) = thread_id {
+            body["message_thread_id"] = serde_json::Value::String(tid);
+        }
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("sendMessage"))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Telegram sendMessage (approval prompt) failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let mut offset: i64 = 0;
 
@@ -2469,7 +2667,7 @@ impl Channel for TelegramChannel {
             let probe = serde_json::json!({
                 "offset": offset,
                 "timeout": 0,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
             match self.http_client().post(&url).json(&probe).send().await {
                 Err(e) => {
@@ -2542,7 +2740,7 @@ impl Channel for TelegramChannel {
             let body = serde_json::json!({
                 "offset": offset,
                 "timeout": 30,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
 
             let resp = match self.http_client().post(&url).json(&body).send().await {
@@ -2603,6 +2801,19 @@ Ensure only one `zeroclaw` process is using this bot token."
                         offset = uid + 1;
                     }
 
+                    if let Some(query) = update.get("callback_query") {
+                        if let Some(query_id) = query.get("id").and_then(serde_json::Value::as_str)
+                        {
+                            self.answer_callback_query(query_id).await;
+                        }
+                        if let Some(msg) = self.parse_callback_query(update) {
+                            if tx.send(msg).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                        continue;
+                    }
+
                     let msg = if let Some(m) = self.parse_update_message(update) {
                         m
                     } else if let Some(m) = self.try_parse_voice_message(update).await {
@@ -3059,6 +3270,74 @@ mod tests {
         assert_eq!(msg.id, "telegram_-100200300_33");
     }
 
+    #[test]
+    fn bound_user_with_username_passes_allows_sender() {
+        // `/bind` stores the numeric id, but messages report the username.
+        let ch = TelegramChannel::new("token".into(), vec!["555".into()], false);
+        assert!(!ch.allows_sender("alice"));
+
+        let update = serde_json::json!({
+            "update_id": 1,
+            "message": {
+                "message_id": 34,
+                "text": "/status",
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 555 }
+            }
+        });
+        let msg = ch
+            .parse_update_message(&update)
+            .expect("bound user should be admitted");
+
+        assert_eq!(msg.sender, "alice");
+        assert!(ch.allows_sender(&msg.sender));
+        assert!(!ch.allows_sender("mallory"));
+    }
+
+    #[test]
+    fn approval_keyboard_buttons_carry_approve_commands() {
+        let keyboard = TelegramChannel::approval_keyboard("ab12cd34");
+        let row = &keyboard["inline_keyboard"][0];
+        assert_eq!(row[0]["callback_data"], "/approve ab12cd34 yes");
+        assert_eq!(row[1]["callback_data"], "/approve ab12cd34 always");
+        assert_eq!(row[2]["callback_data"], "/approve ab12cd34 no");
+    }
+
+    #[test]
+    fn parse_callback_query_maps_button_press_to_message() {
+        let ch = TelegramChannel::new("token".into(), vec!["alice".into()], false);
+        let press = |username: &str, data: &str| {
+            serde_json::json!({
+                "update_id": 9,
+                "callback_query": {
+                    "id": "cb-1",
+                    "data": data,
+                    "from": {"id": 555, "username": username},
+                    "message": {
+                        "message_id": 40,
+                        "message_thread_id": 7,
+                        "chat": {"id": -100_200_300}
+                    }
+                }
+            })
+        };
+
+        let msg = ch
+            .parse_callback_query(&press("alice", "/approve ab12cd34 always"))
+            .expect("button press should parse");
+        assert_eq!(msg.sender, "alice");
+        assert_eq!(msg.reply_target, "-100200300:7");
+        assert_eq!(msg.content, "/approve ab12cd34 always");
+        assert_eq!(msg.thread_ts.as_deref(), Some("7"));
+
+        assert!(ch
+            .parse_callback_query(&press("mallory", "/approve ab12cd34 yes"))
+            .is_none());
+        assert!(ch
+            .parse_callback_query(&press("alice", "something else"))
+            .is_none());
+    }
+
     #[test]
     fn parse_update_message_allows_numeric_id_without_username() {
         let ch = TelegramChannel::new("token".into(), vec!["555".into()], false);
@@ -1,3 +1,4 @@
+use super::attachment::{read_capped_body, Attachment, AttachmentSource};
 use async_trait::async_trait;
 
 /// A message received from or sent to a channel
@@ -12,6 +13,8 @@ pub struct ChannelMessage {
     /// Platform thread identifier (e.g. Slack `ts`, Discord thread ID).
     /// When set, replies should be posted as threaded responses.
     pub thread_ts: Option<String>,
+    /// Files the sender attached (downloaded locally or referenced by URL).
+    pub attachments: Vec<Attachment>,
 }
 
 /// Message to send through a channel
@@ -22,6 +25,9 @@ pub struct SendMessage {
     pub subject: Option<String>,
     /// Platform thread identifier for threaded replies (e.g. Slack `thread_ts`).
     pub thread_ts: Option<String>,
+    /// Files to deliver with the message. Only honoured by
+    /// [`Channel::send_with_attachments`]; `send` delivers the text alone.
+    pub attachments: Vec<Attachment>,
 }
 
 impl SendMessage {
@@ -32,6 +38,7 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: None,
             thread_ts: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -46,6 +53,7 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: Some(subject.into()),
             thread_ts: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -54,6 +62,12 @@ impl SendMessage {
         self.thread_ts = thread_ts;
         self
     }
+
+    /// Attach files to deliver alongside the text.
+    pub fn with_attachments(mut self, attachments: Vec<Attachment>) -> Self {
+        self.attachments = attachments;
+        self
+    }
 }
 
 /// Core channel trait — implement for any messaging platform
@@ -73,6 +87,13 @@ pub trait Channel: Send + Sync {
         true
     }
 
+    /// Whether `sender` (as reported in [`ChannelMessage::sender`]) is on this
+    /// channel's allowlist. Gates runtime slash commands; channels without a
+    /// sender-keyed allowlist accept everyone that `listen` lets through.
+    fn allows_sender(&self, _sender: &str) -> bool {
+        true
+    }
+
     /// Signal that the bot is processing a response (e.g. "typing" indicator).
     /// Implementations should repeat the indicator as needed for their platform.
     async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
@@ -142,6 +163,104 @@ pub trait Channel: Send + Sync {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Ask `recipient` to approve a pending tool call.
+    ///
+    /// The default posts a text prompt answered with `yes`, `no` or
+    /// `always`. Channels with interactive widgets (e.g. Telegram inline
+    /// buttons) override this; their widgets must reply with
+    /// `/approve <id> <decision>`.
+    async fn send_approval_prompt(
+        &self,
+        approval: &crate::approval::PendingApproval,
+        recipient: &str,
+        thread_ts: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.send(&SendMessage::new(approval.prompt_text(), recipient).in_thread(thread_ts))
+            .await
+    }
+
+    /// Whether [`Channel::send_attachment`] delivers in-memory
+    /// [`AttachmentKind::Voice`](super::AttachmentKind::Voice) audio as a
+    /// native voice note. Gates spoken replies.
+    fn supports_voice_notes(&self) -> bool {
+        false
+    }
+
+    /// Deliver a single file to `recipient`.
+    ///
+    /// The default posts a link (or local path) as text and rejects
+    /// in-memory content. Channels with native uploads override this.
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        recipient: &str,
+        thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let location = match &attachment.source {
+            AttachmentSource::Url(url) => url.clone(),
+            AttachmentSource::Path(path) => path.display().to_string(),
+            AttachmentSource::Bytes(_) => anyhow::bail!(
+                "{} cannot deliver in-memory attachment '{}'",
+                self.name(),
+                attachment.filename
+            ),
+        };
+        self.send(
+            &SendMessage::new(format!("📎 {}: {location}", attachment.filename), recipient)
+                .in_thread(thread_ts.map(str::to_string)),
+        )
+        .await
+    }
+
+    /// Send the text of `message`, then each of its attachments.
+    ///
+    /// A failed attachment is reported to the recipient instead of aborting
+    /// the remaining ones.
+    async fn send_with_attachments(&self, message: &SendMessage) -> anyhow::Result<()> {
+        if !message.content.trim().is_empty() || message.attachments.is_empty() {
+            self.send(message).await?;
+        }
+        for attachment in &message.attachments {
+            if let Err(e) = self
+                .send_attachment(attachment, &message.recipient, message.thread_ts.as_deref())
+                .await
+            {
+                tracing::warn!(
+                    channel = self.name(),
+                    file = %attachment.filename,
+                    "Failed to send attachment: {e}"
+                );
+                self.send(
+                    &SendMessage::new(
+                        format!("⚠️ Could not attach {}: {e}", attachment.filename),
+                        &message.recipient,
+                    )
+                    .in_thread(message.thread_ts.clone()),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Download the content of an inbound attachment.
+    ///
+    /// The default reads local and in-memory sources and fetches URLs with a
+    /// plain GET, capped at
+    /// [`MAX_ATTACHMENT_BYTES`](super::attachment::MAX_ATTACHMENT_BYTES).
+    /// Channels whose media URLs need authentication override this.
+    async fn fetch_attachment(&self, attachment: &Attachment) -> anyhow::Result<Vec<u8>> {
+        let AttachmentSource::Url(url) = &attachment.source else {
+            return attachment.read_bytes().await;
+        };
+        let resp = crate::config::build_runtime_proxy_client("channel.attachment")
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?;
+        read_capped_body(resp).await
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +291,7 @@ mod tests {
                 channel: "dummy".into(),
                 timestamp: 123,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -188,6 +308,7 @@ mod tests {
             channel: "dummy".into(),
             timestamp: 999,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         let cloned = message.clone();
@@ -199,6 +320,62 @@ mod tests {
         assert_eq!(cloned.timestamp, 999);
     }
 
+    /// Serve one HTTP response with `headers` and `body_len` bytes of body,
+    /// then close the connection.
+    async fn serve_once(headers: &'static str, body_len: usize) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0_u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let head = format!("HTTP/1.1 200 OK\r\n{headers}Connection: close\r\n\r\n");
+            socket.write_all(head.as_bytes()).await.unwrap();
+            let chunk = vec![b'x'; 64 * 1024];
+            let mut sent = 0;
+            while sent < body_len {
+                let n = chunk.len().min(body_len - sent);
+                if socket.write_all(&chunk[..n]).await.is_err() {
+                    return;
+                }
+                sent += n;
+            }
+        });
+        format!("http://{addr}/file.bin")
+    }
+
+    #[tokio::test]
+    async fn default_fetch_attachment_downloads_small_files() {
+        let url = serve_once("Content-Length: 5\r\n", 5).await;
+        let bytes = DummyChannel
+            .fetch_attachment(&Attachment::from_url(&url))
+            .await
+            .unwrap();
+        assert_eq!(bytes, b"xxxxx");
+    }
+
+    #[tokio::test]
+    async fn default_fetch_attachment_rejects_oversized_content_length() {
+        let url = serve_once("Content-Length: 104857600\r\n", 0).await;
+        let err = DummyChannel
+            .fetch_attachment(&Attachment::from_url(&url))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("25 MB"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn default_fetch_attachment_stops_streaming_past_the_cap() {
+        let url = serve_once("", super::super::attachment::MAX_ATTACHMENT_BYTES + 1).await;
+        let err = DummyChannel
+            .fetch_attachment(&Attachment::from_url(&url))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("25 MB"), "{err}");
+    }
+
     #[tokio::test]
     async fn default_trait_methods_return_success() {
         let channel = DummyChannel;
@@ -212,6 +389,26 @@ mod tests {
             .is_ok());
     }
 
+    #[tokio::test]
+    async fn default_approval_prompt_is_sent_as_text() {
+        let broker = crate::approval::ApprovalBroker::new();
+        let (approval, _rx) = broker.register(
+            &crate::approval::ApprovalRequest {
+                tool_name: "shell".into(),
+                arguments: serde_json::json!({"command": "ls"}),
+            },
+            crate::approval::ApprovalOrigin::default(),
+            std::time::Duration::from_secs(60),
+        );
+        let text = approval.prompt_text();
+        assert!(text.contains("`shell`"));
+        assert!(text.contains(&approval.id));
+        assert!(DummyChannel
+            .send_approval_prompt(&approval, "bob", None)
+            .await
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn default_reaction_methods_return_success() {
         let channel = DummyChannel;
@@ -244,6 +441,48 @@ mod tests {
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
     }
 
+    struct RecordingChannel {
+        sent: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent.lock().unwrap().push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn default_send_with_attachments_falls_back_to_links() {
+        let channel = RecordingChannel {
+            sent: std::sync::Mutex::new(Vec::new()),
+        };
+        let message = SendMessage::new("Here you go", "bob").with_attachments(vec![
+            Attachment::from_url("https://example.com/report.pdf"),
+            Attachment::from_bytes("chart.png", vec![1, 2, 3]),
+        ]);
+
+        channel.send_with_attachments(&message).await.unwrap();
+
+        let sent = channel.sent.lock().unwrap();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0], "Here you go");
+        assert_eq!(sent[1], "📎 report.pdf: https://example.com/report.pdf");
+        assert!(sent[2].starts_with("⚠️ Could not attach chart.png"));
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
@@ -1,13 +1,200 @@
+//! Speech-to-text for inbound voice notes.
+//!
+//! [`Transcriber`] abstracts the backend; [`OpenAiCompatibleTranscriber`]
+//! covers every server that speaks the OpenAI `/audio/transcriptions`
+//! protocol (Groq, OpenAI, a local whisper.cpp or faster-whisper server).
+//! Size, format and duration are checked locally before any upload, so an
+//! over-long voice note never leaves the machine.
+
 use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
+use std::sync::Arc;
 
+use super::traits::{Channel, ChannelMessage};
+use super::AttachmentKind;
 use crate::config::TranscriptionConfig;
 
 /// Maximum upload size accepted by the Groq Whisper API (25 MB).
 const MAX_AUDIO_BYTES: usize = 25 * 1024 * 1024;
 
+const GROQ_TRANSCRIPTION_URL: &str = "https://api.groq.com/openai/v1/audio/transcriptions";
+const OPENAI_TRANSCRIPTION_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+const LOCAL_TRANSCRIPTION_URL: &str = "http://127.0.0.1:8080/v1/audio/transcriptions";
+
+/// A speech-to-text backend.
+#[async_trait]
+pub trait Transcriber: Send + Sync {
+    /// Backend name for logs (e.g. `"groq"`, `"local"`).
+    fn name(&self) -> &str;
+
+    /// Transcribe `audio` (the raw file content) and return the text.
+    async fn transcribe(&self, audio: Vec<u8>, file_name: &str) -> Result<String>;
+}
+
+/// Transcriber for any OpenAI-compatible `/audio/transcriptions` endpoint.
+pub struct OpenAiCompatibleTranscriber {
+    name: String,
+    api_url: String,
+    api_key: Option<String>,
+    model: String,
+    language: Option<String>,
+    max_duration_secs: u64,
+}
+
+impl OpenAiCompatibleTranscriber {
+    pub fn new(
+        name: impl Into<String>,
+        api_url: impl Into<String>,
+        api_key: Option<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            api_url: api_url.into(),
+            api_key,
+            model: model.into(),
+            language: None,
+            max_duration_secs: 0,
+        }
+    }
+
+    /// Language hint (ISO-639-1) passed to the backend.
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Reject audio longer than `secs` before uploading. `0` disables the check.
+    pub fn with_max_duration_secs(mut self, secs: u64) -> Self {
+        self.max_duration_secs = secs;
+        self
+    }
+
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+}
+
+#[async_trait]
+impl Transcriber for OpenAiCompatibleTranscriber {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn transcribe(&self, audio: Vec<u8>, file_name: &str) -> Result<String> {
+        let (file_name, mime) = prepare_audio(&audio, file_name)?;
+
+        if self.max_duration_secs > 0 {
+            if let Some(duration) = probe_duration_secs(&audio) {
+                if duration > self.max_duration_secs as f64 {
+                    bail!(
+                        "Audio too long ({duration:.0}s, max {}s)",
+                        self.max_duration_secs
+                    );
+                }
+            }
+        }
+
+        let client =
+            crate::config::build_runtime_proxy_client(&format!("transcription.{}", self.name));
+
+        let file_part = Part::bytes(audio).file_name(file_name).mime_str(mime)?;
+
+        let mut form = Form::new()
+            .part("file", file_part)
+            .text("model", self.model.clone())
+            .text("response_format", "json");
+
+        if let Some(ref lang) = self.language {
+            form = form.text("language", lang.clone());
+        }
+
+        let mut request = client.post(&self.api_url).multipart(form);
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let resp = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send transcription request to {}", self.name))?;
+
+        let status = resp.status();
+        let raw = resp
+            .text()
+            .await
+            .context("Failed to read transcription response")?;
+        let body: serde_json::Value = serde_json::from_str(&raw).unwrap_or_default();
+
+        if !status.is_success() {
+            let error_msg = body["error"]["message"]
+                .as_str()
+                .or_else(|| body["error"].as_str())
+                .unwrap_or_else(|| raw.trim());
+            bail!("Transcription API error ({}): {}", status, error_msg);
+        }
+
+        let text = body["text"]
+            .as_str()
+            .context("Transcription response missing 'text' field")?
+            .trim()
+            .to_string();
+
+        Ok(text)
+    }
+}
+
+/// Build the transcriber described by `[transcription]`.
+///
+/// `provider` picks the default endpoint and the environment variable used
+/// when `api_key` is empty; a non-default `api_url` always wins.
+pub fn create_transcriber(config: &TranscriptionConfig) -> Result<Arc<dyn Transcriber>> {
+    let provider = config.provider.trim().to_ascii_lowercase();
+    let (name, default_url, key_env) = match provider.as_str() {
+        "" | "groq" => ("groq", GROQ_TRANSCRIPTION_URL, Some("GROQ_API_KEY")),
+        "openai" => ("openai", OPENAI_TRANSCRIPTION_URL, Some("OPENAI_API_KEY")),
+        "local" | "whisper" | "whisper-cpp" => ("local", LOCAL_TRANSCRIPTION_URL, None),
+        "custom" => ("custom", config.api_url.as_str(), None),
+        other => bail!(
+            "Unknown transcription provider '{other}' — expected groq, openai, local or custom"
+        ),
+    };
+
+    let api_url = if config.api_url.trim().is_empty() || config.api_url == GROQ_TRANSCRIPTION_URL {
+        default_url
+    } else {
+        config.api_url.as_str()
+    };
+    if api_url.trim().is_empty() {
+        bail!("transcription.api_url is required for the '{name}' provider");
+    }
+
+    let api_key = config
+        .api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(str::to_string);
+    let api_key = match (api_key, key_env) {
+        (Some(key), _) => Some(key),
+        (None, Some(var)) => Some(std::env::var(var).with_context(|| {
+            format!(
+                "{var} environment variable is not set and transcription.api_key is empty — required for voice transcription"
+            )
+        })?),
+        (None, None) => None,
+    };
+
+    Ok(Arc::new(
+        OpenAiCompatibleTranscriber::new(name, api_url, api_key, config.model.clone())
+            .with_language(config.language.clone())
+            .with_max_duration_secs(config.max_duration_secs),
+    ))
+}
+
 /// Map file extension to MIME type for Whisper-compatible transcription APIs.
-fn mime_for_audio(extension: &str) -> Option<&'static str> {
+pub(crate) fn mime_for_audio(extension: &str) -> Option<&'static str> {
     match extension.to_ascii_lowercase().as_str() {
         "flac" => Some("audio/flac"),
         "mp3" | "mpeg" | "mpga" => Some("audio/mpeg"),
@@ -31,78 +218,254 @@ fn normalize_audio_filename(file_name: &str) -> String {
     }
 }
 
-/// Transcribe audio bytes via a Whisper-compatible transcription API.
+/// Identify the container from its magic bytes.
+pub(crate) fn sniff_audio_extension(audio: &[u8]) -> Option<&'static str> {
+    let head = audio.get(..12)?;
+    if head.starts_with(b"OggS") {
+        Some("ogg")
+    } else if head.starts_with(b"RIFF") && &head[8..12] == b"WAVE" {
+        Some("wav")
+    } else if head.starts_with(b"fLaC") {
+        Some("flac")
+    } else if head.starts_with(b"ID3") || mp3_frame_at(audio, 0).is_some() {
+        Some("mp3")
+    } else if &head[4..8] == b"ftyp" {
+        Some("m4a")
+    } else if head.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        Some("webm")
+    } else {
+        None
+    }
+}
+
+/// Check size and format, returning the upload filename and MIME type.
 ///
-/// Returns the transcribed text on success.  Requires `GROQ_API_KEY` in the
-/// environment.  The caller is responsible for enforcing duration limits
-/// *before* downloading the file; this function enforces the byte-size cap.
-pub async fn transcribe_audio(
-    audio_data: Vec<u8>,
-    file_name: &str,
-    config: &TranscriptionConfig,
-) -> Result<String> {
-    if audio_data.len() > MAX_AUDIO_BYTES {
+/// Files without a usable extension (Signal and WhatsApp often send bare
+/// names) are renamed after the format sniffed from their content.
+fn prepare_audio(audio: &[u8], file_name: &str) -> Result<(String, &'static str)> {
+    if audio.len() > MAX_AUDIO_BYTES {
         bail!(
             "Audio file too large ({} bytes, max {MAX_AUDIO_BYTES})",
-            audio_data.len()
+            audio.len()
         );
     }
 
     let normalized_name = normalize_audio_filename(file_name);
-    let extension = normalized_name
+    let (stem, extension) = normalized_name
         .rsplit_once('.')
-        .map(|(_, e)| e)
-        .unwrap_or("");
-    let mime = mime_for_audio(extension).ok_or_else(|| {
-        anyhow::anyhow!(
-            "Unsupported audio format '.{extension}' — accepted: flac, mp3, mp4, mpeg, mpga, m4a, ogg, opus, wav, webm"
-        )
-    })?;
+        .unwrap_or((normalized_name.as_str(), ""));
+    if let Some(mime) = mime_for_audio(extension) {
+        return Ok((normalized_name.clone(), mime));
+    }
+    if let Some(sniffed) = sniff_audio_extension(audio) {
+        let mime = mime_for_audio(sniffed).unwrap_or("application/octet-stream");
+        return Ok((format!("{stem}.{sniffed}"), mime));
+    }
+    bail!(
+        "Unsupported audio format '.{extension}' — accepted: flac, mp3, mp4, mpeg, mpga, m4a, ogg, opus, wav, webm"
+    )
+}
 
-    let api_key = std::env::var("GROQ_API_KEY").context(
-        "GROQ_API_KEY environment variable is not set — required for voice transcription",
-    )?;
+/// Estimate the playback length from container headers.
+///
+/// Covers WAV, Ogg (Opus/Vorbis), FLAC, MP3 (assuming constant bitrate)
+/// and MP4/M4A. Returns `None` when the length cannot be determined, in
+/// which case the backend is left to enforce its own limits.
+fn probe_duration_secs(audio: &[u8]) -> Option<f64> {
+    match sniff_audio_extension(audio)? {
+        "wav" => wav_duration(audio),
+        "ogg" => ogg_duration(audio),
+        "flac" => flac_duration(audio),
+        "mp3" => mp3_duration(audio),
+        "m4a" => mp4_duration(audio),
+        _ => None,
+    }
+}
 
-    let client = crate::config::build_runtime_proxy_client("transcription.groq");
+fn u16_le(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
 
-    let file_part = Part::bytes(audio_data)
-        .file_name(normalized_name)
-        .mime_str(mime)?;
+fn u32_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
 
-    let mut form = Form::new()
-        .part("file", file_part)
-        .text("model", config.model.clone())
-        .text("response_format", "json");
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
 
-    if let Some(ref lang) = config.language {
-        form = form.text("language", lang.clone());
+fn wav_duration(audio: &[u8]) -> Option<f64> {
+    let mut byte_rate = None;
+    let mut offset = 12;
+    while offset + 8 <= audio.len() {
+        let id = &audio[offset..offset + 4];
+        let size = u32_le(audio, offset + 4)? as usize;
+        let body = offset + 8;
+        if id == b"fmt " {
+            byte_rate = u32_le(audio, body + 8);
+        } else if id == b"data" {
+            let size = size.min(audio.len() - body);
+            let rate = byte_rate.filter(|rate| *rate > 0)?;
+            return Some(size as f64 / f64::from(rate));
+        }
+        offset = body.checked_add(size)?.checked_add(size % 2)?;
     }
+    None
+}
 
-    let resp = client
-        .post(&config.api_url)
-        .bearer_auth(&api_key)
-        .multipart(form)
-        .send()
-        .await
-        .context("Failed to send transcription request")?;
+fn ogg_duration(audio: &[u8]) -> Option<f64> {
+    let segments = usize::from(*audio.get(26)?);
+    let payload = audio.get(27 + segments..)?;
+    let (rate, pre_skip) = if payload.starts_with(b"OpusHead") {
+        (48_000, u64::from(u16_le(payload, 10)?))
+    } else if payload.starts_with(b"\x01vorbis") {
+        (u64::from(u32_le(payload, 12)?), 0)
+    } else {
+        return None;
+    };
+    if rate == 0 {
+        return None;
+    }
+
+    let last_page = audio.windows(4).rposition(|window| window == b"OggS")?;
+    let granule = u64::from_le_bytes(audio.get(last_page + 6..last_page + 14)?.try_into().ok()?);
+    if granule == u64::MAX {
+        return None;
+    }
+    Some(granule.saturating_sub(pre_skip) as f64 / rate as f64)
+}
+
+fn flac_duration(audio: &[u8]) -> Option<f64> {
+    // STREAMINFO is always the first metadata block, right after "fLaC".
+    let info = audio.get(8..26)?;
+    let sample_rate =
+        (u32::from(info[10]) << 12) | (u32::from(info[11]) << 4) | (u32::from(info[12]) >> 4);
+    let total_samples = (u64::from(info[13] & 0x0F) << 32) | u64::from(u32_be(info, 14)?);
+    if sample_rate == 0 || total_samples == 0 {
+        return None;
+    }
+    Some(total_samples as f64 / f64::from(sample_rate))
+}
+
+/// Bitrate (kbit/s) of an MPEG Layer III frame header at `at`.
+fn mp3_frame_at(audio: &[u8], at: usize) -> Option<u32> {
+    const MPEG1: [u32; 15] = [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ];
+    const MPEG2: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+    let header = audio.get(at..at + 3)?;
+    if header[0] != 0xFF || header[1] & 0xE0 != 0xE0 {
+        return None;
+    }
+    let version = (header[1] >> 3) & 0x03;
+    let layer = (header[1] >> 1) & 0x03;
+    if version == 1 || layer != 1 {
+        return None;
+    }
+    let table = if version == 3 { &MPEG1 } else { &MPEG2 };
+    let bitrate = *table.get(usize::from(header[2] >> 4))?;
+    (bitrate > 0).then_some(bitrate)
+}
+
+fn mp3_duration(audio: &[u8]) -> Option<f64> {
+    let mut start = 0;
+    if audio.starts_with(b"ID3") {
+        let header = audio.get(..10)?;
+        let size = header[6..10]
+            .iter()
+            .fold(0usize, |acc, byte| (acc << 7) | usize::from(byte & 0x7F));
+        let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+        start = 10 + size + footer;
+    }
+    let bitrate = mp3_frame_at(audio, start)?;
+    let audio_bytes = audio.len().checked_sub(start)?;
+    Some(audio_bytes as f64 * 8.0 / (f64::from(bitrate) * 1000.0))
+}
+
+fn mp4_duration(audio: &[u8]) -> Option<f64> {
+    let pos = audio.windows(4).position(|window| window == b"mvhd")?;
+    let version = *audio.get(pos + 4)?;
+    let (timescale, duration) = if version == 1 {
+        let duration = u64::from_be_bytes(audio.get(pos + 28..pos + 36)?.try_into().ok()?);
+        (u32_be(audio, pos + 24)?, duration)
+    } else {
+        (
+            u32_be(audio, pos + 16)?,
+            u64::from(u32_be(audio, pos + 20)?),
+        )
+    };
+    if timescale == 0 {
+        return None;
+    }
+    Some(duration as f64 / f64::from(timescale))
+}
 
-    let status = resp.status();
-    let body: serde_json::Value = resp
-        .json()
+/// Transcribe audio bytes with the backend configured in `config`.
+///
+/// Returns the transcribed text on success. The API key comes from
+/// `config.api_key`, falling back to `GROQ_API_KEY` / `OPENAI_API_KEY`.
+pub async fn transcribe_audio(
+    audio_data: Vec<u8>,
+    file_name: &str,
+    config: &TranscriptionConfig,
+) -> Result<String> {
+    prepare_audio(&audio_data, file_name)?;
+    create_transcriber(config)?
+        .transcribe(audio_data, file_name)
         .await
-        .context("Failed to parse transcription response")?;
+}
 
-    if !status.is_success() {
-        let error_msg = body["error"]["message"].as_str().unwrap_or("unknown error");
-        bail!("Transcription API error ({}): {}", status, error_msg);
+/// Replace the voice-note attachments of `msg` with `[Voice] …` transcripts.
+///
+/// Audio is fetched through [`Channel::fetch_attachment`] so channels with
+/// authenticated media URLs work unchanged. Notes that fail to download or
+/// transcribe stay attached and are described to the agent as files.
+pub async fn transcribe_voice_attachments(
+    channel: &dyn Channel,
+    transcriber: &dyn Transcriber,
+    msg: &mut ChannelMessage,
+) {
+    if !msg
+        .attachments
+        .iter()
+        .any(|attachment| attachment.kind == AttachmentKind::Voice)
+    {
+        return;
     }
 
-    let text = body["text"]
-        .as_str()
-        .context("Transcription response missing 'text' field")?
-        .to_string();
+    let mut remaining = Vec::with_capacity(msg.attachments.len());
+    for attachment in std::mem::take(&mut msg.attachments) {
+        if attachment.kind != AttachmentKind::Voice {
+            remaining.push(attachment);
+            continue;
+        }
 
-    Ok(text)
+        let transcript = match channel.fetch_attachment(&attachment).await {
+            Ok(audio) => transcriber.transcribe(audio, &attachment.filename).await,
+            Err(e) => Err(e.context("failed to download voice note")),
+        };
+        match transcript {
+            Ok(text) if !text.is_empty() => {
+                if msg.content.trim().is_empty() {
+                    msg.content = format!("[Voice] {text}");
+                } else {
+                    msg.content = format!("{}\n\n[Voice] {text}", msg.content.trim_end());
+                }
+            }
+            Ok(_) => remaining.push(attachment),
+            Err(e) => {
+                tracing::warn!(
+                    channel = %msg.channel,
+                    transcriber = transcriber.name(),
+                    file = %attachment.filename,
+                    "Voice transcription failed: {e:#}"
+                );
+                remaining.push(attachment);
+            }
+        }
+    }
+    msg.attachments = remaining;
 }
 
 #[cfg(test)]
@@ -215,4 +578,173 @@ mod tests {
             "error should mention the rejected extension, got: {msg}"
         );
     }
+
+    fn wav_fixture(byte_rate: u32, data_len: usize) -> Vec<u8> {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&u32::try_from(36 + data_len).unwrap().to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&(byte_rate / 2).to_le_bytes());
+        wav.extend_from_slice(&byte_rate.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&u32::try_from(data_len).unwrap().to_le_bytes());
+        wav.resize(wav.len() + data_len, 0);
+        wav
+    }
+
+    fn ogg_page(granule: u64, payload: &[u8]) -> Vec<u8> {
+        let mut page = Vec::new();
+        page.extend_from_slice(b"OggS");
+        page.extend_from_slice(&[0, 0]);
+        page.extend_from_slice(&granule.to_le_bytes());
+        page.extend_from_slice(&[0; 12]); // serial, sequence, checksum
+        page.push(1);
+        page.push(u8::try_from(payload.len()).unwrap());
+        page.extend_from_slice(payload);
+        page
+    }
+
+    #[test]
+    fn probes_wav_duration() {
+        let wav = wav_fixture(32_000, 64_000);
+        assert_eq!(probe_duration_secs(&wav), Some(2.0));
+    }
+
+    #[test]
+    fn probes_ogg_opus_duration_from_last_granule() {
+        let mut head = b"OpusHead".to_vec();
+        head.extend_from_slice(&[1, 1]);
+        head.extend_from_slice(&312u16.to_le_bytes()); // pre-skip
+        head.extend_from_slice(&[0; 7]);
+        let mut ogg = ogg_page(0, &head);
+        ogg.extend(ogg_page(48_000 * 3 + 312, &[0; 20]));
+
+        assert_eq!(probe_duration_secs(&ogg), Some(3.0));
+    }
+
+    #[test]
+    fn probe_returns_none_for_unknown_content() {
+        assert_eq!(probe_duration_secs(&[0u8; 64]), None);
+    }
+
+    #[test]
+    fn prepare_audio_renames_extensionless_files_from_content() {
+        let wav = wav_fixture(32_000, 100);
+        let (name, mime) = prepare_audio(&wav, "voice-note").unwrap();
+        assert_eq!(name, "voice-note.wav");
+        assert_eq!(mime, "audio/wav");
+    }
+
+    #[test]
+    fn create_transcriber_resolves_provider_endpoints() {
+        let mut config = TranscriptionConfig {
+            provider: "local".into(),
+            ..Default::default()
+        };
+        let local = create_transcriber(&config).unwrap();
+        assert_eq!(local.name(), "local");
+
+        config.provider = "openai".into();
+        config.api_key = Some("sk-test".into());
+        assert_eq!(create_transcriber(&config).unwrap().name(), "openai");
+
+        config.provider = "custom".into();
+        config.api_url = "http://whisper.lan:9000/v1/audio/transcriptions".into();
+        assert_eq!(create_transcriber(&config).unwrap().name(), "custom");
+
+        config.provider = "nope".into();
+        let err = create_transcriber(&config).err().unwrap();
+        assert!(err.to_string().contains("Unknown transcription provider"));
+    }
+
+    #[test]
+    fn custom_api_url_overrides_provider_default() {
+        let transcriber = OpenAiCompatibleTranscriber::new(
+            "local",
+            "http://10.0.0.5:8080/inference",
+            None,
+            "base.en",
+        );
+        assert_eq!(transcriber.api_url(), "http://10.0.0.5:8080/inference");
+    }
+
+    #[tokio::test]
+    async fn rejects_overlong_audio_before_upload() {
+        let transcriber = OpenAiCompatibleTranscriber::new(
+            "local",
+            "http://127.0.0.1:9/v1/audio/transcriptions",
+            None,
+            "base",
+        )
+        .with_max_duration_secs(1);
+
+        let err = transcriber
+            .transcribe(wav_fixture(32_000, 64_000), "note.wav")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("too long"),
+            "expected duration error, got: {err}"
+        );
+    }
+
+    struct FixedTranscriber;
+
+    #[async_trait]
+    impl Transcriber for FixedTranscriber {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn transcribe(&self, audio: Vec<u8>, _file_name: &str) -> Result<String> {
+            Ok(format!("heard {} bytes", audio.len()))
+        }
+    }
+
+    struct NoopChannel;
+
+    #[async_trait]
+    impl Channel for NoopChannel {
+        fn name(&self) -> &str {
+            "noop"
+        }
+
+        async fn send(&self, _message: &super::super::traits::SendMessage) -> Result<()> {
+            Ok(())
+        }
+
+        async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn voice_attachments_become_transcripts() {
+        use super::super::Attachment;
+
+        let mut msg = ChannelMessage {
+            id: "1".into(),
+            sender: "alice".into(),
+            reply_target: "alice".into(),
+            content: String::new(),
+            channel: "noop".into(),
+            timestamp: 0,
+            thread_ts: None,
+            attachments: vec![
+                Attachment::from_bytes("note.ogg", vec![1, 2, 3]),
+                Attachment::from_bytes("doc.pdf", vec![4]),
+            ],
+        };
+
+        transcribe_voice_attachments(&NoopChannel, &FixedTranscriber, &mut msg).await;
+
+        assert_eq!(msg.content, "[Voice] heard 3 bytes");
+        assert_eq!(msg.attachments.len(), 1);
+        assert_eq!(msg.attachments[0].filename, "doc.pdf");
+    }
 }
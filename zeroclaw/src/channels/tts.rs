@@ -0,0 +1,447 @@
+//! Text-to-speech for spoken replies.
+//!
+//! [`SpeechSynthesizer`] turns reply text into an audio [`Attachment`] of
+//! kind [`AttachmentKind::Voice`], which voice-capable channels deliver as a
+//! native voice note. Backends are any OpenAI-compatible `/audio/speech`
+//! endpoint or a local command such as piper. [`VoiceReplies`] tracks which
+//! senders turned spoken replies on with `/voice on`.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use super::transcription::{mime_for_audio, sniff_audio_extension};
+use super::{Attachment, AttachmentKind};
+use crate::config::TtsConfig;
+use crate::security::OutboundGuard;
+
+const OPENAI_SPEECH_URL: &str = "https://api.openai.com/v1/audio/speech";
+const LOCAL_SPEECH_URL: &str = "http://127.0.0.1:8880/v1/audio/speech";
+
+/// Maximum time a local synthesis command may run.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A text-to-speech backend.
+#[async_trait]
+pub trait SpeechSynthesizer: Send + Sync {
+    /// Backend name for logs (e.g. `"openai"`, `"command"`).
+    fn name(&self) -> &str;
+
+    /// Speak `text`, returning the audio as a voice-note attachment.
+    async fn synthesize(&self, text: &str) -> Result<Attachment>;
+}
+
+/// Synthesizer for any OpenAI-compatible `/audio/speech` endpoint.
+pub struct OpenAiCompatibleSynthesizer {
+    name: String,
+    api_url: String,
+    api_key: Option<String>,
+    model: String,
+    voice: String,
+}
+
+impl OpenAiCompatibleSynthesizer {
+    pub fn new(
+        name: impl Into<String>,
+        api_url: impl Into<String>,
+        api_key: Option<String>,
+        model: impl Into<String>,
+        voice: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            api_url: api_url.into(),
+            api_key,
+            model: model.into(),
+            voice: voice.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for OpenAiCompatibleSynthesizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn synthesize(&self, text: &str) -> Result<Attachment> {
+        let client = crate::config::build_runtime_proxy_client(&format!("tts.{}", self.name));
+        // Ogg/Opus is what Telegram and WhatsApp expect for voice notes.
+        let body = serde_json::json!({
+            "model": self.model,
+            "input": text,
+            "voice": self.voice,
+            "response_format": "opus",
+        });
+
+        let mut request = client.post(&self.api_url).json(&body);
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let resp = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send speech request to {}", self.name))?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let raw = resp.text().await.unwrap_or_default();
+            let parsed: serde_json::Value = serde_json::from_str(&raw).unwrap_or_default();
+            let error_msg = parsed["error"]["message"]
+                .as_str()
+                .unwrap_or_else(|| raw.trim());
+            bail!("Speech API error ({status}): {error_msg}");
+        }
+
+        let audio = resp.bytes().await.context("Failed to read speech audio")?;
+        speech_attachment(audio.to_vec())
+    }
+}
+
+/// Synthesizer that runs a local command (piper, espeak-ng, say, ...).
+///
+/// The text is written to the command's stdin. When the command contains
+/// `{output}`, the audio is read from that file; otherwise from stdout.
+pub struct CommandSynthesizer {
+    command: String,
+}
+
+impl CommandSynthesizer {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for CommandSynthesizer {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    async fn synthesize(&self, text: &str) -> Result<Attachment> {
+        let output_path =
+            std::env::temp_dir().join(format!("zeroclaw-tts-{}.audio", uuid::Uuid::new_v4()));
+        let writes_file = self.command.contains("{output}");
+        let command = self.command.replace(
+            "{output}",
+            &format!(
+                "'{}'",
+                output_path.display().to_string().replace('\'', "'\\''")
+            ),
+        );
+
+        let mut child = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to start TTS command")?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // Commands that ignore stdin may exit before reading it.
+            let _ = stdin.write_all(text.as_bytes()).await;
+        }
+
+        let result = tokio::time::timeout(COMMAND_TIMEOUT, child.wait_with_output())
+            .await
+            .context("TTS command timed out")?
+            .context("TTS command failed")?;
+
+        if !result.status.success() {
+            let _ = tokio::fs::remove_file(&output_path).await;
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            bail!(
+                "TTS command exited with {}: {}",
+                result.status,
+                crate::util::truncate_with_ellipsis(stderr.trim(), 300)
+            );
+        }
+
+        let audio = if writes_file {
+            let audio = tokio::fs::read(&output_path)
+                .await
+                .context("TTS command did not write {output}");
+            let _ = tokio::fs::remove_file(&output_path).await;
+            audio?
+        } else {
+            result.stdout
+        };
+        speech_attachment(audio)
+    }
+}
+
+/// Wrap synthesized audio as a voice note named after its sniffed format.
+fn speech_attachment(audio: Vec<u8>) -> Result<Attachment> {
+    if audio.is_empty() {
+        bail!("Speech synthesis returned no audio");
+    }
+    let extension = sniff_audio_extension(&audio).unwrap_or("ogg");
+    let mime_type = mime_for_audio(extension).unwrap_or("audio/ogg");
+    Ok(Attachment::from_bytes(format!("reply.{extension}"), audio)
+        .with_kind(AttachmentKind::Voice)
+        .with_mime_type(mime_type))
+}
+
+/// Build the synthesizer described by `[tts]`.
+pub fn create_synthesizer(config: &TtsConfig) -> Result<Arc<dyn SpeechSynthesizer>> {
+    let provider = config.provider.trim().to_ascii_lowercase();
+    let configured_key = config
+        .api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(str::to_string);
+    let custom_url = (!config.api_url.trim().is_empty() && config.api_url != OPENAI_SPEECH_URL)
+        .then_some(config.api_url.as_str());
+
+    let synthesizer: Arc<dyn SpeechSynthesizer> = match provider.as_str() {
+        "" | "openai" => {
+            let api_key = match configured_key {
+                Some(key) => key,
+                None => std::env::var("OPENAI_API_KEY").context(
+                    "OPENAI_API_KEY environment variable is not set and tts.api_key is empty — required for voice replies",
+                )?,
+            };
+            Arc::new(OpenAiCompatibleSynthesizer::new(
+                "openai",
+                custom_url.unwrap_or(OPENAI_SPEECH_URL),
+                Some(api_key),
+                config.model.clone(),
+                config.voice.clone(),
+            ))
+        }
+        "local" | "custom" => {
+            let api_url = match (provider.as_str(), custom_url) {
+                (_, Some(url)) => url,
+                ("local", None) => LOCAL_SPEECH_URL,
+                _ => bail!("tts.api_url is required for the 'custom' provider"),
+            };
+            Arc::new(OpenAiCompatibleSynthesizer::new(
+                provider.clone(),
+                api_url,
+                configured_key,
+                config.model.clone(),
+                config.voice.clone(),
+            ))
+        }
+        "command" => {
+            let command = config
+                .command
+                .as_deref()
+                .map(str::trim)
+                .filter(|command| !command.is_empty())
+                .context("tts.command is required for the 'command' provider")?;
+            Arc::new(CommandSynthesizer::new(command))
+        }
+        other => {
+            bail!("Unknown TTS provider '{other}' — expected openai, local, custom or command")
+        }
+    };
+    Ok(synthesizer)
+}
+
+/// Reduce markdown to something worth reading aloud.
+///
+/// Code blocks are dropped, links keep their label and emphasis markers
+/// are removed.
+pub fn speakable_text(text: &str) -> String {
+    let mut spoken = Vec::new();
+    let mut in_code_block = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let line = trimmed.trim_start_matches('#').trim_start();
+        let line = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .unwrap_or(line);
+        spoken.push(strip_inline_markdown(line));
+    }
+    spoken.join("\n").trim().to_string()
+}
+
+fn strip_inline_markdown(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(open) = rest.find('[') {
+        let after = &rest[open + 1..];
+        let link = after.find("](").and_then(|close| {
+            let url_end = after[close + 2..].find(')')?;
+            Some((close, close + 2 + url_end))
+        });
+        match link {
+            Some((close, end)) => {
+                out.push_str(&rest[..open]);
+                out.push_str(&after[..close]);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[..=open]);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out.replace("**", "").replace(['`', '*'], "")
+}
+
+/// Per-sender spoken replies, toggled with `/voice on|off`.
+pub struct VoiceReplies {
+    synthesizer: Arc<dyn SpeechSynthesizer>,
+    max_chars: usize,
+    enabled_for: Mutex<HashSet<String>>,
+    outbound_guard: Option<Arc<OutboundGuard>>,
+}
+
+impl VoiceReplies {
+    pub fn new(synthesizer: Arc<dyn SpeechSynthesizer>, max_chars: usize) -> Self {
+        Self {
+            synthesizer,
+            max_chars,
+            enabled_for: Mutex::new(HashSet::new()),
+            outbound_guard: None,
+        }
+    }
+
+    /// Screen replies with `guard` before they are spoken.
+    pub fn with_outbound_guard(mut self, guard: Option<Arc<OutboundGuard>>) -> Self {
+        self.outbound_guard = guard;
+        self
+    }
+
+    pub fn outbound_guard(&self) -> Option<&Arc<OutboundGuard>> {
+        self.outbound_guard.as_ref()
+    }
+
+    /// Build from `[tts]`; `None` when TTS is disabled or misconfigured.
+    pub fn from_config(config: &TtsConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        match create_synthesizer(config) {
+            Ok(synthesizer) => Some(Self::new(synthesizer, config.max_chars)),
+            Err(e) => {
+                tracing::warn!("Voice replies disabled: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn synthesizer_name(&self) -> &str {
+        self.synthesizer.name()
+    }
+
+    pub fn set_enabled(&self, sender_key: &str, enabled: bool) {
+        let mut senders = self.enabled_for.lock();
+        if enabled {
+            senders.insert(sender_key.to_string());
+        } else {
+            senders.remove(sender_key);
+        }
+    }
+
+    pub fn is_enabled(&self, sender_key: &str) -> bool {
+        self.enabled_for.lock().contains(sender_key)
+    }
+
+    /// Speak `reply`, or `None` when there is nothing to say or it is
+    /// longer than `max_chars`.
+    pub async fn voice_note(&self, reply: &str) -> Result<Option<Attachment>> {
+        let text = speakable_text(reply);
+        if text.is_empty() || text.chars().count() > self.max_chars {
+            return Ok(None);
+        }
+        self.synthesizer.synthesize(&text).await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speakable_text_strips_markdown_and_code() {
+        let reply = "## Summary\n**Done.** See [the docs](https://example.com).\n```rust\nfn main() {}\n```\n- first `item`";
+        assert_eq!(
+            speakable_text(reply),
+            "Summary\nDone. See the docs.\nfirst item"
+        );
+    }
+
+    #[test]
+    fn create_synthesizer_validates_provider_settings() {
+        let mut config = TtsConfig {
+            provider: "command".into(),
+            ..Default::default()
+        };
+        assert!(create_synthesizer(&config).is_err());
+
+        config.command = Some("piper --output_file {output}".into());
+        assert_eq!(create_synthesizer(&config).unwrap().name(), "command");
+
+        config.provider = "local".into();
+        assert_eq!(create_synthesizer(&config).unwrap().name(), "local");
+
+        config.provider = "custom".into();
+        assert!(create_synthesizer(&config).is_err());
+
+        config.provider = "nope".into();
+        assert!(create_synthesizer(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn command_synthesizer_reads_output_file_or_stdout() {
+        let to_file = CommandSynthesizer::new("cat > {output}");
+        let note = to_file.synthesize("hello there").await.unwrap();
+        assert_eq!(note.kind, AttachmentKind::Voice);
+        assert_eq!(note.read_bytes().await.unwrap(), b"hello there");
+
+        let to_stdout = CommandSynthesizer::new("cat");
+        let note = to_stdout.synthesize("hi").await.unwrap();
+        assert_eq!(note.filename, "reply.ogg");
+        assert_eq!(note.read_bytes().await.unwrap(), b"hi");
+    }
+
+    #[tokio::test]
+    async fn command_synthesizer_reports_failures() {
+        let failing = CommandSynthesizer::new("echo boom >&2; exit 3");
+        let err = failing.synthesize("hi").await.unwrap_err();
+        assert!(err.to_string().contains("boom"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn voice_replies_toggle_per_sender_and_respect_max_chars() {
+        let voice = VoiceReplies::new(Arc::new(CommandSynthesizer::new("cat")), 10);
+        assert!(!voice.is_enabled("telegram_alice"));
+        voice.set_enabled("telegram_alice", true);
+        assert!(voice.is_enabled("telegram_alice"));
+        assert!(!voice.is_enabled("telegram_bob"));
+
+        assert!(voice.voice_note("short").await.unwrap().is_some());
+        assert!(voice
+            .voice_note("this reply is far too long")
+            .await
+            .unwrap()
+            .is_none());
+
+        voice.set_enabled("telegram_alice", false);
+        assert!(!voice.is_enabled("telegram_alice"));
+    }
+}
@@ -1,7 +1,10 @@
+use super::attachment::{read_capped_body, Attachment, AttachmentKind, AttachmentSource};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use uuid::Uuid;
 
+const GRAPH_API_BASE: &str = "https://graph.facebook.com/v18.0";
+
 /// `WhatsApp` channel — uses `WhatsApp` Business Cloud API
 ///
 /// This channel operates in webhook mode (push-based) rather than polling.
@@ -105,20 +108,29 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
-                    let content = if let Some(text_obj) = msg.get("text") {
-                        text_obj
+                    // Extract text content; voice notes arrive as media to transcribe
+                    let (content, attachments) = if let Some(text_obj) = msg.get("text") {
+                        let content = text_obj
                             .get("body")
                             .and_then(|b| b.as_str())
                             .unwrap_or("")
-                            .to_string()
+                            .to_string();
+                        (content, Vec::new())
+                    } else if let Some(audio) = msg.get("audio") {
+                        let Some(media_id) = audio.get("id").and_then(|i| i.as_str()) else {
+                            continue;
+                        };
+                        (
+                            String::new(),
+                            vec![Self::voice_note_attachment(media_id, audio)],
+                        )
                     } else {
-                        // Could be image, audio, etc. — skip for now
+                        // Could be image, video, etc. — skip for now
                         tracing::debug!("WhatsApp: skipping non-text message from {from}");
                         continue;
                     };
 
-                    if content.is_empty() {
+                    if content.is_empty() && attachments.is_empty() {
                         continue;
                     }
 
@@ -142,6 +154,7 @@ impl WhatsAppChannel {
                         channel: "whatsapp".to_string(),
                         timestamp,
                         thread_ts: None,
+                        attachments,
                     });
                 }
             }
@@ -149,21 +162,122 @@ impl WhatsAppChannel {
 
         messages
     }
-}
 
-#[async_trait]
-impl Channel for WhatsAppChannel {
-    fn name(&self) -> &str {
-        "whatsapp"
+    /// Voice note attachment pointing at the Graph media object `media_id`.
+    fn voice_note_attachment(media_id: &str, audio: &serde_json::Value) -> Attachment {
+        let mime_type = audio
+            .get("mime_type")
+            .and_then(|m| m.as_str())
+            .unwrap_or("audio/ogg");
+        let mut attachment = Attachment::from_url(format!("{GRAPH_API_BASE}/{media_id}"))
+            .with_kind(AttachmentKind::Voice)
+            .with_mime_type(mime_type);
+        attachment.filename = format!("voice-{media_id}");
+        attachment
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    /// Build a media message. `media` is either `{"link": url}` or `{"id": media_id}`.
+    fn media_message_body(
+        to: &str,
+        attachment: &Attachment,
+        mut media: serde_json::Value,
+    ) -> serde_json::Value {
+        let media_type = match attachment.kind {
+            AttachmentKind::Image => "image",
+            AttachmentKind::Audio | AttachmentKind::Voice => "audio",
+            AttachmentKind::Video => "video",
+            AttachmentKind::Document => "document",
+        };
+        if attachment.kind == AttachmentKind::Document {
+            media["filename"] = serde_json::json!(attachment.filename);
+        }
+        serde_json::json!({
+            "messaging_product": "whatsapp",
+            "recipient_type": "individual",
+            "to": to.strip_prefix('+').unwrap_or(to),
+            "type": media_type,
+            media_type: media
+        })
+    }
+
+    async fn post_message(&self, body: &serde_json::Value) -> anyhow::Result<()> {
         // WhatsApp Cloud API: POST to /v18.0/{phone_number_id}/messages
         let url = format!(
             "https://graph.facebook.com/v18.0/{}/messages",
             self.endpoint_id
         );
 
+        ensure_https(&url)?;
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .header("Content-Type", "application/json")
+            .json(body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp send failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp API error: {status}");
+        }
+
+        Ok(())
+    }
+
+    /// Upload content to `/media` and return the media ID to send by.
+    async fn upload_media(
+        &self,
+        attachment: &Attachment,
+        bytes: Vec<u8>,
+    ) -> anyhow::Result<String> {
+        let url = format!(
+            "https://graph.facebook.com/v18.0/{}/media",
+            self.endpoint_id
+        );
+        ensure_https(&url)?;
+
+        let part = reqwest::multipart::Part::bytes(bytes)
+            .file_name(attachment.filename.clone())
+            .mime_str(&attachment.mime_type)?;
+        let form = reqwest::multipart::Form::new()
+            .text("messaging_product", "whatsapp")
+            .text("type", attachment.mime_type.clone())
+            .part("file", part);
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp media upload failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp media upload error: {status}");
+        }
+
+        let data: serde_json::Value = resp.json().await?;
+        data.get("id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("WhatsApp media upload returned no id"))
+    }
+}
+
+#[async_trait]
+impl Channel for WhatsAppChannel {
+    fn name(&self) -> &str {
+        "whatsapp"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         // Normalize recipient (remove leading + if present for API)
         let to = message
             .recipient
@@ -181,25 +295,68 @@ impl Channel for WhatsAppChannel {
             }
         });
 
-        ensure_https(&url)?;
+        self.post_message(&body).await
+    }
+
+    fn supports_voice_notes(&self) -> bool {
+        true
+    }
+
+    async fn send_attachment(
+        &self,
+        attachment: &Attachment,
+        recipient: &str,
+        _thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let media = match &attachment.source {
+            AttachmentSource::Url(url) => serde_json::json!({ "link": url }),
+            AttachmentSource::Path(_) | AttachmentSource::Bytes(_) => {
+                let bytes = attachment.read_bytes().await?;
+                let id = self.upload_media(attachment, bytes).await?;
+                serde_json::json!({ "id": id })
+            }
+        };
+        self.post_message(&Self::media_message_body(recipient, attachment, media))
+            .await
+    }
+
+    async fn fetch_attachment(&self, attachment: &Attachment) -> anyhow::Result<Vec<u8>> {
+        let AttachmentSource::Url(url) = &attachment.source else {
+            return attachment.read_bytes().await;
+        };
+        ensure_https(url)?;
+
+        // Graph media objects resolve to a short-lived download URL that
+        // needs the same bearer token.
+        let download_url = if url.starts_with(GRAPH_API_BASE) {
+            let meta: serde_json::Value = self
+                .http_client()
+                .get(url)
+                .bearer_auth(&self.access_token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let download_url = meta
+                .get("url")
+                .and_then(|u| u.as_str())
+                .ok_or_else(|| anyhow::anyhow!("WhatsApp media lookup returned no url"))?
+                .to_string();
+            ensure_https(&download_url)?;
+            download_url
+        } else {
+            url.clone()
+        };
 
         let resp = self
             .http_client()
-            .post(&url)
+            .get(&download_url)
             .bearer_auth(&self.access_token)
-            .header("Content-Type", "application/json")
-            .json(&body)
             .send()
-            .await?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let error_body = resp.text().await.unwrap_or_default();
-            tracing::error!("WhatsApp send failed: {status} — {error_body}");
-            anyhow::bail!("WhatsApp API error: {status}");
-        }
-
-        Ok(())
+            .await?
+            .error_for_status()?;
+        read_capped_body(resp).await
     }
 
     async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -248,6 +405,27 @@ mod tests {
         )
     }
 
+    #[test]
+    fn media_message_body_uses_link_and_filename() {
+        let attachment = Attachment::from_url("https://example.com/report.pdf");
+        let body = WhatsAppChannel::media_message_body(
+            "+15551234567",
+            &attachment,
+            serde_json::json!({ "link": "https://example.com/report.pdf" }),
+        );
+        assert_eq!(body["to"], "15551234567");
+        assert_eq!(body["type"], "document");
+        assert_eq!(body["document"]["link"], "https://example.com/report.pdf");
+        assert_eq!(body["document"]["filename"], "report.pdf");
+
+        let image = Attachment::from_bytes("chart.png", vec![0]);
+        let body =
+            WhatsAppChannel::media_message_body("1555", &image, serde_json::json!({ "id": "m1" }));
+        assert_eq!(body["type"], "image");
+        assert_eq!(body["image"]["id"], "m1");
+        assert!(body["image"].get("filename").is_none());
+    }
+
     #[test]
     fn whatsapp_channel_name() {
         let ch = make_channel();
@@ -736,7 +914,7 @@ mod tests {
     }
 
     #[test]
-    fn whatsapp_parse_audio_message_skipped() {
+    fn whatsapp_parse_audio_message_becomes_voice_attachment() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
         let payload = serde_json::json!({
             "entry": [{
@@ -746,14 +924,22 @@ mod tests {
                             "from": "111",
                             "timestamp": "1",
                             "type": "audio",
-                            "audio": { "id": "audio123", "mime_type": "audio/ogg" }
+                            "audio": { "id": "audio123", "mime_type": "audio/ogg; codecs=opus", "voice": true }
                         }]
                     }
                 }]
             }]
         });
         let msgs = ch.parse_webhook_payload(&payload);
-        assert!(msgs.is_empty());
+        assert_eq!(msgs.len(), 1);
+        assert!(msgs[0].content.is_empty());
+        let voice = &msgs[0].attachments[0];
+        assert_eq!(voice.kind, AttachmentKind::Voice);
+        assert_eq!(voice.filename, "voice-audio123");
+        assert_eq!(
+            voice.url(),
+            Some("https://graph.facebook.com/v18.0/audio123")
+        );
     }
 
     #[test]
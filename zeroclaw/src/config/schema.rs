@@ -122,6 +122,10 @@ pub struct Config {
     #[serde(default)]
     pub model_routes: Vec<ModelRouteConfig>,
 
+    /// Policy for choosing among routes that share a hint (`[routing]`).
+    #[serde(default)]
+    pub routing: RoutingConfig,
+
     /// Embedding routing rules — route `hint:<name>` to specific provider+model combos.
     #[serde(default)]
     pub embedding_routes: Vec<EmbeddingRouteConfig>,
@@ -214,9 +218,21 @@ pub struct Config {
     #[serde(default)]
     pub hardware: HardwareConfig,
 
-    /// Voice transcription configuration (Whisper API via Groq).
+    /// Voice transcription configuration (`[transcription]`).
     #[serde(default)]
     pub transcription: TranscriptionConfig,
+
+    /// Text-to-speech voice replies (`[tts]`).
+    #[serde(default)]
+    pub tts: TtsConfig,
+
+    /// Session transcripts (`[sessions]`).
+    #[serde(default)]
+    pub sessions: SessionsConfig,
+
+    /// Standard Operating Procedure engine configuration (`[sop]`).
+    #[serde(default)]
+    pub sop: SopConfig,
 }
 
 /// Named provider profile definition compatible with Codex app-server style config.
@@ -348,6 +364,10 @@ impl Default for HardwareConfig {
 
 // ── Transcription ────────────────────────────────────────────────
 
+fn default_transcription_provider() -> String {
+    "groq".into()
+}
+
 fn default_transcription_api_url() -> String {
     "https://api.groq.com/openai/v1/audio/transcriptions".into()
 }
@@ -360,15 +380,26 @@ fn default_transcription_max_duration_secs() -> u64 {
     120
 }
 
-/// Voice transcription configuration (Whisper API via Groq).
+/// Voice transcription configuration (`[transcription]` section).
+///
+/// Any OpenAI-compatible `/audio/transcriptions` endpoint works: Groq,
+/// OpenAI, or a local whisper.cpp / faster-whisper server.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TranscriptionConfig {
     /// Enable voice transcription for channels that support it.
     #[serde(default)]
     pub enabled: bool,
-    /// Whisper API endpoint URL.
+    /// Backend: `"groq"` (default), `"openai"`, `"local"` or `"custom"`.
+    #[serde(default = "default_transcription_provider")]
+    pub provider: String,
+    /// Whisper API endpoint URL. Leave at the default to use the provider's
+    /// standard endpoint; required for `"custom"`.
     #[serde(default = "default_transcription_api_url")]
     pub api_url: String,
+    /// API key for the backend (encrypted at rest). Falls back to
+    /// `GROQ_API_KEY` / `OPENAI_API_KEY`; not needed for `"local"`.
+    #[serde(default)]
+    pub api_key: Option<String>,
     /// Whisper model name.
     #[serde(default = "default_transcription_model")]
     pub model: String,
@@ -384,7 +415,9 @@ impl Default for TranscriptionConfig {
     fn default() -> Self {
         Self {
             enabled: false,
+            provider: default_transcription_provider(),
             api_url: default_transcription_api_url(),
+            api_key: None,
             model: default_transcription_model(),
             language: None,
             max_duration_secs: default_transcription_max_duration_secs(),
@@ -392,6 +425,106 @@ impl Default for TranscriptionConfig {
     }
 }
 
+// ── Text-to-speech ───────────────────────────────────────────────
+
+fn default_tts_provider() -> String {
+    "openai".into()
+}
+
+fn default_tts_api_url() -> String {
+    "https://api.openai.com/v1/audio/speech".into()
+}
+
+fn default_tts_model() -> String {
+    "tts-1".into()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".into()
+}
+
+fn default_tts_max_chars() -> usize {
+    1500
+}
+
+/// Append-only session transcripts (`[sessions]` section).
+///
+/// CLI, channel, gateway and cron sessions are written to
+/// `<workspace>/state/sessions/<id>.jsonl` and can be inspected or resumed
+/// with `zeroclaw sessions`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionsConfig {
+    /// Record session transcripts. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Tool outputs longer than this many characters are truncated in the
+    /// transcript (`0` keeps them whole).
+    #[serde(default = "default_sessions_max_output_chars")]
+    pub max_output_chars: usize,
+}
+
+fn default_sessions_max_output_chars() -> usize {
+    8000
+}
+
+impl Default for SessionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_output_chars: default_sessions_max_output_chars(),
+        }
+    }
+}
+
+/// Spoken replies for messaging channels (`[tts]` section).
+///
+/// Senders opt in with `/voice on`; replies are then also delivered as a
+/// voice note on Telegram, WhatsApp and Signal.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TtsConfig {
+    /// Enable speech synthesis. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Backend: `"openai"` (default), `"local"`, `"custom"` or `"command"`.
+    #[serde(default = "default_tts_provider")]
+    pub provider: String,
+    /// OpenAI-compatible `/audio/speech` endpoint.
+    #[serde(default = "default_tts_api_url")]
+    pub api_url: String,
+    /// API key for the endpoint (encrypted at rest). Falls back to `OPENAI_API_KEY`.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Speech model name.
+    #[serde(default = "default_tts_model")]
+    pub model: String,
+    /// Voice name passed to the endpoint.
+    #[serde(default = "default_tts_voice")]
+    pub voice: String,
+    /// Shell command for the `"command"` backend, e.g.
+    /// `piper --model en_US-lessac-medium.onnx --output_file {output}`.
+    /// The reply text is written to stdin; audio must be written to `{output}`.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Replies longer than this many characters are sent as text only.
+    #[serde(default = "default_tts_max_chars")]
+    pub max_chars: usize,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: default_tts_provider(),
+            api_url: default_tts_api_url(),
+            api_key: None,
+            model: default_tts_model(),
+            voice: default_tts_voice(),
+            command: None,
+            max_chars: default_tts_max_chars(),
+        }
+    }
+}
+
 /// Agent orchestration configuration (`[agent]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
@@ -775,6 +908,10 @@ pub struct GatewayConfig {
     /// Paired bearer tokens (managed automatically, not user-edited)
     #[serde(default)]
     pub paired_tokens: Vec<String>,
+    /// Named, scoped API tokens (managed via `zeroclaw gateway tokens` or
+    /// `/api/tokens`; only SHA-256 hashes are stored)
+    #[serde(default)]
+    pub api_tokens: Vec<GatewayApiToken>,
 
     /// Max `/pair` requests per minute per client key.
     #[serde(default = "default_pair_rate_limit")]
@@ -842,6 +979,7 @@ impl Default for GatewayConfig {
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: Vec::new(),
+            api_tokens: Vec::new(),
             pair_rate_limit_per_minute: default_pair_rate_limit(),
             webhook_rate_limit_per_minute: default_webhook_rate_limit(),
             trust_forwarded_headers: false,
@@ -852,6 +990,31 @@ impl Default for GatewayConfig {
     }
 }
 
+/// A named gateway API token restricted to a set of scopes
+/// (`[[gateway.api_tokens]]`).
+///
+/// The bearer secret is shown once at creation; only its SHA-256 hash is kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayApiToken {
+    /// Stable identifier used for revocation (`tok_...`).
+    pub id: String,
+    /// Human-readable label, e.g. `android-dashboard`.
+    pub name: String,
+    /// SHA-256 hex digest of the bearer secret.
+    pub token_hash: String,
+    /// Granted scopes, e.g. `memory:read`, `cost:read`, `cron:write`, or `*`.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// RFC 3339 creation time.
+    pub created_at: String,
+    /// RFC 3339 expiry; `None` never expires.
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// RFC 3339 time the token last authenticated a request.
+    #[serde(default)]
+    pub last_used_at: Option<String>,
+}
+
 // ── Composio (managed tool surface) ─────────────────────────────
 
 /// Composio managed OAuth tools integration (`[composio]` section).
@@ -1891,6 +2054,12 @@ pub struct ObservabilityConfig {
     /// Maximum entries retained when runtime_trace_mode = "rolling".
     #[serde(default = "default_runtime_trace_max_entries")]
     pub runtime_trace_max_entries: usize,
+
+    /// When set, every provider request/response pair is appended to this
+    /// JSONL fixture for `zeroclaw doctor replay`. Relative paths are
+    /// resolved under workspace_dir.
+    #[serde(default)]
+    pub provider_fixture_path: Option<String>,
 }
 
 impl Default for ObservabilityConfig {
@@ -1902,6 +2071,7 @@ impl Default for ObservabilityConfig {
             runtime_trace_mode: default_runtime_trace_mode(),
             runtime_trace_path: default_runtime_trace_path(),
             runtime_trace_max_entries: default_runtime_trace_max_entries(),
+            provider_fixture_path: None,
         }
     }
 }
@@ -1929,6 +2099,9 @@ pub struct HooksConfig {
     pub enabled: bool,
     #[serde(default)]
     pub builtin: BuiltinHooksConfig,
+    /// External hook handlers (`[[hooks.external]]`): scripts or local webhooks.
+    #[serde(default)]
+    pub external: Vec<ExternalHookConfig>,
 }
 
 impl Default for HooksConfig {
@@ -1936,6 +2109,7 @@ impl Default for HooksConfig {
         Self {
             enabled: true,
             builtin: BuiltinHooksConfig::default(),
+            external: Vec::new(),
         }
     }
 }
@@ -1954,12 +2128,114 @@ impl Default for BuiltinHooksConfig {
     }
 }
 
+/// Transport used by an external hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalHookKind {
+    /// Run an executable: JSON request on stdin, JSON reply on stdout.
+    Script,
+    /// POST the JSON request to a loopback HTTP endpoint.
+    Webhook,
+}
+
+fn default_external_hook_timeout_secs() -> u64 {
+    5
+}
+
+/// A config-declared hook handler (`[[hooks.external]]`).
+///
+/// Each invocation sends `{"event": "...", "payload": {...}}`. Modifying
+/// events accept `{"action": "continue", "payload": {...}}` (payload optional)
+/// or `{"action": "cancel", "reason": "..."}`; an empty reply continues
+/// unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExternalHookConfig {
+    /// Hook name used in logs and cancel reasons.
+    pub name: String,
+    /// `script` or `webhook`.
+    pub kind: ExternalHookKind,
+    /// Executable to run (`script` hooks).
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments passed to `command`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Endpoint to POST to (`webhook` hooks). Must be a loopback address.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Events this hook subscribes to (e.g. `before_tool_call`,
+    /// `on_message_sending`). Empty subscribes to every supported event.
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Higher priorities run first for modifying events. Default: `0`.
+    #[serde(default)]
+    pub priority: i32,
+    /// Per-invocation timeout. Default: `5`.
+    #[serde(default = "default_external_hook_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Cancel the operation when the hook fails or times out instead of
+    /// continuing unchanged. Default: `false`.
+    #[serde(default)]
+    pub fail_closed: bool,
+}
+
+impl ExternalHookConfig {
+    pub fn validate(&self, index: usize) -> Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("hooks.external[{index}].name must not be empty");
+        }
+        if self.timeout_secs == 0 {
+            anyhow::bail!("hooks.external[{index}].timeout_secs must be greater than 0");
+        }
+        for event in &self.events {
+            if !crate::hooks::external::SUPPORTED_EVENTS.contains(&event.as_str()) {
+                anyhow::bail!("hooks.external[{index}].events contains unknown event '{event}'");
+            }
+        }
+        match self.kind {
+            ExternalHookKind::Script => {
+                if self
+                    .command
+                    .as_deref()
+                    .map_or(true, |c| c.trim().is_empty())
+                {
+                    anyhow::bail!("hooks.external[{index}] is a script hook but has no command");
+                }
+            }
+            ExternalHookKind::Webhook => {
+                let url = self.url.as_deref().map(str::trim).unwrap_or_default();
+                let parsed = reqwest::Url::parse(url)
+                    .with_context(|| format!("hooks.external[{index}].url is not a valid URL"))?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    anyhow::bail!("hooks.external[{index}].url must use http or https");
+                }
+                let host = parsed
+                    .host_str()
+                    .unwrap_or_default()
+                    .trim_start_matches('[')
+                    .trim_end_matches(']');
+                let is_loopback = host.eq_ignore_ascii_case("localhost")
+                    || host
+                        .parse::<std::net::IpAddr>()
+                        .is_ok_and(|ip| ip.is_loopback());
+                if !is_loopback {
+                    anyhow::bail!(
+                        "hooks.external[{index}].url must point at a loopback address (localhost, 127.0.0.1, ::1)"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 // ── Autonomy / Security ──────────────────────────────────────────
 
 /// Autonomy and security policy configuration (`[autonomy]` section).
 ///
 /// Controls what the agent is allowed to do: shell commands, filesystem access,
 /// risk approval gates, and per-policy budgets.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AutonomyConfig {
     /// Autonomy level: `read_only`, `supervised` (default), or `full`.
@@ -2011,6 +2287,22 @@ pub struct AutonomyConfig {
     /// model in tool specs.
     #[serde(default)]
     pub non_cli_excluded_tools: Vec<String>,
+
+    /// Ask for tool approvals on chat channels (Telegram, Slack, ...) in
+    /// supervised mode instead of auto-approving them. Default: `false`.
+    ///
+    /// Prompts go to the originating conversation and are also listed at
+    /// `GET /api/approvals` on the gateway.
+    #[serde(default)]
+    pub channel_approvals: bool,
+
+    /// Seconds an approval request waits for an answer before it is denied. Default: `300`.
+    #[serde(default = "default_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+}
+
+fn default_approval_timeout_secs() -> u64 {
+    300
 }
 
 fn default_auto_approve() -> Vec<String> {
@@ -2079,6 +2371,8 @@ impl Default for AutonomyConfig {
             always_ask: default_always_ask(),
             allowed_roots: Vec::new(),
             non_cli_excluded_tools: Vec::new(),
+            channel_approvals: false,
+            approval_timeout_secs: default_approval_timeout_secs(),
         }
     }
 }
@@ -2088,7 +2382,7 @@ impl Default for AutonomyConfig {
 /// Runtime adapter configuration (`[runtime]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RuntimeConfig {
-    /// Runtime kind (`native` | `docker`).
+    /// Runtime kind (`native` | `docker` | `wasm`).
     #[serde(default = "default_runtime_kind")]
     pub kind: String,
 
@@ -2096,6 +2390,11 @@ pub struct RuntimeConfig {
     #[serde(default)]
     pub docker: DockerRuntimeConfig,
 
+    /// WASM sandbox settings (`[runtime.wasm]`). Also governs WASM tools
+    /// loaded from `tools_dir` when built with the `runtime-wasm` feature.
+    #[serde(default)]
+    pub wasm: WasmRuntimeConfig,
+
     /// Global reasoning override for providers that expose explicit controls.
     /// - `None`: provider default behavior
     /// - `Some(true)`: request reasoning/thinking when supported
@@ -2136,6 +2435,69 @@ pub struct DockerRuntimeConfig {
     pub allowed_workspace_roots: Vec<String>,
 }
 
+/// WASM sandbox configuration (`[runtime.wasm]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WasmRuntimeConfig {
+    /// Directory (relative to workspace) scanned for `*.wasm` tool modules.
+    #[serde(default = "default_wasm_tools_dir")]
+    pub tools_dir: String,
+
+    /// Fuel budget per invocation (roughly one unit per instruction). WASM
+    /// tools are not loaded when this is 0.
+    #[serde(default = "default_wasm_fuel_limit")]
+    pub fuel_limit: u64,
+
+    /// Linear memory ceiling per module instance, in MB.
+    #[serde(default = "default_wasm_memory_limit_mb")]
+    pub memory_limit_mb: u64,
+
+    /// Wall-clock timeout per tool invocation, in seconds.
+    #[serde(default = "default_wasm_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Let modules read workspace files through the `zeroclaw.read_file` import.
+    #[serde(default)]
+    pub allow_workspace_read: bool,
+
+    /// Let modules write workspace files through the `zeroclaw.write_file` import.
+    #[serde(default)]
+    pub allow_workspace_write: bool,
+
+    /// Allowed HTTP hosts (empty = no network).
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+fn default_wasm_tools_dir() -> String {
+    "tools/wasm".into()
+}
+
+fn default_wasm_fuel_limit() -> u64 {
+    1_000_000
+}
+
+fn default_wasm_memory_limit_mb() -> u64 {
+    64
+}
+
+fn default_wasm_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for WasmRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            tools_dir: default_wasm_tools_dir(),
+            fuel_limit: default_wasm_fuel_limit(),
+            memory_limit_mb: default_wasm_memory_limit_mb(),
+            timeout_secs: default_wasm_timeout_secs(),
+            allow_workspace_read: false,
+            allow_workspace_write: false,
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
 fn default_runtime_kind() -> String {
     "native".into()
 }
@@ -2175,6 +2537,7 @@ impl Default for RuntimeConfig {
         Self {
             kind: default_runtime_kind(),
             docker: DockerRuntimeConfig::default(),
+            wasm: WasmRuntimeConfig::default(),
             reasoning_enabled: None,
         }
     }
@@ -2216,6 +2579,13 @@ pub struct ReliabilityConfig {
     /// Max retries for cron job execution attempts.
     #[serde(default = "default_scheduler_retries")]
     pub scheduler_retries: u32,
+    /// Consecutive failures that open a provider/model circuit, after which
+    /// the pair is skipped until a probe succeeds. `0` disables the breaker.
+    #[serde(default = "default_circuit_breaker_threshold")]
+    pub circuit_breaker_threshold: u32,
+    /// Seconds an open circuit waits before letting one probe request through.
+    #[serde(default = "default_circuit_breaker_cooldown_secs")]
+    pub circuit_breaker_cooldown_secs: u64,
 }
 
 fn default_provider_retries() -> u32 {
@@ -2242,6 +2612,14 @@ fn default_scheduler_retries() -> u32 {
     2
 }
 
+fn default_circuit_breaker_threshold() -> u32 {
+    3
+}
+
+fn default_circuit_breaker_cooldown_secs() -> u64 {
+    60
+}
+
 impl Default for ReliabilityConfig {
     fn default() -> Self {
         Self {
@@ -2254,6 +2632,8 @@ impl Default for ReliabilityConfig {
             channel_max_backoff_secs: default_channel_backoff_max_secs(),
             scheduler_poll_secs: default_scheduler_poll_secs(),
             scheduler_retries: default_scheduler_retries(),
+            circuit_breaker_threshold: default_circuit_breaker_threshold(),
+            circuit_breaker_cooldown_secs: default_circuit_breaker_cooldown_secs(),
         }
     }
 }
@@ -2324,6 +2704,101 @@ pub struct ModelRouteConfig {
     /// Optional API key override for this route's provider
     #[serde(default)]
     pub api_key: Option<String>,
+    /// Largest prompt (in tokens) this route should receive. Routing policies
+    /// skip the route for bigger requests; unset means no limit.
+    #[serde(default)]
+    pub max_context_tokens: Option<u64>,
+    /// Whether this route accepts images. Unset defers to the provider's
+    /// own capability report.
+    #[serde(default)]
+    pub vision: Option<bool>,
+}
+
+/// How [`RoutingConfig`] ranks several `[[model_routes]]` sharing a hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RoutingPolicy {
+    /// Use routes in the order they are declared.
+    #[default]
+    Fixed,
+    /// Prefer the lowest `[cost.prices]` entry.
+    Cheapest,
+    /// Prefer the lowest observed latency.
+    Fastest,
+    /// Weigh price, latency and error rate together.
+    Balanced,
+}
+
+impl RoutingPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fixed => "fixed",
+            Self::Cheapest => "cheapest",
+            Self::Fastest => "fastest",
+            Self::Balanced => "balanced",
+        }
+    }
+}
+
+fn default_routing_max_error_rate() -> f64 {
+    0.5
+}
+
+fn default_routing_low_budget_percent() -> u32 {
+    20
+}
+
+/// Routing policy for hints with more than one candidate route (`[routing]`).
+///
+/// Declare several `[[model_routes]]` with the same `hint` and the router
+/// picks among them per request: routes that cannot take the request (images
+/// without vision, prompts over `max_context_tokens`) are skipped, routes
+/// whose recent error rate exceeds `max_error_rate` are tried last, and the
+/// rest are ranked by `policy`. When less than `low_budget_percent` of the
+/// daily cost limit remains, the cheapest route wins regardless of policy.
+///
+/// ```toml
+/// [routing]
+/// policy = "cheapest"
+///
+/// [[model_routes]]
+/// hint = "chat"
+/// provider = "groq"
+/// model = "llama-3.3-70b-versatile"
+/// max_context_tokens = 32000
+///
+/// [[model_routes]]
+/// hint = "chat"
+/// provider = "openrouter"
+/// model = "anthropic/claude-sonnet-4"
+/// vision = true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RoutingConfig {
+    /// Ranking policy: `fixed` (default), `cheapest`, `fastest` or `balanced`.
+    #[serde(default)]
+    pub policy: RoutingPolicy,
+    /// Recent error rate (0.0–1.0) above which a route is tried last.
+    #[serde(default = "default_routing_max_error_rate")]
+    pub max_error_rate: f64,
+    /// Switch to the cheapest route when less than this percent of
+    /// `[cost] daily_limit_usd` remains. `0` disables the switch.
+    #[serde(default = "default_routing_low_budget_percent")]
+    pub low_budget_percent: u32,
+    /// Retry a failed request on the next-ranked route for the same hint.
+    #[serde(default = "default_true")]
+    pub escalate_on_error: bool,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            policy: RoutingPolicy::default(),
+            max_error_rate: default_routing_max_error_rate(),
+            low_budget_percent: default_routing_low_budget_percent(),
+            escalate_on_error: true,
+        }
+    }
 }
 
 // ── Embedding routing ───────────────────────────────────────────
@@ -2450,6 +2925,67 @@ impl Default for CronConfig {
     }
 }
 
+// ── SOP ─────────────────────────────────────────────────────────
+
+/// Standard Operating Procedure engine configuration (`[sop]` section).
+///
+/// SOPs live in `<workspace>/sops/<name>/` as `SOP.toml` + `SOP.md`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SopConfig {
+    /// Enable the SOP engine: daemon trigger sources and `sop_*` agent tools. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Override the SOPs directory. Default: `<workspace>/sops`.
+    #[serde(default)]
+    pub sops_dir: Option<String>,
+    /// Execution mode for SOPs that do not declare one. Default: `supervised`.
+    #[serde(default)]
+    pub default_execution_mode: crate::sop::SopExecutionMode,
+    /// Maximum number of concurrently active runs across all SOPs. Default: `4`.
+    #[serde(default = "default_sop_max_concurrent_total")]
+    pub max_concurrent_total: usize,
+    /// Seconds a run may wait for approval before timing out (`0` = never).
+    /// Critical/high-priority SOPs auto-approve on timeout. Default: `300`.
+    #[serde(default = "default_sop_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+    /// Finished runs kept in memory for status queries (`0` = unlimited). Default: `100`.
+    #[serde(default = "default_sop_max_finished_runs")]
+    pub max_finished_runs: usize,
+    /// Daemon poll interval for cron triggers and approval timeouts. Default: `15`.
+    #[serde(default = "default_sop_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+fn default_sop_max_concurrent_total() -> usize {
+    4
+}
+
+fn default_sop_approval_timeout_secs() -> u64 {
+    300
+}
+
+fn default_sop_max_finished_runs() -> usize {
+    100
+}
+
+fn default_sop_poll_interval_secs() -> u64 {
+    15
+}
+
+impl Default for SopConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sops_dir: None,
+            default_execution_mode: crate::sop::SopExecutionMode::default(),
+            max_concurrent_total: default_sop_max_concurrent_total(),
+            approval_timeout_secs: default_sop_approval_timeout_secs(),
+            max_finished_runs: default_sop_max_finished_runs(),
+            poll_interval_secs: default_sop_poll_interval_secs(),
+        }
+    }
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 /// Tunnel configuration for exposing the gateway publicly (`[tunnel]` section).
@@ -2589,6 +3125,8 @@ pub struct ChannelsConfig {
     pub nostr: Option<NostrConfig>,
     /// ClawdTalk voice channel configuration.
     pub clawdtalk: Option<crate::channels::clawdtalk::ClawdTalkConfig>,
+    /// MQTT ingress configuration (SOP triggers; not a chat channel).
+    pub mqtt: Option<MqttConfig>,
     /// Base timeout in seconds for processing a single channel message (LLM + tools).
     /// Runtime uses this as a per-turn budget that scales with tool-loop depth
     /// (up to 4x, capped) so one slow/retried model call does not consume the
@@ -2596,6 +3134,9 @@ pub struct ChannelsConfig {
     /// Default: 300s for on-device LLMs (Ollama) which are slower than cloud APIs.
     #[serde(default = "default_channel_message_timeout_secs")]
     pub message_timeout_secs: u64,
+    /// Durable per-sender conversation history (`[channels_config.history]`).
+    #[serde(default)]
+    pub history: ChannelHistoryConfig,
 }
 
 impl ChannelsConfig {
@@ -2696,6 +3237,35 @@ fn default_channel_message_timeout_secs() -> u64 {
     300
 }
 
+fn default_channel_history_ttl_hours() -> u64 {
+    24 * 30
+}
+
+/// Persistence for per-sender channel conversation history.
+///
+/// When enabled, each sender's recent turns are mirrored to
+/// `memory/channel_history.db` in the workspace and replayed on startup,
+/// so conversations survive daemon restarts and upgrades.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelHistoryConfig {
+    /// Persist channel conversation history to SQLite. Default: `false`.
+    #[serde(default)]
+    pub persist: bool,
+    /// Forget conversations idle for longer than this many hours.
+    /// `0` keeps history forever. Default: `720` (30 days).
+    #[serde(default = "default_channel_history_ttl_hours")]
+    pub ttl_hours: u64,
+}
+
+impl Default for ChannelHistoryConfig {
+    fn default() -> Self {
+        Self {
+            persist: false,
+            ttl_hours: default_channel_history_ttl_hours(),
+        }
+    }
+}
+
 impl Default for ChannelsConfig {
     fn default() -> Self {
         Self {
@@ -2720,7 +3290,9 @@ impl Default for ChannelsConfig {
             qq: None,
             nostr: None,
             clawdtalk: None,
+            mqtt: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
+            history: ChannelHistoryConfig::default(),
         }
     }
 }
@@ -3260,6 +3832,11 @@ pub struct SecurityConfig {
     /// Emergency-stop state machine configuration.
     #[serde(default)]
     pub estop: EstopConfig,
+
+    /// Credential leak guard for channel replies, webhook responses and
+    /// provider-bound content.
+    #[serde(default)]
+    pub outbound: OutboundGuardConfig,
 }
 
 /// OTP validation strategy.
@@ -3385,6 +3962,11 @@ pub struct SandboxConfig {
     /// Custom Firejail arguments (when backend = firejail)
     #[serde(default)]
     pub firejail_args: Vec<String>,
+
+    /// Refuse to run shell commands when the requested backend is unavailable,
+    /// instead of falling back to application-layer security only.
+    #[serde(default)]
+    pub fail_closed: bool,
 }
 
 impl Default for SandboxConfig {
@@ -3393,6 +3975,7 @@ impl Default for SandboxConfig {
             enabled: None, // Auto-detect
             backend: SandboxBackend::Auto,
             firejail_args: Vec::new(),
+            fail_closed: false,
         }
     }
 }
@@ -3463,6 +4046,87 @@ impl Default for ResourceLimitsConfig {
     }
 }
 
+/// What the outbound leak guard does with a match
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, Default, JsonSchema, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OutboundAction {
+    /// Log and record the match, send the content unchanged
+    Warn,
+    /// Replace the matched text with a placeholder
+    #[default]
+    Redact,
+    /// Withhold the content entirely
+    Block,
+}
+
+/// A user-defined pattern for the outbound leak guard
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OutboundPatternConfig {
+    /// Label reported in logs and observability events
+    pub name: String,
+
+    /// Regular expression matched against outbound text
+    pub regex: String,
+
+    /// Action for this pattern (defaults to `security.outbound.action`)
+    #[serde(default)]
+    pub action: Option<OutboundAction>,
+}
+
+/// Outbound credential leak guard configuration
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OutboundGuardConfig {
+    /// Scan outbound content at all
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Action for built-in credential detections
+    #[serde(default)]
+    pub action: OutboundAction,
+
+    /// Built-in detector sensitivity (0.0-1.0, higher = more aggressive)
+    #[serde(default = "default_outbound_sensitivity")]
+    pub sensitivity: f64,
+
+    /// Additional site-specific patterns
+    #[serde(default)]
+    pub patterns: Vec<OutboundPatternConfig>,
+
+    /// Scan messages sent through channels (and cron announcements)
+    #[serde(default = "default_true")]
+    pub channels: bool,
+
+    /// Scan gateway webhook responses
+    #[serde(default = "default_true")]
+    pub gateway: bool,
+
+    /// Scan conversation content before it is sent to the LLM provider (opt-in).
+    /// Tool output is checked but never rewritten, so file edits keep working.
+    #[serde(default)]
+    pub providers: bool,
+}
+
+fn default_outbound_sensitivity() -> f64 {
+    0.7
+}
+
+impl Default for OutboundGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: OutboundAction::default(),
+            sensitivity: default_outbound_sensitivity(),
+            patterns: Vec::new(),
+            channels: true,
+            gateway: true,
+            providers: false,
+        }
+    }
+}
+
 /// Audit logging configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditConfig {
@@ -3579,6 +4243,113 @@ pub fn default_nostr_relays() -> Vec<String> {
     ]
 }
 
+/// MQTT ingress configuration (`[channels_config.mqtt]`).
+///
+/// Incoming publishes on `topics` are dispatched to the SOP engine as
+/// `SopTrigger::Mqtt` events. Requires the `channel-mqtt` build feature.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MqttConfig {
+    /// Broker URL: `mqtt://host:port` or `mqtts://host:port`.
+    pub broker_url: String,
+    /// MQTT client identifier. Default: `"zeroclaw"`.
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    /// Topic filters to subscribe to (`+` and `#` wildcards allowed).
+    pub topics: Vec<String>,
+    /// Subscription / publish QoS level (0, 1 or 2). Default: `1`.
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+    /// Optional broker username.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Optional broker password.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Use TLS transport. Must match the `mqtts://` scheme.
+    #[serde(default)]
+    pub use_tls: bool,
+    /// Keep-alive interval in seconds. Default: `30`.
+    #[serde(default = "default_mqtt_keep_alive_secs")]
+    pub keep_alive_secs: u64,
+    /// When set, messages that do not start an SOP run are sent to the agent
+    /// and its reply is published to this topic. Unset = SOP triggers only.
+    #[serde(default)]
+    pub response_topic: Option<String>,
+}
+
+fn default_mqtt_client_id() -> String {
+    "zeroclaw".into()
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
+}
+
+fn default_mqtt_keep_alive_secs() -> u64 {
+    30
+}
+
+impl MqttConfig {
+    /// Validate broker URL, TLS flag, QoS, client id and topics.
+    pub fn validate(&self) -> Result<()> {
+        let is_tls_url = self.broker_url.starts_with("mqtts://");
+        if !is_tls_url && !self.broker_url.starts_with("mqtt://") {
+            anyhow::bail!(
+                "mqtt.broker_url must start with mqtt:// or mqtts:// (got '{}')",
+                self.broker_url
+            );
+        }
+        if self.use_tls && !is_tls_url {
+            anyhow::bail!("mqtt.use_tls is true but broker_url does not use mqtts://");
+        }
+        if is_tls_url && !self.use_tls {
+            anyhow::bail!("mqtt.broker_url uses mqtts:// but use_tls is false");
+        }
+        if self.qos > 2 {
+            anyhow::bail!("mqtt.qos must be 0, 1, or 2 (got {})", self.qos);
+        }
+        if self.client_id.trim().is_empty() {
+            anyhow::bail!("mqtt.client_id must not be empty");
+        }
+        if self.topics.iter().all(|t| t.trim().is_empty()) {
+            anyhow::bail!("mqtt.topics must contain at least one topic");
+        }
+        if let Some(topic) = &self.response_topic {
+            if topic.trim().is_empty() || topic.contains(['+', '#']) {
+                anyhow::bail!(
+                    "mqtt.response_topic must be a concrete topic without wildcards (got '{topic}')"
+                );
+            }
+            // Replies published to a subscribed topic would come straight
+            // back as new messages and loop the agent forever.
+            if let Some(filter) = self
+                .topics
+                .iter()
+                .find(|filter| mqtt_topic_matches(filter.trim(), topic))
+            {
+                anyhow::bail!(
+                    "mqtt.response_topic '{topic}' must not match subscribed topic '{filter}'"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `topic` matches the subscription `filter` (`+` and `#` wildcards).
+pub fn mqtt_topic_matches(filter: &str, topic: &str) -> bool {
+    let mut levels = topic.split('/');
+    for part in filter.split('/') {
+        match (part, levels.next()) {
+            ("#", _) => return true,
+            ("+", Some(_)) => {}
+            (part, Some(level)) if part == level => {}
+            _ => return false,
+        }
+    }
+    levels.next().is_none()
+}
+
 // ── Config impl ──────────────────────────────────────────────────
 
 impl Default for Config {
@@ -3605,6 +4376,7 @@ impl Default for Config {
             agent: AgentConfig::default(),
             skills: SkillsConfig::default(),
             model_routes: Vec::new(),
+            routing: RoutingConfig::default(),
             embedding_routes: Vec::new(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
@@ -3629,6 +4401,9 @@ impl Default for Config {
             hardware: HardwareConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             transcription: TranscriptionConfig::default(),
+            tts: TtsConfig::default(),
+            sessions: SessionsConfig::default(),
+            sop: SopConfig::default(),
         }
     }
 }
@@ -4090,6 +4865,14 @@ impl Config {
                 "config.storage.provider.config.db_url",
             )?;
 
+            decrypt_optional_secret(
+                &store,
+                &mut config.transcription.api_key,
+                "config.transcription.api_key",
+            )?;
+
+            decrypt_optional_secret(&store, &mut config.tts.api_key, "config.tts.api_key")?;
+
             for agent in config.agents.values_mut() {
                 decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
             }
@@ -4236,6 +5019,9 @@ impl Config {
         if self.autonomy.max_actions_per_hour == 0 {
             anyhow::bail!("autonomy.max_actions_per_hour must be greater than 0");
         }
+        if self.autonomy.approval_timeout_secs == 0 {
+            anyhow::bail!("autonomy.approval_timeout_secs must be greater than 0");
+        }
         for (i, env_name) in self.autonomy.shell_env_passthrough.iter().enumerate() {
             if !is_valid_env_var_name(env_name) {
                 anyhow::bail!(
@@ -4280,6 +5066,24 @@ impl Config {
         if self.security.estop.state_file.trim().is_empty() {
             anyhow::bail!("security.estop.state_file must not be empty");
         }
+        for (i, pattern) in self.security.outbound.patterns.iter().enumerate() {
+            if pattern.name.trim().is_empty() {
+                anyhow::bail!("security.outbound.patterns[{i}].name must not be empty");
+            }
+            regex::Regex::new(&pattern.regex).with_context(|| {
+                format!("security.outbound.patterns[{i}].regex is not a valid regex")
+            })?;
+        }
+
+        // External hooks
+        for (i, hook) in self.hooks.external.iter().enumerate() {
+            hook.validate(i)?;
+        }
+
+        // MQTT ingress
+        if let Some(mqtt) = &self.channels_config.mqtt {
+            mqtt.validate()?;
+        }
 
         // Scheduler
         if self.scheduler.max_concurrent == 0 {
@@ -4712,6 +5516,18 @@ impl Config {
             "config.storage.provider.config.db_url",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.transcription.api_key,
+            "config.transcription.api_key",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.tts.api_key,
+            "config.tts.api_key",
+        )?;
+
         for agent in config_to_save.agents.values_mut() {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
         }
@@ -5090,6 +5906,8 @@ default_temperature = 0.7
                 always_ask: vec![],
                 allowed_roots: vec![],
                 non_cli_excluded_tools: vec![],
+                channel_approvals: true,
+                approval_timeout_secs: 120,
             },
             security: SecurityConfig::default(),
             runtime: RuntimeConfig {
@@ -5100,6 +5918,7 @@ default_temperature = 0.7
             scheduler: SchedulerConfig::default(),
             skills: SkillsConfig::default(),
             model_routes: Vec::new(),
+            routing: RoutingConfig::default(),
             embedding_routes: Vec::new(),
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig {
@@ -5139,7 +5958,9 @@ default_temperature = 0.7
                 qq: None,
                 nostr: None,
                 clawdtalk: None,
+                mqtt: None,
                 message_timeout_secs: 300,
+                history: ChannelHistoryConfig::default(),
             },
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -5161,6 +5982,9 @@ default_temperature = 0.7
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            tts: TtsConfig::default(),
+            sessions: SessionsConfig::default(),
+            sop: SopConfig::default(),
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -5174,6 +5998,8 @@ default_temperature = 0.7
         assert_eq!(parsed.observability.runtime_trace_mode, "none");
         assert_eq!(parsed.autonomy.level, AutonomyLevel::Full);
         assert!(!parsed.autonomy.workspace_only);
+        assert!(parsed.autonomy.channel_approvals);
+        assert_eq!(parsed.autonomy.approval_timeout_secs, 120);
         assert_eq!(parsed.runtime.kind, "docker");
         assert!(parsed.heartbeat.enabled);
         assert_eq!(parsed.heartbeat.interval_minutes, 15);
@@ -5318,6 +6144,7 @@ tool_dispatcher = "xml"
             scheduler: SchedulerConfig::default(),
             skills: SkillsConfig::default(),
             model_routes: Vec::new(),
+            routing: RoutingConfig::default(),
             embedding_routes: Vec::new(),
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
@@ -5343,6 +6170,9 @@ tool_dispatcher = "xml"
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            tts: TtsConfig::default(),
+            sessions: SessionsConfig::default(),
+            sop: SopConfig::default(),
         };
 
         config.save().await.unwrap();
@@ -5379,6 +6209,7 @@ tool_dispatcher = "xml"
         config.browser.computer_use.api_key = Some("browser-credential".into());
         config.web_search.brave_api_key = Some("brave-credential".into());
         config.storage.provider.config.db_url = Some("postgres://user:pw@host/db".into());
+        config.transcription.api_key = Some("transcription-credential".into());
 
         config.agents.insert(
             "worker".into(),
@@ -5446,6 +6277,15 @@ tool_dispatcher = "xml"
             "postgres://user:pw@host/db"
         );
 
+        let transcription_encrypted = stored.transcription.api_key.as_deref().unwrap();
+        assert!(crate::security::SecretStore::is_encrypted(
+            transcription_encrypted
+        ));
+        assert_eq!(
+            store.decrypt(transcription_encrypted).unwrap(),
+            "transcription-credential"
+        );
+
         let _ = fs::remove_dir_all(&dir).await;
     }
 
@@ -5703,7 +6543,9 @@ allowed_users = ["@ops:matrix.org"]
             qq: None,
             nostr: None,
             clawdtalk: None,
+            mqtt: None,
             message_timeout_secs: 300,
+            history: ChannelHistoryConfig::default(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -5917,7 +6759,9 @@ channel_id = "C123"
             qq: None,
             nostr: None,
             clawdtalk: None,
+            mqtt: None,
             message_timeout_secs: 300,
+            history: ChannelHistoryConfig::default(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -5996,6 +6840,7 @@ channel_id = "C123"
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: vec!["zc_test_token".into()],
+            api_tokens: Vec::new(),
             pair_rate_limit_per_minute: 12,
             webhook_rate_limit_per_minute: 80,
             trust_forwarded_headers: true,
@@ -6591,6 +7436,90 @@ requires_openai_auth = true
             .contains("wire_api must be one of: responses, chat_completions"));
     }
 
+    #[test]
+    async fn validate_rejects_invalid_outbound_leak_pattern() {
+        let mut config = Config::default();
+        config.security.outbound.patterns = vec![OutboundPatternConfig {
+            name: "ticket".to_string(),
+            regex: "TICKET-[0-9".to_string(),
+            action: Some(OutboundAction::Block),
+        }];
+
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error
+            .to_string()
+            .contains("security.outbound.patterns[0].regex is not a valid regex"));
+    }
+
+    #[test]
+    async fn outbound_guard_config_parses_pattern_actions() {
+        let parsed: SecurityConfig = toml::from_str(
+            r#"
+            [outbound]
+            action = "warn"
+            patterns = [{ name = "host", regex = 'db\.corp', action = "block" }]
+            "#,
+        )
+        .unwrap();
+
+        assert!(parsed.outbound.enabled);
+        assert!(parsed.outbound.channels);
+        assert!(!parsed.outbound.providers);
+        assert_eq!(parsed.outbound.action, OutboundAction::Warn);
+        assert_eq!(
+            parsed.outbound.patterns[0].action,
+            Some(OutboundAction::Block)
+        );
+    }
+
+    #[test]
+    async fn external_hooks_parse_and_validate() {
+        let parsed: HooksConfig = toml::from_str(
+            r#"
+enabled = true
+
+[[external]]
+name = "policy"
+kind = "script"
+command = "/usr/local/bin/policy"
+events = ["before_tool_call"]
+priority = 10
+
+[[external]]
+name = "audit"
+kind = "webhook"
+url = "http://127.0.0.1:9000/hook"
+"#,
+        )
+        .unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.external.len(), 2);
+        assert_eq!(parsed.external[0].kind, ExternalHookKind::Script);
+        assert_eq!(parsed.external[1].timeout_secs, 5);
+        assert!(!parsed.external[1].fail_closed);
+
+        let mut config = Config::default();
+        config.hooks = parsed;
+        assert!(config.validate().is_ok());
+
+        config.hooks.external[1].url = Some("https://hooks.example.com/x".into());
+        let err = config
+            .validate()
+            .expect_err("remote webhook must be rejected");
+        assert!(err.to_string().contains("loopback"));
+
+        config.hooks.external[1].url = Some("http://[::1]:9000/hook".into());
+        config.hooks.external[0].events = vec!["on_everything".into()];
+        let err = config
+            .validate()
+            .expect_err("unknown event must be rejected");
+        assert!(err.to_string().contains("unknown event"));
+
+        config.hooks.external[0].events.clear();
+        config.hooks.external[0].command = None;
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     async fn env_override_model_fallback() {
         let _env_guard = env_override_lock().await;
@@ -7533,12 +8462,44 @@ default_model = "legacy-model"
     async fn transcription_config_defaults() {
         let tc = TranscriptionConfig::default();
         assert!(!tc.enabled);
+        assert_eq!(tc.provider, "groq");
         assert!(tc.api_url.contains("groq.com"));
+        assert!(tc.api_key.is_none());
         assert_eq!(tc.model, "whisper-large-v3-turbo");
         assert!(tc.language.is_none());
         assert_eq!(tc.max_duration_secs, 120);
     }
 
+    #[test]
+    async fn tts_config_defaults_and_roundtrip() {
+        let tts = TtsConfig::default();
+        assert!(!tts.enabled);
+        assert_eq!(tts.provider, "openai");
+        assert_eq!(tts.voice, "alloy");
+        assert_eq!(tts.max_chars, 1500);
+
+        let parsed: Config = toml::from_str(
+            r#"
+            default_provider = "openrouter"
+            default_model = "test-model"
+            default_temperature = 0.7
+
+            [tts]
+            enabled = true
+            provider = "command"
+            command = "piper --output_file {output}"
+            "#,
+        )
+        .unwrap();
+        assert!(parsed.tts.enabled);
+        assert_eq!(parsed.tts.provider, "command");
+        assert_eq!(
+            parsed.tts.command.as_deref(),
+            Some("piper --output_file {output}")
+        );
+        assert_eq!(parsed.tts.model, "tts-1");
+    }
+
     #[test]
     async fn config_roundtrip_with_transcription() {
         let mut config = Config::default();
@@ -0,0 +1,344 @@
+//! Budget enforcement for LLM calls.
+//!
+//! A single [`CostTracker`] is shared by the agent loop, channels, cron jobs,
+//! delegate sub-agents and the gateway so `/api/cost` and `/cost` report the
+//! same numbers that `[cost] daily_limit_usd`/`monthly_limit_usd` are
+//! enforced against. Call [`precheck`] before a provider call and [`record`]
+//! once the response (and its token usage) is in.
+
+use super::tracker::CostTracker;
+use super::types::{BudgetCheck, UsagePeriod};
+use crate::config::schema::CostConfig;
+use crate::providers::traits::{ChatMessage, TokenUsage as ProviderTokenUsage};
+use anyhow::Result;
+use std::path::Path;
+use std::sync::{Arc, LazyLock, RwLock};
+
+static COST_TRACKER: LazyLock<RwLock<Option<Arc<CostTracker>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Returned when a call would push spending past a configured limit and
+/// `[cost] allow_override` is off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetExceededError {
+    pub period: UsagePeriod,
+    pub current_usd: f64,
+    pub limit_usd: f64,
+}
+
+impl std::fmt::Display for BudgetExceededError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} budget exhausted: ${:.2} spent of ${:.2}. LLM calls are paused until the limit resets or [cost] limits are raised.",
+            period_label(self.period),
+            self.current_usd,
+            self.limit_usd
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceededError {}
+
+pub fn is_budget_exceeded(err: &anyhow::Error) -> bool {
+    err.chain().any(|source| source.is::<BudgetExceededError>())
+}
+
+/// Install the process-wide tracker from config. Disabled tracking clears it.
+pub fn init_from_config(config: &CostConfig, workspace_dir: &Path) {
+    let tracker = if config.enabled {
+        match CostTracker::new(config.clone(), workspace_dir) {
+            Ok(tracker) => Some(Arc::new(tracker)),
+            Err(e) => {
+                tracing::warn!("Failed to initialize cost tracker: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut guard = COST_TRACKER.write().unwrap_or_else(|e| e.into_inner());
+    *guard = tracker;
+}
+
+/// The process-wide tracker, if cost tracking is enabled.
+pub fn global_tracker() -> Option<Arc<CostTracker>> {
+    COST_TRACKER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// The process-wide tracker, initializing it from `config` on first use.
+pub fn shared_tracker(config: &CostConfig, workspace_dir: &Path) -> Option<Arc<CostTracker>> {
+    if !config.enabled {
+        return None;
+    }
+    if let Some(tracker) = global_tracker() {
+        return Some(tracker);
+    }
+    init_from_config(config, workspace_dir);
+    global_tracker()
+}
+
+/// Check the shared tracker before a provider call. See [`precheck_with`].
+pub fn precheck(
+    provider: &str,
+    model: &str,
+    estimated_input_tokens: u64,
+) -> Result<Option<String>> {
+    match global_tracker() {
+        Some(tracker) => precheck_with(&tracker, provider, model, estimated_input_tokens),
+        None => Ok(None),
+    }
+}
+
+/// Check whether a call expected to send `estimated_input_tokens` fits the
+/// budget. Returns a notice for the user when the warning threshold is first
+/// crossed in a period (or when an exceeded limit is overridden), and a
+/// [`BudgetExceededError`] when the limit is exceeded without override.
+pub fn precheck_with(
+    tracker: &CostTracker,
+    provider: &str,
+    model: &str,
+    estimated_input_tokens: u64,
+) -> Result<Option<String>> {
+    let estimate = tracker.price_usage(provider, model, estimated_input_tokens, 0);
+    match tracker.check_budget(estimate.cost_usd)? {
+        BudgetCheck::Allowed => Ok(None),
+        BudgetCheck::Warning {
+            current_usd,
+            limit_usd,
+            period,
+        } => {
+            if !tracker.first_warning_for(period) {
+                return Ok(None);
+            }
+            tracing::warn!(
+                period = period_label(period),
+                current_usd,
+                limit_usd,
+                "Cost budget warning threshold reached"
+            );
+            Ok(Some(format!(
+                "⚠️ {} budget: ${current_usd:.2} of ${limit_usd:.2} used ({}% warning threshold).",
+                period_label(period),
+                tracker.config().warn_at_percent
+            )))
+        }
+        BudgetCheck::Exceeded {
+            current_usd,
+            limit_usd,
+            period,
+        } => {
+            if !tracker.config().allow_override {
+                return Err(BudgetExceededError {
+                    period,
+                    current_usd,
+                    limit_usd,
+                }
+                .into());
+            }
+            tracing::warn!(
+                period = period_label(period),
+                current_usd,
+                limit_usd,
+                "Cost budget exceeded; continuing because allow_override is set"
+            );
+            Ok(tracker.first_warning_for(period).then(|| {
+                format!(
+                    "⚠️ {} budget exceeded: ${current_usd:.2} of ${limit_usd:.2} spent. Continuing because [cost] allow_override is enabled.",
+                    period_label(period)
+                )
+            }))
+        }
+    }
+}
+
+/// Record provider-reported usage against the shared tracker.
+pub fn record(channel: &str, provider: &str, model: &str, usage: Option<&ProviderTokenUsage>) {
+    if let Some(tracker) = global_tracker() {
+        record_with(&tracker, channel, provider, model, usage);
+    }
+}
+
+/// Price and persist `usage` attributed to `channel`. Calls without usage
+/// data are skipped; failures are logged rather than failing the turn.
+pub fn record_with(
+    tracker: &CostTracker,
+    channel: &str,
+    provider: &str,
+    model: &str,
+    usage: Option<&ProviderTokenUsage>,
+) {
+    let Some(usage) = usage else {
+        return;
+    };
+    let input_tokens = usage.input_tokens.unwrap_or(0);
+    let output_tokens = usage.output_tokens.unwrap_or(0);
+    if input_tokens == 0 && output_tokens == 0 {
+        return;
+    }
+
+    let priced = tracker
+        .price_usage(provider, model, input_tokens, output_tokens)
+        .with_channel(channel);
+    if let Err(e) = tracker.record_usage(priced) {
+        tracing::warn!("Failed to record LLM usage: {e}");
+    }
+}
+
+/// Rough prompt size for pre-call budget checks (~4 characters per token).
+pub fn estimate_input_tokens(messages: &[ChatMessage]) -> u64 {
+    let chars: usize = messages.iter().map(|m| m.content.chars().count()).sum();
+    (chars / 4) as u64
+}
+
+fn period_label(period: UsagePeriod) -> &'static str {
+    match period {
+        UsagePeriod::Session => "Session",
+        UsagePeriod::Day => "Daily",
+        UsagePeriod::Month => "Monthly",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::ModelPricing;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn tracker(tmp: &TempDir, daily_limit_usd: f64, allow_override: bool) -> CostTracker {
+        let mut prices = HashMap::new();
+        prices.insert(
+            "anthropic/claude-test".to_string(),
+            ModelPricing {
+                input: 1_000.0,
+                output: 2_000.0,
+            },
+        );
+        let config = CostConfig {
+            enabled: true,
+            daily_limit_usd,
+            monthly_limit_usd: 1_000.0,
+            warn_at_percent: 50,
+            allow_override,
+            prices,
+        };
+        CostTracker::new(config, tmp.path()).unwrap()
+    }
+
+    fn usage(input: u64, output: u64) -> ProviderTokenUsage {
+        ProviderTokenUsage {
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+        }
+    }
+
+    #[test]
+    fn record_prices_by_suffix_and_attributes_channel() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = tracker(&tmp, 100.0, false);
+
+        record_with(
+            &tracker,
+            "telegram",
+            "anthropic",
+            "claude-test",
+            Some(&usage(1_000, 500)),
+        );
+        record_with(&tracker, "telegram", "anthropic", "claude-test", None);
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert_eq!(summary.total_tokens, 1_500);
+        // 1k input at $1000/M + 500 output at $2000/M.
+        assert!((summary.daily_cost_usd - 2.0).abs() < 1e-9);
+
+        let stored = std::fs::read_to_string(tmp.path().join("state/costs.jsonl")).unwrap();
+        assert!(stored.contains("\"channel\":\"telegram\""));
+    }
+
+    #[test]
+    fn unknown_models_are_recorded_at_zero_cost() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = tracker(&tmp, 100.0, false);
+        record_with(&tracker, "cli", "ollama", "llama3", Some(&usage(10, 10)));
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert!(summary.daily_cost_usd.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn precheck_warns_once_per_period() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = tracker(&tmp, 10.0, false);
+        assert!(precheck_with(&tracker, "anthropic", "claude-test", 10)
+            .unwrap()
+            .is_none());
+
+        record_with(
+            &tracker,
+            "cli",
+            "anthropic",
+            "claude-test",
+            Some(&usage(6_000, 0)),
+        );
+        let notice = precheck_with(&tracker, "anthropic", "claude-test", 10)
+            .unwrap()
+            .expect("warning at 60% of the daily limit");
+        assert!(notice.contains("Daily budget"));
+        assert!(precheck_with(&tracker, "anthropic", "claude-test", 10)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn precheck_blocks_when_limit_exceeded() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = tracker(&tmp, 1.0, false);
+        record_with(
+            &tracker,
+            "cron",
+            "anthropic",
+            "claude-test",
+            Some(&usage(1_000, 0)),
+        );
+
+        let err = precheck_with(&tracker, "anthropic", "claude-test", 100).unwrap_err();
+        assert!(is_budget_exceeded(&err));
+        let exceeded = err.downcast_ref::<BudgetExceededError>().unwrap();
+        assert_eq!(exceeded.period, UsagePeriod::Day);
+        assert!((exceeded.limit_usd - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn allow_override_turns_exceeded_into_notice() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = tracker(&tmp, 1.0, true);
+        record_with(
+            &tracker,
+            "cron",
+            "anthropic",
+            "claude-test",
+            Some(&usage(1_000, 0)),
+        );
+
+        let notice = precheck_with(&tracker, "anthropic", "claude-test", 100)
+            .unwrap()
+            .expect("override notice");
+        assert!(notice.contains("allow_override"));
+        assert!(precheck_with(&tracker, "anthropic", "claude-test", 100)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn disabled_config_has_no_shared_tracker() {
+        let tmp = TempDir::new().unwrap();
+        assert!(shared_tracker(&CostConfig::default(), tmp.path()).is_none());
+    }
+}
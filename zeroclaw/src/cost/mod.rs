@@ -1,8 +1,11 @@
+pub mod budget;
 pub mod tracker;
 pub mod types;
 
 // Re-exported for potential external use (public API)
 #[allow(unused_imports)]
+pub use budget::{is_budget_exceeded, BudgetExceededError};
+#[allow(unused_imports)]
 pub use tracker::CostTracker;
 #[allow(unused_imports)]
 pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
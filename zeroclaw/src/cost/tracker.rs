@@ -1,9 +1,9 @@
 use super::types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
-use crate::config::schema::CostConfig;
+use crate::config::schema::{CostConfig, ModelPricing};
 use anyhow::{anyhow, Context, Result};
 use chrono::{Datelike, NaiveDate, Utc};
 use parking_lot::{Mutex, MutexGuard};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
@@ -15,6 +15,7 @@ pub struct CostTracker {
     storage: Arc<Mutex<CostStorage>>,
     session_id: String,
     session_costs: Arc<Mutex<Vec<CostRecord>>>,
+    warned_periods: Mutex<HashSet<String>>,
 }
 
 impl CostTracker {
@@ -31,6 +32,7 @@ impl CostTracker {
             storage: Arc::new(Mutex::new(storage)),
             session_id: uuid::Uuid::new_v4().to_string(),
             session_costs: Arc::new(Mutex::new(Vec::new())),
+            warned_periods: Mutex::new(HashSet::new()),
         })
     }
 
@@ -39,6 +41,11 @@ impl CostTracker {
         &self.session_id
     }
 
+    /// Get the cost configuration this tracker enforces.
+    pub fn config(&self) -> &CostConfig {
+        &self.config
+    }
+
     fn lock_storage(&self) -> MutexGuard<'_, CostStorage> {
         self.storage.lock()
     }
@@ -106,6 +113,55 @@ impl CostTracker {
         Ok(BudgetCheck::Allowed)
     }
 
+    /// Look up `[cost.prices]` for a model: exact key first, then
+    /// `provider/model`, then any vendor-prefixed key ending in `/model`.
+    pub fn pricing_for(&self, provider: &str, model: &str) -> Option<&ModelPricing> {
+        let prices = &self.config.prices;
+        prices
+            .get(model)
+            .or_else(|| prices.get(&format!("{provider}/{model}")))
+            .or_else(|| {
+                let suffix = format!("/{model}");
+                prices
+                    .iter()
+                    .filter(|(key, _)| key.ends_with(&suffix))
+                    .min_by(|(a, _), (b, _)| a.cmp(b))
+                    .map(|(_, pricing)| pricing)
+            })
+    }
+
+    /// Build a priced usage record. Models without a price entry cost zero.
+    pub fn price_usage(
+        &self,
+        provider: &str,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> TokenUsage {
+        let (input_price, output_price) = self
+            .pricing_for(provider, model)
+            .map_or((0.0, 0.0), |pricing| (pricing.input, pricing.output));
+        TokenUsage::new(
+            model,
+            input_tokens,
+            output_tokens,
+            input_price,
+            output_price,
+        )
+    }
+
+    /// Returns `true` the first time a budget warning is raised for the
+    /// current day or month, so users are not nagged on every call.
+    pub fn first_warning_for(&self, period: UsagePeriod) -> bool {
+        let now = Utc::now();
+        let key = match period {
+            UsagePeriod::Session => format!("session:{}", self.session_id),
+            UsagePeriod::Day => format!("day:{}", now.date_naive()),
+            UsagePeriod::Month => format!("month:{}-{:02}", now.year(), now.month()),
+        };
+        self.warned_periods.lock().insert(key)
+    }
+
     /// Record a usage event.
     pub fn record_usage(&self, usage: TokenUsage) -> Result<()> {
         if !self.config.enabled {
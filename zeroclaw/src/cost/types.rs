@@ -15,6 +15,9 @@ pub struct TokenUsage {
     pub cost_usd: f64,
     /// Timestamp of the request
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Channel or subsystem that made the call (e.g. "telegram", "cron", "delegate")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
 }
 
 impl TokenUsage {
@@ -51,9 +54,17 @@ impl TokenUsage {
             total_tokens,
             cost_usd,
             timestamp: chrono::Utc::now(),
+            channel: None,
         }
     }
 
+    /// Attribute this usage to a channel or subsystem.
+    #[must_use]
+    pub fn with_channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
     /// Get the total cost.
     pub fn cost(&self) -> f64 {
         self.cost_usd
@@ -167,7 +167,7 @@ async fn run_agent_job(
 
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
+            Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prefixed_prompt),
                 None,
@@ -175,7 +175,7 @@ async fn run_agent_job(
                 config.default_temperature,
                 vec![],
                 false,
-            )
+            ))
             .await
         }
     };
@@ -305,6 +305,16 @@ pub(crate) async fn deliver_announcement(
     target: &str,
     output: &str,
 ) -> Result<()> {
+    let observer = crate::observability::create_observer(&config.observability);
+    let output = match crate::security::outbound::screen(
+        crate::security::OutboundSurface::Channel,
+        output,
+        observer.as_ref(),
+    ) {
+        Ok(screened) => screened,
+        Err(blocked) => crate::channels::leak_guard::withheld_notice(&blocked).into(),
+    };
+    let output = output.as_ref();
     match channel.to_ascii_lowercase().as_str() {
         "telegram" => {
             let tg = config
@@ -427,10 +437,24 @@ async fn run_job_command_with_timeout(
         );
     }
 
-    let child = match Command::new("sh")
-        .arg("-lc")
+    let mut cmd = Command::new("sh");
+    cmd.arg("-lc")
         .arg(&job.command)
-        .current_dir(&config.workspace_dir)
+        .current_dir(&config.workspace_dir);
+    let sandbox = crate::security::create_command_sandbox(config);
+    if let Err(e) =
+        crate::security::wrap_tokio_command(sandbox.as_ref(), &mut cmd, &security.sandbox_scope())
+    {
+        return (
+            false,
+            format!("blocked by sandbox ({}): {e}", sandbox.name()),
+        );
+    }
+
+    let limits = crate::security::ResourceLimits::from_config(&config.security.resources);
+    limits.apply(&mut cmd);
+
+    let child = match cmd
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -440,6 +464,7 @@ async fn run_job_command_with_timeout(
         Ok(child) => child,
         Err(e) => return (false, format!("spawn error: {e}")),
     };
+    let watch = limits.watch(child.id());
 
     match time::timeout(timeout, child.wait_with_output()).await {
         Ok(Ok(output)) => {
@@ -451,13 +476,19 @@ async fn run_job_command_with_timeout(
                 stdout.trim(),
                 stderr.trim()
             );
-            (output.status.success(), combined)
+            match watch.finish(Some(&output.status), &stderr) {
+                Some(limit) => (false, format!("killed: {limit}\n{combined}")),
+                None => (output.status.success(), combined),
+            }
         }
         Ok(Err(e)) => (false, format!("spawn error: {e}")),
-        Err(_) => (
-            false,
-            format!("job timed out after {}s", timeout.as_secs_f64()),
-        ),
+        Err(_) => {
+            watch.finish(None, "");
+            (
+                false,
+                format!("job timed out after {}s", timeout.as_secs_f64()),
+            )
+        }
     }
 }
 
@@ -524,6 +555,27 @@ mod tests {
         assert!(output.contains("status=exit status: 0"));
     }
 
+    #[tokio::test]
+    async fn run_job_command_refused_when_fail_closed_sandbox_is_missing() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.security.sandbox.backend = crate::config::SandboxBackend::Bubblewrap;
+        config.security.sandbox.fail_closed = true;
+        if crate::security::create_sandbox(&config.security).is_available() {
+            return;
+        }
+        let job = test_job("echo should-not-run");
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = run_job_command(&config, &security, &job).await;
+        assert!(!success);
+        assert!(
+            output.contains("blocked by sandbox (bubblewrap)"),
+            "{output}"
+        );
+        assert!(!output.contains("should-not-run"));
+    }
+
     #[tokio::test]
     async fn run_job_command_failure() {
         let tmp = TempDir::new().unwrap();
@@ -551,6 +603,25 @@ mod tests {
         assert!(output.contains("job timed out after"));
     }
 
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn run_job_command_reports_exceeded_subprocess_limit() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.autonomy.allowed_commands = vec!["sleep".into()];
+        config.security.resources.max_subprocesses = 1;
+        let job = test_job("sleep 5 | sleep 5 | sleep 5");
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) =
+            run_job_command_with_timeout(&config, &security, &job, Duration::from_secs(4)).await;
+        assert!(!success);
+        assert!(
+            output.starts_with("killed: subprocess limit exceeded"),
+            "{output}"
+        );
+    }
+
     #[tokio::test]
     async fn run_job_command_blocks_disallowed_command() {
         let tmp = TempDir::new().unwrap();
@@ -87,9 +87,50 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.sop.enabled {
+        let sop_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "sop",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = sop_cfg.clone();
+                async move { Box::pin(run_sop_worker(cfg)).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("sop");
+        tracing::info!("SOP engine disabled; SOP supervisor not started");
+    }
+
+    #[cfg(feature = "channel-mqtt")]
+    if config.channels_config.mqtt.is_some() {
+        if config.sop.enabled {
+            let mqtt_cfg = config.clone();
+            handles.push(spawn_component_supervisor(
+                "mqtt",
+                initial_backoff,
+                max_backoff,
+                move || {
+                    let cfg = mqtt_cfg.clone();
+                    async move { Box::pin(run_mqtt_worker(cfg)).await }
+                },
+            ));
+        } else {
+            tracing::warn!("MQTT ingress is configured but [sop] is disabled; not starting it");
+        }
+    }
+
+    #[cfg(not(feature = "channel-mqtt"))]
+    if config.channels_config.mqtt.is_some() {
+        tracing::warn!(
+            "MQTT ingress is configured but this build was compiled without `channel-mqtt`; skipping MQTT."
+        );
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
+    println!("   Components: gateway, channels, heartbeat, scheduler, sop");
     println!("   Ctrl+C to stop");
 
     tokio::signal::ctrl_c().await?;
@@ -198,7 +239,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         for task in tasks {
             let prompt = format!("[Heartbeat Task] {task}");
             let temp = config.default_temperature;
-            match crate::agent::run(
+            match Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prompt),
                 None,
@@ -206,7 +247,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 temp,
                 vec![],
                 false,
-            )
+            ))
             .await
             {
                 Ok(output) => {
@@ -242,6 +283,63 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     }
 }
 
+async fn run_sop_worker(config: Config) -> Result<()> {
+    use crate::sop::dispatch::{
+        check_sop_cron_triggers, execute_dispatch_results, process_approval_timeouts,
+        spawn_step_agent, SopCronCache,
+    };
+
+    let sop = crate::sop::shared(&config);
+    let memory: std::sync::Arc<dyn crate::memory::Memory> =
+        std::sync::Arc::from(crate::memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?);
+    let audit = std::sync::Arc::new(crate::sop::SopAuditLogger::new(memory));
+
+    // Resume runs that were in flight when the daemon last stopped
+    for action in crate::sop::restore_active_runs(&sop.engine, &audit).await? {
+        spawn_step_agent(&config, &audit, action);
+    }
+
+    let cron_cache = SopCronCache::from_engine(&sop.engine);
+    let mut last_check = Utc::now();
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.sop.poll_interval_secs.max(1)));
+
+    loop {
+        interval.tick().await;
+
+        let results =
+            check_sop_cron_triggers(&sop.engine, &audit, &cron_cache, &mut last_check).await;
+        execute_dispatch_results(&config, &audit, results);
+        process_approval_timeouts(&config, &sop, &audit).await;
+
+        crate::health::mark_component_ok("sop");
+    }
+}
+
+#[cfg(feature = "channel-mqtt")]
+async fn run_mqtt_worker(config: Config) -> Result<()> {
+    let Some(mqtt) = config.channels_config.mqtt.clone() else {
+        return Ok(());
+    };
+
+    let sop = crate::sop::shared(&config);
+    let memory: std::sync::Arc<dyn crate::memory::Memory> =
+        std::sync::Arc::from(crate::memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?);
+    let audit = std::sync::Arc::new(crate::sop::SopAuditLogger::new(memory));
+
+    crate::channels::mqtt::run_mqtt_sop_listener(&config, &mqtt, sop.engine, audit).await
+}
+
 fn heartbeat_tasks_for_tick(
     file_tasks: Vec<String>,
     fallback_message: Option<&str>,
@@ -4,6 +4,8 @@ use chrono::{DateTime, Utc};
 use std::io::Write;
 use std::path::Path;
 
+pub mod replay;
+
 const DAEMON_STALE_SECONDS: i64 = 30;
 const SCHEDULER_STALE_SECONDS: i64 = 120;
 const CHANNEL_STALE_SECONDS: i64 = 300;
@@ -766,6 +768,82 @@ fn workspace_probe_path(workspace_dir: &Path) -> std::path::PathBuf {
     ))
 }
 
+// ── OS sandbox ───────────────────────────────────────────────────
+
+fn check_sandbox(config: &Config, items: &mut Vec<DiagItem>) {
+    use crate::config::SandboxBackend;
+
+    let cat = "sandbox";
+    if config.runtime.kind == "docker" {
+        items.push(DiagItem::ok(
+            cat,
+            "commands run inside the docker runtime; no extra sandbox applied",
+        ));
+        return;
+    }
+    let sandbox = crate::security::create_command_sandbox(config);
+
+    if !sandbox.is_available() {
+        items.push(DiagItem::error(
+            cat,
+            format!(
+                "{} requested but unavailable; shell and cron commands are refused (fail_closed)",
+                sandbox.name()
+            ),
+        ));
+        return;
+    }
+
+    if sandbox.name() == "none" {
+        let requested = !matches!(
+            config.security.sandbox.backend,
+            SandboxBackend::Auto | SandboxBackend::None
+        ) && config.security.sandbox.enabled != Some(false);
+        if requested {
+            items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "{:?} requested but unavailable; commands run without OS isolation (set fail_closed = true to refuse)",
+                    config.security.sandbox.backend
+                ),
+            ));
+        } else {
+            items.push(DiagItem::ok(cat, sandbox.description()));
+        }
+        return;
+    }
+
+    items.push(DiagItem::ok(
+        cat,
+        format!("{} active: {}", sandbox.name(), sandbox.description()),
+    ));
+
+    // Docker may need to pull an image; only probe the lightweight backends.
+    if sandbox.name() == "docker" {
+        return;
+    }
+    let policy =
+        crate::security::SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let mut probe = std::process::Command::new("sh");
+    probe.args(["-c", "true"]);
+    let outcome = sandbox
+        .wrap_command_in_scope(&mut probe, &policy.sandbox_scope())
+        .and_then(|()| probe.output());
+    match outcome {
+        Ok(output) if output.status.success() => {
+            items.push(DiagItem::ok(cat, "probe command ran inside the sandbox"));
+        }
+        Ok(output) => items.push(DiagItem::error(
+            cat,
+            format!(
+                "probe command failed inside the sandbox: {}",
+                truncate_for_display(String::from_utf8_lossy(&output.stderr).trim(), 120)
+            ),
+        )),
+        Err(e) => items.push(DiagItem::error(cat, format!("probe command failed: {e}"))),
+    }
+}
+
 // ── Daemon state (original logic, preserved) ─────────────────────
 
 fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
@@ -1166,6 +1244,8 @@ mod tests {
             provider: "groq".into(),
             model: String::new(),
             api_key: None,
+            max_context_tokens: None,
+            vision: None,
         }];
         let mut items = Vec::new();
         check_config_semantics(&config, &mut items);
@@ -1247,6 +1327,37 @@ mod tests {
         assert_eq!(parse_df_available_mb(stdout), Some(500));
     }
 
+    #[test]
+    fn sandbox_check_reports_disabled_sandbox_as_ok() {
+        let mut config = Config::default();
+        config.security.sandbox.enabled = Some(false);
+        let mut items = Vec::new();
+        check_sandbox(&config, &mut items);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].severity, Severity::Ok);
+        assert_eq!(items[0].category, "sandbox");
+    }
+
+    #[test]
+    fn sandbox_check_flags_missing_backend() {
+        let mut config = Config::default();
+        config.security.sandbox.backend = crate::config::SandboxBackend::Bubblewrap;
+        if crate::security::create_sandbox(&config.security).name() != "none" {
+            return;
+        }
+        let mut items = Vec::new();
+        check_sandbox(&config, &mut items);
+        assert_eq!(items[0].severity, Severity::Warn);
+        assert!(items[0].message.contains("fail_closed"));
+
+        config.security.sandbox.fail_closed = true;
+        let mut items = Vec::new();
+        check_sandbox(&config, &mut items);
+        assert_eq!(items[0].severity, Severity::Error);
+        assert!(items[0].message.contains("refused"));
+    }
+
     #[test]
     fn truncate_for_display_preserves_utf8_boundaries() {
         let preview = truncate_for_display("🙂example-alpha-build", 3);
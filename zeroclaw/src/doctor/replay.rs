@@ -0,0 +1,342 @@
+//! `zeroclaw doctor replay <fixture>`: re-run recorded agent turns offline.
+//!
+//! Every recorded response is first run through the tool-call parser the
+//! agent loop uses, then the turns are driven through `run_tool_call_loop`
+//! against a [`ReplayProvider`]. Tools are stubs that return the outputs
+//! captured in the fixture, so nothing is executed on this machine.
+
+use crate::agent::loop_::{inspect_tool_calls, run_tool_call_loop};
+use crate::config::{Config, MultimodalConfig};
+use crate::observability::NoopObserver;
+use crate::providers::replay::{load_fixture, RecordedExchange, ReplayProvider};
+use crate::providers::{ChatMessage, ChatResponse};
+use crate::tools::{Tool, ToolResult, ToolSpec};
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::LazyLock;
+
+static XML_TOOL_RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<tool_result name="([^"]+)">\n(.*?)\n</tool_result>"#).unwrap()
+});
+
+/// Outcome of one re-driven agent turn.
+#[derive(Debug)]
+pub(crate) struct ReplayedTurn {
+    /// Recorded exchanges the turn consumed.
+    pub exchanges: usize,
+    pub outcome: std::result::Result<String, String>,
+}
+
+/// Everything `doctor replay` reports after re-driving a fixture.
+#[derive(Debug)]
+pub(crate) struct ReplayReport {
+    pub turns: Vec<ReplayedTurn>,
+    pub served: usize,
+    pub total: usize,
+    pub divergences: Vec<String>,
+}
+
+pub async fn run_replay(config: &Config, fixture: &Path) -> Result<()> {
+    let exchanges = load_fixture(fixture)?;
+    if exchanges.is_empty() {
+        println!("Fixture {} has no recorded exchanges.", fixture.display());
+        return Ok(());
+    }
+
+    println!("Fixture: {}", fixture.display());
+    println!("Exchanges: {}", exchanges.len());
+    println!();
+    println!("Tool-call parsing:");
+    for (idx, exchange) in exchanges.iter().enumerate() {
+        let response: ChatResponse = exchange.response.clone().into();
+        let (calls, issue) = inspect_tool_calls(&response);
+        let summary = if calls.is_empty() {
+            format!("text ({} chars)", response.text_or_empty().chars().count())
+        } else {
+            format!("{} tool call(s)", calls.len())
+        };
+        println!(
+            "  #{} {} | {} message(s) -> {summary}",
+            idx + 1,
+            exchange.model,
+            exchange.messages.len()
+        );
+        for (name, arguments) in &calls {
+            println!("      ↳ {name} {arguments}");
+        }
+        if let Some(issue) = issue {
+            println!("      ⚠️  {issue}");
+        }
+    }
+
+    let report = replay_turns(exchanges, config.agent.max_tool_iterations).await;
+    println!();
+    println!("Replay:");
+    for (idx, turn) in report.turns.iter().enumerate() {
+        match &turn.outcome {
+            Ok(text) => println!(
+                "  turn {} ({} exchange(s)) ✅ {}",
+                idx + 1,
+                turn.exchanges,
+                super::truncate_for_display(text, 100)
+            ),
+            Err(error) => println!(
+                "  turn {} ({} exchange(s)) ❌ {error}",
+                idx + 1,
+                turn.exchanges
+            ),
+        }
+    }
+    println!(
+        "  served {}/{} recorded response(s)",
+        report.served, report.total
+    );
+    if report.divergences.is_empty() {
+        println!("  requests matched the recording");
+    } else {
+        for divergence in &report.divergences {
+            println!("  ⚠️  diverged at {divergence}");
+        }
+    }
+    Ok(())
+}
+
+/// Drive the agent loop over every recorded exchange, one turn at a time.
+pub(crate) async fn replay_turns(
+    exchanges: Vec<RecordedExchange>,
+    max_tool_iterations: usize,
+) -> ReplayReport {
+    let tools = replay_tools(&exchanges);
+    let total = exchanges.len();
+    let provider = ReplayProvider::new(exchanges);
+    let mut turns = Vec::new();
+
+    while provider.served() < total {
+        let start = provider.served();
+        let exchange = &provider.exchanges()[start];
+        let mut history = exchange.messages.clone();
+        let outcome = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools,
+            &NoopObserver,
+            "replay",
+            &exchange.model,
+            exchange.temperature,
+            true,
+            None,
+            "replay",
+            &MultimodalConfig::default(),
+            max_tool_iterations,
+            None,
+            None,
+            None,
+            &[],
+            None,
+        )
+        .await;
+        turns.push(ReplayedTurn {
+            exchanges: provider.served() - start,
+            outcome: outcome.map_err(|e| format!("{e:#}")),
+        });
+        if provider.served() == start {
+            // The loop failed before reaching the provider; retrying would spin.
+            break;
+        }
+    }
+
+    ReplayReport {
+        turns,
+        served: provider.served(),
+        total,
+        divergences: provider.divergences(),
+    }
+}
+
+/// Stand-in for a tool that answers with the outputs recorded for it.
+struct ReplayTool {
+    spec: ToolSpec,
+    outputs: Mutex<VecDeque<String>>,
+}
+
+#[async_trait]
+impl Tool for ReplayTool {
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn description(&self) -> &str {
+        &self.spec.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.spec.parameters.clone()
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> Result<ToolResult> {
+        let output = self.outputs.lock().pop_front();
+        Ok(ToolResult {
+            success: output.is_some(),
+            output: output.clone().unwrap_or_default(),
+            error: output
+                .is_none()
+                .then(|| "no recorded output left for this tool".to_string()),
+        })
+    }
+}
+
+/// Build one [`ReplayTool`] per tool seen in the fixture, queued with the
+/// results that were fed back to the model, in order.
+fn replay_tools(exchanges: &[RecordedExchange]) -> Vec<Box<dyn Tool>> {
+    let mut specs: Vec<ToolSpec> = Vec::new();
+    let mut outputs: HashMap<String, VecDeque<String>> = HashMap::new();
+    let add_spec = |specs: &mut Vec<ToolSpec>, spec: ToolSpec| {
+        if !specs.iter().any(|existing| existing.name == spec.name) {
+            specs.push(spec);
+        }
+    };
+
+    for (idx, exchange) in exchanges.iter().enumerate() {
+        for spec in &exchange.tools {
+            add_spec(&mut specs, spec.clone());
+        }
+        let (calls, _) = inspect_tool_calls(&exchange.response.clone().into());
+        for (name, _) in calls {
+            add_spec(
+                &mut specs,
+                ToolSpec {
+                    name,
+                    description: "Replayed tool".to_string(),
+                    parameters: serde_json::json!({ "type": "object" }),
+                },
+            );
+        }
+
+        let Some(previous) = idx.checked_sub(1).map(|prev| &exchanges[prev]) else {
+            continue;
+        };
+        let ids: HashMap<&str, &str> = previous
+            .response
+            .tool_calls
+            .iter()
+            .map(|call| (call.id.as_str(), call.name.as_str()))
+            .collect();
+        for (name, output) in trailing_tool_results(&exchange.messages, &ids) {
+            outputs.entry(name).or_default().push_back(output);
+        }
+    }
+
+    specs
+        .into_iter()
+        .map(|spec| {
+            let queued = outputs.remove(&spec.name).unwrap_or_default();
+            Box::new(ReplayTool {
+                spec,
+                outputs: Mutex::new(queued),
+            }) as Box<dyn Tool>
+        })
+        .collect()
+}
+
+/// Tool results appended after the last assistant message of a request.
+fn trailing_tool_results(
+    messages: &[ChatMessage],
+    names_by_id: &HashMap<&str, &str>,
+) -> Vec<(String, String)> {
+    let start = messages
+        .iter()
+        .rposition(|m| m.role == "assistant")
+        .map_or(messages.len(), |idx| idx + 1);
+    let mut results = Vec::new();
+    for message in &messages[start..] {
+        match message.role.as_str() {
+            "tool" => {
+                let Ok(value) = serde_json::from_str::<serde_json::Value>(&message.content) else {
+                    continue;
+                };
+                let id = value
+                    .get("tool_call_id")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                let content = value
+                    .get("content")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                if let Some(name) = names_by_id.get(id) {
+                    results.push(((*name).to_string(), content.to_string()));
+                }
+            }
+            "user" if message.content.starts_with("[Tool results]") => {
+                for capture in XML_TOOL_RESULT.captures_iter(&message.content) {
+                    results.push((capture[1].to_string(), capture[2].to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::replay::RecordedResponse;
+
+    fn exchange(messages: Vec<ChatMessage>, text: &str) -> RecordedExchange {
+        RecordedExchange {
+            model: "recorded-model".into(),
+            temperature: 0.0,
+            native_tools: false,
+            vision: false,
+            messages,
+            tools: Vec::new(),
+            response: RecordedResponse {
+                text: Some(text.into()),
+                ..RecordedResponse::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_prompt_mode_tool_loop_with_recorded_outputs() {
+        let call =
+            "<tool_call>\n{\"name\":\"shell\",\"arguments\":{\"command\":\"ls\"}}\n</tool_call>";
+        let first = vec![ChatMessage::system("sys"), ChatMessage::user("list files")];
+        let mut second = first.clone();
+        second.push(ChatMessage::assistant(call));
+        second.push(ChatMessage::user(
+            "[Tool results]\n<tool_result name=\"shell\">\na.txt\nb.txt\n</tool_result>\n",
+        ));
+
+        let report = replay_turns(
+            vec![
+                exchange(first, call),
+                exchange(second, "Two files: a.txt and b.txt"),
+            ],
+            5,
+        )
+        .await;
+
+        assert_eq!(report.served, 2);
+        assert_eq!(report.turns.len(), 1);
+        assert_eq!(
+            report.turns[0].outcome.as_deref(),
+            Ok("Two files: a.txt and b.txt")
+        );
+        assert!(report.divergences.is_empty(), "{:?}", report.divergences);
+    }
+
+    #[tokio::test]
+    async fn reports_exhausted_fixture_as_failed_turn() {
+        let call = "<tool_call>\n{\"name\":\"shell\",\"arguments\":{}}\n</tool_call>";
+        let report = replay_turns(vec![exchange(vec![ChatMessage::user("go")], call)], 5).await;
+
+        assert_eq!(report.turns.len(), 1);
+        let error = report.turns[0].outcome.as_ref().unwrap_err();
+        assert!(error.contains("exhausted"), "{error}");
+    }
+}
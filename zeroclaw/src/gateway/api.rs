@@ -3,6 +3,7 @@
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
 
 use super::AppState;
+use crate::security::pairing::TokenAuthorization;
 use axum::{
     extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
@@ -22,25 +23,42 @@ fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
         .and_then(|auth| auth.strip_prefix("Bearer "))
 }
 
-/// Verify bearer token against PairingGuard. Returns error response if unauthorized.
+/// Verify bearer token against PairingGuard for `scope`. Returns an error
+/// response if the token is unknown (401) or lacks the scope (403).
 fn require_auth(
     state: &AppState,
     headers: &HeaderMap,
+    scope: &str,
 ) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
     if !state.pairing.require_pairing() {
         return Ok(());
     }
 
     let token = extract_bearer_token(headers).unwrap_or("");
-    if state.pairing.is_authenticated(token) {
-        Ok(())
-    } else {
-        Err((
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            })),
-        ))
+    match state.pairing.authorize(token, scope) {
+        TokenAuthorization::Granted => Ok(()),
+        TokenAuthorization::Forbidden => {
+            super::audit_auth(false, None, &format!("api: missing '{scope}' scope"));
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({
+                    "error": format!("Forbidden — token lacks the '{scope}' scope")
+                })),
+            ))
+        }
+        TokenAuthorization::Unauthenticated => {
+            super::audit_auth(
+                false,
+                None,
+                &format!("api: invalid bearer token for '{scope}'"),
+            );
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({
+                    "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
+                })),
+            ))
+        }
     }
 }
 
@@ -59,6 +77,19 @@ pub struct MemoryStoreBody {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct TokenCreateBody {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_in_days: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct ApprovalDecisionBody {
+    pub id: String,
+    pub decision: crate::approval::ApprovalResponse,
+}
+
 #[derive(Deserialize)]
 pub struct CronAddBody {
     pub name: Option<String>,
@@ -73,7 +104,7 @@ pub async fn handle_api_status(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "status:read") {
         return e.into_response();
     }
 
@@ -107,7 +138,7 @@ pub async fn handle_api_config_get(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "config:read") {
         return e.into_response();
     }
 
@@ -139,7 +170,7 @@ pub async fn handle_api_config_put(
     headers: HeaderMap,
     body: String,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "config:write") {
         return e.into_response();
     }
 
@@ -175,6 +206,14 @@ pub async fn handle_api_config_put(
             .into_response();
     }
 
+    crate::security::audit::record(
+        crate::security::audit::AuditEvent::new(
+            crate::security::audit::AuditEventType::ConfigChange,
+        )
+        .with_actor("gateway".into(), None, None)
+        .with_action("PUT /api/config".into(), "high".into(), false, true),
+    );
+
     // Update in-memory config
     *state.config.lock() = new_config;
 
@@ -186,7 +225,7 @@ pub async fn handle_api_tools(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "status:read") {
         return e.into_response();
     }
 
@@ -210,7 +249,7 @@ pub async fn handle_api_cron_list(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "cron:read") {
         return e.into_response();
     }
 
@@ -247,7 +286,7 @@ pub async fn handle_api_cron_add(
     headers: HeaderMap,
     Json(body): Json<CronAddBody>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "cron:write") {
         return e.into_response();
     }
 
@@ -282,7 +321,7 @@ pub async fn handle_api_cron_delete(
     headers: HeaderMap,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "cron:write") {
         return e.into_response();
     }
 
@@ -302,7 +341,7 @@ pub async fn handle_api_integrations(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "status:read") {
         return e.into_response();
     }
 
@@ -330,7 +369,7 @@ pub async fn handle_api_doctor(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "status:read") {
         return e.into_response();
     }
 
@@ -367,7 +406,7 @@ pub async fn handle_api_memory_list(
     headers: HeaderMap,
     Query(params): Query<MemoryQuery>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "memory:read") {
         return e.into_response();
     }
 
@@ -407,7 +446,7 @@ pub async fn handle_api_memory_store(
     headers: HeaderMap,
     Json(body): Json<MemoryStoreBody>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "memory:write") {
         return e.into_response();
     }
 
@@ -442,7 +481,7 @@ pub async fn handle_api_memory_delete(
     headers: HeaderMap,
     Path(key): Path<String>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "memory:write") {
         return e.into_response();
     }
 
@@ -463,7 +502,7 @@ pub async fn handle_api_cost(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "cost:read") {
         return e.into_response();
     }
 
@@ -496,7 +535,7 @@ pub async fn handle_api_cli_tools(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "status:read") {
         return e.into_response();
     }
 
@@ -505,17 +544,179 @@ pub async fn handle_api_cli_tools(
     Json(serde_json::json!({"cli_tools": tools})).into_response()
 }
 
-/// GET /api/health — component health snapshot
+/// GET /api/health — component health snapshot plus provider circuits
 pub async fn handle_api_health(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_auth(&state, &headers, "status:read") {
         return e.into_response();
     }
 
     let snapshot = crate::health::snapshot();
-    Json(serde_json::json!({"health": snapshot})).into_response()
+    let providers = crate::providers::circuit::global().snapshot();
+    Json(serde_json::json!({"health": snapshot, "providers": providers})).into_response()
+}
+
+/// GET /api/tokens — list named API tokens (hashes omitted)
+pub async fn handle_api_tokens_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers, "tokens:read") {
+        return e.into_response();
+    }
+
+    let now = chrono::Utc::now();
+    let tokens: Vec<serde_json::Value> = state
+        .pairing
+        .api_tokens()
+        .iter()
+        .map(|t| {
+            serde_json::json!({
+                "id": t.id,
+                "name": t.name,
+                "scopes": t.scopes,
+                "created_at": t.created_at,
+                "expires_at": t.expires_at,
+                "last_used_at": t.last_used_at,
+                "expired": crate::security::pairing::is_api_token_expired(t, now),
+            })
+        })
+        .collect();
+
+    Json(serde_json::json!({"tokens": tokens})).into_response()
+}
+
+/// POST /api/tokens — mint a named, scoped API token
+pub async fn handle_api_tokens_create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<TokenCreateBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers, "tokens:write") {
+        return e.into_response();
+    }
+    let caller = extract_bearer_token(&headers).unwrap_or("");
+    if let Some(scope) = state.pairing.ungrantable_scope(caller, &body.scopes) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": format!("Forbidden — cannot grant the '{scope}' scope, which the calling token does not hold")
+            })),
+        )
+            .into_response();
+    }
+
+    let expires_in = body
+        .expires_in_days
+        .map(|days| chrono::Duration::days(i64::from(days)));
+    let (token, record) =
+        match crate::security::pairing::mint_api_token(&body.name, &body.scopes, expires_in) {
+            Ok(minted) => minted,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": e.to_string()})),
+                )
+                    .into_response();
+            }
+        };
+
+    let id = record.id.clone();
+    let scopes = record.scopes.clone();
+    let expires_at = record.expires_at.clone();
+    state.pairing.add_api_token(record);
+    if let Err(e) = super::persist_api_tokens(state.config.clone(), &state.pairing).await {
+        state.pairing.revoke_api_token(&id);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to persist token: {e}")})),
+        )
+            .into_response();
+    }
+
+    Json(serde_json::json!({
+        "id": id,
+        "token": token,
+        "scopes": scopes,
+        "expires_at": expires_at,
+        "message": "Save this token — it will not be shown again",
+    }))
+    .into_response()
+}
+
+/// DELETE /api/tokens/:id — revoke a named API token
+pub async fn handle_api_tokens_delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers, "tokens:write") {
+        return e.into_response();
+    }
+
+    if !state.pairing.revoke_api_token(&id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No API token with id '{id}'")})),
+        )
+            .into_response();
+    }
+    if let Err(e) = super::persist_api_tokens(state.config.clone(), &state.pairing).await {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Token revoked in memory but not persisted: {e}")})),
+        )
+            .into_response();
+    }
+
+    Json(serde_json::json!({"status": "ok", "revoked": id})).into_response()
+}
+
+/// GET /api/approvals — tool calls waiting for a human decision
+pub async fn handle_api_approvals_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers, "approvals:read") {
+        return e.into_response();
+    }
+
+    let approvals = crate::approval::ApprovalBroker::global().list();
+    Json(serde_json::json!({"approvals": approvals})).into_response()
+}
+
+/// POST /api/approvals — answer a pending approval (`yes`, `no` or `always`)
+pub async fn handle_api_approvals_decide(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<ApprovalDecisionBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers, "approvals:write") {
+        return e.into_response();
+    }
+
+    let approver = extract_bearer_token(&headers)
+        .and_then(|token| state.pairing.token_label(token))
+        .map_or_else(|| "gateway".to_string(), |label| format!("gateway:{label}"));
+    match crate::approval::ApprovalBroker::global().resolve(&body.id, body.decision, &approver) {
+        Some(approval) => Json(serde_json::json!({
+            "status": "ok",
+            "id": approval.id,
+            "tool_name": approval.tool_name,
+            "decision": body.decision,
+            "approver": approver,
+        }))
+        .into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("No pending approval with id '{}'", body.id)
+            })),
+        )
+            .into_response(),
+    }
 }
 
 // ── Helpers ─────────────────────────────────────────────────────
@@ -698,11 +899,16 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     mask_optional_secret(&mut masked.api_key);
     mask_vec_secrets(&mut masked.reliability.api_keys);
     mask_vec_secrets(&mut masked.gateway.paired_tokens);
+    for token in &mut masked.gateway.api_tokens {
+        mask_required_secret(&mut token.token_hash);
+    }
     mask_optional_secret(&mut masked.composio.api_key);
     mask_optional_secret(&mut masked.browser.computer_use.api_key);
     mask_optional_secret(&mut masked.web_search.brave_api_key);
     mask_optional_secret(&mut masked.storage.provider.config.db_url);
     mask_optional_secret(&mut masked.memory.qdrant.api_key);
+    mask_optional_secret(&mut masked.transcription.api_key);
+    mask_optional_secret(&mut masked.tts.api_key);
     if let Some(cloudflare) = masked.tunnel.cloudflare.as_mut() {
         mask_required_secret(&mut cloudflare.token);
     }
@@ -786,6 +992,9 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     if let Some(email) = masked.channels_config.email.as_mut() {
         mask_required_secret(&mut email.password);
     }
+    if let Some(mqtt) = masked.channels_config.mqtt.as_mut() {
+        mask_optional_secret(&mut mqtt.password);
+    }
     masked
 }
 
@@ -819,6 +1028,11 @@ fn restore_masked_sensitive_fields(
         &mut incoming.memory.qdrant.api_key,
         &current.memory.qdrant.api_key,
     );
+    restore_optional_secret(
+        &mut incoming.transcription.api_key,
+        &current.transcription.api_key,
+    );
+    restore_optional_secret(&mut incoming.tts.api_key, &current.tts.api_key);
     if let (Some(incoming_tunnel), Some(current_tunnel)) = (
         incoming.tunnel.cloudflare.as_mut(),
         current.tunnel.cloudflare.as_ref(),
@@ -972,6 +1186,12 @@ fn restore_masked_sensitive_fields(
     ) {
         restore_required_secret(&mut incoming_ch.password, &current_ch.password);
     }
+    if let (Some(incoming_ch), Some(current_ch)) = (
+        incoming.channels_config.mqtt.as_mut(),
+        current.channels_config.mqtt.as_ref(),
+    ) {
+        restore_optional_secret(&mut incoming_ch.password, &current_ch.password);
+    }
 }
 
 fn hydrate_config_for_save(
@@ -979,6 +1199,8 @@ fn hydrate_config_for_save(
     current: &crate::config::Config,
 ) -> crate::config::Config {
     restore_masked_sensitive_fields(&mut incoming, current);
+    // API tokens are managed through /api/tokens and the CLI, never via config PUT.
+    incoming.gateway.api_tokens = current.gateway.api_tokens.clone();
     // These are runtime-computed fields skipped from TOML serialization.
     incoming.config_path = current.config_path.clone();
     incoming.workspace_dir = current.workspace_dir.clone();
@@ -999,6 +1221,8 @@ mod tests {
             token: "cf-token".to_string(),
         });
         cfg.memory.qdrant.api_key = Some("qdrant-key".to_string());
+        cfg.transcription.api_key = Some("transcription-key".to_string());
+        cfg.tts.api_key = Some("tts-key".to_string());
         cfg.channels_config.wati = Some(crate::config::schema::WatiConfig {
             api_token: "wati-token".to_string(),
             api_url: "https://live-mt-server.wati.io".to_string(),
@@ -1027,11 +1251,24 @@ mod tests {
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
         });
+        cfg.channels_config.mqtt = Some(crate::config::schema::MqttConfig {
+            broker_url: "mqtts://broker.example.com:8883".to_string(),
+            client_id: "zeroclaw".to_string(),
+            topics: vec!["sensors/#".to_string()],
+            qos: 1,
+            username: Some("agent".to_string()),
+            password: Some("mqtt-password-secret".to_string()),
+            use_tls: true,
+            keep_alive_secs: 30,
+            response_topic: None,
+        });
         cfg.model_routes = vec![crate::config::schema::ModelRouteConfig {
             hint: "reasoning".to_string(),
             provider: "openrouter".to_string(),
             model: "anthropic/claude-sonnet-4.6".to_string(),
             api_key: Some("route-model-key".to_string()),
+            max_context_tokens: None,
+            vision: None,
         }];
         cfg.embedding_routes = vec![crate::config::schema::EmbeddingRouteConfig {
             hint: "semantic".to_string(),
@@ -1068,6 +1305,8 @@ mod tests {
             Some(MASKED_SECRET)
         );
         assert_eq!(parsed.memory.qdrant.api_key.as_deref(), Some(MASKED_SECRET));
+        assert_eq!(parsed.transcription.api_key.as_deref(), Some(MASKED_SECRET));
+        assert_eq!(parsed.tts.api_key.as_deref(), Some(MASKED_SECRET));
         assert_eq!(
             parsed
                 .channels_config
@@ -1114,6 +1353,14 @@ mod tests {
                 .map(|v| v.password.as_str()),
             Some(MASKED_SECRET)
         );
+        assert_eq!(
+            parsed
+                .channels_config
+                .mqtt
+                .as_ref()
+                .and_then(|v| v.password.as_deref()),
+            Some(MASKED_SECRET)
+        );
     }
 
     #[test]
@@ -1132,6 +1379,8 @@ mod tests {
             domain: None,
         });
         current.memory.qdrant.api_key = Some("qdrant-real".to_string());
+        current.transcription.api_key = Some("transcription-real".to_string());
+        current.tts.api_key = Some("tts-real".to_string());
         current.channels_config.wati = Some(crate::config::schema::WatiConfig {
             api_token: "wati-real".to_string(),
             api_url: "https://live-mt-server.wati.io".to_string(),
@@ -1160,18 +1409,33 @@ mod tests {
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
         });
+        current.channels_config.mqtt = Some(crate::config::schema::MqttConfig {
+            broker_url: "mqtts://broker.example.com:8883".to_string(),
+            client_id: "zeroclaw".to_string(),
+            topics: vec!["sensors/#".to_string()],
+            qos: 1,
+            username: Some("agent".to_string()),
+            password: Some("mqtt-password-real".to_string()),
+            use_tls: true,
+            keep_alive_secs: 30,
+            response_topic: None,
+        });
         current.model_routes = vec![
             crate::config::schema::ModelRouteConfig {
                 hint: "reasoning".to_string(),
                 provider: "openrouter".to_string(),
                 model: "anthropic/claude-sonnet-4.6".to_string(),
                 api_key: Some("route-model-key-1".to_string()),
+                max_context_tokens: None,
+                vision: None,
             },
             crate::config::schema::ModelRouteConfig {
                 hint: "fast".to_string(),
                 provider: "openrouter".to_string(),
                 model: "openai/gpt-4.1-mini".to_string(),
                 api_key: Some("route-model-key-2".to_string()),
+                max_context_tokens: None,
+                vision: None,
             },
         ];
         current.embedding_routes = vec![
@@ -1251,6 +1515,11 @@ mod tests {
             hydrated.memory.qdrant.api_key.as_deref(),
             Some("qdrant-real")
         );
+        assert_eq!(
+            hydrated.transcription.api_key.as_deref(),
+            Some("transcription-real")
+        );
+        assert_eq!(hydrated.tts.api_key.as_deref(), Some("tts-real"));
         assert_eq!(
             hydrated
                 .channels_config
@@ -1307,6 +1576,14 @@ mod tests {
                 .map(|v| v.password.as_str()),
             Some("email-password-real")
         );
+        assert_eq!(
+            hydrated
+                .channels_config
+                .mqtt
+                .as_ref()
+                .and_then(|v| v.password.as_deref()),
+            Some("mqtt-password-real")
+        );
     }
 
     #[test]
@@ -1318,12 +1595,16 @@ mod tests {
                 provider: "openrouter".to_string(),
                 model: "anthropic/claude-sonnet-4.6".to_string(),
                 api_key: Some("route-model-key-1".to_string()),
+                max_context_tokens: None,
+                vision: None,
             },
             crate::config::schema::ModelRouteConfig {
                 hint: "fast".to_string(),
                 provider: "openrouter".to_string(),
                 model: "openai/gpt-4.1-mini".to_string(),
                 api_key: Some("route-model-key-2".to_string()),
+                max_context_tokens: None,
+                vision: None,
             },
         ];
         current.embedding_routes = vec![
@@ -1353,6 +1634,8 @@ mod tests {
                 provider: "openai".to_string(),
                 model: "gpt-4.1".to_string(),
                 api_key: Some(MASKED_SECRET.to_string()),
+                max_context_tokens: None,
+                vision: None,
             });
         incoming
             .embedding_routes
@@ -18,12 +18,14 @@ use crate::channels::{
 use crate::config::Config;
 use crate::cost::CostTracker;
 use crate::memory::{self, Memory, MemoryCategory};
-use crate::providers::{self, ChatMessage, Provider};
+use crate::providers::{self, ChatMessage, ChatRequest, Provider};
 use crate::runtime;
-use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
-use crate::security::SecurityPolicy;
+use crate::security::pairing::{
+    constant_time_eq, is_public_bind, PairingGuard, TokenAuthorization,
+};
+use crate::security::{OutboundSurface, SecurityPolicy};
 use crate::tools;
-use crate::tools::traits::ToolSpec;
+use crate::tools::traits::{Tool, ToolSpec};
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use axum::{
@@ -35,7 +37,8 @@ use axum::{
     Router,
 };
 use parking_lot::Mutex;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -53,6 +56,8 @@ pub const RATE_LIMIT_WINDOW_SECS: u64 = 60;
 pub const RATE_LIMIT_MAX_KEYS_DEFAULT: usize = 10_000;
 /// Fallback max distinct idempotency keys retained in gateway memory.
 pub const IDEMPOTENCY_MAX_KEYS_DEFAULT: usize = 10_000;
+/// How often API tokens are reconciled with `config.toml` and `last_used_at` flushed.
+const API_TOKEN_SYNC_INTERVAL_SECS: u64 = 60;
 
 fn webhook_memory_key() -> String {
     format!("webhook_msg_{}", Uuid::new_v4())
@@ -306,6 +311,8 @@ pub struct AppState {
     pub observer: Arc<dyn crate::observability::Observer>,
     /// Registered tool specs (for web dashboard tools page)
     pub tools_registry: Arc<Vec<ToolSpec>>,
+    /// Executable tools backing the `/ws/chat` agent loop
+    pub tools: Arc<Vec<Box<dyn Tool>>>,
     /// Cost tracker (optional, for web dashboard cost page)
     pub cost_tracker: Option<Arc<CostTracker>>,
     /// SSE broadcast channel for real-time events
@@ -328,7 +335,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     // ── Hooks ──────────────────────────────────────────────────────
     let hooks: Option<std::sync::Arc<crate::hooks::HookRunner>> = if config.hooks.enabled {
-        Some(std::sync::Arc::new(crate::hooks::HookRunner::new()))
+        Some(std::sync::Arc::new(crate::hooks::HookRunner::from_config(
+            &config.hooks,
+        )))
     } else {
         None
     };
@@ -338,7 +347,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let actual_port = listener.local_addr()?.port();
     let display_addr = format!("{host}:{actual_port}");
 
-    let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
+    let base_provider = providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
         config.api_key.as_deref(),
         config.api_url.as_deref(),
@@ -350,7 +359,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             secrets_encrypt: config.secrets.encrypt,
             reasoning_enabled: config.runtime.reasoning_enabled,
         },
-    )?);
+    )?;
     let model = config
         .default_model
         .clone()
@@ -395,19 +404,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     );
     let tools_registry: Arc<Vec<ToolSpec>> =
         Arc::new(tools_registry_raw.iter().map(|t| t.spec()).collect());
+    let tools = Arc::new(tools_registry_raw);
 
     // Cost tracker (optional)
-    let cost_tracker = if config.cost.enabled {
-        match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
-            Ok(ct) => Some(Arc::new(ct)),
-            Err(e) => {
-                tracing::warn!("Failed to initialize cost tracker: {e}");
-                None
-            }
-        }
-    } else {
-        None
-    };
+    let cost_tracker = crate::cost::budget::shared_tracker(&config.cost, &config.workspace_dir);
 
     // SSE broadcast channel for real-time events
     let (event_tx, _event_rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
@@ -529,10 +529,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             .map(Arc::from);
 
     // ── Pairing guard ──────────────────────────────────────
-    let pairing = Arc::new(PairingGuard::new(
-        config.gateway.require_pairing,
-        &config.gateway.paired_tokens,
-    ));
+    let pairing = Arc::new(
+        PairingGuard::new(
+            config.gateway.require_pairing,
+            &config.gateway.paired_tokens,
+        )
+        .with_api_tokens(&config.gateway.api_tokens),
+    );
     let rate_limit_max_keys = normalize_max_keys(
         config.gateway.rate_limit_max_keys,
         RATE_LIMIT_MAX_KEYS_DEFAULT,
@@ -576,6 +579,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     println!("  🌐 Web Dashboard: http://{display_addr}/");
     println!("  POST /pair      — pair a new client (X-Pairing-Code header)");
     println!("  POST /webhook   — {{\"message\": \"your prompt\"}}");
+    if config.sop.enabled {
+        println!("  POST /sop/*     — SOP webhook triggers");
+    }
     if whatsapp_channel.is_some() {
         println!("  GET  /whatsapp  — Meta webhook verification");
         println!("  POST /whatsapp  — WhatsApp message webhook");
@@ -621,6 +627,39 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             crate::observability::create_observer(&config.observability),
             event_tx.clone(),
         ));
+    let provider: Arc<dyn Provider> = Arc::from(providers::with_recording(
+        providers::with_response_cache(
+            base_provider,
+            &config.memory,
+            &config.workspace_dir,
+            Some(Arc::clone(&broadcast_observer)),
+        ),
+        &config.observability,
+        &config.workspace_dir,
+    ));
+
+    // Approval prompts from channels and WebSocket sessions show up on SSE.
+    tokio::spawn(sse::forward_approval_events(
+        crate::approval::ApprovalBroker::global().subscribe(),
+        event_tx.clone(),
+    ));
+
+    if pairing.require_pairing() {
+        let config = Arc::clone(&config_state);
+        let pairing = Arc::clone(&pairing);
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(API_TOKEN_SYNC_INTERVAL_SECS));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = sync_api_tokens(Arc::clone(&config), &pairing).await {
+                    tracing::warn!("API token sync failed: {e:#}");
+                }
+            }
+        });
+    }
 
     let state = AppState {
         config: config_state,
@@ -643,6 +682,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         wati: wati_channel,
         observer: broadcast_observer,
         tools_registry,
+        tools,
         cost_tracker,
         event_tx,
     };
@@ -659,6 +699,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/metrics", get(handle_metrics))
         .route("/pair", post(handle_pair))
         .route("/webhook", post(handle_webhook))
+        .route("/sop/{*path}", post(handle_sop_webhook))
         .route("/whatsapp", get(handle_whatsapp_verify))
         .route("/whatsapp", post(handle_whatsapp_message))
         .route("/linq", post(handle_linq_webhook))
@@ -681,6 +722,15 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/memory", post(api::handle_api_memory_store))
         .route("/api/memory/{key}", delete(api::handle_api_memory_delete))
         .route("/api/cost", get(api::handle_api_cost))
+        .route(
+            "/api/tokens",
+            get(api::handle_api_tokens_list).post(api::handle_api_tokens_create),
+        )
+        .route("/api/tokens/{id}", delete(api::handle_api_tokens_delete))
+        .route(
+            "/api/approvals",
+            get(api::handle_api_approvals_list).post(api::handle_api_approvals_decide),
+        )
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
         // ── SSE event stream ──
@@ -748,6 +798,21 @@ async fn handle_metrics(State(state): State<AppState>) -> impl IntoResponse {
     )
 }
 
+/// Record a gateway authentication outcome in the audit log.
+pub(crate) fn audit_auth(success: bool, client: Option<&str>, action: &str) {
+    use crate::security::audit::{record, AuditEvent, AuditEventType};
+    let event_type = if success {
+        AuditEventType::AuthSuccess
+    } else {
+        AuditEventType::AuthFailure
+    };
+    record(
+        AuditEvent::new(event_type)
+            .with_actor("gateway".into(), client.map(str::to_string), None)
+            .with_action(action.into(), "auth".into(), false, success),
+    );
+}
+
 /// POST /pair — exchange one-time code for bearer token
 #[axum::debug_handler]
 async fn handle_pair(
@@ -774,6 +839,7 @@ async fn handle_pair(
     match state.pairing.try_pair(code, &rate_key).await {
         Ok(Some(token)) => {
             tracing::info!("🔐 New client paired successfully");
+            audit_auth(true, Some(&rate_key), "POST /pair");
             if let Err(err) = persist_pairing_tokens(state.config.clone(), &state.pairing).await {
                 tracing::error!("🔐 Pairing succeeded but token persistence failed: {err:#}");
                 let body = serde_json::json!({
@@ -795,10 +861,12 @@ async fn handle_pair(
         }
         Ok(None) => {
             tracing::warn!("🔐 Pairing attempt with invalid code");
+            audit_auth(false, Some(&rate_key), "POST /pair: invalid code");
             let err = serde_json::json!({"error": "Invalid pairing code"});
             (StatusCode::FORBIDDEN, Json(err))
         }
         Err(lockout_secs) => {
+            audit_auth(false, Some(&rate_key), "POST /pair: locked out");
             tracing::warn!(
                 "🔐 Pairing locked out — too many failed attempts ({lockout_secs}s remaining)"
             );
@@ -811,6 +879,55 @@ async fn handle_pair(
     }
 }
 
+/// Write the guard's named API tokens back to `config.toml`.
+async fn persist_api_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGuard) -> Result<()> {
+    let mut updated_cfg = { config.lock().clone() };
+    updated_cfg.gateway.api_tokens = pairing.api_tokens();
+    updated_cfg
+        .save()
+        .await
+        .context("Failed to persist API tokens to config.toml")?;
+    *config.lock() = updated_cfg;
+    Ok(())
+}
+
+/// Minimal view of `config.toml` used to pick up token changes made by the CLI.
+#[derive(serde::Deserialize, Default)]
+struct PersistedGatewayTokens {
+    #[serde(default)]
+    gateway: PersistedGatewaySection,
+}
+
+#[derive(serde::Deserialize, Default)]
+struct PersistedGatewaySection {
+    #[serde(default)]
+    api_tokens: Vec<crate::config::GatewayApiToken>,
+}
+
+/// Reconcile API tokens with `config.toml` (CLI creates/revokes) and flush
+/// pending `last_used_at` updates.
+async fn sync_api_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGuard) -> Result<()> {
+    let config_path = config.lock().config_path.clone();
+    let raw = tokio::fs::read_to_string(&config_path)
+        .await
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    let persisted: PersistedGatewayTokens =
+        toml::from_str(&raw).context("Failed to parse gateway.api_tokens")?;
+    let disk = persisted.gateway.api_tokens;
+
+    let disk_ids: HashSet<&str> = disk.iter().map(|t| t.id.as_str()).collect();
+    let live = pairing.api_tokens();
+    let changed_on_disk =
+        live.len() != disk.len() || live.iter().any(|t| !disk_ids.contains(t.id.as_str()));
+    if changed_on_disk {
+        pairing.reload_api_tokens(disk);
+    }
+    if pairing.take_api_tokens_dirty() || changed_on_disk {
+        persist_api_tokens(config, pairing).await?;
+    }
+    Ok(())
+}
+
 async fn persist_pairing_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGuard) -> Result<()> {
     let paired_tokens = pairing.tokens();
     // This is needed because parking_lot's guard is not Send so we clone the inner
@@ -853,10 +970,38 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
     let prepared =
         crate::multimodal::prepare_messages_for_provider(&messages, &multimodal_config).await?;
 
-    state
+    let provider_name = state
+        .config
+        .lock()
+        .default_provider
+        .clone()
+        .unwrap_or_default();
+    if let Some(notice) = crate::cost::budget::precheck(
+        &provider_name,
+        &state.model,
+        crate::cost::budget::estimate_input_tokens(&prepared.messages),
+    )? {
+        tracing::warn!("{notice}");
+    }
+
+    let response = state
         .provider
-        .chat_with_history(&prepared.messages, &state.model, state.temperature)
-        .await
+        .chat(
+            ChatRequest {
+                messages: &prepared.messages,
+                tools: None,
+            },
+            &state.model,
+            state.temperature,
+        )
+        .await?;
+    crate::cost::budget::record(
+        "gateway",
+        &provider_name,
+        &state.model,
+        response.usage.as_ref(),
+    );
+    Ok(response.text_or_empty().to_string())
 }
 
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
@@ -865,28 +1010,22 @@ async fn run_gateway_chat_with_tools(state: &AppState, message: &str) -> anyhow:
     crate::agent::process_message(config, message).await
 }
 
-/// Webhook request body
-#[derive(serde::Deserialize)]
-pub struct WebhookBody {
-    pub message: String,
-}
-
-/// POST /webhook — main webhook endpoint
-async fn handle_webhook(
-    State(state): State<AppState>,
-    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
-    body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
-) -> impl IntoResponse {
-    let rate_key =
-        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+/// Shared auth for webhook-style routes: rate limit, pairing bearer token and
+/// the optional `X-Webhook-Secret`.
+fn authorize_webhook_request(
+    state: &AppState,
+    peer_addr: SocketAddr,
+    headers: &HeaderMap,
+    route: &str,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let rate_key = client_key_from_request(Some(peer_addr), headers, state.trust_forwarded_headers);
     if !state.rate_limiter.allow_webhook(&rate_key) {
-        tracing::warn!("/webhook rate limit exceeded");
+        tracing::warn!("{route} rate limit exceeded");
         let err = serde_json::json!({
             "error": "Too many webhook requests. Please retry later.",
             "retry_after": RATE_LIMIT_WINDOW_SECS,
         });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+        return Err((StatusCode::TOO_MANY_REQUESTS, Json(err)));
     }
 
     // ── Bearer token auth (pairing) ──
@@ -896,12 +1035,32 @@ async fn handle_webhook(
             .and_then(|v| v.to_str().ok())
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
+        match state.pairing.authorize(token, "chat") {
+            TokenAuthorization::Granted => {}
+            TokenAuthorization::Forbidden => {
+                tracing::warn!("{route}: rejected — token lacks the 'chat' scope");
+                audit_auth(
+                    false,
+                    Some(&rate_key),
+                    &format!("{route}: missing 'chat' scope"),
+                );
+                let err = serde_json::json!({
+                    "error": "Forbidden — token lacks the 'chat' scope"
+                });
+                return Err((StatusCode::FORBIDDEN, Json(err)));
+            }
+            TokenAuthorization::Unauthenticated => {
+                tracing::warn!("{route}: rejected — not paired / invalid bearer token");
+                audit_auth(
+                    false,
+                    Some(&rate_key),
+                    &format!("{route}: invalid bearer token"),
+                );
+                let err = serde_json::json!({
+                    "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
+                });
+                return Err((StatusCode::UNAUTHORIZED, Json(err)));
+            }
         }
     }
 
@@ -916,13 +1075,100 @@ async fn handle_webhook(
         match header_hash {
             Some(val) if constant_time_eq(&val, secret_hash.as_ref()) => {}
             _ => {
-                tracing::warn!("Webhook: rejected request — invalid or missing X-Webhook-Secret");
+                tracing::warn!("{route}: rejected request — invalid or missing X-Webhook-Secret");
+                audit_auth(
+                    false,
+                    Some(&rate_key),
+                    &format!("{route}: invalid webhook secret"),
+                );
                 let err = serde_json::json!({"error": "Unauthorized — invalid or missing X-Webhook-Secret header"});
-                return (StatusCode::UNAUTHORIZED, Json(err));
+                return Err((StatusCode::UNAUTHORIZED, Json(err)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// POST /sop/{*path} — fire SOP webhook triggers whose `path` matches `/sop/<path>`.
+///
+/// The raw request body becomes the trigger payload. Started runs execute in
+/// background agent turns; the response lists what was started or skipped.
+async fn handle_sop_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    axum::extract::Path(path): axum::extract::Path<String>,
+    body: Bytes,
+) -> impl IntoResponse {
+    use crate::sop::dispatch::{dispatch_sop_event, execute_dispatch_results, DispatchResult};
+
+    if let Err(rejection) = authorize_webhook_request(&state, peer_addr, &headers, "/sop") {
+        return rejection;
+    }
+
+    let config = state.config.lock().clone();
+    if !config.sop.enabled {
+        let err = serde_json::json!({"error": "SOP engine is disabled ([sop] enabled = false)"});
+        return (StatusCode::NOT_FOUND, Json(err));
+    }
+
+    let payload = String::from_utf8_lossy(&body).trim().to_string();
+    let event = crate::sop::SopEvent {
+        source: crate::sop::SopTriggerSource::Webhook,
+        topic: Some(format!("/sop/{}", path.trim_start_matches('/'))),
+        payload: (!payload.is_empty()).then_some(payload),
+        timestamp: crate::sop::engine::now_iso8601(),
+    };
+
+    let sop = crate::sop::shared(&config);
+    let audit = Arc::new(crate::sop::SopAuditLogger::new(Arc::clone(&state.mem)));
+    let results = dispatch_sop_event(&sop.engine, &audit, event).await;
+
+    let mut started = Vec::new();
+    let mut skipped = Vec::new();
+    for result in &results {
+        match result {
+            DispatchResult::Started {
+                run_id, sop_name, ..
+            } => started.push(serde_json::json!({"run_id": run_id, "sop": sop_name})),
+            DispatchResult::Skipped { sop_name, reason } => {
+                skipped.push(serde_json::json!({"sop": sop_name, "reason": reason}));
             }
+            DispatchResult::NoMatch => {}
         }
     }
 
+    execute_dispatch_results(&config, &audit, results);
+
+    let status = if started.is_empty() && skipped.is_empty() {
+        StatusCode::NOT_FOUND
+    } else {
+        StatusCode::ACCEPTED
+    };
+    (
+        status,
+        Json(serde_json::json!({"started": started, "skipped": skipped})),
+    )
+}
+
+/// Webhook request body
+#[derive(serde::Deserialize)]
+pub struct WebhookBody {
+    pub message: String,
+}
+
+/// POST /webhook — main webhook endpoint
+async fn handle_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Err(rejection) = authorize_webhook_request(&state, peer_addr, &headers, "/webhook") {
+        return rejection;
+    }
+
     // ── Parse body ──
     let Json(webhook_body) = match body {
         Ok(b) => b,
@@ -1013,6 +1259,7 @@ async fn handle_webhook(
                     cost_usd: None,
                 });
 
+            let response = screen_outbound(&state, OutboundSurface::Gateway, response);
             let body = serde_json::json!({"response": response, "model": state.model});
             (StatusCode::OK, Json(body))
         }
@@ -1057,6 +1304,15 @@ async fn handle_webhook(
     }
 }
 
+/// `text` after the outbound leak guard: redacted, or a notice when blocked.
+fn screen_outbound(state: &AppState, surface: OutboundSurface, text: String) -> String {
+    match crate::security::outbound::screen(surface, &text, state.observer.as_ref()) {
+        Ok(Cow::Borrowed(_)) => text,
+        Ok(Cow::Owned(redacted)) => redacted,
+        Err(blocked) => crate::channels::leak_guard::withheld_notice(&blocked),
+    }
+}
+
 /// `WhatsApp` verification query params
 #[derive(serde::Deserialize)]
 pub struct WhatsAppVerifyQuery {
@@ -1166,15 +1422,40 @@ async fn handle_whatsapp_message(
     };
 
     // Parse messages from the webhook payload
-    let messages = wa.parse_webhook_payload(&payload);
+    let mut messages = wa.parse_webhook_payload(&payload);
 
     if messages.is_empty() {
         // Acknowledge the webhook even if no messages (could be status updates)
         return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
     }
 
+    // Turn voice notes into text before the agent sees them
+    let transcription = state.config.lock().transcription.clone();
+    if transcription.enabled {
+        match crate::channels::transcription::create_transcriber(&transcription) {
+            Ok(transcriber) => {
+                for msg in &mut messages {
+                    crate::channels::transcription::transcribe_voice_attachments(
+                        wa.as_ref(),
+                        transcriber.as_ref(),
+                        msg,
+                    )
+                    .await;
+                }
+            }
+            Err(e) => tracing::warn!("WhatsApp voice transcription unavailable: {e}"),
+        }
+    }
+
     // Process each message
     for msg in &messages {
+        if msg.content.trim().is_empty() {
+            tracing::debug!(
+                "WhatsApp: skipping untranscribed voice note from {}",
+                msg.sender
+            );
+            continue;
+        }
         tracing::info!(
             "WhatsApp message from {}: {}",
             msg.sender,
@@ -1194,7 +1475,10 @@ async fn handle_whatsapp_message(
             Ok(response) => {
                 // Send reply via WhatsApp
                 if let Err(e) = wa
-                    .send(&SendMessage::new(response, &msg.reply_target))
+                    .send(&SendMessage::new(
+                        screen_outbound(&state, OutboundSurface::Channel, response),
+                        &msg.reply_target,
+                    ))
                     .await
                 {
                     tracing::error!("Failed to send WhatsApp reply: {e}");
@@ -1302,7 +1586,10 @@ async fn handle_linq_webhook(
             Ok(response) => {
                 // Send reply via Linq
                 if let Err(e) = linq
-                    .send(&SendMessage::new(response, &msg.reply_target))
+                    .send(&SendMessage::new(
+                        screen_outbound(&state, OutboundSurface::Channel, response),
+                        &msg.reply_target,
+                    ))
                     .await
                 {
                     tracing::error!("Failed to send Linq reply: {e}");
@@ -1394,7 +1681,10 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
             Ok(response) => {
                 // Send reply via WATI
                 if let Err(e) = wati
-                    .send(&SendMessage::new(response, &msg.reply_target))
+                    .send(&SendMessage::new(
+                        screen_outbound(&state, OutboundSurface::Channel, response),
+                        &msg.reply_target,
+                    ))
                     .await
                 {
                     tracing::error!("Failed to send WATI reply: {e}");
@@ -1497,7 +1787,10 @@ async fn handle_nextcloud_talk_webhook(
         match run_gateway_chat_with_tools(&state, &msg.content).await {
             Ok(response) => {
                 if let Err(e) = nextcloud_talk
-                    .send(&SendMessage::new(response, &msg.reply_target))
+                    .send(&SendMessage::new(
+                        screen_outbound(&state, OutboundSurface::Channel, response),
+                        &msg.reply_target,
+                    ))
                     .await
                 {
                     tracing::error!("Failed to send Nextcloud Talk reply: {e}");
@@ -1598,6 +1891,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -1647,6 +1941,7 @@ mod tests {
             wati: None,
             observer,
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -1827,6 +2122,239 @@ mod tests {
         assert_eq!(&in_memory.gateway.paired_tokens[0], persisted);
     }
 
+    #[tokio::test]
+    async fn scoped_api_tokens_gate_routes_and_can_be_revoked() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.config_path = temp.path().join("config.toml");
+        config.workspace_dir = temp.path().join("workspace");
+        config.save().await.unwrap();
+
+        let (admin, admin_record) =
+            crate::security::pairing::mint_api_token("admin", &["*".into()], None).unwrap();
+        let (reader, reader_record) =
+            crate::security::pairing::mint_api_token("dashboard", &["cost:read".into()], None)
+                .unwrap();
+        let pairing = Arc::new(
+            PairingGuard::new(true, &[]).with_api_tokens(&[admin_record, reader_record.clone()]),
+        );
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(config.clone())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing,
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+        };
+        let bearer = |token: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::AUTHORIZATION,
+                format!("Bearer {token}").parse().unwrap(),
+            );
+            headers
+        };
+
+        let ok = api::handle_api_cost(State(state.clone()), bearer(&reader))
+            .await
+            .into_response();
+        assert_eq!(ok.status(), StatusCode::OK);
+        let forbidden = api::handle_api_memory_list(
+            State(state.clone()),
+            bearer(&reader),
+            Query(api::MemoryQuery {
+                query: None,
+                category: None,
+            }),
+        )
+        .await
+        .into_response();
+        assert_eq!(forbidden.status(), StatusCode::FORBIDDEN);
+        let unknown = api::handle_api_cost(State(state.clone()), bearer("zc_nope"))
+            .await
+            .into_response();
+        assert_eq!(unknown.status(), StatusCode::UNAUTHORIZED);
+        assert!(state.pairing.api_tokens()[1].last_used_at.is_some());
+
+        let revoked = api::handle_api_tokens_delete(
+            State(state.clone()),
+            bearer(&admin),
+            axum::extract::Path(reader_record.id.clone()),
+        )
+        .await
+        .into_response();
+        assert_eq!(revoked.status(), StatusCode::OK);
+        let after = api::handle_api_cost(State(state.clone()), bearer(&reader))
+            .await
+            .into_response();
+        assert_eq!(after.status(), StatusCode::UNAUTHORIZED);
+
+        let saved = tokio::fs::read_to_string(&config.config_path)
+            .await
+            .unwrap();
+        let parsed: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(parsed.gateway.api_tokens.len(), 1);
+        assert_eq!(parsed.gateway.api_tokens[0].name, "admin");
+        assert!(
+            !saved.contains(&admin),
+            "plaintext token must never be persisted"
+        );
+    }
+
+    #[tokio::test]
+    async fn approvals_api_lists_and_resolves_with_approver_identity() {
+        let (token, record) =
+            crate::security::pairing::mint_api_token("ops", &["approvals:write".into()], None)
+                .unwrap();
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(true, &[]).with_api_tokens(&[record])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {token}").parse().unwrap(),
+        );
+
+        let broker = crate::approval::ApprovalBroker::global();
+        let (pending, rx) = broker.register(
+            &crate::approval::ApprovalRequest {
+                tool_name: "shell".into(),
+                arguments: serde_json::json!({"command": "ls"}),
+            },
+            crate::approval::ApprovalOrigin {
+                channel: "telegram".into(),
+                conversation: uuid::Uuid::new_v4().to_string(),
+                requested_by: Some("alice".into()),
+            },
+            Duration::from_secs(60),
+        );
+
+        let response = api::handle_api_approvals_list(State(state.clone()), headers.clone())
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let listed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(listed["approvals"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|a| a["id"] == pending.id.as_str() && a["tool_name"] == "shell"));
+
+        let decide = |id: String| {
+            api::handle_api_approvals_decide(
+                State(state.clone()),
+                headers.clone(),
+                Json(api::ApprovalDecisionBody {
+                    id,
+                    decision: crate::approval::ApprovalResponse::Yes,
+                }),
+            )
+        };
+        let response = decide(pending.id.clone()).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let decision = rx.await.unwrap();
+        assert_eq!(decision.response, crate::approval::ApprovalResponse::Yes);
+        assert_eq!(decision.approver.as_deref(), Some("gateway:token:ops"));
+
+        let again = decide(pending.id.clone()).await.into_response();
+        assert_eq!(again.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn approval_events_are_relayed_to_sse() {
+        let broker = crate::approval::ApprovalBroker::new();
+        let (event_tx, mut events) = tokio::sync::broadcast::channel(16);
+        let relay = tokio::spawn(sse::forward_approval_events(broker.subscribe(), event_tx));
+
+        let (pending, _rx) = broker.register(
+            &crate::approval::ApprovalRequest {
+                tool_name: "file_write".into(),
+                arguments: serde_json::json!({}),
+            },
+            crate::approval::ApprovalOrigin::default(),
+            Duration::from_secs(60),
+        );
+        broker.resolve(&pending.id, crate::approval::ApprovalResponse::No, "tester");
+
+        let requested = events.recv().await.unwrap();
+        assert_eq!(requested["type"], "approval_requested");
+        assert_eq!(requested["approval"]["id"], pending.id.as_str());
+        let resolved = events.recv().await.unwrap();
+        assert_eq!(resolved["type"], "approval_resolved");
+        assert_eq!(resolved["decision"], "no");
+        assert_eq!(resolved["approver"], "tester");
+        relay.abort();
+    }
+
+    #[tokio::test]
+    async fn sync_api_tokens_picks_up_cli_revocations() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.config_path = temp.path().join("config.toml");
+        config.workspace_dir = temp.path().join("workspace");
+        let (token, record) =
+            crate::security::pairing::mint_api_token("ci", &["status:read".into()], None).unwrap();
+        config.gateway.api_tokens = vec![record];
+        config.save().await.unwrap();
+
+        let guard = PairingGuard::new(true, &[]).with_api_tokens(&config.gateway.api_tokens);
+        assert!(guard.authorize(&token, "status:read").is_granted());
+
+        // Simulate `zeroclaw gateway tokens revoke` editing config.toml.
+        let mut on_disk = config.clone();
+        on_disk.gateway.api_tokens.clear();
+        on_disk.save().await.unwrap();
+
+        sync_api_tokens(Arc::new(Mutex::new(config)), &guard)
+            .await
+            .unwrap();
+        assert_eq!(
+            guard.authorize(&token, "status:read"),
+            TokenAuthorization::Unauthenticated
+        );
+    }
+
     #[test]
     fn webhook_memory_key_is_unique() {
         let key1 = webhook_memory_key();
@@ -1847,6 +2375,7 @@ mod tests {
             channel: "whatsapp".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         let key = whatsapp_memory_key(&msg);
@@ -2013,6 +2542,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -2077,6 +2607,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -2153,6 +2684,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -2201,6 +2733,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -2254,6 +2787,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -2312,6 +2846,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -2366,6 +2901,7 @@ mod tests {
             wati: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
+            tools: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
@@ -3,6 +3,7 @@
 //! Wraps the broadcast channel in AppState to deliver events to web dashboard clients.
 
 use super::AppState;
+use crate::security::pairing::TokenAuthorization;
 use axum::{
     extract::State,
     http::{header, HeaderMap, StatusCode},
@@ -28,12 +29,24 @@ pub async fn handle_sse_events(
             .and_then(|auth| auth.strip_prefix("Bearer "))
             .unwrap_or("");
 
-        if !state.pairing.is_authenticated(token) {
-            return (
-                StatusCode::UNAUTHORIZED,
-                "Unauthorized — provide Authorization: Bearer <token>",
-            )
-                .into_response();
+        match state.pairing.authorize(token, "events:read") {
+            TokenAuthorization::Granted => {}
+            TokenAuthorization::Forbidden => {
+                super::audit_auth(false, None, "GET /api/events: missing 'events:read' scope");
+                return (
+                    StatusCode::FORBIDDEN,
+                    "Forbidden — token lacks the 'events:read' scope",
+                )
+                    .into_response();
+            }
+            TokenAuthorization::Unauthenticated => {
+                super::audit_auth(false, None, "GET /api/events: invalid bearer token");
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    "Unauthorized — provide Authorization: Bearer <token>",
+                )
+                    .into_response();
+            }
         }
     }
 
@@ -57,6 +70,27 @@ pub async fn handle_sse_events(
         .into_response()
 }
 
+/// Relay approval requests and decisions onto the SSE broadcast channel.
+pub async fn forward_approval_events(
+    mut rx: tokio::sync::broadcast::Receiver<crate::approval::ApprovalEvent>,
+    tx: tokio::sync::broadcast::Sender<serde_json::Value>,
+) {
+    use tokio::sync::broadcast::error::RecvError;
+    loop {
+        match rx.recv().await {
+            Ok(event) => {
+                if let Ok(value) = serde_json::to_value(&event) {
+                    let _ = tx.send(value);
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::debug!("SSE approval relay skipped {skipped} events");
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
 /// Broadcast observer that forwards events to the SSE broadcast channel.
 pub struct BroadcastObserver {
     inner: Box<dyn crate::observability::Observer>,
@@ -134,6 +168,17 @@ impl crate::observability::Observer for BroadcastObserver {
                 "cost_usd": cost_usd,
                 "timestamp": chrono::Utc::now().to_rfc3339(),
             }),
+            crate::observability::ObserverEvent::OutboundLeak {
+                surface,
+                action,
+                patterns,
+            } => serde_json::json!({
+                "type": "outbound_leak",
+                "surface": surface,
+                "action": action,
+                "patterns": patterns,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            }),
             _ => return, // Skip events we don't broadcast
         };
 
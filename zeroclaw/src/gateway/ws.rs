@@ -1,15 +1,46 @@
 //! WebSocket agent chat handler.
 //!
+//! Each connection keeps its own conversation history and drives the full
+//! agent tool loop, so tool calls, approvals and multi-turn context work the
+//! same way they do on channels.
+//!
 //! Protocol:
 //! ```text
 //! Client -> Server: {"type":"message","content":"Hello"}
-//! Server -> Client: {"type":"chunk","content":"Hi! "}
+//! Client -> Server: {"type":"cancel"}
+//! Client -> Server: {"type":"approval_response","id":"...","decision":"yes|no|always"}
 //! Server -> Client: {"type":"tool_call","name":"shell","args":{...}}
-//! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
+//! Server -> Client: {"type":"approval_request","id":"...","tool":"shell","arguments":{...}}
+//! Server -> Client: {"type":"tool_result","name":"shell","success":true,"output":"..."}
+//! Server -> Client: {"type":"chunk","content":"Hi! "}
 //! Server -> Client: {"type":"done","full_response":"..."}
+//! Server -> Client: {"type":"cancelled"}
+//! Server -> Client: {"type":"error","message":"..."}
 //! ```
+//!
+//! `chunk` frames are only sent when the provider supports streaming.
+//! Approval requests are also registered with the shared approval broker, so
+//! they show up at `GET /api/approvals` and can be answered there. Unanswered
+//! requests are denied after `[autonomy].approval_timeout_secs`, on `cancel`,
+//! and when the socket closes.
 
-use super::AppState;
+use super::{screen_outbound, AppState};
+use crate::agent::loop_::{
+    auto_compact_history, build_tool_instructions, is_tool_loop_cancelled, run_tool_call_loop,
+    scrub_credentials, trim_history, DRAFT_CLEAR_SENTINEL,
+};
+use crate::approval::{
+    ApprovalBroker, ApprovalDecision, ApprovalManager, ApprovalOrigin, ApprovalPrompter,
+    ApprovalRequest, ApprovalResponse,
+};
+use crate::hooks::{HookHandler, HookResult, HookRunner};
+use crate::observability::Observer;
+use crate::providers::{ChatMessage, Provider};
+use crate::security::pairing::TokenAuthorization;
+use crate::security::{OutboundGuard, OutboundSurface};
+use crate::sessions::{SessionStore, Transcript};
+use crate::tools::ToolResult;
+use async_trait::async_trait;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -19,6 +50,19 @@ use axum::{
 };
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
+use serde_json::Value;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Channel name reported to the agent loop, approval log and hooks.
+const WS_CHANNEL: &str = "ws";
+
+/// Outbound frame buffer per connection.
+const OUTBOUND_BUFFER: usize = 64;
 
 #[derive(Deserialize)]
 pub struct WsQuery {
@@ -34,134 +78,687 @@ pub async fn handle_ws_chat(
     // Auth via query param (browser WebSocket limitation)
     if state.pairing.require_pairing() {
         let token = params.token.as_deref().unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            return (
-                axum::http::StatusCode::UNAUTHORIZED,
-                "Unauthorized — provide ?token=<bearer_token>",
-            )
-                .into_response();
+        match state.pairing.authorize(token, "chat") {
+            TokenAuthorization::Granted => {}
+            TokenAuthorization::Forbidden => {
+                super::audit_auth(false, None, "GET /ws/chat: missing 'chat' scope");
+                return (
+                    axum::http::StatusCode::FORBIDDEN,
+                    "Forbidden — token lacks the 'chat' scope",
+                )
+                    .into_response();
+            }
+            TokenAuthorization::Unauthenticated => {
+                super::audit_auth(false, None, "GET /ws/chat: invalid bearer token");
+                return (
+                    axum::http::StatusCode::UNAUTHORIZED,
+                    "Unauthorized — provide ?token=<bearer_token>",
+                )
+                    .into_response();
+            }
         }
     }
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    let client = params
+        .token
+        .as_deref()
+        .and_then(|token| state.pairing.token_label(token))
+        .map_or_else(|| "ws:local".to_string(), |label| format!("ws:{label}"));
+    ws.on_upgrade(move |socket| handle_socket(socket, state, client))
         .into_response()
 }
 
-async fn handle_socket(socket: WebSocket, state: AppState) {
-    let (mut sender, mut receiver) = socket.split();
+// ── Approval over the socket ─────────────────────────────────────
 
-    while let Some(msg) = receiver.next().await {
-        let msg = match msg {
-            Ok(Message::Text(text)) => text,
-            Ok(Message::Close(_)) => break,
-            Err(_) => break,
-            _ => continue,
+/// This connection's view of the shared approval broker.
+#[derive(Clone)]
+struct WsApprovals {
+    broker: Arc<ApprovalBroker>,
+    session_id: String,
+    /// Approver identity recorded for answers sent over this socket.
+    client: String,
+}
+
+impl WsApprovals {
+    fn new(client: String) -> Self {
+        Self {
+            broker: ApprovalBroker::global(),
+            session_id: Uuid::new_v4().to_string(),
+            client,
+        }
+    }
+
+    /// Resolve a pending approval from an `approval_response` frame.
+    /// Only requests raised by this session can be answered here.
+    fn resolve(&self, frame: &Value) -> bool {
+        let Some(id) = frame["id"].as_str() else {
+            return false;
+        };
+        let owned = self.broker.get(id).is_some_and(|approval| {
+            approval.origin.channel == WS_CHANNEL && approval.origin.conversation == self.session_id
+        });
+        if !owned {
+            return false;
+        }
+        let decision = match frame["decision"].as_str().map(str::to_ascii_lowercase) {
+            Some(d) if d == "yes" || d == "y" => ApprovalResponse::Yes,
+            Some(d) if d == "always" || d == "a" => ApprovalResponse::Always,
+            _ => ApprovalResponse::No,
         };
+        self.broker.resolve(id, decision, &self.client).is_some()
+    }
 
-        // Parse incoming message
-        let parsed: serde_json::Value = match serde_json::from_str(&msg) {
-            Ok(v) => v,
-            Err(_) => {
-                let err = serde_json::json!({"type": "error", "message": "Invalid JSON"});
-                let _ = sender.send(Message::Text(err.to_string().into())).await;
-                continue;
-            }
+    /// Deny every outstanding approval (used on cancel and disconnect).
+    fn deny_all(&self) {
+        self.broker
+            .deny_conversation(WS_CHANNEL, &self.session_id, &self.client);
+    }
+}
+
+/// Sends `approval_request` frames and waits for the matching
+/// `approval_response` from the client (or an answer via `/api/approvals`).
+struct WsApprovalPrompter {
+    out: mpsc::Sender<Value>,
+    approvals: WsApprovals,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl ApprovalPrompter for WsApprovalPrompter {
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalDecision {
+        let origin = ApprovalOrigin {
+            channel: WS_CHANNEL.to_string(),
+            conversation: self.approvals.session_id.clone(),
+            requested_by: Some(self.approvals.client.clone()),
         };
+        let broker = &self.approvals.broker;
+        let (approval, rx) = broker.register(request, origin, self.timeout);
 
-        let msg_type = parsed["type"].as_str().unwrap_or("");
-        if msg_type != "message" {
-            continue;
+        let frame = serde_json::json!({
+            "type": "approval_request",
+            "id": approval.id,
+            "tool": request.tool_name,
+            "arguments": request.arguments,
+        });
+        if self.out.send(frame).await.is_err() {
+            broker.resolve(&approval.id, ApprovalResponse::No, &self.approvals.client);
+            return ApprovalDecision::automatic(ApprovalResponse::No);
         }
 
-        let content = parsed["content"].as_str().unwrap_or("").to_string();
-        if content.is_empty() {
+        // Timed out, cancelled or socket closed — never execute unanswered.
+        broker.wait(&approval, rx, self.timeout).await
+    }
+}
+
+// ── Tool progress frames ─────────────────────────────────────────
+
+/// Mirrors tool activity from the agent loop onto the socket.
+struct WsToolEventHook {
+    out: mpsc::Sender<Value>,
+}
+
+#[async_trait]
+impl HookHandler for WsToolEventHook {
+    fn name(&self) -> &str {
+        "ws-tool-events"
+    }
+
+    // Run last so the frame reflects arguments rewritten by other hooks.
+    fn priority(&self) -> i32 {
+        i32::MIN
+    }
+
+    async fn before_tool_call(&self, name: String, args: Value) -> HookResult<(String, Value)> {
+        let _ = self
+            .out
+            .send(serde_json::json!({
+                "type": "tool_call",
+                "name": name,
+                "args": args,
+            }))
+            .await;
+        HookResult::Continue((name, args))
+    }
+
+    async fn on_after_tool_call(&self, tool: &str, result: &ToolResult, _duration: Duration) {
+        let _ = self
+            .out
+            .send(serde_json::json!({
+                "type": "tool_result",
+                "name": tool,
+                "success": result.success,
+                "output": scrub_credentials(&result.output),
+            }))
+            .await;
+    }
+}
+
+/// Forward the final answer from the loop's delta stream as `chunk` frames.
+///
+/// Progress lines emitted before [`DRAFT_CLEAR_SENTINEL`] are dropped; the
+/// socket already receives structured `tool_call`/`tool_result` frames. With
+/// a leak guard, deltas are held back until a line completes so a secret split
+/// across deltas is screened as a whole.
+async fn forward_chunks(
+    mut rx: mpsc::Receiver<String>,
+    out: mpsc::Sender<Value>,
+    guard: Option<Arc<OutboundGuard>>,
+    observer: Arc<dyn Observer>,
+) {
+    let screen = |text: &str| match &guard {
+        Some(guard) => match guard.screen(OutboundSurface::Gateway, text, observer.as_ref()) {
+            Ok(text) => text.into_owned(),
+            Err(blocked) => crate::channels::leak_guard::withheld_notice(&blocked),
+        },
+        None => text.to_string(),
+    };
+    let mut streaming = false;
+    let mut pending = String::new();
+    while let Some(delta) = rx.recv().await {
+        if delta == DRAFT_CLEAR_SENTINEL {
+            streaming = true;
+            continue;
+        }
+        if !streaming {
             continue;
         }
+        let chunk = if guard.is_some() {
+            pending.push_str(&delta);
+            let Some(end) = pending.rfind('\n') else {
+                continue;
+            };
+            let lines: String = pending.drain(..=end).collect();
+            screen(&lines)
+        } else {
+            delta
+        };
+        if out
+            .send(serde_json::json!({"type": "chunk", "content": chunk}))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+    if !pending.is_empty() {
+        let _ = out
+            .send(serde_json::json!({"type": "chunk", "content": screen(&pending)}))
+            .await;
+    }
+}
+
+// ── Session ──────────────────────────────────────────────────────
+
+/// Per-connection agent state.
+struct WsSession {
+    state: AppState,
+    history: tokio::sync::Mutex<Vec<ChatMessage>>,
+    approval: ApprovalManager,
+    hooks: HookRunner,
+    out: mpsc::Sender<Value>,
+    sessions: Option<SessionStore>,
+    /// Started on the first turn so idle connections leave no transcript.
+    transcript: OnceLock<Option<Transcript>>,
+}
+
+impl WsSession {
+    fn new(state: AppState, out: mpsc::Sender<Value>, approvals: WsApprovals) -> Self {
+        let config = state.config.lock().clone();
+
+        let excluded = &config.autonomy.non_cli_excluded_tools;
+        let tool_descs: Vec<(&str, &str)> = state
+            .tools
+            .iter()
+            .filter(|tool| !excluded.iter().any(|ex| ex == tool.name()))
+            .map(|tool| (tool.name(), tool.description()))
+            .collect();
+        let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+        let bootstrap_max_chars = if config.agent.compact_context {
+            Some(6000)
+        } else {
+            None
+        };
+        let native_tools = state.provider.supports_native_tools();
+        let mut system_prompt = crate::channels::build_system_prompt_with_mode(
+            &config.workspace_dir,
+            &state.model,
+            &tool_descs,
+            &skills,
+            Some(&config.identity),
+            bootstrap_max_chars,
+            native_tools,
+            config.skills.prompt_injection_mode,
+        );
+        if !native_tools {
+            system_prompt.push_str(&build_tool_instructions(state.tools.as_ref()));
+        }
+
+        let approval = ApprovalManager::from_config(&config.autonomy).with_prompter(Arc::new(
+            WsApprovalPrompter {
+                out: out.clone(),
+                approvals,
+                timeout: Duration::from_secs(config.autonomy.approval_timeout_secs),
+            },
+        ));
+
+        let mut hooks = if config.hooks.enabled {
+            HookRunner::from_config(&config.hooks)
+        } else {
+            HookRunner::new()
+        };
+        hooks.register(Box::new(WsToolEventHook { out: out.clone() }));
+
+        Self {
+            state,
+            history: tokio::sync::Mutex::new(vec![ChatMessage::system(system_prompt)]),
+            approval,
+            hooks,
+            out,
+            sessions: SessionStore::from_config(&config.sessions, &config.workspace_dir),
+            transcript: OnceLock::new(),
+        }
+    }
+
+    async fn send(&self, frame: Value) {
+        let _ = self.out.send(frame).await;
+    }
+}
+
+/// Run one user turn through the agent loop and report the outcome.
+async fn run_turn(session: Arc<WsSession>, content: String, cancel: CancellationToken) {
+    let state = &session.state;
+    let (provider_label, multimodal_config, max_tool_iterations, max_history, excluded_tools) = {
+        let config = state.config.lock();
+        (
+            config
+                .default_provider
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            config.multimodal.clone(),
+            config.agent.max_tool_iterations,
+            config.agent.max_history_messages,
+            config.autonomy.non_cli_excluded_tools.clone(),
+        )
+    };
 
-        // Process message with the LLM provider
-        let provider_label = state
-            .config
-            .lock()
-            .default_provider
-            .clone()
-            .unwrap_or_else(|| "unknown".to_string());
-
-        // Broadcast agent_start event
-        let _ = state.event_tx.send(serde_json::json!({
-            "type": "agent_start",
-            "provider": provider_label,
-            "model": state.model,
-        }));
-
-        // Simple single-turn chat (no streaming for now — use provider.chat_with_system)
-        let system_prompt = {
-            let config_guard = state.config.lock();
-            crate::channels::build_system_prompt(
-                &config_guard.workspace_dir,
+    // Broadcast agent_start event
+    let _ = state.event_tx.send(serde_json::json!({
+        "type": "agent_start",
+        "provider": provider_label,
+        "model": state.model,
+    }));
+
+    let mut history = session.history.lock().await;
+    let checkpoint = history.len();
+    history.push(ChatMessage::user(&content));
+    let transcript = session
+        .transcript
+        .get_or_init(|| {
+            crate::sessions::start_session(
+                session.sessions.as_ref(),
+                "gateway",
+                Some(WS_CHANNEL),
+                &provider_label,
                 &state.model,
-                &[],
-                &[],
-                Some(&config_guard.identity),
-                None,
             )
-        };
+        })
+        .as_ref();
+    if let Some(transcript) = transcript {
+        transcript.record_message("user", &content);
+    }
+
+    let (on_delta, forwarder) = if state.provider.supports_streaming() {
+        let (tx, rx) = mpsc::channel(OUTBOUND_BUFFER);
+        (
+            Some(tx),
+            Some(tokio::spawn(forward_chunks(
+                rx,
+                session.out.clone(),
+                crate::security::outbound::global_guard()
+                    .filter(|guard| guard.covers(OutboundSurface::Gateway)),
+                state.observer.clone(),
+            ))),
+        )
+    } else {
+        (None, None)
+    };
+
+    let result = run_tool_call_loop(
+        state.provider.as_ref(),
+        &mut history,
+        state.tools.as_ref(),
+        state.observer.as_ref(),
+        &provider_label,
+        &state.model,
+        state.temperature,
+        true,
+        Some(&session.approval),
+        WS_CHANNEL,
+        &multimodal_config,
+        max_tool_iterations,
+        Some(cancel),
+        on_delta,
+        Some(&session.hooks),
+        &excluded_tools,
+        transcript,
+    )
+    .await;
 
-        let messages = vec![
-            crate::providers::ChatMessage::system(system_prompt),
-            crate::providers::ChatMessage::user(&content),
-        ];
+    // The loop owned the delta sender, so the forwarder drains and exits.
+    if let Some(forwarder) = forwarder {
+        let _ = forwarder.await;
+    }
+
+    match result {
+        Ok(response) => {
+            session
+                .send(serde_json::json!({
+                    "type": "done",
+                    "full_response": screen_outbound(state, OutboundSurface::Gateway, response),
+                }))
+                .await;
 
-        let multimodal_config = state.config.lock().multimodal.clone();
-        let prepared =
-            match crate::multimodal::prepare_messages_for_provider(&messages, &multimodal_config)
+            // Broadcast agent_end event
+            let _ = state.event_tx.send(serde_json::json!({
+                "type": "agent_end",
+                "provider": provider_label,
+                "model": state.model,
+            }));
+
+            bound_history(
+                &mut history,
+                state.provider.as_ref(),
+                &state.model,
+                max_history,
+            )
+            .await;
+        }
+        Err(e) if is_tool_loop_cancelled(&e) => {
+            // Drop the partial turn so the next message starts from a clean history.
+            history.truncate(checkpoint);
+            session.send(serde_json::json!({"type": "cancelled"})).await;
+        }
+        Err(e) => {
+            history.truncate(checkpoint);
+            let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+            session
+                .send(serde_json::json!({
+                    "type": "error",
+                    "message": sanitized,
+                }))
+                .await;
+
+            // Broadcast error event
+            let _ = state.event_tx.send(serde_json::json!({
+                "type": "error",
+                "component": "ws_chat",
+                "message": sanitized,
+            }));
+        }
+    }
+}
+
+/// Keep a long-lived socket's history within `max_history` messages, like
+/// the CLI loop: compact older turns into a summary, then hard-trim.
+async fn bound_history(
+    history: &mut Vec<ChatMessage>,
+    provider: &dyn Provider,
+    model: &str,
+    max_history: usize,
+) {
+    if let Err(e) = auto_compact_history(history, provider, model, max_history).await {
+        tracing::warn!("WebSocket history compaction failed: {e}");
+    }
+    trim_history(history, max_history);
+}
+
+async fn handle_socket(socket: WebSocket, state: AppState, client: String) {
+    let (mut sink, mut receiver) = socket.split();
+
+    // Single writer task so the agent turn and the read loop can both emit frames.
+    let (out_tx, mut out_rx) = mpsc::channel::<Value>(OUTBOUND_BUFFER);
+    let writer = tokio::spawn(async move {
+        while let Some(frame) = out_rx.recv().await {
+            if sink
+                .send(Message::Text(frame.to_string().into()))
                 .await
+                .is_err()
             {
-                Ok(p) => p,
-                Err(e) => {
+                break;
+            }
+        }
+    });
+
+    let approvals = WsApprovals::new(client);
+    let session = Arc::new(WsSession::new(state, out_tx.clone(), approvals.clone()));
+    let mut active: Option<(CancellationToken, JoinHandle<()>)> = None;
+
+    while let Some(msg) = receiver.next().await {
+        let msg = match msg {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => break,
+            _ => continue,
+        };
+
+        // Parse incoming message
+        let Ok(parsed) = serde_json::from_str::<Value>(&msg) else {
+            let err = serde_json::json!({"type": "error", "message": "Invalid JSON"});
+            let _ = out_tx.send(err).await;
+            continue;
+        };
+
+        match parsed["type"].as_str().unwrap_or("") {
+            "message" => {
+                let content = parsed["content"].as_str().unwrap_or("").to_string();
+                if content.is_empty() {
+                    continue;
+                }
+                if active.as_ref().is_some_and(|(_, turn)| !turn.is_finished()) {
                     let err = serde_json::json!({
                         "type": "error",
-                        "message": format!("Multimodal prep failed: {e}")
+                        "message": "A response is already in progress; send {\"type\":\"cancel\"} first",
                     });
-                    let _ = sender.send(Message::Text(err.to_string().into())).await;
+                    let _ = out_tx.send(err).await;
                     continue;
                 }
-            };
-
-        match state
-            .provider
-            .chat_with_history(&prepared.messages, &state.model, state.temperature)
-            .await
-        {
-            Ok(response) => {
-                // Send the full response as a done message
-                let done = serde_json::json!({
-                    "type": "done",
-                    "full_response": response,
-                });
-                let _ = sender.send(Message::Text(done.to_string().into())).await;
-
-                // Broadcast agent_end event
-                let _ = state.event_tx.send(serde_json::json!({
-                    "type": "agent_end",
-                    "provider": provider_label,
-                    "model": state.model,
-                }));
+                let cancel = CancellationToken::new();
+                let turn = tokio::spawn(run_turn(Arc::clone(&session), content, cancel.clone()));
+                active = Some((cancel, turn));
+            }
+            "cancel" => {
+                if let Some((cancel, _)) = &active {
+                    cancel.cancel();
+                }
+                approvals.deny_all();
             }
-            Err(e) => {
-                let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+            "approval_response" if !approvals.resolve(&parsed) => {
                 let err = serde_json::json!({
                     "type": "error",
-                    "message": sanitized,
+                    "message": "Unknown or expired approval id",
                 });
-                let _ = sender.send(Message::Text(err.to_string().into())).await;
-
-                // Broadcast error event
-                let _ = state.event_tx.send(serde_json::json!({
-                    "type": "error",
-                    "component": "ws_chat",
-                    "message": sanitized,
-                }));
+                let _ = out_tx.send(err).await;
             }
+            _ => {}
         }
     }
+
+    // Client went away: stop the in-flight turn and never run unanswered tools.
+    if let Some((cancel, turn)) = active {
+        cancel.cancel();
+        approvals.deny_all();
+        let _ = turn.await;
+    }
+    writer.abort();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+
+    fn request() -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        }
+    }
+
+    fn prompter(out: mpsc::Sender<Value>) -> (WsApprovalPrompter, WsApprovals) {
+        let approvals = WsApprovals::new("ws:token:dashboard".into());
+        let prompter = WsApprovalPrompter {
+            out,
+            approvals: approvals.clone(),
+            timeout: Duration::from_secs(60),
+        };
+        (prompter, approvals)
+    }
+
+    struct SummaryProvider;
+
+    #[async_trait]
+    impl Provider for SummaryProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("- earlier turns".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn session_history_is_bounded_after_each_turn() {
+        let mut history = vec![ChatMessage::system("system")];
+        for i in 0..60 {
+            history.push(ChatMessage::user(format!("question {i}")));
+            history.push(ChatMessage::assistant(format!("answer {i}")));
+        }
+
+        bound_history(&mut history, &SummaryProvider, "model", 50).await;
+        assert!(history.len() <= 51, "{}", history.len());
+        assert_eq!(history[0].role, "system");
+        assert!(history[1].content.contains("earlier turns"));
+        assert_eq!(history.last().unwrap().content, "answer 59");
+    }
+
+    #[tokio::test]
+    async fn prompter_emits_request_and_resolves_response() {
+        let (out, mut rx) = mpsc::channel(4);
+        let (prompter, approvals) = prompter(out);
+
+        let waiter = tokio::spawn(async move { prompter.prompt(&request()).await });
+        let frame = rx.recv().await.unwrap();
+        assert_eq!(frame["type"], "approval_request");
+        assert_eq!(frame["tool"], "shell");
+        let id = frame["id"].as_str().unwrap().to_string();
+        assert_eq!(
+            approvals.broker.get(&id).unwrap().origin.conversation,
+            approvals.session_id
+        );
+
+        let reply = serde_json::json!({
+            "type": "approval_response",
+            "id": id,
+            "decision": "always",
+        });
+        assert!(approvals.resolve(&reply));
+        assert_eq!(
+            waiter.await.unwrap(),
+            ApprovalDecision::by(ApprovalResponse::Always, "ws:token:dashboard")
+        );
+        assert!(approvals.broker.get(&id).is_none());
+    }
+
+    #[tokio::test]
+    async fn prompter_denies_on_cancel() {
+        let (out, mut rx) = mpsc::channel(4);
+        let (prompter, approvals) = prompter(out);
+
+        let waiter = tokio::spawn(async move { prompter.prompt(&request()).await });
+        let _ = rx.recv().await.unwrap();
+        approvals.deny_all();
+        assert_eq!(waiter.await.unwrap().response, ApprovalResponse::No);
+    }
+
+    #[tokio::test]
+    async fn prompter_denies_when_socket_closed() {
+        let (out, rx) = mpsc::channel(4);
+        drop(rx);
+        let (prompter, _) = prompter(out);
+        assert_eq!(
+            prompter.prompt(&request()).await.response,
+            ApprovalResponse::No
+        );
+    }
+
+    #[test]
+    fn resolve_approval_rejects_unknown_id() {
+        let approvals = WsApprovals::new("ws:local".into());
+        let reply = serde_json::json!({"id": "missing", "decision": "yes"});
+        assert!(!approvals.resolve(&reply));
+    }
+
+    #[tokio::test]
+    async fn other_sessions_cannot_answer_requests() {
+        let (out, mut rx) = mpsc::channel(4);
+        let (prompter, owner) = prompter(out);
+        let waiter = tokio::spawn(async move { prompter.prompt(&request()).await });
+        let frame = rx.recv().await.unwrap();
+
+        let intruder = WsApprovals::new("ws:other".into());
+        let reply = serde_json::json!({"id": frame["id"], "decision": "yes"});
+        assert!(!intruder.resolve(&reply));
+        owner.deny_all();
+        assert_eq!(waiter.await.unwrap().response, ApprovalResponse::No);
+    }
+
+    #[tokio::test]
+    async fn forward_chunks_skips_progress_before_sentinel() {
+        let (delta_tx, delta_rx) = mpsc::channel(8);
+        let (out, mut frames) = mpsc::channel(8);
+        delta_tx.send("⏳ Thinking...\n".to_string()).await.unwrap();
+        delta_tx
+            .send(DRAFT_CLEAR_SENTINEL.to_string())
+            .await
+            .unwrap();
+        delta_tx.send("Hello ".to_string()).await.unwrap();
+        delta_tx.send("world".to_string()).await.unwrap();
+        drop(delta_tx);
+
+        forward_chunks(delta_rx, out, None, Arc::new(NoopObserver)).await;
+
+        let first = frames.recv().await.unwrap();
+        assert_eq!(first["type"], "chunk");
+        assert_eq!(first["content"], "Hello ");
+        assert_eq!(frames.recv().await.unwrap()["content"], "world");
+        assert!(frames.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn forward_chunks_screens_secrets_split_across_deltas() {
+        let guard =
+            Arc::new(OutboundGuard::new(&crate::config::OutboundGuardConfig::default()).unwrap());
+        let (delta_tx, delta_rx) = mpsc::channel(8);
+        let (out, mut frames) = mpsc::channel(8);
+        for delta in [
+            DRAFT_CLEAR_SENTINEL,
+            "Token: ghp_abcdefghij",
+            "klmnopqrstuvwxyz0123456789AB\n",
+            "done",
+        ] {
+            delta_tx.send(delta.to_string()).await.unwrap();
+        }
+        drop(delta_tx);
+
+        forward_chunks(delta_rx, out, Some(guard), Arc::new(NoopObserver)).await;
+
+        let first = frames.recv().await.unwrap();
+        let content = first["content"].as_str().unwrap();
+        assert!(content.starts_with("Token: "), "{content}");
+        assert!(!content.contains("ghp_"), "{content}");
+        assert_eq!(frames.recv().await.unwrap()["content"], "done");
+        assert!(frames.recv().await.is_none());
+    }
 }
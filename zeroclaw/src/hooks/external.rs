@@ -0,0 +1,602 @@
+//! Config-declared hook handlers backed by scripts or local webhooks.
+//!
+//! Every invocation sends one JSON document:
+//!
+//! ```json
+//! {"event": "before_tool_call", "payload": {"name": "shell", "args": {...}}}
+//! ```
+//!
+//! Scripts receive it on stdin and reply on stdout; webhooks receive it as a
+//! POST body and reply in the response body. For modifying events the reply
+//! may be empty (continue unchanged), `{"action": "continue", "payload": {...}}`
+//! (fields present in `payload` replace the originals), or
+//! `{"action": "cancel", "reason": "..."}`. Replies to void events are ignored.
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use crate::channels::traits::ChannelMessage;
+use crate::config::{ExternalHookConfig, ExternalHookKind};
+use crate::hooks::traits::{HookHandler, HookResult};
+use crate::providers::traits::{ChatMessage, ChatResponse};
+use crate::tools::traits::ToolResult;
+
+/// Event names accepted in `[[hooks.external]].events`.
+pub const SUPPORTED_EVENTS: &[&str] = &[
+    "on_gateway_start",
+    "on_gateway_stop",
+    "on_session_start",
+    "on_session_end",
+    "on_llm_input",
+    "on_llm_output",
+    "on_after_tool_call",
+    "on_message_sent",
+    "on_heartbeat_tick",
+    "before_model_resolve",
+    "before_prompt_build",
+    "before_llm_call",
+    "before_tool_call",
+    "on_message_received",
+    "on_message_sending",
+];
+
+/// Cap on captured stdout/stderr and webhook bodies.
+const MAX_REPLY_BYTES: usize = 1024 * 1024;
+
+enum Transport {
+    Script {
+        command: String,
+        args: Vec<String>,
+    },
+    Webhook {
+        url: String,
+        client: reqwest::Client,
+    },
+}
+
+/// Decision returned by an external hook.
+#[derive(Debug, PartialEq)]
+enum Reply {
+    Continue(Option<Value>),
+    Cancel(String),
+}
+
+#[derive(Deserialize)]
+struct RawReply {
+    #[serde(default)]
+    action: Option<String>,
+    #[serde(default)]
+    payload: Option<Value>,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// A [`HookHandler`] that forwards events to a script or loopback webhook.
+pub struct ExternalHook {
+    name: String,
+    priority: i32,
+    events: Vec<String>,
+    timeout: Duration,
+    fail_closed: bool,
+    transport: Transport,
+}
+
+impl ExternalHook {
+    /// Build a handler from an already-validated config entry.
+    pub fn from_config(config: &ExternalHookConfig) -> Self {
+        let transport = match config.kind {
+            ExternalHookKind::Script => Transport::Script {
+                command: config.command.clone().unwrap_or_default(),
+                args: config.args.clone(),
+            },
+            ExternalHookKind::Webhook => Transport::Webhook {
+                url: config.url.clone().unwrap_or_default(),
+                client: reqwest::Client::new(),
+            },
+        };
+        Self {
+            name: config.name.clone(),
+            priority: config.priority,
+            events: config.events.clone(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            fail_closed: config.fail_closed,
+            transport,
+        }
+    }
+
+    fn subscribes_to(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == event)
+    }
+
+    async fn invoke(&self, event: &str, payload: Value) -> anyhow::Result<Reply> {
+        let request = serde_json::to_vec(&json!({ "event": event, "payload": payload }))?;
+        let body = match &self.transport {
+            Transport::Script { command, args } => {
+                run_script(command, args, &request, self.timeout).await?
+            }
+            Transport::Webhook { url, client } => {
+                post_webhook(client, url, request, self.timeout).await?
+            }
+        };
+        parse_reply(&body)
+    }
+
+    /// Dispatch a void event; the reply is ignored and failures are logged.
+    async fn notify(&self, event: &str, payload: impl FnOnce() -> Value) {
+        if !self.subscribes_to(event) {
+            return;
+        }
+        if let Err(e) = self.invoke(event, payload()).await {
+            tracing::warn!(hook = %self.name, event, "external hook failed: {e:#}");
+        }
+    }
+
+    /// Dispatch a modifying event and apply the hook's decision to `value`.
+    async fn modify<T>(
+        &self,
+        event: &str,
+        value: T,
+        to_json: impl FnOnce(&T) -> Value,
+        apply: impl FnOnce(&T, &Value) -> anyhow::Result<T>,
+    ) -> HookResult<T> {
+        if !self.subscribes_to(event) {
+            return HookResult::Continue(value);
+        }
+        let outcome = match self.invoke(event, to_json(&value)).await {
+            Ok(Reply::Continue(None)) => return HookResult::Continue(value),
+            Ok(Reply::Continue(Some(patch))) => apply(&value, &patch),
+            Ok(Reply::Cancel(reason)) => return HookResult::Cancel(reason),
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(updated) => HookResult::Continue(updated),
+            Err(e) if self.fail_closed => {
+                tracing::warn!(hook = %self.name, event, "external hook failed (fail_closed): {e:#}");
+                HookResult::Cancel(format!("hook '{}' failed: {e}", self.name))
+            }
+            Err(e) => {
+                tracing::warn!(hook = %self.name, event, "external hook failed; continuing: {e:#}");
+                HookResult::Continue(value)
+            }
+        }
+    }
+}
+
+async fn run_script(
+    command: &str,
+    args: &[String],
+    request: &[u8],
+    timeout: Duration,
+) -> anyhow::Result<Vec<u8>> {
+    let mut child = tokio::process::Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("failed to spawn hook script '{command}'"))?;
+
+    let run = async {
+        if let Some(mut stdin) = child.stdin.take() {
+            // A script that ignores stdin may close it early; that is not an error.
+            let _ = stdin.write_all(request).await;
+        }
+        child.wait_with_output().await
+    };
+    let output = tokio::time::timeout(timeout, run)
+        .await
+        .map_err(|_| anyhow::anyhow!("hook script timed out after {}s", timeout.as_secs()))??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "hook script exited with {}: {}",
+            output.status,
+            truncate(stderr.trim(), 512)
+        );
+    }
+    if output.stdout.len() > MAX_REPLY_BYTES {
+        anyhow::bail!("hook script reply exceeds {MAX_REPLY_BYTES} bytes");
+    }
+    Ok(output.stdout)
+}
+
+async fn post_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    request: Vec<u8>,
+    timeout: Duration,
+) -> anyhow::Result<Vec<u8>> {
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(request)
+        .timeout(timeout)
+        .send()
+        .await
+        .with_context(|| format!("hook webhook request to {url} failed"))?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("hook webhook returned {status}");
+    }
+    let body = response.bytes().await?;
+    if body.len() > MAX_REPLY_BYTES {
+        anyhow::bail!("hook webhook reply exceeds {MAX_REPLY_BYTES} bytes");
+    }
+    Ok(body.to_vec())
+}
+
+fn parse_reply(body: &[u8]) -> anyhow::Result<Reply> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(Reply::Continue(None));
+    }
+    let raw: RawReply = serde_json::from_slice(body).context("hook reply is not valid JSON")?;
+    match raw.action.as_deref().unwrap_or("continue") {
+        "continue" => Ok(Reply::Continue(raw.payload.filter(|p| !p.is_null()))),
+        "cancel" => Ok(Reply::Cancel(
+            raw.reason
+                .filter(|r| !r.trim().is_empty())
+                .unwrap_or_else(|| "cancelled by external hook".to_string()),
+        )),
+        other => anyhow::bail!("unknown hook action '{other}'"),
+    }
+}
+
+fn truncate(s: &str, max_chars: usize) -> &str {
+    s.char_indices().nth(max_chars).map_or(s, |(i, _)| &s[..i])
+}
+
+/// Read `key` from a reply payload as a string, keeping `current` when absent.
+fn patch_string(patch: &Value, key: &str, current: &str) -> anyhow::Result<String> {
+    match patch.get(key) {
+        None | Some(Value::Null) => Ok(current.to_string()),
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(_) => anyhow::bail!("hook payload field '{key}' must be a string"),
+    }
+}
+
+#[async_trait]
+impl HookHandler for ExternalHook {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    async fn on_gateway_start(&self, host: &str, port: u16) {
+        self.notify("on_gateway_start", || json!({ "host": host, "port": port }))
+            .await;
+    }
+
+    async fn on_gateway_stop(&self) {
+        self.notify("on_gateway_stop", || json!({})).await;
+    }
+
+    async fn on_session_start(&self, session_id: &str, channel: &str) {
+        self.notify(
+            "on_session_start",
+            || json!({ "session_id": session_id, "channel": channel }),
+        )
+        .await;
+    }
+
+    async fn on_session_end(&self, session_id: &str, channel: &str) {
+        self.notify(
+            "on_session_end",
+            || json!({ "session_id": session_id, "channel": channel }),
+        )
+        .await;
+    }
+
+    async fn on_llm_input(&self, messages: &[ChatMessage], model: &str) {
+        self.notify(
+            "on_llm_input",
+            || json!({ "messages": messages, "model": model }),
+        )
+        .await;
+    }
+
+    async fn on_llm_output(&self, response: &ChatResponse) {
+        self.notify(
+            "on_llm_output",
+            || json!({ "text": response.text, "tool_calls": response.tool_calls }),
+        )
+        .await;
+    }
+
+    async fn on_after_tool_call(&self, tool: &str, result: &ToolResult, duration: Duration) {
+        self.notify("on_after_tool_call", || {
+            json!({
+                "tool": tool,
+                "result": result,
+                "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            })
+        })
+        .await;
+    }
+
+    async fn on_message_sent(&self, channel: &str, recipient: &str, content: &str) {
+        self.notify(
+            "on_message_sent",
+            || json!({ "channel": channel, "recipient": recipient, "content": content }),
+        )
+        .await;
+    }
+
+    async fn on_heartbeat_tick(&self) {
+        self.notify("on_heartbeat_tick", || json!({})).await;
+    }
+
+    async fn before_model_resolve(
+        &self,
+        provider: String,
+        model: String,
+    ) -> HookResult<(String, String)> {
+        self.modify(
+            "before_model_resolve",
+            (provider, model),
+            |(p, m)| json!({ "provider": p, "model": m }),
+            |(p, m), patch| {
+                Ok((
+                    patch_string(patch, "provider", p)?,
+                    patch_string(patch, "model", m)?,
+                ))
+            },
+        )
+        .await
+    }
+
+    async fn before_prompt_build(&self, prompt: String) -> HookResult<String> {
+        self.modify(
+            "before_prompt_build",
+            prompt,
+            |p| json!({ "prompt": p }),
+            |p, patch| patch_string(patch, "prompt", p),
+        )
+        .await
+    }
+
+    async fn before_llm_call(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: String,
+    ) -> HookResult<(Vec<ChatMessage>, String)> {
+        self.modify(
+            "before_llm_call",
+            (messages, model),
+            |(msgs, m)| json!({ "messages": msgs, "model": m }),
+            |(msgs, m), patch| {
+                let messages = match patch.get("messages") {
+                    None | Some(Value::Null) => msgs.clone(),
+                    Some(v) => Vec::<ChatMessage>::deserialize(v)
+                        .context("hook payload field 'messages' is malformed")?,
+                };
+                Ok((messages, patch_string(patch, "model", m)?))
+            },
+        )
+        .await
+    }
+
+    async fn before_tool_call(&self, name: String, args: Value) -> HookResult<(String, Value)> {
+        self.modify(
+            "before_tool_call",
+            (name, args),
+            |(n, a)| json!({ "name": n, "args": a }),
+            |(n, a), patch| {
+                let args = patch.get("args").cloned().unwrap_or_else(|| a.clone());
+                Ok((patch_string(patch, "name", n)?, args))
+            },
+        )
+        .await
+    }
+
+    /// Only `content` may be rewritten; routing fields are informational.
+    async fn on_message_received(&self, message: ChannelMessage) -> HookResult<ChannelMessage> {
+        self.modify(
+            "on_message_received",
+            message,
+            |m| {
+                json!({
+                    "id": m.id,
+                    "sender": m.sender,
+                    "reply_target": m.reply_target,
+                    "content": m.content,
+                    "channel": m.channel,
+                    "timestamp": m.timestamp,
+                    "thread_ts": m.thread_ts,
+                })
+            },
+            |m, patch| {
+                let mut updated = m.clone();
+                updated.content = patch_string(patch, "content", &m.content)?;
+                Ok(updated)
+            },
+        )
+        .await
+    }
+
+    async fn on_message_sending(
+        &self,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String)> {
+        self.modify(
+            "on_message_sending",
+            (channel, recipient, content),
+            |(c, r, body)| json!({ "channel": c, "recipient": r, "content": body }),
+            |(c, r, body), patch| {
+                Ok((
+                    c.clone(),
+                    patch_string(patch, "recipient", r)?,
+                    patch_string(patch, "content", body)?,
+                ))
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn script_hook(script: &str) -> ExternalHook {
+        ExternalHook::from_config(&ExternalHookConfig {
+            name: "test-script".into(),
+            kind: ExternalHookKind::Script,
+            command: Some("sh".into()),
+            args: vec!["-c".into(), script.into()],
+            url: None,
+            events: Vec::new(),
+            priority: 0,
+            timeout_secs: 5,
+            fail_closed: false,
+        })
+    }
+
+    fn webhook_hook(url: String, events: Vec<String>) -> ExternalHook {
+        ExternalHook::from_config(&ExternalHookConfig {
+            name: "test-webhook".into(),
+            kind: ExternalHookKind::Webhook,
+            command: None,
+            args: Vec::new(),
+            url: Some(url),
+            events,
+            priority: 10,
+            timeout_secs: 5,
+            fail_closed: false,
+        })
+    }
+
+    #[test]
+    fn parse_reply_variants() {
+        assert_eq!(parse_reply(b"  \n").unwrap(), Reply::Continue(None));
+        assert_eq!(parse_reply(b"{}").unwrap(), Reply::Continue(None));
+        assert_eq!(
+            parse_reply(br#"{"action":"cancel","reason":"nope"}"#).unwrap(),
+            Reply::Cancel("nope".into())
+        );
+        assert_eq!(
+            parse_reply(br#"{"payload":{"prompt":"x"}}"#).unwrap(),
+            Reply::Continue(Some(json!({"prompt": "x"})))
+        );
+        assert!(parse_reply(br#"{"action":"explode"}"#).is_err());
+        assert!(parse_reply(b"not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn script_hook_rewrites_tool_args() {
+        let hook = script_hook(
+            r#"cat >/dev/null; echo '{"action":"continue","payload":{"args":{"command":"ls"}}}'"#,
+        );
+        match hook
+            .before_tool_call("shell".into(), json!({"command": "rm -rf /"}))
+            .await
+        {
+            HookResult::Continue((name, args)) => {
+                assert_eq!(name, "shell");
+                assert_eq!(args, json!({"command": "ls"}));
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn script_hook_sees_request_and_can_cancel() {
+        let hook = script_hook(
+            r#"if grep -q '"event":"before_tool_call"'; then echo '{"action":"cancel","reason":"blocked"}'; fi"#,
+        );
+        let result = hook.before_tool_call("shell".into(), json!({})).await;
+        assert!(matches!(result, HookResult::Cancel(ref r) if r == "blocked"));
+    }
+
+    #[tokio::test]
+    async fn script_failure_respects_fail_closed() {
+        let mut hook = script_hook("exit 3");
+        let open = hook.before_prompt_build("prompt".into()).await;
+        assert!(matches!(open, HookResult::Continue(ref p) if p == "prompt"));
+
+        hook.fail_closed = true;
+        assert!(hook.before_prompt_build("prompt".into()).await.is_cancel());
+    }
+
+    #[tokio::test]
+    async fn script_timeout_is_a_failure() {
+        let mut hook = script_hook("sleep 5");
+        hook.timeout = Duration::from_millis(200);
+        hook.fail_closed = true;
+        let started = std::time::Instant::now();
+        assert!(hook.before_prompt_build("p".into()).await.is_cancel());
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn webhook_hook_rewrites_outgoing_content() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_partial_json(json!({"event": "on_message_sending"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "action": "continue",
+                "payload": {"content": "[redacted]"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let hook = webhook_hook(format!("{}/hook", server.uri()), Vec::new());
+        match hook
+            .on_message_sending("telegram".into(), "alice".into(), "secret".into())
+            .await
+        {
+            HookResult::Continue((channel, recipient, content)) => {
+                assert_eq!(channel, "telegram");
+                assert_eq!(recipient, "alice");
+                assert_eq!(content, "[redacted]");
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unsubscribed_events_are_not_sent() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"action": "cancel"})))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let hook = webhook_hook(server.uri(), vec!["before_tool_call".into()]);
+        let result = hook.before_prompt_build("p".into()).await;
+        assert!(matches!(result, HookResult::Continue(ref p) if p == "p"));
+    }
+
+    #[tokio::test]
+    async fn webhook_error_status_continues_when_fail_open() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let hook = webhook_hook(server.uri(), Vec::new());
+        let result = hook
+            .before_model_resolve("openai".into(), "gpt".into())
+            .await;
+        assert!(
+            matches!(result, HookResult::Continue((ref p, ref m)) if p == "openai" && m == "gpt")
+        );
+    }
+}
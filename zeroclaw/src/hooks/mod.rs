@@ -1,4 +1,5 @@
 pub mod builtin;
+pub mod external;
 mod runner;
 mod traits;
 
@@ -6,9 +6,12 @@ use std::panic::AssertUnwindSafe;
 use tracing::info;
 
 use crate::channels::traits::ChannelMessage;
+use crate::config::HooksConfig;
 use crate::providers::traits::{ChatMessage, ChatResponse};
 use crate::tools::traits::ToolResult;
 
+use super::builtin::CommandLoggerHook;
+use super::external::ExternalHook;
 use super::traits::{HookHandler, HookResult};
 
 /// Dispatcher that manages registered hook handlers.
@@ -28,6 +31,19 @@ impl HookRunner {
         }
     }
 
+    /// Build a runner from `[hooks]`: enabled builtins plus every
+    /// `[[hooks.external]]` entry. Does not check `config.enabled`.
+    pub fn from_config(config: &HooksConfig) -> Self {
+        let mut runner = Self::new();
+        if config.builtin.command_logger {
+            runner.register(Box::new(CommandLoggerHook::new()));
+        }
+        for hook in &config.external {
+            runner.register(Box::new(ExternalHook::from_config(hook)));
+        }
+        runner
+    }
+
     /// Register a handler and re-sort by descending priority.
     pub fn register(&mut self, handler: Box<dyn HookHandler>) {
         self.handlers.push(handler);
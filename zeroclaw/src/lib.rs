@@ -65,7 +65,9 @@ pub mod rag;
 pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
+pub(crate) mod sessions;
 pub(crate) mod skills;
+pub(crate) mod sop;
 pub mod tools;
 pub(crate) mod tunnel;
 pub(crate) mod util;
@@ -160,6 +162,23 @@ pub enum SkillCommands {
     },
 }
 
+/// SOP (Standard Operating Procedure) subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SopCommands {
+    /// List all loaded SOPs
+    List,
+    /// Validate SOP definitions (all, or a single SOP by name)
+    Validate {
+        /// SOP name to validate (defaults to all)
+        name: Option<String>,
+    },
+    /// Show a single SOP with its triggers and steps
+    Show {
+        /// SOP name
+        name: String,
+    },
+}
+
 /// Migration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MigrateCommands {
@@ -327,6 +346,85 @@ pub enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Inspect or wipe the LLM response cache
+    Cache {
+        #[command(subcommand)]
+        cache_command: MemoryCacheCommands,
+    },
+}
+
+/// Response cache subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MemoryCacheCommands {
+    /// Show cached entries, hits and tokens saved
+    Stats,
+    /// Delete every cached response
+    Clear,
+}
+
+/// Audit log subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuditCommands {
+    /// Check the hash chain across the active and rotated audit logs
+    Verify,
+    /// Print the most recent audit records
+    Tail {
+        #[arg(long, short = 'n', default_value = "20")]
+        lines: usize,
+        /// Print raw JSON records
+        #[arg(long)]
+        json: bool,
+    },
+    /// Find audit records matching text and filters
+    Search {
+        /// Case-insensitive text to look for anywhere in the record
+        query: Option<String>,
+        /// Only this event type (e.g. auth_failure, command_execution)
+        #[arg(long = "type")]
+        event_type: Option<String>,
+        /// Only records from this actor channel (e.g. gateway, shell)
+        #[arg(long)]
+        channel: Option<String>,
+        #[arg(long, default_value = "50")]
+        limit: usize,
+        /// Print raw JSON records
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Session transcript subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// List recorded sessions, most recent first
+    List {
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Print a session transcript
+    Show {
+        /// Session id
+        id: String,
+    },
+    /// Export a session transcript
+    Export {
+        /// Session id
+        id: String,
+        /// Output format: json, jsonl or markdown
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Reload a session into the agent and continue the conversation
+    Resume {
+        /// Session id
+        id: String,
+        /// Send a single message and exit instead of starting interactive mode
+        #[arg(long, short)]
+        message: Option<String>,
+    },
 }
 
 /// Integration subcommands
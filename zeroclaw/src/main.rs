@@ -77,8 +77,10 @@ mod providers;
 mod runtime;
 mod security;
 mod service;
+mod sessions;
 mod skillforge;
 mod skills;
+mod sop;
 mod tools;
 mod tunnel;
 mod util;
@@ -87,8 +89,9 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    AuditCommands, ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands,
+    MemoryCacheCommands, MigrateCommands, PeripheralCommands, ServiceCommands, SessionCommands,
+    SkillCommands, SopCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -208,8 +211,14 @@ Examples:
   zeroclaw gateway                  # use config defaults
   zeroclaw gateway -p 8080          # listen on port 8080
   zeroclaw gateway --host 0.0.0.0   # bind to all interfaces
-  zeroclaw gateway -p 0             # random available port")]
+  zeroclaw gateway -p 0             # random available port
+  zeroclaw gateway tokens list
+  zeroclaw gateway tokens create dashboard --scope memory:read --scope cost:read
+  zeroclaw gateway tokens revoke tok_1a2b3c4d5e6f")]
     Gateway {
+        #[command(subcommand)]
+        gateway_command: Option<GatewayCommands>,
+
         /// Port to listen on (use 0 for random available port); defaults to config gateway.port
         #[arg(short, long)]
         port: Option<u16>,
@@ -357,6 +366,23 @@ Examples:
         skill_command: SkillCommands,
     },
 
+    /// Inspect Standard Operating Procedures (SOPs)
+    #[command(long_about = "\
+Inspect Standard Operating Procedures.
+
+SOPs are loaded from <workspace>/sops/<name>/ (SOP.toml + SOP.md) \
+and triggered by the daemon on cron, webhook, MQTT, or peripheral \
+events, or manually by the agent via the sop_execute tool.
+
+Examples:
+  zeroclaw sop list
+  zeroclaw sop validate
+  zeroclaw sop show pump-overpressure")]
+    Sop {
+        #[command(subcommand)]
+        sop_command: SopCommands,
+    },
+
     /// Migrate data from other agent runtimes
     Migrate {
         #[command(subcommand)]
@@ -424,6 +450,46 @@ Examples:
         memory_command: MemoryCommands,
     },
 
+    /// Verify and query the tamper-evident audit log
+    #[command(long_about = "\
+Verify and query the tamper-evident audit log.
+
+Shell, file and git tool calls, gateway pairing and authentication, \
+config updates, approvals and emergency stops are appended to \
+[security.audit] log_path. Each record is hash-chained to the \
+previous one; 'verify' reports the first record that was edited, \
+inserted or removed.
+
+Examples:
+  zeroclaw audit verify
+  zeroclaw audit tail -n 50
+  zeroclaw audit search --type auth_failure
+  zeroclaw audit search \"rm -rf\" --channel shell --json")]
+    Audit {
+        #[command(subcommand)]
+        audit_command: AuditCommands,
+    },
+
+    /// Inspect, export and resume recorded agent sessions
+    #[command(long_about = "\
+Inspect, export and resume recorded agent sessions.
+
+Every CLI, channel, gateway and cron session is written as an \
+append-only JSONL transcript under <workspace>/state/sessions. \
+Resuming reloads the conversation into the agent and appends to \
+the same transcript.
+
+Examples:
+  zeroclaw sessions list
+  zeroclaw sessions show <id>
+  zeroclaw sessions export <id> --format markdown -o session.md
+  zeroclaw sessions resume <id>
+  zeroclaw sessions resume <id> -m \"continue where we left off\"")]
+    Sessions {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -463,6 +529,37 @@ enum ConfigCommands {
     Schema,
 }
 
+#[derive(Subcommand, Debug)]
+enum GatewayCommands {
+    /// Manage named, scoped gateway API tokens
+    Tokens {
+        #[command(subcommand)]
+        token_command: GatewayTokenCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum GatewayTokenCommands {
+    /// List API tokens (secrets are never shown)
+    List,
+    /// Create a token; the bearer secret is printed once
+    Create {
+        /// Label for the token, e.g. `android-dashboard`
+        name: String,
+        /// Scope to grant (repeatable), e.g. `memory:read`, `cron:write`, `*`
+        #[arg(long = "scope", required = true)]
+        scopes: Vec<String>,
+        /// Expire the token after this many days
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+    /// Revoke a token by id
+    Revoke {
+        /// Token id (`tok_...`)
+        id: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum EstopSubcommands {
     /// Print current estop status.
@@ -625,6 +722,11 @@ enum DoctorCommands {
         #[arg(long, default_value = "20")]
         limit: usize,
     },
+    /// Re-run a recorded provider fixture offline through the tool loop
+    Replay {
+        /// JSONL fixture written via `[observability] provider_fixture_path`
+        fixture: std::path::PathBuf,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -655,6 +757,11 @@ enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Inspect or wipe the LLM response cache
+    Cache {
+        #[command(subcommand)]
+        cache_command: MemoryCacheCommands,
+    },
 }
 
 #[tokio::main]
@@ -751,6 +858,12 @@ async fn main() -> Result<()> {
     let mut config = Config::load_or_init().await?;
     config.apply_env_overrides();
     observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
+    cost::budget::init_from_config(&config.cost, &config.workspace_dir);
+    security::outbound::init_from_config(&config.security.outbound);
+    if let Some(config_dir) = config.config_path.parent() {
+        security::audit::init_from_config(&config.security.audit, config_dir);
+        security::estop::init_from_config(&config.security, config_dir);
+    }
     if config.security.otp.enabled {
         let config_dir = config
             .config_path
@@ -787,7 +900,16 @@ async fn main() -> Result<()> {
         .await
         .map(|_| ()),
 
-        Commands::Gateway { port, host } => {
+        Commands::Gateway {
+            gateway_command: Some(GatewayCommands::Tokens { token_command }),
+            ..
+        } => handle_gateway_token_command(&config, token_command).await,
+
+        Commands::Gateway {
+            gateway_command: None,
+            port,
+            host,
+        } => {
             let port = port.unwrap_or(config.gateway.port);
             let host = host.unwrap_or_else(|| config.gateway.host.clone());
             if port == 0 {
@@ -992,11 +1114,20 @@ async fn main() -> Result<()> {
                 contains.as_deref(),
                 limit,
             ),
+            Some(DoctorCommands::Replay { fixture }) => {
+                doctor::replay::run_replay(&config, &fixture).await
+            }
             None => doctor::run(&config),
         },
 
         Commands::Channel { channel_command } => match channel_command {
-            ChannelCommands::Start => channels::start_channels(config).await,
+            ChannelCommands::Start => {
+                // Dropping the channel future on Ctrl+C flushes queued history.
+                tokio::select! {
+                    result = channels::start_channels(config) => result,
+                    _ = tokio::signal::ctrl_c() => Ok(()),
+                }
+            }
             ChannelCommands::Doctor => channels::doctor_channels(config).await,
             other => channels::handle_command(other, &config).await,
         },
@@ -1007,6 +1138,8 @@ async fn main() -> Result<()> {
 
         Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
 
+        Commands::Sop { sop_command } => sop::handle_command(sop_command, &config),
+
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
@@ -1015,6 +1148,14 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Audit { audit_command } => {
+            security::audit_cli::handle_command(audit_command, &config)
+        }
+
+        Commands::Sessions { session_command } => {
+            sessions::cli::handle_command(session_command, &config).await
+        }
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -1171,6 +1312,73 @@ fn build_resume_selector(
     Ok(security::ResumeSelector::KillAll)
 }
 
+async fn handle_gateway_token_command(
+    config: &Config,
+    command: GatewayTokenCommands,
+) -> Result<()> {
+    match command {
+        GatewayTokenCommands::List => {
+            let tokens = &config.gateway.api_tokens;
+            if tokens.is_empty() {
+                println!("No API tokens. Create one with `zeroclaw gateway tokens create`.");
+                return Ok(());
+            }
+            let now = chrono::Utc::now();
+            for token in tokens {
+                let status = if security::pairing::is_api_token_expired(token, now) {
+                    " (expired)"
+                } else {
+                    ""
+                };
+                println!("{}  {}{status}", token.id, token.name);
+                println!("  scopes:     {}", token.scopes.join(", "));
+                println!("  created:    {}", token.created_at);
+                println!(
+                    "  expires:    {}",
+                    token.expires_at.as_deref().unwrap_or("never")
+                );
+                println!(
+                    "  last used:  {}",
+                    token.last_used_at.as_deref().unwrap_or("never")
+                );
+            }
+            Ok(())
+        }
+        GatewayTokenCommands::Create {
+            name,
+            scopes,
+            expires_in_days,
+        } => {
+            let expires_in = expires_in_days.map(|days| chrono::Duration::days(i64::from(days)));
+            let (token, record) = security::pairing::mint_api_token(&name, &scopes, expires_in)?;
+            let mut updated = config.clone();
+            updated.gateway.api_tokens.push(record.clone());
+            updated.save().await?;
+            println!("Created API token {} ({})", record.id, record.name);
+            println!("  scopes: {}", record.scopes.join(", "));
+            if let Some(expires_at) = &record.expires_at {
+                println!("  expires: {expires_at}");
+            }
+            println!();
+            println!("  {token}");
+            println!();
+            println!("Save this token now — it will not be shown again.");
+            Ok(())
+        }
+        GatewayTokenCommands::Revoke { id } => {
+            let mut updated = config.clone();
+            let before = updated.gateway.api_tokens.len();
+            updated.gateway.api_tokens.retain(|t| t.id != id);
+            if updated.gateway.api_tokens.len() == before {
+                bail!("No API token with id '{id}'");
+            }
+            updated.save().await?;
+            println!("Revoked API token {id}. A running gateway drops it within a minute.");
+            Ok(())
+        }
+    }
+}
+
 fn print_estop_status(state: &security::EstopState) {
     println!("Estop status:");
     println!(
@@ -23,6 +23,7 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::Clear { key, category, yes } => {
             handle_clear(config, key, category, yes).await
         }
+        crate::MemoryCommands::Cache { cache_command } => handle_cache(config, &cache_command),
     }
 }
 
@@ -198,6 +199,46 @@ async fn handle_stats(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Handle `zeroclaw memory cache stats|clear`.
+fn handle_cache(config: &Config, command: &crate::MemoryCacheCommands) -> Result<()> {
+    let cache = super::ResponseCache::new(
+        &config.workspace_dir,
+        config.memory.response_cache_ttl_minutes,
+        config.memory.response_cache_max_entries,
+    )?;
+
+    match command {
+        crate::MemoryCacheCommands::Stats => {
+            let (entries, hits, tokens_saved) = cache.stats()?;
+            println!("Response Cache:\n");
+            println!(
+                "  Enabled:      {}",
+                if config.memory.response_cache_enabled {
+                    style("yes").green().bold().to_string()
+                } else {
+                    style("no").yellow().bold().to_string()
+                }
+            );
+            println!(
+                "  TTL:          {} min",
+                config.memory.response_cache_ttl_minutes
+            );
+            println!("  Entries:      {entries}");
+            println!("  Hits:         {hits}");
+            println!("  Tokens saved: {tokens_saved}");
+        }
+        crate::MemoryCacheCommands::Clear => {
+            let cleared = cache.clear()?;
+            println!(
+                "{} Cleared {cleared} cached responses.",
+                style("✓").green().bold()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 async fn handle_clear(
     config: &Config,
     key: Option<String>,
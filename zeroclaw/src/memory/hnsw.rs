@@ -0,0 +1,749 @@
+// HNSW (Hierarchical Navigable Small World) index for approximate
+// nearest-neighbour recall over memory embeddings.
+//
+// SQLite stays the source of truth: each node remembers the `updated_at`
+// stamp of the row it was built from so `SqliteMemory` can reconcile the
+// index against the `memories` table when it opens `brain.db`. The graph is
+// persisted next to it as `brain.hnsw`.
+
+use anyhow::Context;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Max neighbours per node on upper layers.
+const M: usize = 16;
+/// Max neighbours per node on layer 0 (denser base layer).
+const M0: usize = 2 * M;
+/// Candidate list size while building the graph.
+const EF_CONSTRUCTION: usize = 100;
+/// Level assignment: each extra layer has a 1-in-`LEVEL_FANOUT` chance.
+const LEVEL_FANOUT: u64 = 16;
+/// Hard cap on graph height.
+const MAX_LEVEL: usize = 12;
+/// Compact the graph once this fraction (1/n) of nodes are tombstones.
+const COMPACT_TOMBSTONE_RATIO: usize = 2;
+
+const MAGIC: &[u8; 8] = b"ZCHNSW01";
+
+/// Similarity wrapper with a total order for the search heaps.
+#[derive(Clone, Copy)]
+struct Scored {
+    sim: f32,
+    slot: usize,
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sim
+            .total_cmp(&other.sim)
+            .then_with(|| self.slot.cmp(&other.slot))
+    }
+}
+
+struct Node {
+    id: String,
+    stamp: String,
+    /// L2-normalised, so cosine similarity is a plain dot product.
+    vector: Vec<f32>,
+    /// Neighbour slots per layer (`links[0]` is the base layer).
+    links: Vec<Vec<usize>>,
+    deleted: bool,
+}
+
+/// In-memory HNSW graph keyed by memory id.
+pub struct HnswIndex {
+    dims: usize,
+    nodes: Vec<Node>,
+    slots: HashMap<String, usize>,
+    entry_point: Option<usize>,
+    max_level: usize,
+    tombstones: usize,
+    rng_state: u64,
+    dirty: bool,
+}
+
+impl Default for HnswIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HnswIndex {
+    pub fn new() -> Self {
+        Self {
+            dims: 0,
+            nodes: Vec::new(),
+            slots: HashMap::new(),
+            entry_point: None,
+            max_level: 0,
+            tombstones: 0,
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+            dirty: false,
+        }
+    }
+
+    /// Vector dimensionality (0 while empty).
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// Number of live (non-deleted) entries.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Whether the graph changed since the last load/save.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// `updated_at` stamp recorded for `id`, if indexed.
+    pub fn stamp(&self, id: &str) -> Option<&str> {
+        self.slots
+            .get(id)
+            .map(|&slot| self.nodes[slot].stamp.as_str())
+    }
+
+    /// Ids and stamps of all live entries.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.slots.values().map(|&slot| {
+            let node = &self.nodes[slot];
+            (node.id.as_str(), node.stamp.as_str())
+        })
+    }
+
+    /// Insert or replace `id`. Returns `false` if the vector cannot be
+    /// indexed (zero norm, or dimensions differ from the existing graph).
+    pub fn insert(&mut self, id: &str, stamp: &str, vector: &[f32]) -> bool {
+        let Some(vector) = normalize(vector) else {
+            self.remove(id);
+            return false;
+        };
+        self.remove(id);
+        if self.nodes.is_empty() {
+            self.dims = vector.len();
+        }
+        if vector.len() != self.dims {
+            return false;
+        }
+        self.dirty = true;
+
+        let level = self.random_level();
+        let slot = self.nodes.len();
+        self.nodes.push(Node {
+            id: id.to_string(),
+            stamp: stamp.to_string(),
+            vector,
+            links: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.slots.insert(id.to_string(), slot);
+
+        let Some(entry) = self.entry_point else {
+            self.entry_point = Some(slot);
+            self.max_level = level;
+            return true;
+        };
+
+        let query = self.nodes[slot].vector.clone();
+        let mut current = entry;
+        for layer in (level + 1..=self.max_level).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let candidates = self.search_layer(&query, current, EF_CONSTRUCTION, layer);
+            let max_links = if layer == 0 { M0 } else { M };
+            let neighbours: Vec<usize> = candidates
+                .iter()
+                .filter(|c| c.slot != slot && !self.nodes[c.slot].deleted)
+                .take(M)
+                .map(|c| c.slot)
+                .collect();
+
+            for &neighbour in &neighbours {
+                self.nodes[neighbour].links[layer].push(slot);
+                if self.nodes[neighbour].links[layer].len() > max_links {
+                    self.prune_links(neighbour, layer, max_links);
+                }
+            }
+            self.nodes[slot].links[layer] = neighbours;
+
+            if let Some(best) = candidates.first() {
+                current = best.slot;
+            }
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(slot);
+        }
+        true
+    }
+
+    /// Remove `id` from search results. Returns `true` if it was indexed.
+    ///
+    /// Nodes are tombstoned so the graph stays navigable; the index is
+    /// compacted once tombstones dominate.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let Some(slot) = self.slots.remove(id) else {
+            return false;
+        };
+        self.nodes[slot].deleted = true;
+        self.tombstones += 1;
+        self.dirty = true;
+        if self.tombstones * COMPACT_TOMBSTONE_RATIO > self.nodes.len() {
+            self.compact();
+        }
+        true
+    }
+
+    /// Top-`k` live entries by cosine similarity (only positive scores),
+    /// exploring `ef` candidates on the base layer.
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(String, f32)> {
+        if k == 0 || query.len() != self.dims || self.is_empty() {
+            return Vec::new();
+        }
+        let (Some(query), Some(entry)) = (normalize(query), self.entry_point) else {
+            return Vec::new();
+        };
+
+        let mut current = entry;
+        for layer in (1..=self.max_level).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+
+        self.search_layer(&query, current, ef.max(k), 0)
+            .into_iter()
+            .filter(|c| !self.nodes[c.slot].deleted && c.sim > 0.0)
+            .take(k)
+            .map(|c| (self.nodes[c.slot].id.clone(), c.sim.min(1.0)))
+            .collect()
+    }
+
+    /// Load a persisted index.
+    ///
+    /// Every length in the file is checked against the bytes left to read
+    /// before anything is allocated, so a truncated or corrupt index fails
+    /// with an error instead of exhausting memory.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        let size = file.metadata()?.len();
+        let mut reader = IndexReader {
+            inner: BufReader::new(file),
+            remaining: size,
+        };
+
+        let mut magic = [0_u8; 8];
+        reader.read_exact(&mut magic)?;
+        anyhow::ensure!(&magic == MAGIC, "not an HNSW index file");
+
+        let dims = reader.read_count(4, "vector dimensions")?;
+        let max_level = read_len(&mut reader)?;
+        anyhow::ensure!(max_level <= MAX_LEVEL, "corrupt HNSW level count");
+        let entry_raw = read_u64(&mut reader)?;
+        let rng_state = read_u64(&mut reader)?;
+        // Smallest node: deleted flag, two empty strings, vector, layer count
+        let node_bytes = 13 + 4 * u64::try_from(dims)?;
+        let node_count = reader.read_count(node_bytes, "node count")?;
+
+        let mut nodes = Vec::with_capacity(node_count);
+        let mut slots = HashMap::new();
+        let mut tombstones = 0;
+        for slot in 0..node_count {
+            let mut flag = [0_u8; 1];
+            reader.read_exact(&mut flag)?;
+            let deleted = flag[0] != 0;
+            let id = read_string(&mut reader)?;
+            let stamp = read_string(&mut reader)?;
+            let mut vector = Vec::with_capacity(dims);
+            for _ in 0..dims {
+                let mut buf = [0_u8; 4];
+                reader.read_exact(&mut buf)?;
+                vector.push(f32::from_le_bytes(buf));
+            }
+            let layer_count = reader.read_count(4, "layer count")?;
+            let mut links = Vec::with_capacity(layer_count);
+            for _ in 0..layer_count {
+                let count = reader.read_count(4, "neighbour count")?;
+                let mut layer = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let neighbour = read_len(&mut reader)?;
+                    anyhow::ensure!(neighbour < node_count, "corrupt HNSW link");
+                    layer.push(neighbour);
+                }
+                links.push(layer);
+            }
+            if deleted {
+                tombstones += 1;
+            } else {
+                slots.insert(id.clone(), slot);
+            }
+            nodes.push(Node {
+                id,
+                stamp,
+                vector,
+                links,
+                deleted,
+            });
+        }
+
+        let entry_point = if entry_raw == u64::MAX {
+            None
+        } else {
+            let entry = usize::try_from(entry_raw)?;
+            anyhow::ensure!(entry < node_count, "corrupt HNSW entry point");
+            Some(entry)
+        };
+
+        Ok(Self {
+            dims,
+            nodes,
+            slots,
+            entry_point,
+            max_level,
+            tombstones,
+            rng_state,
+            dirty: false,
+        })
+    }
+
+    /// Persist the index atomically (write to a temp file, then rename).
+    pub fn save(&mut self, path: &Path) -> anyhow::Result<()> {
+        let tmp_path = path.with_extension("hnsw.tmp");
+        {
+            let file = std::fs::File::create(&tmp_path)
+                .with_context(|| format!("failed to create {}", tmp_path.display()))?;
+            let mut writer = BufWriter::new(file);
+            writer.write_all(MAGIC)?;
+            write_len(&mut writer, self.dims)?;
+            write_len(&mut writer, self.max_level)?;
+            let entry = match self.entry_point {
+                Some(slot) => u64::try_from(slot)?,
+                None => u64::MAX,
+            };
+            writer.write_all(&entry.to_le_bytes())?;
+            writer.write_all(&self.rng_state.to_le_bytes())?;
+            write_len(&mut writer, self.nodes.len())?;
+            for node in &self.nodes {
+                writer.write_all(&[u8::from(node.deleted)])?;
+                write_string(&mut writer, &node.id)?;
+                write_string(&mut writer, &node.stamp)?;
+                for value in &node.vector {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+                write_len(&mut writer, node.links.len())?;
+                for layer in &node.links {
+                    write_len(&mut writer, layer.len())?;
+                    for &neighbour in layer {
+                        write_len(&mut writer, neighbour)?;
+                    }
+                }
+            }
+            writer.flush()?;
+        }
+        std::fs::rename(&tmp_path, path)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    // ── Graph internals ─────────────────────────────────────────
+
+    fn similarity(&self, query: &[f32], slot: usize) -> f32 {
+        self.nodes[slot]
+            .vector
+            .iter()
+            .zip(query)
+            .map(|(a, b)| a * b)
+            .sum()
+    }
+
+    /// Greedy walk on one layer towards the closest node to `query`.
+    fn greedy_closest(&self, query: &[f32], start: usize, layer: usize) -> usize {
+        let mut current = start;
+        let mut best = self.similarity(query, current);
+        loop {
+            let mut improved = false;
+            if let Some(links) = self.nodes[current].links.get(layer) {
+                for &neighbour in links {
+                    let sim = self.similarity(query, neighbour);
+                    if sim > best {
+                        best = sim;
+                        current = neighbour;
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// Beam search on one layer; returns up to `ef` nodes, best first.
+    fn search_layer(&self, query: &[f32], entry: usize, ef: usize, layer: usize) -> Vec<Scored> {
+        let start = Scored {
+            sim: self.similarity(query, entry),
+            slot: entry,
+        };
+        let mut visited = HashSet::from([entry]);
+        // Max-heap of candidates to expand; min-heap (via Reverse) of results.
+        let mut candidates = BinaryHeap::from([start]);
+        let mut results = BinaryHeap::from([std::cmp::Reverse(start)]);
+
+        while let Some(candidate) = candidates.pop() {
+            let worst = results.peek().map_or(f32::MIN, |r| r.0.sim);
+            if candidate.sim < worst && results.len() >= ef {
+                break;
+            }
+            let Some(links) = self.nodes[candidate.slot].links.get(layer) else {
+                continue;
+            };
+            for &neighbour in links {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let scored = Scored {
+                    sim: self.similarity(query, neighbour),
+                    slot: neighbour,
+                };
+                let worst = results.peek().map_or(f32::MIN, |r| r.0.sim);
+                if results.len() < ef || scored.sim > worst {
+                    candidates.push(scored);
+                    results.push(std::cmp::Reverse(scored));
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        let mut ordered: Vec<Scored> = results.into_iter().map(|r| r.0).collect();
+        ordered.sort_by(|a, b| b.cmp(a));
+        ordered
+    }
+
+    /// Keep only the `max_links` closest neighbours of `slot` on `layer`.
+    fn prune_links(&mut self, slot: usize, layer: usize, max_links: usize) {
+        let base = self.nodes[slot].vector.clone();
+        let mut scored: Vec<Scored> = self.nodes[slot].links[layer]
+            .iter()
+            .map(|&neighbour| Scored {
+                sim: self.similarity(&base, neighbour),
+                slot: neighbour,
+            })
+            .collect();
+        scored.sort_by(|a, b| b.cmp(a));
+        scored.truncate(max_links);
+        self.nodes[slot].links[layer] = scored.into_iter().map(|s| s.slot).collect();
+    }
+
+    /// Rebuild the graph from live nodes, dropping tombstones.
+    fn compact(&mut self) {
+        let live: Vec<Node> = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .filter(|node| !node.deleted)
+            .collect();
+        let rng_state = self.rng_state;
+        *self = Self::new();
+        self.rng_state = rng_state;
+        for node in live {
+            self.insert(&node.id, &node.stamp, &node.vector);
+        }
+        self.dirty = true;
+    }
+
+    /// Geometric level draw: P(level >= n) = `LEVEL_FANOUT`^-n.
+    fn random_level(&mut self) -> usize {
+        let mut level = 0;
+        while level < MAX_LEVEL && self.next_random().is_multiple_of(LEVEL_FANOUT) {
+            level += 1;
+        }
+        level
+    }
+
+    /// xorshift64* — deterministic so rebuilt graphs are reproducible.
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.rng_state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.rng_state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+fn normalize(vector: &[f32]) -> Option<Vec<f32>> {
+    let norm = vector
+        .iter()
+        .map(|v| f64::from(*v) * f64::from(*v))
+        .sum::<f64>()
+        .sqrt();
+    if vector.is_empty() || !norm.is_finite() || norm < f64::EPSILON {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let inv = (1.0 / norm) as f32;
+    Some(vector.iter().map(|v| v * inv).collect())
+}
+
+/// Reader that tracks how much of the index file is left, so lengths read
+/// from it can be checked before they size an allocation.
+struct IndexReader<R> {
+    inner: R,
+    remaining: u64,
+}
+
+impl<R: Read> IndexReader<R> {
+    /// Read a count of items that each take at least `item_bytes` in the
+    /// rest of the file, rejecting counts the file is too short to hold.
+    fn read_count(&mut self, item_bytes: u64, what: &str) -> anyhow::Result<usize> {
+        let count = read_len(self)?;
+        let needed = u64::try_from(count)?.saturating_mul(item_bytes);
+        anyhow::ensure!(
+            needed <= self.remaining,
+            "corrupt HNSW index: {what} ({count}) exceeds the remaining file size"
+        );
+        Ok(count)
+    }
+}
+
+impl<R: Read> Read for IndexReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.remaining = self.remaining.saturating_sub(read as u64);
+        Ok(read)
+    }
+}
+
+fn read_u64(reader: &mut impl Read) -> anyhow::Result<u64> {
+    let mut buf = [0_u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_len(reader: &mut impl Read) -> anyhow::Result<usize> {
+    let mut buf = [0_u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(usize::try_from(u32::from_le_bytes(buf))?)
+}
+
+fn read_string<R: Read>(reader: &mut IndexReader<R>) -> anyhow::Result<String> {
+    let len = reader.read_count(1, "string length")?;
+    let mut buf = vec![0_u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(String::from_utf8(buf)?)
+}
+
+fn write_len(writer: &mut impl Write, len: usize) -> anyhow::Result<()> {
+    writer.write_all(&u32::try_from(len)?.to_le_bytes())?;
+    Ok(())
+}
+
+fn write_string(writer: &mut impl Write, value: &str) -> anyhow::Result<()> {
+    write_len(writer, value.len())?;
+    writer.write_all(value.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::vector::cosine_similarity;
+    use tempfile::TempDir;
+
+    /// Deterministic pseudo-random vectors for recall checks.
+    fn vectors(count: usize, dims: usize) -> Vec<Vec<f32>> {
+        let mut state = 0x1234_5678_u32;
+        (0..count)
+            .map(|_| {
+                (0..dims)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 17;
+                        state ^= state << 5;
+                        f32::from(u16::try_from(state % 1000).unwrap()) / 1000.0
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn exact_top(data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<String> {
+        let mut scored: Vec<(usize, f32)> = data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, cosine_similarity(query, v)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored
+            .into_iter()
+            .take(k)
+            .map(|(i, _)| format!("m{i}"))
+            .collect()
+    }
+
+    #[test]
+    fn empty_index_returns_nothing() {
+        let index = HnswIndex::new();
+        assert!(index.search(&[1.0, 0.0], 5, 16).is_empty());
+        assert_eq!(index.dims(), 0);
+    }
+
+    #[test]
+    fn finds_exact_match_first() {
+        let mut index = HnswIndex::new();
+        index.insert("a", "t1", &[1.0, 0.0, 0.0]);
+        index.insert("b", "t1", &[0.0, 1.0, 0.0]);
+        index.insert("c", "t1", &[0.7, 0.7, 0.0]);
+
+        let hits = index.search(&[0.0, 1.0, 0.0], 2, 16);
+        assert_eq!(hits[0].0, "b");
+        assert!((hits[0].1 - 1.0).abs() < 1e-5);
+        assert_eq!(hits[1].0, "c");
+    }
+
+    #[test]
+    fn high_recall_against_brute_force() {
+        let data = vectors(2000, 32);
+        let mut index = HnswIndex::new();
+        for (i, v) in data.iter().enumerate() {
+            assert!(index.insert(&format!("m{i}"), "t", v));
+        }
+
+        let queries = vectors(20, 32);
+        let mut hits = 0;
+        for q in &queries {
+            let expected = exact_top(&data, q, 10);
+            let found: HashSet<String> = index
+                .search(q, 10, 64)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            hits += expected.iter().filter(|id| found.contains(*id)).count();
+        }
+        assert!(hits >= 180, "recall@10 too low: {hits}/200");
+    }
+
+    #[test]
+    fn remove_and_update_are_reflected() {
+        let mut index = HnswIndex::new();
+        index.insert("a", "t1", &[1.0, 0.0]);
+        index.insert("b", "t1", &[0.0, 1.0]);
+
+        assert!(index.remove("a"));
+        assert!(!index.remove("a"));
+        assert!(index
+            .search(&[1.0, 0.0], 5, 16)
+            .iter()
+            .all(|(id, _)| id != "a"));
+
+        index.insert("b", "t2", &[1.0, 0.0]);
+        assert_eq!(index.stamp("b"), Some("t2"));
+        assert_eq!(index.search(&[1.0, 0.0], 1, 16)[0].0, "b");
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn rejects_zero_and_mismatched_vectors() {
+        let mut index = HnswIndex::new();
+        assert!(!index.insert("z", "t", &[0.0, 0.0]));
+        assert!(index.insert("a", "t", &[1.0, 0.0]));
+        assert!(!index.insert("b", "t", &[1.0, 0.0, 0.0]));
+        assert!(index.search(&[1.0, 0.0, 0.0], 1, 16).is_empty());
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.hnsw");
+        let data = vectors(200, 8);
+        let mut index = HnswIndex::new();
+        for (i, v) in data.iter().enumerate() {
+            index.insert(&format!("m{i}"), &format!("s{i}"), v);
+        }
+        index.remove("m3");
+        index.save(&path).unwrap();
+        assert!(!index.is_dirty());
+
+        let loaded = HnswIndex::load(&path).unwrap();
+        assert_eq!(loaded.len(), 199);
+        assert_eq!(loaded.dims(), 8);
+        assert_eq!(loaded.stamp("m7"), Some("s7"));
+        assert_eq!(loaded.stamp("m3"), None);
+        assert_eq!(
+            loaded.search(&data[42], 1, 32),
+            index.search(&data[42], 1, 32)
+        );
+    }
+
+    #[test]
+    fn load_rejects_garbage() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.hnsw");
+        std::fs::write(&path, b"not an index").unwrap();
+        assert!(HnswIndex::load(&path).is_err());
+    }
+
+    #[test]
+    fn load_rejects_lengths_past_the_end_of_the_file() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.hnsw");
+        let mut index = HnswIndex::new();
+        index.insert("a", "t", &[1.0, 0.0]);
+        index.save(&path).unwrap();
+        let saved = std::fs::read(&path).unwrap();
+
+        // Header: magic, dims, max level, entry point, rng state, node count
+        let corrupt = |offset: usize| {
+            let mut bytes = saved.clone();
+            bytes[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+            std::fs::write(&path, bytes).unwrap();
+            HnswIndex::load(&path).err().unwrap().to_string()
+        };
+        assert!(corrupt(8).contains("vector dimensions"));
+        assert!(corrupt(32).contains("node count"));
+        // First node: deleted flag, then the id's length
+        assert!(corrupt(37).contains("string length"));
+
+        std::fs::write(&path, &saved[..saved.len() - 2]).unwrap();
+        assert!(HnswIndex::load(&path).is_err());
+    }
+
+    #[test]
+    fn compaction_keeps_live_entries() {
+        let data = vectors(100, 8);
+        let mut index = HnswIndex::new();
+        for (i, v) in data.iter().enumerate() {
+            index.insert(&format!("m{i}"), "t", v);
+        }
+        for i in 0..60 {
+            index.remove(&format!("m{i}"));
+        }
+        assert_eq!(index.len(), 40);
+        assert!(index.nodes.len() < 100, "tombstones should be compacted");
+        let hits = index.search(&data[80], 1, 32);
+        assert_eq!(hits[0].0, "m80");
+    }
+}
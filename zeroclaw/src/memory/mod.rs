@@ -2,6 +2,7 @@ pub mod backend;
 pub mod chunker;
 pub mod cli;
 pub mod embeddings;
+pub mod hnsw;
 pub mod hygiene;
 pub mod lucid;
 pub mod markdown;
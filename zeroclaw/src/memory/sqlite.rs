@@ -1,11 +1,13 @@
 use super::embeddings::EmbeddingProvider;
+use super::hnsw::HnswIndex;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
 use anyhow::Context;
 use async_trait::async_trait;
 use chrono::Local;
 use parking_lot::Mutex;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
@@ -16,11 +18,52 @@ use uuid::Uuid;
 
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
+/// Base-layer beam width for ANN queries.
+const ANN_EF_SEARCH: usize = 64;
+/// Over-fetch factor when category/session filters drop ANN candidates.
+const ANN_FILTER_OVERSAMPLE: usize = 8;
+
+/// HNSW index persisted next to `brain.db`.
+///
+/// Updated in memory on every `store`/`forget` and written to disk on open,
+/// `reindex` and drop; a stale file is reconciled against SQLite on open.
+/// Writes committed through other connections (another `SqliteMemory` on the
+/// same workspace, hygiene, another process) are picked up before each search
+/// via SQLite's `data_version`.
+struct AnnState {
+    index: HnswIndex,
+    path: PathBuf,
+    /// `PRAGMA data_version` when the index was last reconciled.
+    data_version: i64,
+}
+
+impl AnnState {
+    /// Reconcile the index with `memories` if another connection has
+    /// committed since the last check.
+    fn refresh(&mut self, conn: &Connection) -> anyhow::Result<()> {
+        let version = SqliteMemory::data_version(conn)?;
+        if version != self.data_version {
+            SqliteMemory::sync_ann_index(conn, &mut self.index)?;
+            self.data_version = version;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) {
+        if !self.index.is_dirty() {
+            return;
+        }
+        if let Err(e) = self.index.save(&self.path) {
+            tracing::warn!("Failed to persist memory ANN index: {e}");
+        }
+    }
+}
 
 /// SQLite-backed persistent memory — the brain
 ///
 /// Full-stack search engine:
 /// - **Vector DB**: embeddings stored as BLOB, cosine similarity search
+/// - **ANN Index**: HNSW graph (`brain.hnsw`) for sub-linear vector recall
 /// - **Keyword Search**: FTS5 virtual table with BM25 scoring
 /// - **Hybrid Merge**: weighted fusion of vector + keyword results
 /// - **Embedding Cache**: LRU-evicted cache to avoid redundant API calls
@@ -28,6 +71,7 @@ const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 pub struct SqliteMemory {
     conn: Arc<Mutex<Connection>>,
     db_path: PathBuf,
+    ann: Arc<Mutex<AnnState>>,
     embedder: Arc<dyn EmbeddingProvider>,
     vector_weight: f32,
     keyword_weight: f32,
@@ -83,9 +127,27 @@ impl SqliteMemory {
 
         Self::init_schema(&conn)?;
 
+        let ann_path = db_path.with_extension("hnsw");
+        let mut index = if ann_path.exists() {
+            HnswIndex::load(&ann_path).unwrap_or_else(|e| {
+                tracing::warn!("Rebuilding memory ANN index: {e}");
+                HnswIndex::new()
+            })
+        } else {
+            HnswIndex::new()
+        };
+        Self::sync_ann_index(&conn, &mut index)?;
+        let mut ann = AnnState {
+            index,
+            path: ann_path,
+            data_version: Self::data_version(&conn)?,
+        };
+        ann.flush();
+
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
             db_path,
+            ann: Arc::new(Mutex::new(ann)),
             embedder,
             vector_weight,
             keyword_weight,
@@ -327,11 +389,144 @@ impl SqliteMemory {
         Ok(results)
     }
 
-    /// Vector similarity search: scan embeddings and compute cosine similarity.
+    /// Counter that changes whenever another connection commits to the database.
+    fn data_version(conn: &Connection) -> anyhow::Result<i64> {
+        Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+    }
+
+    /// Reconcile the ANN index with the `memories` table.
+    ///
+    /// Entries whose row vanished or whose `updated_at` changed are dropped,
+    /// and rows missing from the index are (re-)inserted. This repairs an
+    /// index left stale by a crash or by out-of-band writes (e.g. hygiene).
+    fn sync_ann_index(conn: &Connection, index: &mut HnswIndex) -> anyhow::Result<()> {
+        let mut stmt =
+            conn.prepare("SELECT id, updated_at FROM memories WHERE embedding IS NOT NULL")?;
+        let current: HashMap<String, String> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+
+        let stale: Vec<String> = index
+            .entries()
+            .filter(|(id, stamp)| current.get(*id).map(String::as_str) != Some(*stamp))
+            .map(|(id, _)| id.to_string())
+            .collect();
+        for id in &stale {
+            index.remove(id);
+        }
+
+        let missing: HashSet<&str> = current
+            .keys()
+            .map(String::as_str)
+            .filter(|id| index.stamp(id).is_none())
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT id, updated_at, embedding FROM memories WHERE embedding IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Vec<u8>>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (id, stamp, blob) = row?;
+            if missing.contains(id.as_str()) {
+                index.insert(&id, &stamp, &vector::bytes_to_vec(&blob));
+            }
+        }
+        Ok(())
+    }
+
+    /// Vector similarity search.
+    ///
+    /// Uses the HNSW index when it covers the query's dimensionality and
+    /// falls back to an exact scan otherwise, or when category/session
+    /// filters leave too few ANN candidates.
+    fn vector_search(
+        conn: &Connection,
+        index: Option<&HnswIndex>,
+        query_embedding: &[f32],
+        limit: usize,
+        category: Option<&str>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<(String, f32)>> {
+        if let Some(index) = index.filter(|i| !i.is_empty() && i.dims() == query_embedding.len()) {
+            let filtered = category.is_some() || session_id.is_some();
+            let fetch = if filtered {
+                limit.saturating_mul(ANN_FILTER_OVERSAMPLE)
+            } else {
+                limit
+            };
+            let candidates = index.search(query_embedding, fetch, ANN_EF_SEARCH.max(fetch));
+            let exhausted = candidates.len() < fetch;
+            let mut hits = if filtered {
+                Self::filter_ann_candidates(conn, candidates, category, session_id)?
+            } else {
+                candidates
+            };
+            if hits.len() >= limit || exhausted {
+                hits.truncate(limit);
+                return Ok(hits);
+            }
+        }
+
+        Self::vector_scan(conn, query_embedding, limit, category, session_id)
+    }
+
+    /// Keep only ANN candidates matching the category/session filters.
+    fn filter_ann_candidates(
+        conn: &Connection,
+        candidates: Vec<(String, f32)>,
+        category: Option<&str>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<(String, f32)>> {
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+        let placeholders: String = (1..=candidates.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut sql = format!("SELECT id FROM memories WHERE id IN ({placeholders})");
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = candidates
+            .iter()
+            .map(|(id, _)| Box::new(id.clone()) as Box<dyn rusqlite::types::ToSql>)
+            .collect();
+        let mut idx = candidates.len() + 1;
+        if let Some(cat) = category {
+            let _ = write!(sql, " AND category = ?{idx}");
+            param_values.push(Box::new(cat.to_string()));
+            idx += 1;
+        }
+        if let Some(sid) = session_id {
+            let _ = write!(sql, " AND session_id = ?{idx}");
+            param_values.push(Box::new(sid.to_string()));
+        }
+
+        let mut stmt = conn.prepare(&sql)?;
+        let params_ref: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(AsRef::as_ref).collect();
+        let allowed: HashSet<String> = stmt
+            .query_map(params_ref.as_slice(), |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+
+        Ok(candidates
+            .into_iter()
+            .filter(|(id, _)| allowed.contains(id))
+            .collect())
+    }
+
+    /// Exact vector search: scan embeddings and compute cosine similarity.
     ///
     /// Optional `category` and `session_id` filters reduce full-table scans
     /// when the caller already knows the scope of relevant memories.
-    fn vector_search(
+    fn vector_scan(
         conn: &Connection,
         query_embedding: &[f32],
         limit: usize,
@@ -376,7 +571,8 @@ impl SqliteMemory {
         Ok(scored)
     }
 
-    /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
+    /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure,
+    /// then rebuild the ANN index from the stored embeddings
     #[allow(dead_code)]
     pub async fn reindex(&self) -> anyhow::Result<usize> {
         // Step 1: Rebuild FTS5
@@ -391,10 +587,31 @@ impl SqliteMemory {
         }
 
         // Step 2: Re-embed all memories that lack embeddings
-        if self.embedder.dimensions() == 0 {
-            return Ok(0);
-        }
+        let count = if self.embedder.dimensions() == 0 {
+            0
+        } else {
+            self.embed_missing().await?
+        };
 
+        // Step 3: Rebuild the ANN index from scratch
+        let conn = self.conn.clone();
+        let ann = self.ann.clone();
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let conn = conn.lock();
+            let mut fresh = HnswIndex::new();
+            Self::sync_ann_index(&conn, &mut fresh)?;
+            let mut ann = ann.lock();
+            ann.index = fresh;
+            ann.flush();
+            Ok(())
+        })
+        .await??;
+
+        Ok(count)
+    }
+
+    /// Compute embeddings for rows stored without one; returns how many.
+    async fn embed_missing(&self) -> anyhow::Result<usize> {
         let conn = self.conn.clone();
         let entries: Vec<(String, String)> = tokio::task::spawn_blocking(move || {
             let conn = conn.lock();
@@ -444,12 +661,11 @@ impl Memory for SqliteMemory {
         session_id: Option<&str>,
     ) -> anyhow::Result<()> {
         // Compute embedding (async, before blocking work)
-        let embedding_bytes = self
-            .get_or_compute_embedding(content)
-            .await?
-            .map(|emb| vector::vec_to_bytes(&emb));
+        let embedding = self.get_or_compute_embedding(content).await?;
+        let embedding_bytes = embedding.as_deref().map(vector::vec_to_bytes);
 
         let conn = self.conn.clone();
+        let ann = self.ann.clone();
         let key = key.to_string();
         let content = content.to_string();
         let sid = session_id.map(String::from);
@@ -471,6 +687,18 @@ impl Memory for SqliteMemory {
                     session_id = excluded.session_id",
                 params![id, key, content, cat, embedding_bytes, now, now, sid],
             )?;
+
+            // Upserts keep the original row id, so look it up for the index.
+            let (row_id, stamp): (String, String) = conn.query_row(
+                "SELECT id, updated_at FROM memories WHERE key = ?1",
+                params![key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            let mut ann = ann.lock();
+            match embedding {
+                Some(emb) => ann.index.insert(&row_id, &stamp, &emb),
+                None => ann.index.remove(&row_id),
+            };
             Ok(())
         })
         .await?
@@ -490,6 +718,7 @@ impl Memory for SqliteMemory {
         let query_embedding = self.get_or_compute_embedding(query).await?;
 
         let conn = self.conn.clone();
+        let ann = self.ann.clone();
         let query = query.to_string();
         let sid = session_id.map(String::from);
         let vector_weight = self.vector_weight;
@@ -504,7 +733,12 @@ impl Memory for SqliteMemory {
 
             // Vector similarity search (if embeddings available)
             let vector_results = if let Some(ref qe) = query_embedding {
-                Self::vector_search(&conn, qe, limit * 2, None, session_ref).unwrap_or_default()
+                let mut ann = ann.lock();
+                if let Err(e) = ann.refresh(&conn) {
+                    tracing::warn!("Failed to refresh memory ANN index: {e}");
+                }
+                Self::vector_search(&conn, Some(&ann.index), qe, limit * 2, None, session_ref)
+                    .unwrap_or_default()
             } else {
                 Vec::new()
             };
@@ -752,9 +986,21 @@ impl Memory for SqliteMemory {
         let conn = self.conn.clone();
         let key = key.to_string();
 
+        let ann = self.ann.clone();
+
         tokio::task::spawn_blocking(move || -> anyhow::Result<bool> {
             let conn = conn.lock();
+            let id: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM memories WHERE key = ?1",
+                    params![key],
+                    |row| row.get(0),
+                )
+                .optional()?;
             let affected = conn.execute("DELETE FROM memories WHERE key = ?1", params![key])?;
+            if let Some(id) = id {
+                ann.lock().index.remove(&id);
+            }
             Ok(affected > 0)
         })
         .await?
@@ -781,6 +1027,12 @@ impl Memory for SqliteMemory {
     }
 }
 
+impl Drop for SqliteMemory {
+    fn drop(&mut self) {
+        self.ann.lock().flush();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1897,4 +2149,209 @@ mod tests {
 
         assert_eq!(mem.count().await.unwrap(), 1);
     }
+
+    // ── ANN index ────────────────────────────────────────────────
+
+    /// Bag-of-letters embedding: deterministic, 26 dims, no network.
+    struct LetterEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for LetterEmbedding {
+        fn name(&self) -> &str {
+            "letters"
+        }
+
+        fn dimensions(&self) -> usize {
+            26
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut v = vec![0.0_f32; 26];
+                    for b in text.bytes().filter(u8::is_ascii_lowercase) {
+                        v[usize::from(b - b'a')] += 1.0;
+                    }
+                    v
+                })
+                .collect())
+        }
+    }
+
+    fn letter_sqlite(dir: &Path) -> SqliteMemory {
+        SqliteMemory::with_embedder(dir, Arc::new(LetterEmbedding), 0.7, 0.3, 1000, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn ann_index_tracks_store_and_forget() {
+        let tmp = TempDir::new().unwrap();
+        let mem = letter_sqlite(tmp.path());
+        mem.store("a", "aaaa", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "bbbb", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("z", "zzzz", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        assert_eq!(mem.ann.lock().index.len(), 3);
+
+        // Upsert keeps a single entry per key.
+        mem.store("a", "aaab", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(mem.ann.lock().index.len(), 3);
+
+        assert!(mem.forget("b").await.unwrap());
+        assert_eq!(mem.ann.lock().index.len(), 2);
+
+        let query = LetterEmbedding.embed_one("zz").await.unwrap();
+        let conn = mem.conn.lock();
+        let ann = mem.ann.lock();
+        let hits =
+            SqliteMemory::vector_search(&conn, Some(&ann.index), &query, 1, None, None).unwrap();
+        let z_id: String = conn
+            .query_row("SELECT id FROM memories WHERE key = 'z'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(hits[0].0, z_id);
+    }
+
+    #[tokio::test]
+    async fn ann_search_matches_exact_scan_with_filters() {
+        let tmp = TempDir::new().unwrap();
+        let mem = letter_sqlite(tmp.path());
+        for (i, text) in ["abc", "abd", "xyz", "aaz", "bcd"].iter().enumerate() {
+            let category = if i % 2 == 0 {
+                MemoryCategory::Core
+            } else {
+                MemoryCategory::Daily
+            };
+            mem.store(&format!("k{i}"), text, category, Some("s1"))
+                .await
+                .unwrap();
+        }
+
+        let query = LetterEmbedding.embed_one("abc").await.unwrap();
+        let conn = mem.conn.lock();
+        let ann = mem.ann.lock();
+        for category in [None, Some("core"), Some("daily")] {
+            let ann_hits = SqliteMemory::vector_search(
+                &conn,
+                Some(&ann.index),
+                &query,
+                3,
+                category,
+                Some("s1"),
+            )
+            .unwrap();
+            let exact = SqliteMemory::vector_scan(&conn, &query, 3, category, Some("s1")).unwrap();
+            // Ties may come back in a different order, so compare as sets.
+            let ann_ids: HashSet<&str> = ann_hits.iter().map(|(id, _)| id.as_str()).collect();
+            let exact_ids: HashSet<&str> = exact.iter().map(|(id, _)| id.as_str()).collect();
+            assert_eq!(ann_ids, exact_ids, "category filter {category:?}");
+            assert!((ann_hits[0].1 - exact[0].1).abs() < 1e-4);
+        }
+        assert!(SqliteMemory::vector_search(
+            &conn,
+            Some(&ann.index),
+            &query,
+            3,
+            None,
+            Some("other")
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[tokio::test]
+    async fn ann_index_persists_and_reconciles_on_open() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mem = letter_sqlite(tmp.path());
+            mem.store("a", "alpha", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+            mem.store("b", "beta", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+            mem.store("c", "gamma", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+        let index_path = tmp.path().join("memory").join("brain.hnsw");
+        assert!(index_path.exists());
+        assert_eq!(HnswIndex::load(&index_path).unwrap().len(), 3);
+
+        // Out-of-band delete (as hygiene does) must not leave ghost entries.
+        {
+            let conn = Connection::open(tmp.path().join("memory").join("brain.db")).unwrap();
+            conn.execute("DELETE FROM memories WHERE key = 'b'", [])
+                .unwrap();
+        }
+
+        let mem = letter_sqlite(tmp.path());
+        assert_eq!(mem.ann.lock().index.len(), 2);
+        assert_eq!(HnswIndex::load(&index_path).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn ann_index_sees_writes_from_other_instances() {
+        let tmp = TempDir::new().unwrap();
+        let reader = letter_sqlite(tmp.path());
+        let writer = letter_sqlite(tmp.path());
+        reader
+            .store("a", "alpha", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        writer
+            .store("z", "zzzz", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let results = reader.recall("zz", 1, None).await.unwrap();
+        assert_eq!(results[0].key, "z");
+        assert_eq!(reader.ann.lock().index.len(), 2);
+
+        writer
+            .store("z", "alpha alpha", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert!(writer.forget("a").await.unwrap());
+        reader.recall("alpha", 1, None).await.unwrap();
+        let ann = reader.ann.lock();
+        assert_eq!(ann.index.len(), 1);
+        let stamp: String = reader
+            .conn
+            .lock()
+            .query_row(
+                "SELECT updated_at FROM memories WHERE key = 'z'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(ann.index.entries().next().unwrap().1, stamp);
+    }
+
+    #[tokio::test]
+    async fn reindex_rebuilds_ann_index() {
+        let tmp = TempDir::new().unwrap();
+        let mem = letter_sqlite(tmp.path());
+        mem.store("a", "alpha", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "beta", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.ann.lock().index = HnswIndex::new();
+
+        mem.reindex().await.unwrap();
+        assert_eq!(mem.ann.lock().index.len(), 2);
+
+        let results = mem.recall("alpha", 1, None).await.unwrap();
+        assert_eq!(results[0].key, "a");
+    }
 }
@@ -44,6 +44,16 @@ impl Observer for LogObserver {
             ObserverEvent::ChannelMessage { channel, direction } => {
                 info!(channel = %channel, direction = %direction, "channel.message");
             }
+            ObserverEvent::ResponseCache { model, hit } => {
+                info!(model = %model, hit = hit, "response_cache.lookup");
+            }
+            ObserverEvent::OutboundLeak {
+                surface,
+                action,
+                patterns,
+            } => {
+                info!(surface = %surface, action = %action, patterns = %patterns.join(", "), "outbound.leak");
+            }
             ObserverEvent::HeartbeatTick => {
                 info!("heartbeat.tick");
             }
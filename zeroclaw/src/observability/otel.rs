@@ -22,6 +22,8 @@ pub struct OtelObserver {
     tool_duration: Histogram<f64>,
     channel_messages: Counter<u64>,
     heartbeat_ticks: Counter<u64>,
+    response_cache_lookups: Counter<u64>,
+    outbound_leaks: Counter<u64>,
     errors: Counter<u64>,
     request_latency: Histogram<f64>,
     tokens_used: Counter<u64>,
@@ -126,6 +128,16 @@ impl OtelObserver {
             .with_description("Total heartbeat ticks")
             .build();
 
+        let response_cache_lookups = meter
+            .u64_counter("zeroclaw.response_cache.lookups")
+            .with_description("Response cache lookups by model and hit/miss")
+            .build();
+
+        let outbound_leaks = meter
+            .u64_counter("zeroclaw.outbound.leaks")
+            .with_description("Outbound content matched by the leak guard, by surface and action")
+            .build();
+
         let errors = meter
             .u64_counter("zeroclaw.errors")
             .with_description("Total errors by component")
@@ -163,6 +175,8 @@ impl OtelObserver {
             tool_duration,
             channel_messages,
             heartbeat_ticks,
+            response_cache_lookups,
+            outbound_leaks,
             errors,
             request_latency,
             tokens_used,
@@ -316,6 +330,26 @@ impl Observer for OtelObserver {
                     ],
                 );
             }
+            ObserverEvent::ResponseCache { model, hit } => {
+                self.response_cache_lookups.add(
+                    1,
+                    &[
+                        KeyValue::new("model", model.clone()),
+                        KeyValue::new("hit", hit.to_string()),
+                    ],
+                );
+            }
+            ObserverEvent::OutboundLeak {
+                surface, action, ..
+            } => {
+                self.outbound_leaks.add(
+                    1,
+                    &[
+                        KeyValue::new("surface", surface.clone()),
+                        KeyValue::new("action", action.clone()),
+                    ],
+                );
+            }
             ObserverEvent::HeartbeatTick => {
                 self.heartbeat_ticks.add(1, &[]);
             }
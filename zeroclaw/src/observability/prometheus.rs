@@ -16,6 +16,9 @@ pub struct PrometheusObserver {
     channel_messages: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
     errors: IntCounterVec,
+    response_cache_hits: IntCounterVec,
+    response_cache_misses: IntCounterVec,
+    outbound_leaks: IntCounterVec,
 
     // Histograms
     agent_duration: HistogramVec,
@@ -26,6 +29,8 @@ pub struct PrometheusObserver {
     tokens_used: prometheus::IntGauge,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
+    provider_circuit_state: GaugeVec,
+    provider_health_score: GaugeVec,
 }
 
 impl PrometheusObserver {
@@ -81,6 +86,33 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let response_cache_hits = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_response_cache_hits_total",
+                "LLM requests served from the response cache",
+            ),
+            &["model"],
+        )
+        .expect("valid metric");
+
+        let response_cache_misses = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_response_cache_misses_total",
+                "Cacheable LLM requests not found in the response cache",
+            ),
+            &["model"],
+        )
+        .expect("valid metric");
+
+        let outbound_leaks = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_outbound_leaks_total",
+                "Outbound content matched by the leak guard",
+            ),
+            &["surface", "action"],
+        )
+        .expect("valid metric");
+
         let agent_duration = HistogramVec::new(
             HistogramOpts::new(
                 "zeroclaw_agent_duration_seconds",
@@ -128,6 +160,24 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let provider_circuit_state = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_provider_circuit_state",
+                "Provider circuit state (0 = closed, 1 = half-open, 2 = open)",
+            ),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
+        let provider_health_score = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_provider_health_score",
+                "Moving provider success rate (1.0 = healthy)",
+            ),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
         // Register all metrics
         registry.register(Box::new(agent_starts.clone())).ok();
         registry.register(Box::new(llm_requests.clone())).ok();
@@ -139,12 +189,25 @@ impl PrometheusObserver {
         registry.register(Box::new(channel_messages.clone())).ok();
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
+        registry
+            .register(Box::new(response_cache_hits.clone()))
+            .ok();
+        registry
+            .register(Box::new(response_cache_misses.clone()))
+            .ok();
+        registry.register(Box::new(outbound_leaks.clone())).ok();
         registry.register(Box::new(agent_duration.clone())).ok();
         registry.register(Box::new(tool_duration.clone())).ok();
         registry.register(Box::new(request_latency.clone())).ok();
         registry.register(Box::new(tokens_used.clone())).ok();
         registry.register(Box::new(active_sessions.clone())).ok();
         registry.register(Box::new(queue_depth.clone())).ok();
+        registry
+            .register(Box::new(provider_circuit_state.clone()))
+            .ok();
+        registry
+            .register(Box::new(provider_health_score.clone()))
+            .ok();
 
         Self {
             registry,
@@ -156,17 +219,36 @@ impl PrometheusObserver {
             channel_messages,
             heartbeat_ticks,
             errors,
+            response_cache_hits,
+            response_cache_misses,
+            outbound_leaks,
             agent_duration,
             tool_duration,
             request_latency,
             tokens_used,
             active_sessions,
             queue_depth,
+            provider_circuit_state,
+            provider_health_score,
+        }
+    }
+
+    /// Refresh the provider circuit gauges from the shared breaker registry.
+    fn refresh_provider_gauges(&self) {
+        for circuit in crate::providers::circuit::global().snapshot() {
+            let labels = [circuit.provider.as_str(), circuit.model.as_str()];
+            self.provider_circuit_state
+                .with_label_values(&labels)
+                .set(circuit.state.gauge_value());
+            self.provider_health_score
+                .with_label_values(&labels)
+                .set(circuit.health_score);
         }
     }
 
     /// Encode all registered metrics into Prometheus text exposition format.
     pub fn encode(&self) -> String {
+        self.refresh_provider_gauges();
         let encoder = TextEncoder::new();
         let families = self.registry.gather();
         let mut buf = Vec::new();
@@ -242,6 +324,21 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[channel, direction])
                     .inc();
             }
+            ObserverEvent::ResponseCache { model, hit } => {
+                let counter = if *hit {
+                    &self.response_cache_hits
+                } else {
+                    &self.response_cache_misses
+                };
+                counter.with_label_values(&[model]).inc();
+            }
+            ObserverEvent::OutboundLeak {
+                surface, action, ..
+            } => {
+                self.outbound_leaks
+                    .with_label_values(&[surface, action])
+                    .inc();
+            }
             ObserverEvent::HeartbeatTick => {
                 self.heartbeat_ticks.inc();
             }
@@ -493,4 +590,24 @@ mod tests {
         assert!(!output.contains("zeroclaw_tokens_input_total{"));
         assert!(!output.contains("zeroclaw_tokens_output_total{"));
     }
+
+    #[test]
+    fn encode_exports_provider_circuit_gauges() {
+        let policy = crate::providers::circuit::CircuitPolicy {
+            failure_threshold: 1,
+            cooldown: Duration::from_secs(3600),
+        };
+        crate::providers::circuit::global().record_failure(
+            "prom-circuit-test",
+            "model-x",
+            policy,
+            "down",
+        );
+
+        let output = PrometheusObserver::new().encode();
+        assert!(output.contains(
+            r#"zeroclaw_provider_circuit_state{model="model-x",provider="prom-circuit-test"} 2"#
+        ));
+        assert!(output.contains("zeroclaw_provider_health_score{"));
+    }
 }
@@ -56,6 +56,18 @@ pub enum ObserverEvent {
         /// `"inbound"` or `"outbound"`.
         direction: String,
     },
+    /// A deterministic LLM request was looked up in the response cache.
+    ResponseCache { model: String, hit: bool },
+    /// Outbound content matched the leak guard.
+    ///
+    /// Carries pattern names only, never the matched text.
+    OutboundLeak {
+        /// `"channel"`, `"gateway"` or `"provider"`.
+        surface: String,
+        /// `"warn"`, `"redact"` or `"block"`.
+        action: String,
+        patterns: Vec<String>,
+    },
     /// Periodic heartbeat tick from the runtime keep-alive loop.
     HeartbeatTick,
     /// An error occurred in a named component.
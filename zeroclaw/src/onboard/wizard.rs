@@ -145,6 +145,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         agent: crate::config::schema::AgentConfig::default(),
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
+        routing: crate::config::RoutingConfig::default(),
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
@@ -169,6 +170,9 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        tts: crate::config::TtsConfig::default(),
+        sessions: crate::config::SessionsConfig::default(),
+        sop: crate::config::SopConfig::default(),
     };
 
     println!(
@@ -496,6 +500,7 @@ async fn run_quick_setup_with_home(
         agent: crate::config::schema::AgentConfig::default(),
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
+        routing: crate::config::RoutingConfig::default(),
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
@@ -520,6 +525,9 @@ async fn run_quick_setup_with_home(
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        tts: crate::config::TtsConfig::default(),
+        sessions: crate::config::SessionsConfig::default(),
+        sop: crate::config::SopConfig::default(),
     };
 
     config.save().await?;
@@ -0,0 +1,380 @@
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult, TokenUsage,
+};
+use super::Provider;
+use crate::memory::ResponseCache;
+use crate::observability::{Observer, ObserverEvent};
+use async_trait::async_trait;
+use futures_util::stream;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Response-caching wrapper — serves repeated deterministic requests from
+/// [`ResponseCache`] instead of calling the inner provider.
+///
+/// Only requests at temperature 0 without tools are cached; anything else is
+/// passed straight through. Wraps any provider, so it composes with
+/// `ReliableProvider` and `RouterProvider` (the model hint is part of the key).
+pub struct CachedProvider {
+    inner: Box<dyn Provider>,
+    cache: Arc<ResponseCache>,
+    observer: Option<Arc<dyn Observer>>,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Box<dyn Provider>, cache: Arc<ResponseCache>) -> Self {
+        Self {
+            inner,
+            cache,
+            observer: None,
+        }
+    }
+
+    /// Report cache hits and misses to `observer`.
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    fn is_cacheable(temperature: f64) -> bool {
+        temperature.abs() < f64::EPSILON
+    }
+
+    /// Key a conversation by its system prompt plus every non-system turn.
+    fn key_for_messages(model: &str, messages: &[ChatMessage]) -> String {
+        let system = messages
+            .iter()
+            .find(|m| m.role == "system")
+            .map(|m| m.content.as_str());
+        let mut transcript = String::new();
+        for message in messages.iter().filter(|m| m.role != "system") {
+            let _ = write!(
+                transcript,
+                "{}\u{1f}{}\u{1e}",
+                message.role, message.content
+            );
+        }
+        ResponseCache::cache_key(model, system, &transcript)
+    }
+
+    fn lookup(&self, key: &str, model: &str) -> Option<String> {
+        let cached = match self.cache.get(key) {
+            Ok(cached) => cached,
+            Err(e) => {
+                tracing::warn!("Response cache lookup failed: {e}");
+                None
+            }
+        };
+        if let Some(observer) = &self.observer {
+            observer.record_event(&ObserverEvent::ResponseCache {
+                model: model.to_string(),
+                hit: cached.is_some(),
+            });
+        }
+        cached
+    }
+
+    fn store(&self, key: &str, model: &str, response: &str, usage: Option<&TokenUsage>) {
+        if response.is_empty() {
+            return;
+        }
+        let tokens = usage
+            .and_then(|u| u.output_tokens)
+            .unwrap_or_else(|| (response.chars().count() / 4) as u64);
+        let tokens = u32::try_from(tokens).unwrap_or(u32::MAX);
+        if let Err(e) = self.cache.put(key, model, response, tokens) {
+            tracing::warn!("Response cache write failed: {e}");
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for CachedProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if !Self::is_cacheable(temperature) {
+            return self
+                .inner
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
+        }
+
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system_prompt) = system_prompt {
+            messages.push(ChatMessage::system(system_prompt));
+        }
+        messages.push(ChatMessage::user(message));
+        let key = Self::key_for_messages(model, &messages);
+        if let Some(cached) = self.lookup(&key, model) {
+            return Ok(cached);
+        }
+
+        let response = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await?;
+        self.store(&key, model, &response, None);
+        Ok(response)
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if !Self::is_cacheable(temperature) {
+            return self
+                .inner
+                .chat_with_history(messages, model, temperature)
+                .await;
+        }
+
+        let key = Self::key_for_messages(model, messages);
+        if let Some(cached) = self.lookup(&key, model) {
+            return Ok(cached);
+        }
+
+        let response = self
+            .inner
+            .chat_with_history(messages, model, temperature)
+            .await?;
+        self.store(&key, model, &response, None);
+        Ok(response)
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let has_tools = request.tools.is_some_and(|tools| !tools.is_empty());
+        if has_tools || !Self::is_cacheable(temperature) {
+            return self.inner.chat(request, model, temperature).await;
+        }
+
+        let key = Self::key_for_messages(model, request.messages);
+        if let Some(cached) = self.lookup(&key, model) {
+            return Ok(ChatResponse {
+                text: Some(cached),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            });
+        }
+
+        let response = self.inner.chat(request, model, temperature).await?;
+        if response.tool_calls.is_empty() {
+            self.store(
+                &key,
+                model,
+                response.text_or_empty(),
+                response.usage.as_ref(),
+            );
+        }
+        Ok(response)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await
+    }
+
+    fn capabilities(&self) -> super::traits::ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[crate::tools::ToolSpec]) -> super::traits::ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    // Streams are never cached; they go straight to the inner provider.
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_system(system_prompt, message, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_history(messages, model, temperature, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::PrometheusObserver;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Provider for CountingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("{message} #{n}"))
+        }
+    }
+
+    fn cached(tmp: &TempDir) -> (CachedProvider, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = Arc::new(ResponseCache::new(tmp.path(), 60, 100).unwrap());
+        let provider = CachedProvider::new(
+            Box::new(CountingProvider {
+                calls: Arc::clone(&calls),
+            }),
+            cache,
+        );
+        (provider, calls)
+    }
+
+    #[tokio::test]
+    async fn deterministic_requests_are_served_from_cache() {
+        let tmp = TempDir::new().unwrap();
+        let (provider, calls) = cached(&tmp);
+
+        let first = provider
+            .chat_with_system(Some("sys"), "summarise", "m", 0.0)
+            .await
+            .unwrap();
+        let second = provider
+            .chat_with_system(Some("sys"), "summarise", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // A different system prompt is a different request.
+        provider
+            .chat_with_system(Some("other"), "summarise", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn non_zero_temperature_bypasses_cache() {
+        let tmp = TempDir::new().unwrap();
+        let (provider, calls) = cached(&tmp);
+
+        for _ in 0..2 {
+            provider
+                .chat_with_system(None, "hello", "m", 0.7)
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn requests_with_tools_bypass_cache() {
+        let tmp = TempDir::new().unwrap();
+        let (provider, calls) = cached(&tmp);
+        let messages = vec![ChatMessage::user("list files")];
+        let tools = vec![crate::tools::ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+
+        for _ in 0..2 {
+            provider
+                .chat(
+                    ChatRequest {
+                        messages: &messages,
+                        tools: Some(&tools),
+                    },
+                    "m",
+                    0.0,
+                )
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        for _ in 0..2 {
+            provider
+                .chat(
+                    ChatRequest {
+                        messages: &messages,
+                        tools: None,
+                    },
+                    "m",
+                    0.0,
+                )
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn hits_and_misses_reach_prometheus() {
+        let tmp = TempDir::new().unwrap();
+        let (provider, _calls) = cached(&tmp);
+        let prom = Arc::new(PrometheusObserver::new());
+        let provider = provider.with_observer(prom.clone());
+
+        for _ in 0..3 {
+            provider
+                .chat_with_history(&[ChatMessage::user("digest")], "m", 0.0)
+                .await
+                .unwrap();
+        }
+
+        let text = prom.encode();
+        assert!(text.contains(r#"zeroclaw_response_cache_hits_total{model="m"} 2"#));
+        assert!(text.contains(r#"zeroclaw_response_cache_misses_total{model="m"} 1"#));
+    }
+}
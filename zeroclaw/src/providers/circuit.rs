@@ -0,0 +1,527 @@
+//! Circuit breakers and health scores per provider/model pair.
+//!
+//! [`ReliableProvider`](super::reliable::ReliableProvider) asks
+//! [`CircuitBreakers::allow`] before each attempt and reports the outcome
+//! afterwards. After `failure_threshold` consecutive failures the circuit
+//! opens and the pair is skipped without a request; once the cooldown has
+//! passed a single half-open probe is let through, and its result closes or
+//! re-opens the circuit. An outage therefore costs one probe per cooldown
+//! instead of a full retry budget on every message.
+//!
+//! State changes are mirrored into [`crate::health`] as
+//! `provider:<name>/<model>` components, listed under `providers` by
+//! `/api/health`, exported as Prometheus gauges, and saved to
+//! `state/provider_health.json` so a restart does not forget an outage. Saves
+//! are debounced and written on a blocking thread, so a burst of failures
+//! costs one file write off the async runtime.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+/// Weight given to the newest outcome in the health score.
+const HEALTH_ALPHA: f64 = 0.2;
+/// Longest error message kept per circuit.
+const MAX_ERROR_CHARS: usize = 200;
+/// How long state changes are batched before the health file is rewritten.
+const PERSIST_DEBOUNCE: Duration = Duration::from_secs(1);
+
+static GLOBAL_BREAKERS: LazyLock<CircuitBreakers> = LazyLock::new(CircuitBreakers::default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests are skipped until the cooldown elapses.
+    Open,
+    /// One probe request decides whether to close or re-open.
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+
+    /// Numeric value exported by the `zeroclaw_provider_circuit_state` gauge.
+    pub fn gauge_value(self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::HalfOpen => 1.0,
+            Self::Open => 2.0,
+        }
+    }
+}
+
+/// When circuits open and how long they stay open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitPolicy {
+    /// Consecutive failures that open the circuit; `0` disables breaking.
+    pub failure_threshold: u32,
+    /// Time an open circuit waits before letting a probe through.
+    pub cooldown: Duration,
+}
+
+impl CircuitPolicy {
+    pub const DISABLED: Self = Self {
+        failure_threshold: 0,
+        cooldown: Duration::ZERO,
+    };
+
+    pub fn is_enabled(&self) -> bool {
+        self.failure_threshold > 0
+    }
+}
+
+/// Public view of one circuit, as served by `/api/health` and persisted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitSnapshot {
+    pub provider: String,
+    pub model: String,
+    pub state: CircuitState,
+    /// Moving success rate (0.0–1.0); 1.0 is fully healthy.
+    pub health_score: f64,
+    pub consecutive_failures: u32,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Circuit {
+    snapshot: CircuitSnapshot,
+    /// When the current half-open probe was let through.
+    probe_started: Option<DateTime<Utc>>,
+}
+
+impl Circuit {
+    fn new(provider: &str, model: &str) -> Self {
+        Self {
+            snapshot: CircuitSnapshot {
+                provider: provider.to_string(),
+                model: model.to_string(),
+                state: CircuitState::Closed,
+                health_score: 1.0,
+                consecutive_failures: 0,
+                opened_at: None,
+                last_error: None,
+            },
+            probe_started: None,
+        }
+    }
+}
+
+fn component_name(provider: &str, model: &str) -> String {
+    format!("provider:{provider}/{model}")
+}
+
+fn elapsed_since(at: DateTime<Utc>) -> Duration {
+    (Utc::now() - at).to_std().unwrap_or(Duration::ZERO)
+}
+
+/// Health file contents waiting to be written.
+#[derive(Debug, Default)]
+struct PendingSave {
+    /// Newest serialized circuits and where they go.
+    latest: Mutex<Option<(PathBuf, String)>>,
+    /// Held while taking and writing `latest`, so an older save never lands
+    /// after a newer one.
+    writer: Mutex<()>,
+}
+
+impl PendingSave {
+    fn write(&self) {
+        let _writer = self.writer.lock();
+        let Some((path, json)) = self.latest.lock().take() else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let tmp = path.with_extension("json.tmp");
+        if let Err(e) = std::fs::write(&tmp, json).and_then(|()| std::fs::rename(&tmp, &path)) {
+            tracing::warn!(path = %path.display(), "Failed to persist provider health: {e}");
+        }
+    }
+}
+
+/// Registry of circuits keyed by provider and model.
+#[derive(Debug, Default)]
+pub struct CircuitBreakers {
+    circuits: Mutex<BTreeMap<(String, String), Circuit>>,
+    persist_path: Mutex<Option<PathBuf>>,
+    pending: Arc<PendingSave>,
+}
+
+impl CircuitBreakers {
+    /// Whether an attempt against `provider`/`model` may go ahead. An open
+    /// circuit whose cooldown has elapsed turns half-open and admits exactly
+    /// one probe.
+    pub fn allow(&self, provider: &str, model: &str, policy: CircuitPolicy) -> bool {
+        if !policy.is_enabled() {
+            return true;
+        }
+        let mut circuits = self.circuits.lock();
+        let Some(circuit) = circuits.get_mut(&(provider.to_string(), model.to_string())) else {
+            return true;
+        };
+
+        match circuit.snapshot.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                let cooled_down = circuit
+                    .snapshot
+                    .opened_at
+                    .is_none_or(|at| elapsed_since(at) >= policy.cooldown);
+                if cooled_down {
+                    circuit.snapshot.state = CircuitState::HalfOpen;
+                    circuit.probe_started = Some(Utc::now());
+                    tracing::info!(provider, model, "Circuit half-open, sending probe request");
+                }
+                cooled_down
+            }
+            CircuitState::HalfOpen => {
+                // A probe that never reported back (e.g. a cancelled turn)
+                // must not wedge the circuit, so allow a new one after the
+                // cooldown.
+                let probe_free = circuit
+                    .probe_started
+                    .is_none_or(|at| elapsed_since(at) >= policy.cooldown);
+                if probe_free {
+                    circuit.probe_started = Some(Utc::now());
+                }
+                probe_free
+            }
+        }
+    }
+
+    /// Record a successful call, closing the circuit if it was not already.
+    pub fn record_success(&self, provider: &str, model: &str) {
+        let recovered = {
+            let mut circuits = self.circuits.lock();
+            let circuit = circuits
+                .entry((provider.to_string(), model.to_string()))
+                .or_insert_with(|| Circuit::new(provider, model));
+            let snapshot = &mut circuit.snapshot;
+            snapshot.health_score = HEALTH_ALPHA + (1.0 - HEALTH_ALPHA) * snapshot.health_score;
+            let recovered =
+                snapshot.state != CircuitState::Closed || snapshot.consecutive_failures > 0;
+            snapshot.state = CircuitState::Closed;
+            snapshot.consecutive_failures = 0;
+            snapshot.opened_at = None;
+            snapshot.last_error = None;
+            circuit.probe_started = None;
+            recovered
+        };
+
+        if recovered {
+            tracing::info!(provider, model, "Provider circuit closed");
+            crate::health::mark_component_ok(&component_name(provider, model));
+            self.persist();
+        }
+    }
+
+    /// Record a failed call and return the circuit's new state.
+    pub fn record_failure(
+        &self,
+        provider: &str,
+        model: &str,
+        policy: CircuitPolicy,
+        error: &str,
+    ) -> CircuitState {
+        let (state, opened, failures) = {
+            let mut circuits = self.circuits.lock();
+            let circuit = circuits
+                .entry((provider.to_string(), model.to_string()))
+                .or_insert_with(|| Circuit::new(provider, model));
+            let snapshot = &mut circuit.snapshot;
+            snapshot.health_score *= 1.0 - HEALTH_ALPHA;
+            snapshot.consecutive_failures = snapshot.consecutive_failures.saturating_add(1);
+            snapshot.last_error = Some(crate::util::truncate_with_ellipsis(error, MAX_ERROR_CHARS));
+
+            let should_open = policy.is_enabled()
+                && match snapshot.state {
+                    CircuitState::HalfOpen => true,
+                    CircuitState::Closed => {
+                        snapshot.consecutive_failures >= policy.failure_threshold
+                    }
+                    CircuitState::Open => false,
+                };
+            if should_open {
+                snapshot.state = CircuitState::Open;
+                snapshot.opened_at = Some(Utc::now());
+                circuit.probe_started = None;
+            }
+            (snapshot.state, should_open, snapshot.consecutive_failures)
+        };
+
+        if opened {
+            tracing::warn!(
+                provider,
+                model,
+                consecutive_failures = failures,
+                cooldown_secs = policy.cooldown.as_secs(),
+                "Provider circuit opened; skipping until cooldown elapses"
+            );
+            crate::health::mark_component_error(
+                &component_name(provider, model),
+                format!("circuit open after {failures} consecutive failures: {error}"),
+            );
+        }
+        self.persist();
+        state
+    }
+
+    /// Current circuit for a pair, if it has ever been called.
+    pub fn get(&self, provider: &str, model: &str) -> Option<CircuitSnapshot> {
+        self.circuits
+            .lock()
+            .get(&(provider.to_string(), model.to_string()))
+            .map(|circuit| circuit.snapshot.clone())
+    }
+
+    /// All known circuits, sorted by provider and model.
+    pub fn snapshot(&self) -> Vec<CircuitSnapshot> {
+        self.circuits
+            .lock()
+            .values()
+            .map(|circuit| circuit.snapshot.clone())
+            .collect()
+    }
+
+    /// Persist circuits to `path` from now on, first restoring whatever an
+    /// earlier process saved there. Later calls with the same path are no-ops.
+    pub fn persist_to(&self, path: PathBuf) {
+        {
+            let mut current = self.persist_path.lock();
+            if current.as_deref() == Some(path.as_path()) {
+                return;
+            }
+            *current = Some(path.clone());
+        }
+        self.restore(&path);
+    }
+
+    fn restore(&self, path: &Path) {
+        let Ok(raw) = std::fs::read_to_string(path) else {
+            return;
+        };
+        let saved: Vec<CircuitSnapshot> = match serde_json::from_str(&raw) {
+            Ok(saved) => saved,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "Ignoring unreadable provider health file: {e}");
+                return;
+            }
+        };
+
+        let mut circuits = self.circuits.lock();
+        for snapshot in saved {
+            let key = (snapshot.provider.clone(), snapshot.model.clone());
+            if circuits.contains_key(&key) {
+                continue;
+            }
+            let component = component_name(&snapshot.provider, &snapshot.model);
+            match (snapshot.state, &snapshot.last_error) {
+                (CircuitState::Closed, _) => crate::health::mark_component_ok(&component),
+                (_, Some(error)) => crate::health::mark_component_error(&component, error),
+                (_, None) => crate::health::mark_component_error(&component, "circuit open"),
+            }
+            circuits.insert(
+                key,
+                Circuit {
+                    snapshot,
+                    probe_started: None,
+                },
+            );
+        }
+    }
+
+    /// Write any debounced save now.
+    pub fn flush(&self) {
+        self.pending.write();
+    }
+
+    /// Queue the current circuits for the health file. Inside a tokio
+    /// runtime the write happens on a blocking thread after
+    /// [`PERSIST_DEBOUNCE`]; outside one it happens immediately.
+    fn persist(&self) {
+        let Some(path) = self.persist_path.lock().clone() else {
+            return;
+        };
+        let json = match serde_json::to_string_pretty(&self.snapshot()) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize provider health: {e}");
+                return;
+            }
+        };
+        let already_queued = self.pending.latest.lock().replace((path, json)).is_some();
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            self.pending.write();
+            return;
+        };
+        if already_queued {
+            return;
+        }
+        let pending = Arc::clone(&self.pending);
+        runtime.spawn(async move {
+            tokio::time::sleep(PERSIST_DEBOUNCE).await;
+            let _ = tokio::task::spawn_blocking(move || pending.write()).await;
+        });
+    }
+}
+
+/// The process-wide registry used by `ReliableProvider`.
+pub fn global() -> &'static CircuitBreakers {
+    &GLOBAL_BREAKERS
+}
+
+/// Default location of the persisted circuits under the zeroclaw directory.
+pub fn health_file(zeroclaw_dir: &Path) -> PathBuf {
+    zeroclaw_dir.join("state").join("provider_health.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(threshold: u32, cooldown: Duration) -> CircuitPolicy {
+        CircuitPolicy {
+            failure_threshold: threshold,
+            cooldown,
+        }
+    }
+
+    #[test]
+    fn opens_after_threshold_and_skips_until_cooldown() {
+        let breakers = CircuitBreakers::default();
+        let policy = policy(2, Duration::from_secs(3600));
+
+        assert!(breakers.allow("p", "m", policy));
+        assert_eq!(
+            breakers.record_failure("p", "m", policy, "boom"),
+            CircuitState::Closed
+        );
+        assert!(breakers.allow("p", "m", policy));
+        assert_eq!(
+            breakers.record_failure("p", "m", policy, "boom"),
+            CircuitState::Open
+        );
+        assert!(!breakers.allow("p", "m", policy));
+        // Other models on the same provider are unaffected.
+        assert!(breakers.allow("p", "other", policy));
+
+        let circuit = breakers.get("p", "m").unwrap();
+        assert_eq!(circuit.consecutive_failures, 2);
+        assert!(circuit.health_score < 1.0);
+        assert_eq!(circuit.last_error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn half_open_admits_one_probe_and_success_closes() {
+        let breakers = CircuitBreakers::default();
+        let policy = policy(1, Duration::ZERO);
+
+        breakers.record_failure("p", "m", policy, "down");
+        assert!(breakers.allow("p", "m", policy));
+        assert_eq!(
+            breakers.get("p", "m").unwrap().state,
+            CircuitState::HalfOpen
+        );
+
+        breakers.record_success("p", "m");
+        let circuit = breakers.get("p", "m").unwrap();
+        assert_eq!(circuit.state, CircuitState::Closed);
+        assert_eq!(circuit.consecutive_failures, 0);
+        assert!(circuit.last_error.is_none());
+    }
+
+    #[test]
+    fn failed_probe_reopens_circuit() {
+        let breakers = CircuitBreakers::default();
+        let probe_policy = policy(3, Duration::ZERO);
+        for _ in 0..3 {
+            breakers.record_failure("p", "m", probe_policy, "down");
+        }
+        assert!(breakers.allow("p", "m", probe_policy));
+
+        let long = policy(3, Duration::from_secs(3600));
+        assert_eq!(
+            breakers.record_failure("p", "m", long, "still down"),
+            CircuitState::Open
+        );
+        assert!(!breakers.allow("p", "m", long));
+    }
+
+    #[test]
+    fn half_open_blocks_concurrent_probes() {
+        let breakers = CircuitBreakers::default();
+        breakers.record_failure("p", "m", policy(1, Duration::ZERO), "down");
+
+        let long = policy(1, Duration::from_secs(3600));
+        // The open circuit has no cooldown left under the zero policy, so it
+        // turns half-open; a second caller under a long cooldown must wait.
+        assert!(breakers.allow("p", "m", policy(1, Duration::ZERO)));
+        assert!(!breakers.allow("p", "m", long));
+    }
+
+    #[test]
+    fn disabled_policy_never_opens() {
+        let breakers = CircuitBreakers::default();
+        for _ in 0..10 {
+            assert_eq!(
+                breakers.record_failure("p", "m", CircuitPolicy::DISABLED, "boom"),
+                CircuitState::Closed
+            );
+        }
+        assert!(breakers.allow("p", "m", CircuitPolicy::DISABLED));
+    }
+
+    #[test]
+    fn circuits_survive_restart_through_health_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = health_file(dir.path());
+        let policy = policy(1, Duration::from_secs(3600));
+
+        let before = CircuitBreakers::default();
+        before.persist_to(path.clone());
+        before.record_failure("circuit-restart", "m", policy, "outage");
+        assert!(path.exists());
+
+        let after = CircuitBreakers::default();
+        after.persist_to(path);
+        let restored = after.get("circuit-restart", "m").unwrap();
+        assert_eq!(restored.state, CircuitState::Open);
+        assert_eq!(restored.last_error.as_deref(), Some("outage"));
+        assert!(!after.allow("circuit-restart", "m", policy));
+    }
+
+    #[tokio::test]
+    async fn saves_inside_a_runtime_are_debounced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = health_file(dir.path());
+        let policy = policy(3, Duration::from_secs(3600));
+
+        let breakers = CircuitBreakers::default();
+        breakers.persist_to(path.clone());
+        for _ in 0..3 {
+            breakers.record_failure("circuit-debounce", "m", policy, "outage");
+        }
+        assert!(!path.exists());
+
+        breakers.flush();
+        let saved: Vec<CircuitSnapshot> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved[0].state, CircuitState::Open);
+        assert_eq!(saved[0].consecutive_failures, 3);
+    }
+}
@@ -18,6 +18,8 @@
 
 pub mod anthropic;
 pub mod bedrock;
+pub mod cached;
+pub mod circuit;
 pub mod compatible;
 pub mod copilot;
 pub mod gemini;
@@ -26,7 +28,9 @@ pub mod openai;
 pub mod openai_codex;
 pub mod openrouter;
 pub mod reliable;
+pub mod replay;
 pub mod router;
+pub mod stats;
 pub mod telnyx;
 pub mod traits;
 
@@ -1339,11 +1343,58 @@ pub fn create_resilient_provider_with_options(
         reliability.provider_backoff_ms,
     )
     .with_api_keys(reliability.api_keys.clone())
-    .with_model_fallbacks(reliability.model_fallbacks.clone());
+    .with_model_fallbacks(reliability.model_fallbacks.clone())
+    .with_circuit_breaker(
+        reliability.circuit_breaker_threshold,
+        std::time::Duration::from_secs(reliability.circuit_breaker_cooldown_secs),
+    );
+
+    if let Some(dir) = options.zeroclaw_dir.as_deref() {
+        circuit::global().persist_to(circuit::health_file(dir));
+    }
 
     Ok(Box::new(reliable))
 }
 
+/// Wrap `provider` in a [`CachedProvider`](cached::CachedProvider) when
+/// `[memory] response_cache_enabled` is set; otherwise return it unchanged.
+pub fn with_response_cache(
+    provider: Box<dyn Provider>,
+    memory: &crate::config::MemoryConfig,
+    workspace_dir: &std::path::Path,
+    observer: Option<std::sync::Arc<dyn crate::observability::Observer>>,
+) -> Box<dyn Provider> {
+    let Some(cache) = crate::memory::create_response_cache(memory, workspace_dir) else {
+        return provider;
+    };
+    let cached = cached::CachedProvider::new(provider, std::sync::Arc::new(cache));
+    Box::new(match observer {
+        Some(observer) => cached.with_observer(observer),
+        None => cached,
+    })
+}
+
+/// Wrap `provider` in a [`RecordingProvider`](replay::RecordingProvider)
+/// when `[observability] provider_fixture_path` is set; otherwise return it
+/// unchanged.
+pub fn with_recording(
+    provider: Box<dyn Provider>,
+    observability: &crate::config::ObservabilityConfig,
+    workspace_dir: &std::path::Path,
+) -> Box<dyn Provider> {
+    let Some(raw) = observability
+        .provider_fixture_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+    else {
+        return provider;
+    };
+    let path = workspace_dir.join(raw);
+    tracing::info!("Recording provider exchanges to {}", path.display());
+    Box::new(replay::RecordingProvider::new(provider, path))
+}
+
 /// Create a RouterProvider if model routes are configured, otherwise return a
 /// standard resilient provider. The router wraps individual providers per route,
 /// each with its own retry/fallback chain.
@@ -1353,6 +1404,7 @@ pub fn create_routed_provider(
     api_url: Option<&str>,
     reliability: &crate::config::ReliabilityConfig,
     model_routes: &[crate::config::ModelRouteConfig],
+    routing: &crate::config::RoutingConfig,
     default_model: &str,
 ) -> anyhow::Result<Box<dyn Provider>> {
     create_routed_provider_with_options(
@@ -1361,18 +1413,21 @@ pub fn create_routed_provider(
         api_url,
         reliability,
         model_routes,
+        routing,
         default_model,
         &ProviderRuntimeOptions::default(),
     )
 }
 
 /// Create a routed provider using explicit runtime options.
+#[allow(clippy::too_many_arguments)]
 pub fn create_routed_provider_with_options(
     primary_name: &str,
     api_key: Option<&str>,
     api_url: Option<&str>,
     reliability: &crate::config::ReliabilityConfig,
     model_routes: &[crate::config::ModelRouteConfig],
+    routing: &crate::config::RoutingConfig,
     default_model: &str,
     options: &ProviderRuntimeOptions,
 ) -> anyhow::Result<Box<dyn Provider>> {
@@ -1432,16 +1487,17 @@ pub fn create_routed_provider_with_options(
                 router::Route {
                     provider_name: r.provider.clone(),
                     model: r.model.clone(),
+                    max_context_tokens: r.max_context_tokens,
+                    vision: r.vision,
                 },
             )
         })
         .collect();
 
-    Ok(Box::new(router::RouterProvider::new(
-        providers,
-        routes,
-        default_model.to_string(),
-    )))
+    Ok(Box::new(
+        router::RouterProvider::new(providers, routes, default_model.to_string())
+            .with_routing(routing.clone()),
+    ))
 }
 
 /// Information about a supported provider for display purposes.
@@ -2454,6 +2510,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: 60,
         };
 
         let provider = create_resilient_provider(
@@ -2493,6 +2551,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: 60,
         };
 
         // Primary uses a ZAI key; fallbacks (lmstudio, ollama) should NOT
@@ -2515,6 +2575,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: 60,
         };
 
         let provider =
@@ -2541,6 +2603,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: 60,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2573,6 +2637,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: 60,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2866,6 +2932,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: 60,
         };
 
         // openai-codex resolves its own OAuth credential; it should not
@@ -2895,6 +2963,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: 60,
         };
 
         let provider = create_resilient_provider("ollama", None, None, &reliability);
@@ -1,3 +1,5 @@
+use super::circuit::{self, CircuitPolicy, CircuitState};
+use super::stats;
 use super::traits::{
     ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
 };
@@ -6,7 +8,7 @@ use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // ── Error Classification ─────────────────────────────────────────────────
 // Errors are split into retryable (transient server/network failures) and
@@ -211,6 +213,17 @@ fn push_failure(
     ));
 }
 
+fn push_circuit_skip(failures: &mut Vec<String>, provider_name: &str, model: &str) {
+    tracing::debug!(
+        provider = provider_name,
+        model,
+        "Circuit open, skipping provider"
+    );
+    failures.push(format!(
+        "provider={provider_name} model={model}: skipped; circuit open"
+    ));
+}
+
 // ── Resilient Provider Wrapper ────────────────────────────────────────────
 // Three-level failover strategy: model chain → provider chain → retry loop.
 //   Outer loop:  iterate model fallback chain (original model first, then
@@ -231,6 +244,8 @@ pub struct ReliableProvider {
     key_index: AtomicUsize,
     /// Per-model fallback chains: model_name → [fallback_model_1, fallback_model_2, ...]
     model_fallbacks: HashMap<String, Vec<String>>,
+    /// Circuit breaking for failing provider/model pairs (disabled by default).
+    circuit: CircuitPolicy,
 }
 
 impl ReliableProvider {
@@ -246,6 +261,7 @@ impl ReliableProvider {
             api_keys: Vec::new(),
             key_index: AtomicUsize::new(0),
             model_fallbacks: HashMap::new(),
+            circuit: CircuitPolicy::DISABLED,
         }
     }
 
@@ -261,6 +277,16 @@ impl ReliableProvider {
         self
     }
 
+    /// Open a provider/model circuit after `failure_threshold` consecutive
+    /// failures and probe it again once `cooldown` has passed.
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.circuit = CircuitPolicy {
+            failure_threshold,
+            cooldown,
+        };
+        self
+    }
+
     /// Build the list of models to try: [original, fallback1, fallback2, ...]
     fn model_chain<'a>(&'a self, model: &'a str) -> Vec<&'a str> {
         let mut chain = vec![model];
@@ -279,6 +305,40 @@ impl ReliableProvider {
         Some(&self.api_keys[idx])
     }
 
+    fn record_success(&self, provider_name: &str, model: &str, started: Instant) {
+        stats::global().record(provider_name, model, started.elapsed(), true);
+        if self.circuit.is_enabled() {
+            circuit::global().record_success(provider_name, model);
+        }
+    }
+
+    /// Record a failed attempt and report whether its circuit is now open,
+    /// in which case remaining retries against this pair are pointless.
+    fn record_failure(
+        &self,
+        provider_name: &str,
+        model: &str,
+        started: Instant,
+        err: &anyhow::Error,
+    ) -> bool {
+        // Oversized prompts say nothing about provider health.
+        if is_context_window_exceeded(err) {
+            return false;
+        }
+        stats::global().record(provider_name, model, started.elapsed(), false);
+        // Only outages (5xx, timeouts, transient rate limits) count toward
+        // the breaker; a bad request or revoked key is not the provider's fault.
+        if !self.circuit.is_enabled() || is_non_retryable(err) || is_non_retryable_rate_limit(err) {
+            return false;
+        }
+        circuit::global().record_failure(
+            provider_name,
+            model,
+            self.circuit,
+            &compact_error_detail(err),
+        ) == CircuitState::Open
+    }
+
     /// Compute backoff duration, respecting Retry-After if present.
     fn compute_backoff(&self, base: u64, err: &anyhow::Error) -> u64 {
         if let Some(retry_after) = parse_retry_after_ms(err) {
@@ -309,6 +369,7 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
+        crate::security::estop::enforce_network("provider")?;
         let models = self.model_chain(model);
         let mut failures = Vec::new();
 
@@ -318,14 +379,20 @@ impl Provider for ReliableProvider {
         // retryable error, sleep with exponential backoff and retry.
         for current_model in &models {
             for (provider_name, provider) in &self.providers {
+                if !circuit::global().allow(provider_name, current_model, self.circuit) {
+                    push_circuit_skip(&mut failures, provider_name, current_model);
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let started = Instant::now();
                     match provider
                         .chat_with_system(system_prompt, message, current_model, temperature)
                         .await
                     {
                         Ok(resp) => {
+                            self.record_success(provider_name, current_model, started);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -338,6 +405,8 @@ impl Provider for ReliableProvider {
                             return Ok(resp);
                         }
                         Err(e) => {
+                            let circuit_open =
+                                self.record_failure(provider_name, current_model, started, &e);
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
@@ -387,6 +456,10 @@ impl Provider for ReliableProvider {
                                 break;
                             }
 
+                            if circuit_open {
+                                break;
+                            }
+
                             if attempt < self.max_retries {
                                 let wait = self.compute_backoff(backoff_ms, &e);
                                 tracing::warn!(
@@ -433,19 +506,26 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
+        crate::security::estop::enforce_network("provider")?;
         let models = self.model_chain(model);
         let mut failures = Vec::new();
 
         for current_model in &models {
             for (provider_name, provider) in &self.providers {
+                if !circuit::global().allow(provider_name, current_model, self.circuit) {
+                    push_circuit_skip(&mut failures, provider_name, current_model);
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let started = Instant::now();
                     match provider
                         .chat_with_history(messages, current_model, temperature)
                         .await
                     {
                         Ok(resp) => {
+                            self.record_success(provider_name, current_model, started);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -458,6 +538,8 @@ impl Provider for ReliableProvider {
                             return Ok(resp);
                         }
                         Err(e) => {
+                            let circuit_open =
+                                self.record_failure(provider_name, current_model, started, &e);
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
@@ -505,6 +587,10 @@ impl Provider for ReliableProvider {
                                 break;
                             }
 
+                            if circuit_open {
+                                break;
+                            }
+
                             if attempt < self.max_retries {
                                 let wait = self.compute_backoff(backoff_ms, &e);
                                 tracing::warn!(
@@ -557,19 +643,26 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
+        crate::security::estop::enforce_network("provider")?;
         let models = self.model_chain(model);
         let mut failures = Vec::new();
 
         for current_model in &models {
             for (provider_name, provider) in &self.providers {
+                if !circuit::global().allow(provider_name, current_model, self.circuit) {
+                    push_circuit_skip(&mut failures, provider_name, current_model);
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let started = Instant::now();
                     match provider
                         .chat_with_tools(messages, tools, current_model, temperature)
                         .await
                     {
                         Ok(resp) => {
+                            self.record_success(provider_name, current_model, started);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -582,6 +675,8 @@ impl Provider for ReliableProvider {
                             return Ok(resp);
                         }
                         Err(e) => {
+                            let circuit_open =
+                                self.record_failure(provider_name, current_model, started, &e);
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
@@ -629,6 +724,10 @@ impl Provider for ReliableProvider {
                                 break;
                             }
 
+                            if circuit_open {
+                                break;
+                            }
+
                             if attempt < self.max_retries {
                                 let wait = self.compute_backoff(backoff_ms, &e);
                                 tracing::warn!(
@@ -667,20 +766,27 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
+        crate::security::estop::enforce_network("provider")?;
         let models = self.model_chain(model);
         let mut failures = Vec::new();
 
         for current_model in &models {
             for (provider_name, provider) in &self.providers {
+                if !circuit::global().allow(provider_name, current_model, self.circuit) {
+                    push_circuit_skip(&mut failures, provider_name, current_model);
+                    continue;
+                }
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let started = Instant::now();
                     let req = ChatRequest {
                         messages: request.messages,
                         tools: request.tools,
                     };
                     match provider.chat(req, current_model, temperature).await {
                         Ok(resp) => {
+                            self.record_success(provider_name, current_model, started);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -693,6 +799,8 @@ impl Provider for ReliableProvider {
                             return Ok(resp);
                         }
                         Err(e) => {
+                            let circuit_open =
+                                self.record_failure(provider_name, current_model, started, &e);
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
@@ -740,6 +848,10 @@ impl Provider for ReliableProvider {
                                 break;
                             }
 
+                            if circuit_open {
+                                break;
+                            }
+
                             if attempt < self.max_retries {
                                 let wait = self.compute_backoff(backoff_ms, &e);
                                 tracing::warn!(
@@ -792,6 +904,13 @@ impl Provider for ReliableProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        if let Err(blocked) = crate::security::estop::enforce_network("provider") {
+            return stream::once(async move {
+                Err(super::traits::StreamError::Provider(blocked.to_string()))
+            })
+            .boxed();
+        }
+
         // Try each provider/model combination for streaming
         // For streaming, we use the first provider that supports it and has streaming enabled
         for (provider_name, provider) in &self.providers {
@@ -1980,4 +2099,128 @@ mod tests {
         assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
         assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
     }
+
+    #[tokio::test]
+    async fn open_circuit_skips_provider_until_cooldown() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "circuit-skip-primary".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: "503 service unavailable",
+                    }),
+                ),
+                (
+                    "circuit-skip-fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 0,
+                        response: "from fallback",
+                        error: "fallback down",
+                    }),
+                ),
+            ],
+            3,
+            1,
+        )
+        .with_circuit_breaker(2, Duration::from_secs(3600));
+
+        let first = provider.simple_chat("hello", "m", 0.0).await.unwrap();
+        assert_eq!(first, "from fallback");
+        // The circuit opened on the second failure, so the last retry was skipped.
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+
+        let second = provider.simple_chat("hello", "m", 0.0).await.unwrap();
+        assert_eq!(second, "from fallback");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 2);
+
+        let circuit = circuit::global()
+            .get("circuit-skip-primary", "m")
+            .expect("circuit should be tracked");
+        assert_eq!(circuit.state, CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn client_errors_do_not_open_circuit() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "circuit-client-error".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: usize::MAX,
+                    response: "never",
+                    error: "400 Bad Request: malformed tool schema",
+                }),
+            )],
+            0,
+            1,
+        )
+        .with_circuit_breaker(1, Duration::from_secs(3600));
+
+        assert!(provider.simple_chat("hello", "m", 0.0).await.is_err());
+        assert!(provider.simple_chat("hello", "m", 0.0).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(circuit::global().get("circuit-client-error", "m").is_none());
+    }
+
+    #[tokio::test]
+    async fn all_circuits_open_fails_fast_with_reason() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "circuit-fail-fast".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: usize::MAX,
+                    response: "never",
+                    error: "503 service unavailable",
+                }),
+            )],
+            0,
+            1,
+        )
+        .with_circuit_breaker(1, Duration::from_secs(3600));
+
+        assert!(provider.simple_chat("hello", "m", 0.0).await.is_err());
+        let err = provider
+            .simple_chat("hello", "m", 0.0)
+            .await
+            .expect_err("open circuit should fail without calling the provider");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(err.to_string().contains("circuit open"));
+    }
+
+    #[tokio::test]
+    async fn half_open_probe_success_closes_circuit() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "circuit-probe".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: 1,
+                    response: "back",
+                    error: "503 service unavailable",
+                }),
+            )],
+            0,
+            1,
+        )
+        .with_circuit_breaker(1, Duration::ZERO);
+
+        assert!(provider.simple_chat("hello", "m", 0.0).await.is_err());
+        let result = provider.simple_chat("hello", "m", 0.0).await.unwrap();
+        assert_eq!(result, "back");
+        assert_eq!(
+            circuit::global().get("circuit-probe", "m").unwrap().state,
+            CircuitState::Closed
+        );
+    }
 }
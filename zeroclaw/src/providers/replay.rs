@@ -0,0 +1,565 @@
+//! Record provider round-trips to fixture files and serve them back offline.
+//!
+//! [`RecordingProvider`] wraps a live provider and appends every request and
+//! response to a JSONL fixture (`[observability] provider_fixture_path`).
+//! [`ReplayProvider`] reads such a fixture and answers calls with the recorded
+//! responses in order, so a tool-loop bug or a tool-call parsing regression
+//! can be reproduced deterministically with `zeroclaw doctor replay`.
+
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamOptions,
+    StreamResult, TokenUsage, ToolCall,
+};
+use super::Provider;
+use crate::tools::ToolSpec;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use futures_util::stream;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One provider call and its response, one per fixture line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub model: String,
+    pub temperature: f64,
+    /// Whether the recorded provider used native tool calling.
+    #[serde(default)]
+    pub native_tools: bool,
+    #[serde(default)]
+    pub vision: bool,
+    pub messages: Vec<ChatMessage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolSpec>,
+    pub response: RecordedResponse,
+}
+
+/// Serializable form of [`ChatResponse`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_content: Option<String>,
+}
+
+impl From<&ChatResponse> for RecordedResponse {
+    fn from(response: &ChatResponse) -> Self {
+        Self {
+            text: response.text.clone(),
+            tool_calls: response.tool_calls.clone(),
+            input_tokens: response.usage.as_ref().and_then(|u| u.input_tokens),
+            output_tokens: response.usage.as_ref().and_then(|u| u.output_tokens),
+            reasoning_content: response.reasoning_content.clone(),
+        }
+    }
+}
+
+impl From<RecordedResponse> for ChatResponse {
+    fn from(recorded: RecordedResponse) -> Self {
+        let usage =
+            (recorded.input_tokens.is_some() || recorded.output_tokens.is_some()).then(|| {
+                TokenUsage {
+                    input_tokens: recorded.input_tokens,
+                    output_tokens: recorded.output_tokens,
+                }
+            });
+        Self {
+            text: recorded.text,
+            tool_calls: recorded.tool_calls,
+            usage,
+            reasoning_content: recorded.reasoning_content,
+        }
+    }
+}
+
+/// Read every exchange in a fixture file.
+pub fn load_fixture(path: &Path) -> Result<Vec<RecordedExchange>> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("failed to read fixture {}", path.display()))?;
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("{}:{}: invalid exchange", path.display(), idx + 1))
+        })
+        .collect()
+}
+
+/// Pass-through wrapper that appends each call to a fixture file.
+pub struct RecordingProvider {
+    inner: Box<dyn Provider>,
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl RecordingProvider {
+    pub fn new(inner: Box<dyn Provider>, path: PathBuf) -> Self {
+        Self {
+            inner,
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn record(
+        &self,
+        model: &str,
+        temperature: f64,
+        messages: &[ChatMessage],
+        tools: Option<&[ToolSpec]>,
+        response: RecordedResponse,
+    ) {
+        let exchange = RecordedExchange {
+            model: model.to_string(),
+            temperature,
+            native_tools: self.inner.supports_native_tools(),
+            vision: self.inner.supports_vision(),
+            messages: messages.to_vec(),
+            tools: tools.map(<[ToolSpec]>::to_vec).unwrap_or_default(),
+            response,
+        };
+        if let Err(e) = self.append(&exchange) {
+            tracing::warn!("Failed to record provider fixture: {e}");
+        }
+    }
+
+    fn append(&self, exchange: &RecordedExchange) -> Result<()> {
+        let line = serde_json::to_string(exchange)?;
+        let _guard = self.write_lock.lock();
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&self.path)?;
+        writeln!(file, "{line}")?;
+        Ok(())
+    }
+}
+
+fn text_response(text: &str) -> RecordedResponse {
+    RecordedResponse {
+        text: Some(text.to_string()),
+        ..RecordedResponse::default()
+    }
+}
+
+fn system_and_user(system_prompt: Option<&str>, message: &str) -> Vec<ChatMessage> {
+    let mut messages = Vec::with_capacity(2);
+    if let Some(system_prompt) = system_prompt {
+        messages.push(ChatMessage::system(system_prompt));
+    }
+    messages.push(ChatMessage::user(message));
+    messages
+}
+
+#[async_trait]
+impl Provider for RecordingProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let response = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await?;
+        self.record(
+            model,
+            temperature,
+            &system_and_user(system_prompt, message),
+            None,
+            text_response(&response),
+        );
+        Ok(response)
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let response = self
+            .inner
+            .chat_with_history(messages, model, temperature)
+            .await?;
+        self.record(model, temperature, messages, None, text_response(&response));
+        Ok(response)
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let response = self.inner.chat(request, model, temperature).await?;
+        self.record(
+            model,
+            temperature,
+            request.messages,
+            request.tools,
+            RecordedResponse::from(&response),
+        );
+        Ok(response)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let response = self
+            .inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await?;
+        self.record(
+            model,
+            temperature,
+            messages,
+            None,
+            RecordedResponse::from(&response),
+        );
+        Ok(response)
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> super::traits::ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    // Streams are not recorded; they go straight to the inner provider.
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_system(system_prompt, message, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_history(messages, model, temperature, options)
+    }
+}
+
+/// Serves recorded responses in order, ignoring the live request except to
+/// note where it diverges from the recording.
+pub struct ReplayProvider {
+    exchanges: Vec<RecordedExchange>,
+    capabilities: ProviderCapabilities,
+    state: Mutex<ReplayState>,
+}
+
+#[derive(Default)]
+struct ReplayState {
+    next: usize,
+    divergences: Vec<String>,
+}
+
+impl ReplayProvider {
+    pub fn new(exchanges: Vec<RecordedExchange>) -> Self {
+        let capabilities = exchanges
+            .first()
+            .map(|first| ProviderCapabilities {
+                native_tool_calling: first.native_tools,
+                vision: first.vision,
+            })
+            .unwrap_or_default();
+        Self {
+            exchanges,
+            capabilities,
+            state: Mutex::new(ReplayState::default()),
+        }
+    }
+
+    pub fn from_fixture(path: &Path) -> Result<Self> {
+        Ok(Self::new(load_fixture(path)?))
+    }
+
+    pub fn exchanges(&self) -> &[RecordedExchange] {
+        &self.exchanges
+    }
+
+    /// Number of recorded responses served so far.
+    pub fn served(&self) -> usize {
+        self.state.lock().next
+    }
+
+    /// Requests that did not match what was recorded, in call order.
+    pub fn divergences(&self) -> Vec<String> {
+        self.state.lock().divergences.clone()
+    }
+
+    fn next_response(&self, messages: &[ChatMessage]) -> Result<RecordedResponse> {
+        let mut state = self.state.lock();
+        let index = state.next;
+        let Some(exchange) = self.exchanges.get(index) else {
+            bail!(
+                "replay fixture exhausted after {} recorded exchange(s)",
+                self.exchanges.len()
+            );
+        };
+        state.next += 1;
+
+        if let Some(reason) = divergence(&exchange.messages, messages) {
+            tracing::warn!(exchange = index + 1, "Replay request diverged: {reason}");
+            state
+                .divergences
+                .push(format!("exchange {}: {reason}", index + 1));
+        }
+        Ok(exchange.response.clone())
+    }
+}
+
+fn divergence(recorded: &[ChatMessage], live: &[ChatMessage]) -> Option<String> {
+    if recorded.len() != live.len() {
+        return Some(format!(
+            "expected {} messages, got {}",
+            recorded.len(),
+            live.len()
+        ));
+    }
+    recorded
+        .iter()
+        .zip(live)
+        .position(|(expected, actual)| expected != actual)
+        .map(|idx| format!("message {} ({}) differs", idx + 1, live[idx].role))
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        _model: &str,
+        _temperature: f64,
+    ) -> anyhow::Result<String> {
+        let response = self.next_response(&system_and_user(system_prompt, message))?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        _model: &str,
+        _temperature: f64,
+    ) -> anyhow::Result<String> {
+        Ok(self.next_response(messages)?.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        Ok(self.next_response(request.messages)?.into())
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        _tools: &[serde_json::Value],
+        _model: &str,
+        _temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        Ok(self.next_response(messages)?.into())
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.capabilities.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct ScriptedProvider;
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: false,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(format!("echo: {message}"))
+        }
+
+        async fn chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            Ok(ChatResponse {
+                text: Some(String::new()),
+                tool_calls: vec![ToolCall {
+                    id: "call_1".into(),
+                    name: "shell".into(),
+                    arguments: r#"{"command":"ls"}"#.into(),
+                }],
+                usage: Some(TokenUsage {
+                    input_tokens: Some(10),
+                    output_tokens: Some(3),
+                }),
+                reasoning_content: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_fixture_replays_in_order() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("fixtures").join("turn.jsonl");
+        let recorder = RecordingProvider::new(Box::new(ScriptedProvider), path.clone());
+
+        let messages = vec![ChatMessage::system("sys"), ChatMessage::user("list files")];
+        let live = recorder
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
+                "model-a",
+                0.2,
+            )
+            .await
+            .unwrap();
+        recorder
+            .chat_with_system(None, "hi", "model-a", 0.2)
+            .await
+            .unwrap();
+
+        let exchanges = load_fixture(&path).unwrap();
+        assert_eq!(exchanges.len(), 2);
+        assert!(exchanges[0].native_tools);
+        assert_eq!(exchanges[0].response.input_tokens, Some(10));
+
+        let replay = ReplayProvider::new(exchanges);
+        assert!(replay.supports_native_tools());
+        let replayed = replay
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
+                "ignored",
+                0.0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(replayed.tool_calls, live.tool_calls);
+        assert_eq!(replayed.usage.unwrap().output_tokens, Some(3));
+        assert_eq!(
+            replay
+                .chat_with_system(None, "hi", "ignored", 0.0)
+                .await
+                .unwrap(),
+            "echo: hi"
+        );
+        assert!(replay.divergences().is_empty());
+
+        let err = replay
+            .chat_with_system(None, "again", "ignored", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exhausted after 2"));
+    }
+
+    #[tokio::test]
+    async fn replay_reports_diverging_requests() {
+        let replay = ReplayProvider::new(vec![RecordedExchange {
+            model: "m".into(),
+            temperature: 0.0,
+            native_tools: false,
+            vision: false,
+            messages: vec![ChatMessage::user("recorded")],
+            tools: Vec::new(),
+            response: text_response("ok"),
+        }]);
+
+        let text = replay
+            .chat_with_history(&[ChatMessage::user("changed")], "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(text, "ok");
+        assert_eq!(replay.served(), 1);
+        assert_eq!(
+            replay.divergences(),
+            vec!["exchange 1: message 1 (user) differs".to_string()]
+        );
+    }
+
+    #[test]
+    fn load_fixture_reports_bad_line() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("bad.jsonl");
+        fs::write(&path, "{\"model\":1}\n").unwrap();
+        let err = load_fixture(&path).unwrap_err();
+        assert!(format!("{err:#}").contains("bad.jsonl:1"));
+    }
+}
@@ -1,5 +1,9 @@
+use super::stats::{self, ProviderStats, RouteStats};
 use super::traits::{ChatMessage, ChatRequest, ChatResponse};
 use super::Provider;
+use crate::config::{RoutingConfig, RoutingPolicy};
+use crate::cost::budget;
+use crate::observability::runtime_trace;
 use async_trait::async_trait;
 use std::collections::HashMap;
 
@@ -8,6 +12,72 @@ use std::collections::HashMap;
 pub struct Route {
     pub provider_name: String,
     pub model: String,
+    /// Largest prompt (in tokens) this route should receive; `None` means no limit.
+    pub max_context_tokens: Option<u64>,
+    /// Whether the route accepts images; `None` asks the provider.
+    pub vision: Option<bool>,
+}
+
+impl Route {
+    pub fn new(provider_name: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            provider_name: provider_name.into(),
+            model: model.into(),
+            max_context_tokens: None,
+            vision: None,
+        }
+    }
+}
+
+/// A route resolved against the provider list.
+#[derive(Debug, Clone)]
+struct Candidate {
+    provider_index: usize,
+    model: String,
+    max_context_tokens: Option<u64>,
+    vision: Option<bool>,
+}
+
+/// What a request needs from the route that serves it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Requirements {
+    vision: bool,
+    tools: bool,
+    context_tokens: u64,
+}
+
+impl Requirements {
+    fn for_messages(messages: &[ChatMessage], tools: bool) -> Self {
+        Self {
+            vision: crate::multimodal::contains_image_markers(messages),
+            tools,
+            context_tokens: budget::estimate_input_tokens(messages),
+        }
+    }
+
+    fn for_prompt(system_prompt: Option<&str>, message: &str) -> Self {
+        let chars = system_prompt.map_or(0, |s| s.chars().count()) + message.chars().count();
+        Self {
+            vision: !crate::multimodal::parse_image_markers(message).1.is_empty(),
+            tools: false,
+            context_tokens: (chars / 4) as u64,
+        }
+    }
+}
+
+/// How one candidate fared against a request, in ranked order.
+#[derive(Debug, Clone)]
+struct Assessment {
+    candidate: usize,
+    /// Input + output price per million tokens from `[cost.prices]`.
+    price: Option<f64>,
+    stats: Option<RouteStats>,
+    /// Why the route cannot serve the request at all.
+    unfit: Option<&'static str>,
+    /// Recent error rate is above `[routing] max_error_rate`.
+    unhealthy: bool,
+    /// Tools were requested but the provider only supports prompt-guided calls.
+    prompt_guided_tools: bool,
 }
 
 /// Multi-model router — routes requests to different provider+model combos
@@ -17,12 +87,17 @@ pub struct Route {
 /// - A regular model name (e.g. "anthropic/claude-sonnet-4") → uses default provider
 /// - A hint-prefixed string (e.g. "hint:reasoning") → resolves via route table
 ///
+/// A hint may have several candidate routes; [`RoutingConfig`] decides which
+/// one serves each request and whether failures escalate to the next one.
+/// Every decision is written to the runtime trace as a `route_decision` event.
+///
 /// This wraps multiple pre-created providers and selects the right one per request.
 pub struct RouterProvider {
-    routes: HashMap<String, (usize, String)>, // hint → (provider_index, model)
+    routes: HashMap<String, Vec<Candidate>>, // hint → candidates in declared order
     providers: Vec<(String, Box<dyn Provider>)>,
     default_index: usize,
     default_model: String,
+    routing: RoutingConfig,
 }
 
 impl RouterProvider {
@@ -30,6 +105,7 @@ impl RouterProvider {
     ///
     /// `providers` is a list of (name, provider) pairs. The first one is the default.
     /// `routes` maps hint names to Route structs containing provider_name and model.
+    /// Repeating a hint adds another candidate for that hint.
     pub fn new(
         providers: Vec<(String, Box<dyn Provider>)>,
         routes: Vec<(String, Route)>,
@@ -43,41 +119,59 @@ impl RouterProvider {
             .collect();
 
         // Resolve routes to provider indices
-        let resolved_routes: HashMap<String, (usize, String)> = routes
-            .into_iter()
-            .filter_map(|(hint, route)| {
-                let index = name_to_index.get(route.provider_name.as_str()).copied();
-                match index {
-                    Some(i) => Some((hint, (i, route.model))),
-                    None => {
-                        tracing::warn!(
-                            hint = hint,
-                            provider = route.provider_name,
-                            "Route references unknown provider, skipping"
-                        );
-                        None
-                    }
+        let mut resolved_routes: HashMap<String, Vec<Candidate>> = HashMap::new();
+        for (hint, route) in routes {
+            match name_to_index.get(route.provider_name.as_str()).copied() {
+                Some(provider_index) => {
+                    resolved_routes.entry(hint).or_default().push(Candidate {
+                        provider_index,
+                        model: route.model,
+                        max_context_tokens: route.max_context_tokens,
+                        vision: route.vision,
+                    });
                 }
-            })
-            .collect();
+                None => {
+                    tracing::warn!(
+                        hint = hint,
+                        provider = route.provider_name,
+                        "Route references unknown provider, skipping"
+                    );
+                }
+            }
+        }
 
         Self {
             routes: resolved_routes,
             providers,
             default_index: 0,
             default_model,
+            routing: RoutingConfig::default(),
         }
     }
 
-    /// Resolve a model parameter to a (provider, actual_model) pair.
+    /// Set the policy used to choose among candidate routes.
+    pub fn with_routing(mut self, routing: RoutingConfig) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// Resolve a model parameter to a (provider_index, actual_model) pair.
     ///
     /// If the model starts with "hint:", look up the hint in the route table.
     /// Otherwise, use the default provider with the given model name.
-    /// Resolve a model parameter to a (provider_index, actual_model) pair.
     fn resolve(&self, model: &str) -> (usize, String) {
+        self.plan(model, Requirements::default())
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| (self.default_index, model.to_string()))
+    }
+
+    /// Ordered (provider_index, model) pairs to try for a request. The first
+    /// entry is the routing decision; the rest are escalation targets.
+    fn plan(&self, model: &str, needs: Requirements) -> Vec<(usize, String)> {
         if let Some(hint) = model.strip_prefix("hint:") {
-            if let Some((idx, resolved_model)) = self.routes.get(hint) {
-                return (*idx, resolved_model.clone());
+            if let Some(candidates) = self.routes.get(hint) {
+                return self.plan_hint(hint, candidates, needs);
             }
             tracing::warn!(
                 hint = hint,
@@ -86,8 +180,233 @@ impl RouterProvider {
         }
 
         // Not a hint or hint not found — use default provider with the model as-is
-        (self.default_index, model.to_string())
+        vec![(self.default_index, model.to_string())]
     }
+
+    fn plan_hint(
+        &self,
+        hint: &str,
+        candidates: &[Candidate],
+        needs: Requirements,
+    ) -> Vec<(usize, String)> {
+        let remaining = remaining_daily_budget();
+        let (policy, budget_low) = self.effective_policy(remaining);
+        let mut ranked = self.assess(candidates, needs, stats::global(), |provider, model| {
+            budget::global_tracker().and_then(|tracker| {
+                tracker
+                    .pricing_for(provider, model)
+                    .map(|p| p.input + p.output)
+            })
+        });
+        rank(&mut ranked, policy);
+
+        let all_unfit = ranked.iter().all(|a| a.unfit.is_some());
+        let plan: Vec<(usize, String)> = ranked
+            .iter()
+            .filter(|a| all_unfit || a.unfit.is_none())
+            .map(|a| {
+                let candidate = &candidates[a.candidate];
+                (candidate.provider_index, candidate.model.clone())
+            })
+            .collect();
+
+        let (chosen_index, chosen_model) = &plan[0];
+        let chosen_provider = self.providers[*chosen_index].0.as_str();
+        let mut summary = format!(
+            "hint:{hint} routed to {chosen_provider}/{chosen_model} by {} policy",
+            policy.as_str()
+        );
+        if budget_low {
+            summary.push_str(" (daily budget low)");
+        }
+        if all_unfit {
+            summary.push_str(" (no route met the request's requirements)");
+        }
+        tracing::info!(
+            hint,
+            provider = chosen_provider,
+            model = chosen_model.as_str(),
+            policy = policy.as_str(),
+            "Router selected route"
+        );
+
+        let candidate_trace: Vec<serde_json::Value> = ranked
+            .iter()
+            .map(|a| {
+                let candidate = &candidates[a.candidate];
+                serde_json::json!({
+                    "provider": self.providers[candidate.provider_index].0,
+                    "model": candidate.model,
+                    "price_per_mtok": a.price,
+                    "avg_latency_ms": a.stats.map(|s| s.avg_latency_ms),
+                    "error_rate": a.stats.map(|s| s.error_rate),
+                    "unfit": a.unfit,
+                    "unhealthy": a.unhealthy,
+                    "prompt_guided_tools": a.prompt_guided_tools,
+                })
+            })
+            .collect();
+        runtime_trace::record_event(
+            "route_decision",
+            None,
+            Some(chosen_provider),
+            Some(chosen_model),
+            None,
+            None,
+            Some(&summary),
+            serde_json::json!({
+                "hint": hint,
+                "policy": self.routing.policy.as_str(),
+                "effective_policy": policy.as_str(),
+                "daily_budget_remaining": remaining,
+                "requirements": {
+                    "vision": needs.vision,
+                    "tools": needs.tools,
+                    "context_tokens": needs.context_tokens,
+                },
+                "candidates": candidate_trace,
+            }),
+        );
+
+        plan
+    }
+
+    /// The configured policy, or `Cheapest` when the remaining share of the
+    /// daily budget has dropped below `low_budget_percent`.
+    fn effective_policy(&self, remaining: Option<f64>) -> (RoutingPolicy, bool) {
+        let threshold = f64::from(self.routing.low_budget_percent) / 100.0;
+        match remaining {
+            Some(fraction) if fraction < threshold => (RoutingPolicy::Cheapest, true),
+            _ => (self.routing.policy, false),
+        }
+    }
+
+    fn assess(
+        &self,
+        candidates: &[Candidate],
+        needs: Requirements,
+        stats: &ProviderStats,
+        price_of: impl Fn(&str, &str) -> Option<f64>,
+    ) -> Vec<Assessment> {
+        candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| {
+                let (provider_name, provider) = &self.providers[candidate.provider_index];
+                let vision = candidate
+                    .vision
+                    .unwrap_or_else(|| provider.supports_vision());
+                let unfit = if needs.vision && !vision {
+                    Some("no vision support")
+                } else if candidate
+                    .max_context_tokens
+                    .is_some_and(|max| needs.context_tokens > max)
+                {
+                    Some("context too large")
+                } else {
+                    None
+                };
+                let stats = stats.get(provider_name, &candidate.model);
+                Assessment {
+                    candidate: index,
+                    price: price_of(provider_name, &candidate.model),
+                    stats,
+                    unfit,
+                    unhealthy: stats.is_some_and(|s| s.error_rate > self.routing.max_error_rate),
+                    prompt_guided_tools: needs.tools && !provider.supports_native_tools(),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether a failed call should move on to the next planned route.
+    fn should_escalate(
+        &self,
+        err: &anyhow::Error,
+        attempt: usize,
+        planned: usize,
+        provider_name: &str,
+        model: &str,
+    ) -> bool {
+        if !self.routing.escalate_on_error
+            || attempt + 1 >= planned
+            || budget::is_budget_exceeded(err)
+        {
+            return false;
+        }
+        tracing::warn!(
+            provider = provider_name,
+            model,
+            "Routed provider failed, escalating to next route: {err}"
+        );
+        runtime_trace::record_event(
+            "route_escalation",
+            None,
+            Some(provider_name),
+            Some(model),
+            None,
+            Some(false),
+            Some("Routed provider failed; trying next route"),
+            serde_json::json!({ "error": err.to_string() }),
+        );
+        true
+    }
+}
+
+/// Order assessments best-first. Routes that cannot serve the request sink
+/// to the bottom, then unhealthy routes, then routes without native tool
+/// calling when tools are needed; `policy` orders the rest. Sorting is
+/// stable, so ties keep their declared order.
+fn rank(assessments: &mut [Assessment], policy: RoutingPolicy) {
+    let max_price = assessments
+        .iter()
+        .filter_map(|a| a.price)
+        .fold(0.0_f64, f64::max);
+    let max_latency = assessments
+        .iter()
+        .filter_map(|a| a.stats.map(|s| s.avg_latency_ms))
+        .fold(0.0_f64, f64::max);
+
+    let score = |a: &Assessment| -> f64 {
+        match policy {
+            RoutingPolicy::Fixed => 0.0,
+            RoutingPolicy::Cheapest => a.price.unwrap_or(f64::INFINITY),
+            // Unmeasured routes go first so every route gets a latency sample.
+            RoutingPolicy::Fastest => a.stats.map_or(0.0, |s| s.avg_latency_ms),
+            RoutingPolicy::Balanced => {
+                let price = match a.price {
+                    Some(price) if max_price > 0.0 => price / max_price,
+                    Some(_) => 0.0,
+                    None => 1.0,
+                };
+                let latency = match a.stats {
+                    Some(s) if max_latency > 0.0 => s.avg_latency_ms / max_latency,
+                    _ => 0.0,
+                };
+                let errors = a.stats.map_or(0.0, |s| s.error_rate);
+                price + latency + errors
+            }
+        }
+    };
+
+    assessments.sort_by(|a, b| {
+        (a.unfit.is_some(), a.unhealthy, a.prompt_guided_tools)
+            .cmp(&(b.unfit.is_some(), b.unhealthy, b.prompt_guided_tools))
+            .then_with(|| score(a).total_cmp(&score(b)))
+    });
+}
+
+/// Share of `[cost] daily_limit_usd` still unspent, when a limit is enforced.
+fn remaining_daily_budget() -> Option<f64> {
+    let tracker = budget::global_tracker()?;
+    let limit = tracker.config().daily_limit_usd;
+    if limit <= 0.0 {
+        return None;
+    }
+    let spent = tracker
+        .get_daily_cost(chrono::Utc::now().date_naive())
+        .ok()?;
+    Some(((limit - spent) / limit).clamp(0.0, 1.0))
 }
 
 #[async_trait]
@@ -99,18 +418,33 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let (provider_idx, resolved_model) = self.resolve(model);
+        let plan = self.plan(model, Requirements::for_prompt(system_prompt, message));
+        let mut last_error = None;
 
-        let (provider_name, provider) = &self.providers[provider_idx];
-        tracing::info!(
-            provider = provider_name.as_str(),
-            model = resolved_model.as_str(),
-            "Router dispatching request"
-        );
+        for (attempt, (provider_idx, resolved_model)) in plan.iter().enumerate() {
+            let (provider_name, provider) = &self.providers[*provider_idx];
+            tracing::info!(
+                provider = provider_name.as_str(),
+                model = resolved_model.as_str(),
+                "Router dispatching request"
+            );
 
-        provider
-            .chat_with_system(system_prompt, message, &resolved_model, temperature)
-            .await
+            match provider
+                .chat_with_system(system_prompt, message, resolved_model, temperature)
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    if !self.should_escalate(&e, attempt, plan.len(), provider_name, resolved_model)
+                    {
+                        return Err(e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No route available for {model}")))
     }
 
     async fn chat_with_history(
@@ -119,11 +453,27 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let (provider_idx, resolved_model) = self.resolve(model);
-        let (_, provider) = &self.providers[provider_idx];
-        provider
-            .chat_with_history(messages, &resolved_model, temperature)
-            .await
+        let plan = self.plan(model, Requirements::for_messages(messages, false));
+        let mut last_error = None;
+
+        for (attempt, (provider_idx, resolved_model)) in plan.iter().enumerate() {
+            let (provider_name, provider) = &self.providers[*provider_idx];
+            match provider
+                .chat_with_history(messages, resolved_model, temperature)
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    if !self.should_escalate(&e, attempt, plan.len(), provider_name, resolved_model)
+                    {
+                        return Err(e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No route available for {model}")))
     }
 
     async fn chat(
@@ -132,9 +482,32 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (provider_idx, resolved_model) = self.resolve(model);
-        let (_, provider) = &self.providers[provider_idx];
-        provider.chat(request, &resolved_model, temperature).await
+        let needs = Requirements::for_messages(
+            request.messages,
+            request.tools.is_some_and(|tools| !tools.is_empty()),
+        );
+        let plan = self.plan(model, needs);
+        let mut last_error = None;
+
+        for (attempt, (provider_idx, resolved_model)) in plan.iter().enumerate() {
+            let (provider_name, provider) = &self.providers[*provider_idx];
+            let req = ChatRequest {
+                messages: request.messages,
+                tools: request.tools,
+            };
+            match provider.chat(req, resolved_model, temperature).await {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    if !self.should_escalate(&e, attempt, plan.len(), provider_name, resolved_model)
+                    {
+                        return Err(e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No route available for {model}")))
     }
 
     async fn chat_with_tools(
@@ -144,11 +517,30 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (provider_idx, resolved_model) = self.resolve(model);
-        let (_, provider) = &self.providers[provider_idx];
-        provider
-            .chat_with_tools(messages, tools, &resolved_model, temperature)
-            .await
+        let plan = self.plan(
+            model,
+            Requirements::for_messages(messages, !tools.is_empty()),
+        );
+        let mut last_error = None;
+
+        for (attempt, (provider_idx, resolved_model)) in plan.iter().enumerate() {
+            let (provider_name, provider) = &self.providers[*provider_idx];
+            match provider
+                .chat_with_tools(messages, tools, resolved_model, temperature)
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    if !self.should_escalate(&e, attempt, plan.len(), provider_name, resolved_model)
+                    {
+                        return Err(e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No route available for {model}")))
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -245,10 +637,7 @@ mod tests {
             .map(|(hint, provider_name, model)| {
                 (
                     hint.to_string(),
-                    Route {
-                        provider_name: provider_name.to_string(),
-                        model: model.to_string(),
-                    },
+                    Route::new(provider_name.to_string(), model.to_string()),
                 )
             })
             .collect();
@@ -461,4 +850,294 @@ mod tests {
         assert_eq!(mocks[1].last_model(), "claude-opus");
         assert_eq!(mocks[0].call_count(), 0);
     }
+
+    struct FailingProvider;
+
+    #[async_trait]
+    impl Provider for FailingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("upstream unavailable")
+        }
+    }
+
+    fn candidates_router(routes: Vec<(&str, Route)>, routing: RoutingConfig) -> RouterProvider {
+        let mut names: Vec<String> = Vec::new();
+        for (_, route) in &routes {
+            if !names.contains(&route.provider_name) {
+                names.push(route.provider_name.clone());
+            }
+        }
+        let providers = names
+            .into_iter()
+            .map(|name| {
+                (
+                    name,
+                    Box::new(Arc::new(MockProvider::new("ok"))) as Box<dyn Provider>,
+                )
+            })
+            .collect();
+        let routes = routes
+            .into_iter()
+            .map(|(hint, route)| (hint.to_string(), route))
+            .collect();
+        RouterProvider::new(providers, routes, "default-model".into()).with_routing(routing)
+    }
+
+    fn ranked_models(
+        router: &RouterProvider,
+        needs: Requirements,
+        stats: &ProviderStats,
+        prices: &[(&str, f64)],
+        policy: RoutingPolicy,
+    ) -> Vec<String> {
+        let candidates = &router.routes["chat"];
+        let mut ranked = router.assess(candidates, needs, stats, |_, model| {
+            prices
+                .iter()
+                .find(|(name, _)| *name == model)
+                .map(|(_, price)| *price)
+        });
+        rank(&mut ranked, policy);
+        ranked
+            .iter()
+            .map(|a| candidates[a.candidate].model.clone())
+            .collect()
+    }
+
+    #[test]
+    fn repeated_hint_collects_candidates_in_declared_order() {
+        let router = candidates_router(
+            vec![
+                ("chat", Route::new("a", "small")),
+                ("chat", Route::new("b", "large")),
+            ],
+            RoutingConfig::default(),
+        );
+
+        assert_eq!(router.routes["chat"].len(), 2);
+        let (idx, model) = router.resolve("hint:chat");
+        assert_eq!(idx, 0);
+        assert_eq!(model, "small");
+    }
+
+    #[test]
+    fn cheapest_policy_prefers_lowest_price_and_unpriced_last() {
+        let router = candidates_router(
+            vec![
+                ("chat", Route::new("a", "unpriced")),
+                ("chat", Route::new("a", "premium")),
+                ("chat", Route::new("b", "budget")),
+            ],
+            RoutingConfig::default(),
+        );
+
+        let order = ranked_models(
+            &router,
+            Requirements::default(),
+            &ProviderStats::default(),
+            &[("premium", 18.0), ("budget", 0.5)],
+            RoutingPolicy::Cheapest,
+        );
+        assert_eq!(order, vec!["budget", "premium", "unpriced"]);
+    }
+
+    #[test]
+    fn fastest_policy_uses_observed_latency_and_measures_unseen_routes_first() {
+        let router = candidates_router(
+            vec![
+                ("chat", Route::new("a", "slow")),
+                ("chat", Route::new("a", "quick")),
+                ("chat", Route::new("b", "unseen")),
+            ],
+            RoutingConfig::default(),
+        );
+        let stats = ProviderStats::default();
+        stats.record("a", "slow", std::time::Duration::from_millis(900), true);
+        stats.record("a", "quick", std::time::Duration::from_millis(150), true);
+
+        let order = ranked_models(
+            &router,
+            Requirements::default(),
+            &stats,
+            &[],
+            RoutingPolicy::Fastest,
+        );
+        assert_eq!(order, vec!["unseen", "quick", "slow"]);
+    }
+
+    #[test]
+    fn unhealthy_routes_are_tried_last() {
+        let router = candidates_router(
+            vec![
+                ("chat", Route::new("a", "flaky")),
+                ("chat", Route::new("b", "steady")),
+            ],
+            RoutingConfig::default(),
+        );
+        let stats = ProviderStats::default();
+        for _ in 0..5 {
+            stats.record("a", "flaky", std::time::Duration::from_millis(100), false);
+        }
+
+        let order = ranked_models(
+            &router,
+            Requirements::default(),
+            &stats,
+            &[("flaky", 0.1), ("steady", 10.0)],
+            RoutingPolicy::Cheapest,
+        );
+        assert_eq!(order, vec!["steady", "flaky"]);
+    }
+
+    #[test]
+    fn requirements_mark_routes_without_vision_or_context_as_unfit() {
+        let mut small = Route::new("a", "small");
+        small.max_context_tokens = Some(1_000);
+        let mut text_only = Route::new("a", "text-only");
+        text_only.vision = Some(false);
+        let mut large = Route::new("b", "large");
+        large.vision = Some(true);
+        let router = candidates_router(
+            vec![("chat", small), ("chat", text_only), ("chat", large)],
+            RoutingConfig::default(),
+        );
+
+        let needs = Requirements {
+            vision: true,
+            tools: false,
+            context_tokens: 5_000,
+        };
+        let candidates = &router.routes["chat"];
+        let assessed = router.assess(candidates, needs, &ProviderStats::default(), |_, _| None);
+        assert_eq!(assessed[0].unfit, Some("no vision support"));
+        assert_eq!(assessed[1].unfit, Some("no vision support"));
+        assert_eq!(assessed[2].unfit, None);
+
+        let order = ranked_models(
+            &router,
+            Requirements {
+                vision: false,
+                tools: false,
+                context_tokens: 5_000,
+            },
+            &ProviderStats::default(),
+            &[],
+            RoutingPolicy::Fixed,
+        );
+        assert_eq!(order, vec!["text-only", "large", "small"]);
+    }
+
+    #[test]
+    fn low_daily_budget_switches_to_cheapest() {
+        let router = candidates_router(
+            vec![("chat", Route::new("a", "m"))],
+            RoutingConfig {
+                policy: RoutingPolicy::Fastest,
+                ..RoutingConfig::default()
+            },
+        );
+
+        assert_eq!(
+            router.effective_policy(None),
+            (RoutingPolicy::Fastest, false)
+        );
+        assert_eq!(
+            router.effective_policy(Some(0.5)),
+            (RoutingPolicy::Fastest, false)
+        );
+        assert_eq!(
+            router.effective_policy(Some(0.1)),
+            (RoutingPolicy::Cheapest, true)
+        );
+    }
+
+    #[test]
+    fn requirements_detect_images_and_prompt_size() {
+        let needs = Requirements::for_prompt(Some("be brief"), "look [IMAGE:/tmp/a.png] here");
+        assert!(needs.vision);
+        assert!(!needs.tools);
+        assert!(needs.context_tokens > 0);
+
+        let messages = vec![ChatMessage::user("no images here")];
+        let needs = Requirements::for_messages(&messages, true);
+        assert!(!needs.vision);
+        assert!(needs.tools);
+    }
+
+    #[tokio::test]
+    async fn failed_route_escalates_to_next_candidate() {
+        let backup = Arc::new(MockProvider::new("backup-response"));
+        let router = RouterProvider::new(
+            vec![
+                (
+                    "router-escalation-primary".into(),
+                    Box::new(FailingProvider) as Box<dyn Provider>,
+                ),
+                (
+                    "router-escalation-backup".into(),
+                    Box::new(Arc::clone(&backup)) as Box<dyn Provider>,
+                ),
+            ],
+            vec![
+                (
+                    "chat".into(),
+                    Route::new("router-escalation-primary", "cheap"),
+                ),
+                (
+                    "chat".into(),
+                    Route::new("router-escalation-backup", "strong"),
+                ),
+            ],
+            "default-model".into(),
+        );
+
+        let result = router.simple_chat("hello", "hint:chat", 0.5).await.unwrap();
+        assert_eq!(result, "backup-response");
+        assert_eq!(backup.last_model(), "strong");
+    }
+
+    #[tokio::test]
+    async fn escalation_can_be_disabled() {
+        let backup = Arc::new(MockProvider::new("backup-response"));
+        let router = RouterProvider::new(
+            vec![
+                (
+                    "router-no-escalation-primary".into(),
+                    Box::new(FailingProvider) as Box<dyn Provider>,
+                ),
+                (
+                    "router-no-escalation-backup".into(),
+                    Box::new(Arc::clone(&backup)) as Box<dyn Provider>,
+                ),
+            ],
+            vec![
+                (
+                    "chat".into(),
+                    Route::new("router-no-escalation-primary", "cheap"),
+                ),
+                (
+                    "chat".into(),
+                    Route::new("router-no-escalation-backup", "strong"),
+                ),
+            ],
+            "default-model".into(),
+        )
+        .with_routing(RoutingConfig {
+            escalate_on_error: false,
+            ..RoutingConfig::default()
+        });
+
+        let err = router
+            .simple_chat("hello", "hint:chat", 0.5)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("upstream unavailable"));
+        assert_eq!(backup.call_count(), 0);
+    }
 }
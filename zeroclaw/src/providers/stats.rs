@@ -0,0 +1,113 @@
+//! Observed latency and error rates per provider/model pair.
+//!
+//! [`ReliableProvider`](super::reliable::ReliableProvider) records every
+//! attempt here, and [`RouterProvider`](super::router::RouterProvider) reads
+//! the numbers back when a routing policy ranks candidate routes. Both use
+//! exponentially weighted moving averages so recent behaviour dominates.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Weight given to the newest sample in the moving averages.
+const EWMA_ALPHA: f64 = 0.2;
+
+static GLOBAL_STATS: LazyLock<ProviderStats> = LazyLock::new(ProviderStats::default);
+
+/// Snapshot of what has been observed for one provider/model pair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RouteStats {
+    pub calls: u64,
+    pub failures: u64,
+    /// Moving average of call latency in milliseconds.
+    pub avg_latency_ms: f64,
+    /// Moving average of the failure rate (0.0–1.0).
+    pub error_rate: f64,
+}
+
+impl RouteStats {
+    fn first(latency_ms: f64, success: bool) -> Self {
+        Self {
+            calls: 1,
+            failures: u64::from(!success),
+            avg_latency_ms: latency_ms,
+            error_rate: if success { 0.0 } else { 1.0 },
+        }
+    }
+
+    fn update(&mut self, latency_ms: f64, success: bool) {
+        self.calls += 1;
+        if !success {
+            self.failures += 1;
+        }
+        self.avg_latency_ms = ewma(self.avg_latency_ms, latency_ms);
+        self.error_rate = ewma(self.error_rate, if success { 0.0 } else { 1.0 });
+    }
+}
+
+fn ewma(previous: f64, sample: f64) -> f64 {
+    EWMA_ALPHA * sample + (1.0 - EWMA_ALPHA) * previous
+}
+
+/// Thread-safe registry of [`RouteStats`] keyed by provider and model.
+#[derive(Debug, Default)]
+pub struct ProviderStats {
+    entries: Mutex<HashMap<(String, String), RouteStats>>,
+}
+
+impl ProviderStats {
+    /// Record one call attempt.
+    pub fn record(&self, provider: &str, model: &str, latency: Duration, success: bool) {
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        let mut entries = self.entries.lock();
+        entries
+            .entry((provider.to_string(), model.to_string()))
+            .and_modify(|stats| stats.update(latency_ms, success))
+            .or_insert_with(|| RouteStats::first(latency_ms, success));
+    }
+
+    /// Stats for a provider/model pair, or `None` if it has never been called.
+    pub fn get(&self, provider: &str, model: &str) -> Option<RouteStats> {
+        self.entries
+            .lock()
+            .get(&(provider.to_string(), model.to_string()))
+            .copied()
+    }
+}
+
+/// The process-wide registry fed by `ReliableProvider`.
+pub fn global() -> &'static ProviderStats {
+    &GLOBAL_STATS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_pair_has_no_stats() {
+        let stats = ProviderStats::default();
+        assert!(stats.get("openai", "gpt-4o").is_none());
+    }
+
+    #[test]
+    fn record_tracks_moving_latency_and_error_rate() {
+        let stats = ProviderStats::default();
+        stats.record("openai", "gpt-4o", Duration::from_millis(100), true);
+        let first = stats.get("openai", "gpt-4o").unwrap();
+        assert_eq!(first.calls, 1);
+        assert_eq!(first.failures, 0);
+        assert!((first.avg_latency_ms - 100.0).abs() < 1e-6);
+        assert!(first.error_rate.abs() < f64::EPSILON);
+
+        stats.record("openai", "gpt-4o", Duration::from_millis(600), false);
+        let second = stats.get("openai", "gpt-4o").unwrap();
+        assert_eq!(second.calls, 2);
+        assert_eq!(second.failures, 1);
+        assert!((second.avg_latency_ms - 200.0).abs() < 1e-6);
+        assert!((second.error_rate - 0.2).abs() < 1e-6);
+
+        assert!(stats.get("openai", "gpt-4o-mini").is_none());
+    }
+}
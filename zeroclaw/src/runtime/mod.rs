@@ -1,10 +1,12 @@
 pub mod docker;
 pub mod native;
 pub mod traits;
+pub mod wasm;
 
 pub use docker::DockerRuntime;
 pub use native::NativeRuntime;
 pub use traits::RuntimeAdapter;
+pub use wasm::WasmRuntime;
 
 use crate::config::RuntimeConfig;
 
@@ -13,13 +15,25 @@ pub fn create_runtime(config: &RuntimeConfig) -> anyhow::Result<Box<dyn RuntimeA
     match config.kind.as_str() {
         "native" => Ok(Box::new(NativeRuntime::new())),
         "docker" => Ok(Box::new(DockerRuntime::new(config.docker.clone()))),
+        "wasm" => {
+            if !WasmRuntime::is_available() {
+                anyhow::bail!(
+                    "runtime.kind='wasm' requires a build with `--features runtime-wasm`."
+                );
+            }
+            let runtime = WasmRuntime::new(config.wasm.clone());
+            runtime.validate_config()?;
+            Ok(Box::new(runtime))
+        }
         "cloudflare" => anyhow::bail!(
             "runtime.kind='cloudflare' is not implemented yet. Use runtime.kind='native' for now."
         ),
         other if other.trim().is_empty() => {
-            anyhow::bail!("runtime.kind cannot be empty. Supported values: native, docker")
+            anyhow::bail!("runtime.kind cannot be empty. Supported values: native, docker, wasm")
+        }
+        other => {
+            anyhow::bail!("Unknown runtime kind '{other}'. Supported values: native, docker, wasm")
         }
-        other => anyhow::bail!("Unknown runtime kind '{other}'. Supported values: native, docker"),
     }
 }
 
@@ -49,6 +63,25 @@ mod tests {
         assert!(rt.has_shell_access());
     }
 
+    #[test]
+    fn factory_wasm_matches_feature() {
+        let cfg = RuntimeConfig {
+            kind: "wasm".into(),
+            ..RuntimeConfig::default()
+        };
+        match create_runtime(&cfg) {
+            Ok(rt) => {
+                assert!(WasmRuntime::is_available());
+                assert_eq!(rt.name(), "wasm");
+                assert!(!rt.has_shell_access());
+            }
+            Err(err) => {
+                assert!(!WasmRuntime::is_available());
+                assert!(err.to_string().contains("runtime-wasm"));
+            }
+        }
+    }
+
     #[test]
     fn factory_cloudflare_errors() {
         let cfg = RuntimeConfig {
@@ -4,11 +4,33 @@
 //! Each WASM module runs with:
 //! - **Fuel limits**: prevents infinite loops (each instruction costs 1 fuel)
 //! - **Memory caps**: configurable per-module memory ceiling
-//! - **No filesystem access**: by default, tools are pure computation
+//! - **No filesystem access**: by default, tools are pure computation; workspace
+//!   reads/writes go through capability-gated host imports
 //! - **No network access**: unless explicitly allowlisted hosts are configured
 //!
+//! # Tool ABI
+//! Every `<name>.wasm` in `tools_dir` is exposed to the agent as tool `<name>`.
+//! The module must export:
+//! - `memory`
+//! - `alloc(len: i32) -> i32` — returns a guest buffer for the JSON arguments
+//! - `call(ptr: i32, len: i32) -> i64` — runs the tool on the UTF-8 JSON
+//!   arguments at `ptr` and returns `(out_ptr << 32) | out_len` pointing at a
+//!   UTF-8 JSON result
+//!
+//! A result shaped like `{"success": bool, "output": "...", "error": "..."}`
+//! maps onto `ToolResult`; any other JSON value is a successful output.
+//! An optional `<name>.json` sidecar supplies `description` and `parameters`.
+//!
+//! Host imports (module `zeroclaw`), gated by [`WasmCapabilities`]:
+//! - `read_file(path_ptr, path_len, buf_ptr, buf_cap) -> i64` — copies up to
+//!   `buf_cap` bytes and returns the full file length
+//! - `write_file(path_ptr, path_len, data_ptr, data_len) -> i32` — returns `0`
+//!
+//! Both return `-1` when the capability or path is denied and `-2` on I/O
+//! errors. Paths are workspace-relative; absolute paths and `..` are denied.
+//!
 //! # Feature gate
-//! This module is only compiled when `--features runtime-wasm` is enabled.
+//! The interpreter is only compiled when `--features runtime-wasm` is enabled.
 //! The default ZeroClaw binary excludes it to maintain the 4.6 MB size target.
 
 use super::traits::RuntimeAdapter;
@@ -141,7 +163,118 @@ impl WasmRuntime {
         workspace_dir: &Path,
         caps: &WasmCapabilities,
     ) -> Result<WasmExecutionResult> {
-        use wasmi::{Engine, Linker, Module, Store};
+        let (mut store, instance) = self.instantiate(module_name, workspace_dir, caps)?;
+
+        // Look for exported entry point
+        let run_fn = instance
+            .get_typed_func::<(), i32>(&store, "run")
+            .or_else(|_| instance.get_typed_func::<(), i32>(&store, "_start"))
+            .with_context(|| {
+                format!(
+                    "WASM module '{module_name}' must export a 'run() -> i32' or '_start() -> i32' function"
+                )
+            })?;
+
+        // Execute with fuel accounting
+        let fuel = self.effective_fuel(caps);
+        let fuel_before = store.get_fuel().unwrap_or(0);
+        let exit_code = match run_fn.call(&mut store, ()) {
+            Ok(code) => code,
+            Err(e) => return Self::trap_result(module_name, &store, fuel, &e),
+        };
+        let fuel_after = store.get_fuel().unwrap_or(0);
+        let fuel_consumed = fuel_before.saturating_sub(fuel_after);
+
+        Ok(WasmExecutionResult {
+            stdout: String::new(), // No WASI stdout yet — pure computation
+            stderr: String::new(),
+            exit_code,
+            fuel_consumed,
+        })
+    }
+
+    /// Invoke a WASM tool module through the JSON ABI (see module docs).
+    ///
+    /// `stdout` of the returned result carries the module's JSON output.
+    #[cfg(feature = "runtime-wasm")]
+    pub fn call_tool(
+        &self,
+        module_name: &str,
+        workspace_dir: &Path,
+        caps: &WasmCapabilities,
+        input: &str,
+    ) -> Result<WasmExecutionResult> {
+        let (mut store, instance) = self.instantiate(module_name, workspace_dir, caps)?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .with_context(|| format!("WASM tool '{module_name}' must export 'memory'"))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&store, "alloc")
+            .with_context(|| {
+                format!("WASM tool '{module_name}' must export 'alloc(len: i32) -> i32'")
+            })?;
+        let call = instance
+            .get_typed_func::<(i32, i32), i64>(&store, "call")
+            .with_context(|| {
+                format!("WASM tool '{module_name}' must export 'call(ptr: i32, len: i32) -> i64'")
+            })?;
+
+        let input_len = i32::try_from(input.len()).context("WASM tool input exceeds 2 GB")?;
+        let fuel = self.effective_fuel(caps);
+        let fuel_before = store.get_fuel().unwrap_or(0);
+
+        let input_ptr = match alloc.call(&mut store, input_len) {
+            Ok(ptr) => ptr,
+            Err(e) => return Self::trap_result(module_name, &store, fuel, &e),
+        };
+        memory
+            .write(&mut store, guest_offset(input_ptr), input.as_bytes())
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "WASM tool '{module_name}' returned an out-of-bounds input buffer: {e}"
+                )
+            })?;
+
+        let packed = match call.call(&mut store, (input_ptr, input_len)) {
+            Ok(packed) => u64::from_ne_bytes(packed.to_ne_bytes()),
+            Err(e) => return Self::trap_result(module_name, &store, fuel, &e),
+        };
+        let out_ptr = usize::try_from(packed >> 32)?;
+        let out_len = usize::try_from(packed & 0xFFFF_FFFF)?;
+        let output = memory
+            .data(&store)
+            .get(out_ptr..out_ptr.saturating_add(out_len))
+            .with_context(|| {
+                format!("WASM tool '{module_name}' returned an out-of-bounds output buffer")
+            })?;
+        let stdout = std::str::from_utf8(output)
+            .with_context(|| format!("WASM tool '{module_name}' returned non-UTF-8 output"))?
+            .to_string();
+
+        let fuel_after = store.get_fuel().unwrap_or(0);
+        Ok(WasmExecutionResult {
+            stdout,
+            stderr: String::new(),
+            exit_code: 0,
+            fuel_consumed: fuel_before.saturating_sub(fuel_after),
+        })
+    }
+
+    /// Load, validate and instantiate a module with fuel, memory limits and
+    /// the capability-gated host imports linked in.
+    #[cfg(feature = "runtime-wasm")]
+    fn instantiate(
+        &self,
+        module_name: &str,
+        workspace_dir: &Path,
+        caps: &WasmCapabilities,
+    ) -> Result<(wasmi::Store<HostState>, wasmi::Instance)> {
+        use wasmi::{Engine, Linker, Module, Store, StoreLimitsBuilder};
+
+        if module_name.is_empty() || module_name.contains(['/', '\\']) || module_name == ".." {
+            bail!("Invalid WASM module name: {module_name}");
+        }
 
         // Resolve module path
         let tools_path = self.tools_dir(workspace_dir);
@@ -168,26 +301,36 @@ impl WasmRuntime {
             );
         }
 
-        // Configure engine with fuel metering
+        // Configure engine with fuel metering (fuel_limit = 0 disables metering)
+        let fuel = self.effective_fuel(caps);
         let mut engine_config = wasmi::Config::default();
-        engine_config.consume_fuel(true);
+        engine_config.consume_fuel(fuel > 0);
         let engine = Engine::new(&engine_config);
 
         // Parse and validate module
         let module = Module::new(&engine, &wasm_bytes[..])
             .with_context(|| format!("Failed to parse WASM module: {module_name}"))?;
 
-        // Create store with fuel budget
-        let mut store = Store::new(&engine, ());
-        let fuel = self.effective_fuel(caps);
+        // Create store with fuel budget and memory ceiling
+        let memory_limit = usize::try_from(self.effective_memory_bytes(caps)).unwrap_or(usize::MAX);
+        let mut store = Store::new(
+            &engine,
+            HostState {
+                workspace_dir: workspace_dir.to_path_buf(),
+                caps: caps.clone(),
+                limits: StoreLimitsBuilder::new().memory_size(memory_limit).build(),
+            },
+        );
+        store.limiter(|state| &mut state.limits);
         if fuel > 0 {
-            store.set_fuel(fuel).with_context(|| {
-                format!("Failed to set fuel budget ({fuel}) for module: {module_name}")
+            store.set_fuel(fuel).map_err(|e| {
+                anyhow::anyhow!("Failed to set fuel budget ({fuel}) for module {module_name}: {e}")
             })?;
         }
 
-        // Link host functions (minimal — pure sandboxing)
-        let linker = Linker::new(&engine);
+        // Link capability-gated host functions
+        let mut linker = Linker::new(&engine);
+        link_host_functions(&mut linker)?;
 
         // Instantiate module
         let instance = linker
@@ -195,45 +338,33 @@ impl WasmRuntime {
             .and_then(|pre| pre.start(&mut store))
             .with_context(|| format!("Failed to instantiate WASM module: {module_name}"))?;
 
-        // Look for exported entry point
-        let run_fn = instance
-            .get_typed_func::<(), i32>(&store, "run")
-            .or_else(|_| instance.get_typed_func::<(), i32>(&store, "_start"))
-            .with_context(|| {
-                format!(
-                    "WASM module '{module_name}' must export a 'run() -> i32' or '_start() -> i32' function"
-                )
-            })?;
-
-        // Execute with fuel accounting
-        let fuel_before = store.get_fuel().unwrap_or(0);
-        let exit_code = match run_fn.call(&mut store, ()) {
-            Ok(code) => code,
-            Err(e) => {
-                // Check if we ran out of fuel (infinite loop protection)
-                let fuel_after = store.get_fuel().unwrap_or(0);
-                if fuel_after == 0 && fuel > 0 {
-                    return Ok(WasmExecutionResult {
-                        stdout: String::new(),
-                        stderr: format!(
-                            "WASM module '{module_name}' exceeded fuel limit ({fuel} ticks) — likely an infinite loop"
-                        ),
-                        exit_code: -1,
-                        fuel_consumed: fuel,
-                    });
-                }
-                bail!("WASM execution error in '{module_name}': {e}");
-            }
-        };
-        let fuel_after = store.get_fuel().unwrap_or(0);
-        let fuel_consumed = fuel_before.saturating_sub(fuel_after);
+        Ok((store, instance))
+    }
 
-        Ok(WasmExecutionResult {
-            stdout: String::new(),  // No WASI stdout yet — pure computation
-            stderr: String::new(),
-            exit_code,
-            fuel_consumed,
-        })
+    /// Map a trap to a result: fuel exhaustion is reported as exit code -1,
+    /// anything else is an execution error.
+    #[cfg(feature = "runtime-wasm")]
+    fn trap_result(
+        module_name: &str,
+        store: &wasmi::Store<HostState>,
+        fuel: u64,
+        error: &wasmi::Error,
+    ) -> Result<WasmExecutionResult> {
+        // Check if we ran out of fuel (infinite loop protection). Fuel is
+        // charged per block, so the store may still hold a remainder.
+        let out_of_fuel = error.as_trap_code() == Some(wasmi::core::TrapCode::OutOfFuel)
+            || store.get_fuel().is_ok_and(|left| left == 0);
+        if out_of_fuel && fuel > 0 {
+            return Ok(WasmExecutionResult {
+                stdout: String::new(),
+                stderr: format!(
+                    "WASM module '{module_name}' exceeded fuel limit ({fuel} ticks) — likely an infinite loop"
+                ),
+                exit_code: -1,
+                fuel_consumed: fuel,
+            });
+        }
+        bail!("WASM execution error in '{module_name}': {error}");
     }
 
     /// Stub for when the `runtime-wasm` feature is not enabled.
@@ -251,6 +382,18 @@ impl WasmRuntime {
         )
     }
 
+    /// Stub for when the `runtime-wasm` feature is not enabled.
+    #[cfg(not(feature = "runtime-wasm"))]
+    pub fn call_tool(
+        &self,
+        module_name: &str,
+        workspace_dir: &Path,
+        caps: &WasmCapabilities,
+        _input: &str,
+    ) -> Result<WasmExecutionResult> {
+        self.execute_module(module_name, workspace_dir, caps)
+    }
+
     /// List available WASM tool modules in the tools directory.
     pub fn list_modules(&self, workspace_dir: &Path) -> Result<Vec<String>> {
         let tools_path = self.tools_dir(workspace_dir);
@@ -316,6 +459,176 @@ impl RuntimeAdapter for WasmRuntime {
     }
 }
 
+// ── Host imports ────────────────────────────────────────────────
+
+/// Host import return code: capability or path denied.
+#[cfg(feature = "runtime-wasm")]
+const HOST_DENIED: i32 = -1;
+/// Host import return code: I/O or guest memory error.
+#[cfg(feature = "runtime-wasm")]
+const HOST_IO_ERROR: i32 = -2;
+
+/// Per-invocation state visible to host imports.
+#[cfg(feature = "runtime-wasm")]
+struct HostState {
+    workspace_dir: PathBuf,
+    caps: WasmCapabilities,
+    limits: wasmi::StoreLimits,
+}
+
+#[cfg(feature = "runtime-wasm")]
+fn link_host_functions(linker: &mut wasmi::Linker<HostState>) -> Result<()> {
+    use wasmi::Caller;
+
+    linker
+        .func_wrap(
+            "zeroclaw",
+            "read_file",
+            |mut caller: Caller<'_, HostState>,
+             path_ptr: i32,
+             path_len: i32,
+             buf_ptr: i32,
+             buf_cap: i32|
+             -> i64 {
+                host_read_file(&mut caller, path_ptr, path_len, buf_ptr, buf_cap)
+                    .unwrap_or_else(i64::from)
+            },
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to link zeroclaw.read_file: {e}"))?;
+    linker
+        .func_wrap(
+            "zeroclaw",
+            "write_file",
+            |mut caller: Caller<'_, HostState>,
+             path_ptr: i32,
+             path_len: i32,
+             data_ptr: i32,
+             data_len: i32|
+             -> i32 {
+                host_write_file(&mut caller, path_ptr, path_len, data_ptr, data_len)
+                    .map_or_else(|code| code, |()| 0)
+            },
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to link zeroclaw.write_file: {e}"))?;
+    Ok(())
+}
+
+#[cfg(feature = "runtime-wasm")]
+fn host_read_file(
+    caller: &mut wasmi::Caller<'_, HostState>,
+    path_ptr: i32,
+    path_len: i32,
+    buf_ptr: i32,
+    buf_cap: i32,
+) -> Result<i64, i32> {
+    let memory = guest_memory(caller)?;
+    let (bytes, state) = memory.data_and_store_mut(&mut *caller);
+    if !state.caps.read_workspace {
+        return Err(HOST_DENIED);
+    }
+    let path = guest_str(bytes, path_ptr, path_len)?;
+    let resolved = resolve_workspace_path(&state.workspace_dir, path, false).ok_or(HOST_DENIED)?;
+    let data = std::fs::read(resolved).map_err(|_| HOST_IO_ERROR)?;
+
+    let cap = usize::try_from(buf_cap).map_err(|_| HOST_IO_ERROR)?;
+    let copy_len = data.len().min(cap);
+    let start = guest_offset(buf_ptr);
+    bytes
+        .get_mut(start..start.saturating_add(copy_len))
+        .ok_or(HOST_IO_ERROR)?
+        .copy_from_slice(&data[..copy_len]);
+    i64::try_from(data.len()).map_err(|_| HOST_IO_ERROR)
+}
+
+#[cfg(feature = "runtime-wasm")]
+fn host_write_file(
+    caller: &mut wasmi::Caller<'_, HostState>,
+    path_ptr: i32,
+    path_len: i32,
+    data_ptr: i32,
+    data_len: i32,
+) -> Result<(), i32> {
+    let memory = guest_memory(caller)?;
+    let (bytes, state) = memory.data_and_store_mut(&mut *caller);
+    if !state.caps.write_workspace {
+        return Err(HOST_DENIED);
+    }
+    let path = guest_str(bytes, path_ptr, path_len)?;
+    let resolved = resolve_workspace_path(&state.workspace_dir, path, true).ok_or(HOST_DENIED)?;
+    let data = guest_slice(bytes, data_ptr, data_len)?;
+    std::fs::write(resolved, data).map_err(|_| HOST_IO_ERROR)
+}
+
+#[cfg(feature = "runtime-wasm")]
+fn guest_memory(caller: &wasmi::Caller<'_, HostState>) -> Result<wasmi::Memory, i32> {
+    caller
+        .get_export("memory")
+        .and_then(wasmi::Extern::into_memory)
+        .ok_or(HOST_IO_ERROR)
+}
+
+/// Reinterpret a guest `i32` pointer as an unsigned linear-memory offset.
+#[cfg(feature = "runtime-wasm")]
+fn guest_offset(ptr: i32) -> usize {
+    u32::from_ne_bytes(ptr.to_ne_bytes()) as usize
+}
+
+#[cfg(feature = "runtime-wasm")]
+fn guest_slice(memory: &[u8], ptr: i32, len: i32) -> Result<&[u8], i32> {
+    let len = usize::try_from(len).map_err(|_| HOST_IO_ERROR)?;
+    let start = guest_offset(ptr);
+    memory
+        .get(start..start.saturating_add(len))
+        .ok_or(HOST_IO_ERROR)
+}
+
+#[cfg(feature = "runtime-wasm")]
+fn guest_str(memory: &[u8], ptr: i32, len: i32) -> Result<&str, i32> {
+    std::str::from_utf8(guest_slice(memory, ptr, len)?).map_err(|_| HOST_IO_ERROR)
+}
+
+/// Resolve a guest-supplied path inside the workspace.
+///
+/// Rejects absolute paths, `..` components and symlinks that escape the
+/// workspace. Writes require an existing parent directory and never follow
+/// a symlink at the target.
+#[cfg(feature = "runtime-wasm")]
+fn resolve_workspace_path(
+    workspace_dir: &Path,
+    relative: &str,
+    for_write: bool,
+) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let rel = Path::new(relative);
+    if relative.is_empty()
+        || !rel
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return None;
+    }
+
+    let root = workspace_dir.canonicalize().ok()?;
+    let joined = root.join(rel);
+    let resolved = if for_write {
+        if joined
+            .symlink_metadata()
+            .is_ok_and(|meta| meta.file_type().is_symlink())
+        {
+            return None;
+        }
+        joined
+            .parent()?
+            .canonicalize()
+            .ok()?
+            .join(joined.file_name()?)
+    } else {
+        joined.canonicalize().ok()?
+    };
+    resolved.starts_with(&root).then_some(resolved)
+}
+
 // ── Tests ───────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -379,7 +692,10 @@ mod tests {
         let rt = WasmRuntime::new(default_config());
         let result = rt.build_shell_command("echo hello", Path::new("/tmp"));
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("does not support shell"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("does not support shell"));
     }
 
     #[test]
@@ -391,7 +707,10 @@ mod tests {
     #[test]
     fn wasm_storage_path_with_workspace() {
         let rt = WasmRuntime::with_workspace(default_config(), PathBuf::from("/home/user/project"));
-        assert_eq!(rt.storage_path(), PathBuf::from("/home/user/project/.zeroclaw"));
+        assert_eq!(
+            rt.storage_path(),
+            PathBuf::from("/home/user/project/.zeroclaw")
+        );
     }
 
     // ── Config validation ──────────────────────────────────────
@@ -636,10 +955,7 @@ mod tests {
         let rt = WasmRuntime::new(default_config());
         let caps = WasmCapabilities::default();
         let mem_bytes = rt.effective_memory_bytes(&caps);
-        assert!(
-            mem_bytes > 0,
-            "default memory limit must be > 0"
-        );
+        assert!(mem_bytes > 0, "default memory limit must be > 0");
         assert!(
             mem_bytes <= 4096 * 1024 * 1024,
             "default memory must not exceed 4 GB safety limit"
@@ -669,6 +985,111 @@ mod tests {
         assert!(err.to_string().contains("4 GB safety limit"));
     }
 
+    // ── JSON ABI & host imports (runtime-wasm only) ────────────
+
+    /// Reads the file named by the input bytes and returns its contents.
+    #[cfg(feature = "runtime-wasm")]
+    const READ_FILE_WAT: &str = r#"
+        (module
+          (import "zeroclaw" "read_file" (func $read_file (param i32 i32 i32 i32) (result i64)))
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "call") (param $ptr i32) (param $len i32) (result i64)
+            (local $n i64)
+            (local.set $n (call $read_file (local.get $ptr) (local.get $len) (i32.const 4096) (i32.const 1024)))
+            (if (i64.lt_s (local.get $n) (i64.const 0))
+              (then (return (i64.const 0))))
+            (i64.or (i64.shl (i64.const 4096) (i64.const 32)) (local.get $n))))
+    "#;
+
+    #[cfg(feature = "runtime-wasm")]
+    fn install_module(workspace: &Path, name: &str, wat: &str) {
+        let dir = workspace.join("tools/wasm");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(format!("{name}.wasm")),
+            wat::parse_str(wat).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[test]
+    fn call_tool_read_file_requires_capability() {
+        let dir = tempfile::tempdir().unwrap();
+        install_module(dir.path(), "reader", READ_FILE_WAT);
+        std::fs::write(dir.path().join("note.txt"), "hello").unwrap();
+        let rt = WasmRuntime::new(default_config());
+
+        let denied = rt
+            .call_tool(
+                "reader",
+                dir.path(),
+                &WasmCapabilities::default(),
+                "note.txt",
+            )
+            .unwrap();
+        assert_eq!(denied.stdout, "");
+
+        let caps = WasmCapabilities {
+            read_workspace: true,
+            ..Default::default()
+        };
+        let allowed = rt
+            .call_tool("reader", dir.path(), &caps, "note.txt")
+            .unwrap();
+        assert_eq!(allowed.stdout, "hello");
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[test]
+    fn call_tool_read_file_rejects_paths_outside_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        install_module(&workspace, "reader", READ_FILE_WAT);
+        std::fs::write(dir.path().join("secret.txt"), "top secret").unwrap();
+        let rt = WasmRuntime::new(default_config());
+        let caps = WasmCapabilities {
+            read_workspace: true,
+            ..Default::default()
+        };
+
+        for path in ["../secret.txt", "/etc/hostname"] {
+            let result = rt.call_tool("reader", &workspace, &caps, path).unwrap();
+            assert_eq!(result.stdout, "", "path {path} must be denied");
+        }
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[test]
+    fn resolve_workspace_path_confines_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        assert_eq!(
+            resolve_workspace_path(dir.path(), "out.txt", true),
+            Some(root.join("out.txt"))
+        );
+        assert!(resolve_workspace_path(dir.path(), "missing/out.txt", true).is_none());
+        assert!(resolve_workspace_path(dir.path(), "../out.txt", true).is_none());
+        assert!(resolve_workspace_path(dir.path(), "", true).is_none());
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[test]
+    fn call_tool_memory_limit_rejects_large_initial_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        // 2048 pages = 128 MB, above the 64 MB default ceiling
+        install_module(
+            dir.path(),
+            "huge",
+            r#"(module (memory (export "memory") 2048))"#,
+        );
+        let rt = WasmRuntime::new(default_config());
+        let result = rt.call_tool("huge", dir.path(), &WasmCapabilities::default(), "{}");
+        assert!(result.is_err());
+    }
+
     #[test]
     fn execute_module_stub_returns_error_without_feature() {
         if !WasmRuntime::is_available() {
@@ -1,15 +1,29 @@
 //! Audit logging for security events
+//!
+//! Records are appended as JSONL and hash-chained: each record carries the
+//! `hash` of the one before it as `prev_hash`, and its own `hash` is the
+//! SHA-256 of the serialized record without the `hash` field. The chain
+//! continues across rotated files, so `zeroclaw audit verify` detects edited,
+//! inserted or deleted records anywhere but at the very end.
 
 use crate::config::AuditConfig;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
 use uuid::Uuid;
 
+/// Serializes appends within the process; [`ChainLock`] covers other processes.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+static AUDIT_LOGGER: LazyLock<RwLock<Option<Arc<AuditLogger>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
 /// Audit event types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -21,6 +35,7 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    ApprovalDecision,
 }
 
 /// Actor information (who performed the action)
@@ -67,6 +82,12 @@ pub struct AuditEvent {
     pub action: Option<Action>,
     pub result: Option<ExecutionResult>,
     pub security: SecurityContext,
+    /// `hash` of the previous record; `None` at the start of the chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// Set by [`AuditLogger::log`] when the record is written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 impl AuditEvent {
@@ -84,6 +105,8 @@ impl AuditEvent {
                 rate_limit_remaining: None,
                 sandbox_backend: None,
             },
+            prev_hash: None,
+            hash: None,
         }
     }
 
@@ -136,6 +159,12 @@ impl AuditEvent {
         self
     }
 
+    /// Mark the event as a policy violation
+    pub fn with_policy_violation(mut self) -> Self {
+        self.security.policy_violation = true;
+        self
+    }
+
     /// Set security context
     pub fn with_security(mut self, sandbox_backend: Option<String>) -> Self {
         self.security.sandbox_backend = sandbox_backend;
@@ -173,28 +202,41 @@ impl AuditLogger {
         })
     }
 
-    /// Log an event
+    /// Log an event, chaining it to the last record on disk
     pub fn log(&self, event: &AuditEvent) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
 
+        let _guard = WRITE_LOCK.lock();
+        let _chain_lock = ChainLock::acquire(&self.log_path)?;
+
         // Check log size and rotate if needed
         self.rotate_if_needed()?;
 
+        let mut event = event.clone();
+        event.prev_hash = chain_head(&self.log_path)?;
+        event.hash = None;
+        let body = serde_json::to_string(&event)?;
+        let hash = record_hash(&body);
+
         // Serialize and write
-        let line = serde_json::to_string(event)?;
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.log_path)?;
 
-        writeln!(file, "{}", line)?;
+        writeln!(file, "{}", with_hash(&body, &hash))?;
         file.sync_all()?;
 
         Ok(())
     }
 
+    /// Path of the active log file.
+    pub fn log_path(&self) -> &Path {
+        &self.log_path
+    }
+
     /// Log a command execution event.
     pub fn log_command_event(&self, entry: CommandExecutionLog<'_>) -> Result<()> {
         let event = AuditEvent::new(AuditEventType::CommandExecution)
@@ -258,6 +300,252 @@ impl AuditLogger {
     }
 }
 
+/// Exclusive advisory lock on `<log>.lock`, held while the chain head is read
+/// and the next record appended, so a daemon and a CLI writing the same log
+/// cannot both chain onto the same predecessor. A sidecar file is used because
+/// rotation renames the log itself. Released on drop.
+struct ChainLock {
+    _file: File,
+}
+
+impl ChainLock {
+    fn acquire(log_path: &Path) -> Result<Self> {
+        if let Some(parent) = log_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(PathBuf::from(format!("{}.lock", log_path.display())))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::AsRawFd;
+            loop {
+                // SAFETY: flock only reads the descriptor, which `file` keeps open.
+                if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+                    break;
+                }
+                let err = std::io::Error::last_os_error();
+                if err.kind() != std::io::ErrorKind::Interrupted {
+                    return Err(err.into());
+                }
+            }
+        }
+        // Closing the descriptor on drop releases the lock.
+        Ok(Self { _file: file })
+    }
+}
+
+/// Hex SHA-256 of a serialized record without its `hash` field.
+fn record_hash(body: &str) -> String {
+    hex::encode(Sha256::digest(body.as_bytes()))
+}
+
+/// Append `"hash"` as the last field of the serialized object `body`.
+fn with_hash(body: &str, hash: &str) -> String {
+    let open = body.strip_suffix('}').unwrap_or(body);
+    format!("{open},\"hash\":\"{hash}\"}}")
+}
+
+/// `hash` of the last record on disk, looking into the newest rotated file
+/// when the active one is missing or empty.
+fn chain_head(log_path: &Path) -> Result<Option<String>> {
+    let line = match last_line(log_path)? {
+        Some(line) => Some(line),
+        None => last_line(&rotated_path(log_path, 1))?,
+    };
+    Ok(line.and_then(|line| {
+        serde_json::from_str::<serde_json::Value>(&line)
+            .ok()?
+            .get("hash")?
+            .as_str()
+            .map(str::to_string)
+    }))
+}
+
+/// Last non-empty line of `path`, read backwards so large logs stay cheap.
+fn last_line(path: &Path) -> std::io::Result<Option<String>> {
+    const CHUNK: u64 = 8 * 1024;
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut end = file.metadata()?.len();
+    let mut tail: Vec<u8> = Vec::new();
+    while end > 0 {
+        let start = end.saturating_sub(CHUNK);
+        let mut chunk = vec![0; usize::try_from(end - start).unwrap_or(0)];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        end = start;
+
+        let trimmed = tail.trim_ascii_end();
+        if let Some(pos) = trimmed.iter().rposition(|b| *b == b'\n') {
+            return Ok(Some(
+                String::from_utf8_lossy(&trimmed[pos + 1..]).into_owned(),
+            ));
+        }
+    }
+    let trimmed = tail.trim_ascii();
+    Ok((!trimmed.is_empty()).then(|| String::from_utf8_lossy(trimmed).into_owned()))
+}
+
+fn rotated_path(log_path: &Path, index: usize) -> PathBuf {
+    PathBuf::from(format!("{}.{index}.log", log_path.display()))
+}
+
+/// Existing log files, oldest rotation first and the active file last.
+pub fn log_files(log_path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = (1..=10)
+        .rev()
+        .map(|index| rotated_path(log_path, index))
+        .filter(|path| path.exists())
+        .collect();
+    if log_path.exists() {
+        files.push(log_path.to_path_buf());
+    }
+    files
+}
+
+/// Where the hash chain first fails to verify.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainBreak {
+    pub file: PathBuf,
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Outcome of [`verify_chain`].
+#[derive(Debug, Default)]
+pub struct ChainReport {
+    pub files: Vec<PathBuf>,
+    /// Hash-chained records checked.
+    pub records: usize,
+    /// Records written before hash chaining, ahead of the first chained one.
+    pub legacy: usize,
+    pub broken: Option<ChainBreak>,
+}
+
+/// Check every record across the active and rotated log files.
+///
+/// The first chained record anchors the chain (its predecessor may have
+/// rotated away); every later record must hash correctly and point at the
+/// record before it.
+pub fn verify_chain(log_path: &Path) -> Result<ChainReport> {
+    let mut report = ChainReport {
+        files: log_files(log_path),
+        ..ChainReport::default()
+    };
+    let mut previous: Option<String> = None;
+
+    for file in report.files.clone() {
+        let reader = BufReader::new(File::open(&file)?);
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fail = |reason: String| ChainBreak {
+                file: file.clone(),
+                line: idx + 1,
+                reason,
+            };
+            let event = match serde_json::from_str::<AuditEvent>(&line) {
+                Ok(event) => event,
+                Err(e) => {
+                    report.broken = Some(fail(format!("unparseable record: {e}")));
+                    return Ok(report);
+                }
+            };
+            let Some(hash) = event.hash.as_deref() else {
+                if previous.is_none() {
+                    report.legacy += 1;
+                    continue;
+                }
+                report.broken = Some(fail("record is not hash-chained".into()));
+                return Ok(report);
+            };
+
+            let suffix = format!(",\"hash\":\"{hash}\"}}");
+            let body = line.strip_suffix(&suffix).map(|open| format!("{open}}}"));
+            if body.as_deref().map(record_hash).as_deref() != Some(hash) {
+                report.broken = Some(fail("record hash does not match its contents".into()));
+                return Ok(report);
+            }
+            if previous.is_some() && event.prev_hash != previous {
+                report.broken = Some(fail(
+                    "prev_hash does not match the preceding record (record removed or reordered)"
+                        .into(),
+                ));
+                return Ok(report);
+            }
+            previous = Some(hash.to_string());
+            report.records += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// Parsed records from all log files, oldest first. Unparseable lines are skipped.
+pub fn read_events(log_path: &Path) -> Result<Vec<AuditEvent>> {
+    let mut events = Vec::new();
+    for file in log_files(log_path) {
+        for line in BufReader::new(File::open(&file)?).lines() {
+            if let Ok(event) = serde_json::from_str::<AuditEvent>(&line?) {
+                events.push(event);
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// Install the process-wide audit logger. Disabled auditing clears it.
+pub fn init_from_config(config: &AuditConfig, config_dir: &Path) {
+    let logger = config
+        .enabled
+        .then(|| AuditLogger::new(config.clone(), config_dir.to_path_buf()))
+        .and_then(|logger| match logger {
+            Ok(logger) => Some(Arc::new(logger)),
+            Err(e) => {
+                tracing::warn!("Failed to open audit log: {e}");
+                None
+            }
+        });
+    *AUDIT_LOGGER.write() = logger;
+}
+
+/// The process-wide logger, if auditing is enabled.
+pub fn global_logger() -> Option<Arc<AuditLogger>> {
+    AUDIT_LOGGER.read().clone()
+}
+
+/// The process-wide logger, or a fresh one for `config_dir` when none is
+/// installed (tests and embedders that skip `main`).
+pub fn shared_logger(config: &AuditConfig, config_dir: &Path) -> Option<Arc<AuditLogger>> {
+    if let Some(logger) = global_logger() {
+        return Some(logger);
+    }
+    if !config.enabled {
+        return None;
+    }
+    AuditLogger::new(config.clone(), config_dir.to_path_buf())
+        .ok()
+        .map(Arc::new)
+}
+
+/// Write `event` to the process-wide audit log; a no-op when none is installed.
+pub fn record(event: AuditEvent) {
+    if let Some(logger) = global_logger() {
+        if let Err(e) = logger.log(&event) {
+            tracing::warn!("Failed to write audit event: {e}");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,4 +708,122 @@ mod tests {
         );
         Ok(())
     }
+
+    fn chained_logger(tmp: &TempDir) -> AuditLogger {
+        let config = AuditConfig {
+            enabled: true,
+            max_size_mb: 10,
+            ..Default::default()
+        };
+        AuditLogger::new(config, tmp.path().to_path_buf()).unwrap()
+    }
+
+    fn write_events(logger: &AuditLogger, count: usize) {
+        for idx in 0..count {
+            let event = AuditEvent::new(AuditEventType::CommandExecution).with_action(
+                format!("echo {idx}"),
+                "low".into(),
+                false,
+                true,
+            );
+            logger.log(&event).unwrap();
+        }
+    }
+
+    #[test]
+    fn concurrent_writers_keep_a_single_chain() {
+        let tmp = TempDir::new().unwrap();
+        let writers: Vec<_> = (0..2)
+            .map(|_| {
+                let logger = chained_logger(&tmp);
+                std::thread::spawn(move || write_events(&logger, 25))
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let report = verify_chain(&tmp.path().join("audit.log")).unwrap();
+        assert_eq!(report.records, 50);
+        assert!(report.broken.is_none(), "{:?}", report.broken);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn appends_wait_for_the_file_lock_held_by_another_writer() {
+        let tmp = TempDir::new().unwrap();
+        let first = chained_logger(&tmp);
+        write_events(&first, 1);
+
+        // Stand-in for another process mid-append
+        let held = ChainLock::acquire(first.log_path()).unwrap();
+        let second = chained_logger(&tmp);
+        let writer = std::thread::spawn(move || write_events(&second, 1));
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert!(!writer.is_finished());
+        assert_eq!(read_events(first.log_path()).unwrap().len(), 1);
+
+        drop(held);
+        writer.join().unwrap();
+        write_events(&first, 1);
+        let report = verify_chain(first.log_path()).unwrap();
+        assert_eq!(report.records, 3);
+        assert!(report.broken.is_none(), "{:?}", report.broken);
+    }
+
+    #[test]
+    fn audit_records_are_hash_chained() {
+        let tmp = TempDir::new().unwrap();
+        let logger = chained_logger(&tmp);
+        write_events(&logger, 3);
+
+        let events = read_events(logger.log_path()).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].prev_hash, None);
+        assert_eq!(events[1].prev_hash, events[0].hash);
+        assert_eq!(events[2].prev_hash, events[1].hash);
+
+        let report = verify_chain(logger.log_path()).unwrap();
+        assert_eq!(report.records, 3);
+        assert!(report.broken.is_none(), "{:?}", report.broken);
+    }
+
+    #[test]
+    fn verify_detects_edited_and_removed_records() {
+        let tmp = TempDir::new().unwrap();
+        let logger = chained_logger(&tmp);
+        write_events(&logger, 3);
+        let original = std::fs::read_to_string(logger.log_path()).unwrap();
+
+        let edited = original.replacen("echo 1", "echo X", 1);
+        std::fs::write(logger.log_path(), edited).unwrap();
+        let broken = verify_chain(logger.log_path()).unwrap().broken.unwrap();
+        assert_eq!(broken.line, 2);
+        assert!(broken.reason.contains("hash does not match"));
+
+        let removed: Vec<&str> = original
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, l)| l)
+            .collect();
+        std::fs::write(logger.log_path(), removed.join("\n")).unwrap();
+        let broken = verify_chain(logger.log_path()).unwrap().broken.unwrap();
+        assert_eq!(broken.line, 2);
+        assert!(broken.reason.contains("prev_hash"));
+    }
+
+    #[test]
+    fn chain_continues_across_rotation() {
+        let tmp = TempDir::new().unwrap();
+        let logger = chained_logger(&tmp);
+        write_events(&logger, 2);
+        logger.rotate().unwrap();
+        write_events(&logger, 1);
+
+        let report = verify_chain(logger.log_path()).unwrap();
+        assert_eq!(report.files.len(), 2);
+        assert_eq!(report.records, 3);
+        assert!(report.broken.is_none(), "{:?}", report.broken);
+    }
 }
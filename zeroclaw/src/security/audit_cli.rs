@@ -0,0 +1,177 @@
+use super::audit::{read_events, verify_chain, AuditEvent};
+use crate::config::Config;
+use crate::AuditCommands;
+use anyhow::{bail, Context, Result};
+use console::style;
+use std::path::PathBuf;
+
+/// Handle `zeroclaw audit <subcommand>` CLI commands.
+pub fn handle_command(command: AuditCommands, config: &Config) -> Result<()> {
+    let log_path = audit_log_path(config)?;
+    match command {
+        AuditCommands::Verify => handle_verify(&log_path),
+        AuditCommands::Tail { lines, json } => {
+            let events = read_events(&log_path)?;
+            let skip = events.len().saturating_sub(lines);
+            print_events(&events[skip..], json)
+        }
+        AuditCommands::Search {
+            query,
+            event_type,
+            channel,
+            limit,
+            json,
+        } => {
+            let query = query.map(|q| q.to_lowercase());
+            let matches: Vec<AuditEvent> = read_events(&log_path)?
+                .into_iter()
+                .filter(|event| {
+                    matches(
+                        event,
+                        query.as_deref(),
+                        event_type.as_deref(),
+                        channel.as_deref(),
+                    )
+                })
+                .collect();
+            let skip = matches.len().saturating_sub(limit);
+            print_events(&matches[skip..], json)
+        }
+    }
+}
+
+fn audit_log_path(config: &Config) -> Result<PathBuf> {
+    let config_dir = config
+        .config_path
+        .parent()
+        .context("Config path must have a parent directory")?;
+    Ok(config_dir.join(&config.security.audit.log_path))
+}
+
+fn handle_verify(log_path: &std::path::Path) -> Result<()> {
+    let report = verify_chain(log_path)?;
+    if report.files.is_empty() {
+        println!("No audit log at {}", log_path.display());
+        return Ok(());
+    }
+    for file in &report.files {
+        println!("  {}", style(file.display()).dim());
+    }
+    if report.legacy > 0 {
+        println!(
+            "{} {} record(s) predate hash chaining and were not checked",
+            style("!").yellow(),
+            report.legacy
+        );
+    }
+    match report.broken {
+        None => {
+            println!(
+                "{} {} chained record(s) verified",
+                style("✓").green().bold(),
+                report.records
+            );
+            Ok(())
+        }
+        Some(broken) => bail!(
+            "audit chain broken at {}:{} — {} ({} record(s) verified before it)",
+            broken.file.display(),
+            broken.line,
+            broken.reason,
+            report.records
+        ),
+    }
+}
+
+fn matches(
+    event: &AuditEvent,
+    query: Option<&str>,
+    event_type: Option<&str>,
+    channel: Option<&str>,
+) -> bool {
+    if let Some(wanted) = event_type {
+        if event_type_name(event) != wanted {
+            return false;
+        }
+    }
+    if let Some(wanted) = channel {
+        if event.actor.as_ref().map(|a| a.channel.as_str()) != Some(wanted) {
+            return false;
+        }
+    }
+    match query {
+        Some(query) => serde_json::to_string(event)
+            .map(|raw| raw.to_lowercase().contains(query))
+            .unwrap_or(false),
+        None => true,
+    }
+}
+
+fn event_type_name(event: &AuditEvent) -> String {
+    serde_json::to_value(&event.event_type)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn print_events(events: &[AuditEvent], json: bool) -> Result<()> {
+    if events.is_empty() && !json {
+        println!("No matching audit records.");
+        return Ok(());
+    }
+    for event in events {
+        if json {
+            println!("{}", serde_json::to_string(event)?);
+            continue;
+        }
+        let actor = event.actor.as_ref().map_or("-", |a| a.channel.as_str());
+        let who = event
+            .actor
+            .as_ref()
+            .and_then(|a| a.username.as_deref().or(a.user_id.as_deref()))
+            .map(|who| format!(" ({who})"))
+            .unwrap_or_default();
+        let action = event
+            .action
+            .as_ref()
+            .and_then(|a| a.command.as_deref())
+            .unwrap_or("");
+        let verdict = match (&event.action, &event.result) {
+            (_, Some(result)) if !result.success => style("failed").red(),
+            (Some(action), _) if !action.allowed => style("denied").red(),
+            _ => style("ok").green(),
+        };
+        println!(
+            "{} {:<18} {actor}{who} {verdict} {action}",
+            style(event.timestamp.to_rfc3339()).dim(),
+            event_type_name(event),
+        );
+        if let Some(error) = event.result.as_ref().and_then(|r| r.error.as_deref()) {
+            println!("    {}", style(error).dim());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::audit::AuditEventType;
+
+    #[test]
+    fn search_filters_by_type_channel_and_text() {
+        let event = AuditEvent::new(AuditEventType::AuthFailure)
+            .with_actor("gateway".into(), Some("10.0.0.5".into()), None)
+            .with_action("POST /pair".into(), "auth".into(), false, false);
+
+        assert!(matches(
+            &event,
+            Some("/pair"),
+            Some("auth_failure"),
+            Some("gateway")
+        ));
+        assert!(!matches(&event, None, Some("auth_success"), None));
+        assert!(!matches(&event, None, None, Some("shell")));
+        assert!(!matches(&event, Some("rm -rf"), None, None));
+    }
+}
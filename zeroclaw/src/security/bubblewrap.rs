@@ -1,6 +1,7 @@
 //! Bubblewrap sandbox (user namespaces for Linux/macOS)
 
-use crate::security::traits::Sandbox;
+use crate::security::traits::{wrap_with, Sandbox, SandboxScope};
+use std::path::Path;
 use std::process::Command;
 
 /// Bubblewrap sandbox backend
@@ -32,33 +33,49 @@ impl BubblewrapSandbox {
     }
 }
 
+/// Host directories bound read-only so ordinary commands can run.
+const SYSTEM_BINDS: &[&str] = &["/usr", "/bin", "/sbin", "/lib", "/lib64"];
+
 impl Sandbox for BubblewrapSandbox {
     fn wrap_command(&self, cmd: &mut Command) -> std::io::Result<()> {
-        let program = cmd.get_program().to_string_lossy().to_string();
-        let args: Vec<String> = cmd
-            .get_args()
-            .map(|s| s.to_string_lossy().to_string())
-            .collect();
+        self.wrap_command_in_scope(cmd, &SandboxScope::default())
+    }
 
+    fn wrap_command_in_scope(
+        &self,
+        cmd: &mut Command,
+        scope: &SandboxScope,
+    ) -> std::io::Result<()> {
         let mut bwrap_cmd = Command::new("bwrap");
-        bwrap_cmd.args([
-            "--ro-bind",
-            "/usr",
-            "/usr",
-            "--dev",
-            "/dev",
-            "--proc",
-            "/proc",
-            "--bind",
-            "/tmp",
-            "/tmp",
-            "--unshare-all",
-            "--die-with-parent",
-        ]);
-        bwrap_cmd.arg(&program);
-        bwrap_cmd.args(&args);
-
-        *cmd = bwrap_cmd;
+        bwrap_cmd.args(["--ro-bind", "/usr", "/usr"]);
+        for dir in &SYSTEM_BINDS[1..] {
+            // Absent on merged-/usr systems
+            bwrap_cmd.args(["--ro-bind-try", dir, dir]);
+        }
+        bwrap_cmd.args(["--dev", "/dev", "--proc", "/proc", "--bind", "/tmp", "/tmp"]);
+
+        let mut mounted: Vec<&Path> = SYSTEM_BINDS.iter().map(Path::new).collect();
+        mounted.push(Path::new("/tmp"));
+        if let Some(workspace) = &scope.workspace_dir {
+            bwrap_cmd.arg("--bind").arg(workspace).arg(workspace);
+            bwrap_cmd.arg("--chdir").arg(workspace);
+            mounted.push(workspace);
+        }
+
+        // Anything outside the binds is already absent; mask what a bind exposes.
+        for hidden in &scope.hidden_paths {
+            if !mounted.iter().any(|root| hidden.starts_with(root)) {
+                continue;
+            }
+            if hidden.is_dir() {
+                bwrap_cmd.arg("--tmpfs").arg(hidden);
+            } else if hidden.exists() {
+                bwrap_cmd.args(["--ro-bind", "/dev/null"]).arg(hidden);
+            }
+        }
+
+        bwrap_cmd.args(["--unshare-all", "--die-with-parent"]);
+        wrap_with(cmd, bwrap_cmd);
         Ok(())
     }
 
@@ -180,4 +197,45 @@ mod tests {
             "must include /proc mount"
         );
     }
+
+    #[test]
+    fn bubblewrap_scope_binds_workspace_and_masks_hidden_paths() {
+        let workspace = tempfile::tempdir().unwrap();
+        let secret_dir = workspace.path().join("secrets");
+        std::fs::create_dir(&secret_dir).unwrap();
+        let secret_file = workspace.path().join(".env");
+        std::fs::write(&secret_file, "TOKEN=x").unwrap();
+
+        let scope = SandboxScope {
+            workspace_dir: Some(workspace.path().to_path_buf()),
+            hidden_paths: vec![secret_dir.clone(), secret_file.clone(), "/root".into()],
+        };
+        let mut cmd = Command::new("sh");
+        BubblewrapSandbox
+            .wrap_command_in_scope(&mut cmd, &scope)
+            .unwrap();
+
+        let args: Vec<String> = cmd
+            .get_args()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect();
+        let ws = workspace.path().display().to_string();
+        let has = |window: &[&str]| {
+            args.windows(window.len())
+                .any(|w| w.iter().zip(window).all(|(a, b)| a == b))
+        };
+
+        assert!(has(&["--bind", &ws, &ws]));
+        assert!(has(&["--chdir", &ws]));
+        assert!(has(&["--tmpfs", &secret_dir.display().to_string()]));
+        assert!(has(&[
+            "--ro-bind",
+            "/dev/null",
+            &secret_file.display().to_string()
+        ]));
+        assert!(
+            !args.contains(&"/root".to_string()),
+            "unbound paths need no mask"
+        );
+    }
 }
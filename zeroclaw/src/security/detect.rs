@@ -1,10 +1,15 @@
 //! Auto-detection of available security features
 
-use crate::config::{SandboxBackend, SecurityConfig};
+use crate::config::{Config, SandboxBackend, SecurityConfig};
 use crate::security::traits::Sandbox;
 use std::sync::Arc;
 
 /// Create a sandbox based on auto-detection or explicit config
+///
+/// When the requested backend is unavailable this falls back to
+/// [`NoopSandbox`](super::traits::NoopSandbox), or to an
+/// [`UnavailableSandbox`] that refuses every command when
+/// `[security.sandbox] fail_closed = true`.
 pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
     let backend = &config.sandbox.backend;
 
@@ -25,10 +30,7 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
                     }
                 }
             }
-            tracing::warn!(
-                "Landlock requested but not available, falling back to application-layer"
-            );
-            Arc::new(super::traits::NoopSandbox)
+            unavailable(config, "landlock", "Landlock")
         }
         SandboxBackend::Firejail => {
             #[cfg(target_os = "linux")]
@@ -37,10 +39,7 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
                     return Arc::new(sandbox);
                 }
             }
-            tracing::warn!(
-                "Firejail requested but not available, falling back to application-layer"
-            );
-            Arc::new(super::traits::NoopSandbox)
+            unavailable(config, "firejail", "Firejail")
         }
         SandboxBackend::Bubblewrap => {
             #[cfg(feature = "sandbox-bubblewrap")]
@@ -52,25 +51,90 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
                     }
                 }
             }
-            tracing::warn!(
-                "Bubblewrap requested but not available, falling back to application-layer"
-            );
-            Arc::new(super::traits::NoopSandbox)
+            unavailable(config, "bubblewrap", "Bubblewrap")
         }
         SandboxBackend::Docker => {
             if let Ok(sandbox) = super::docker::DockerSandbox::new() {
                 return Arc::new(sandbox);
             }
-            tracing::warn!("Docker requested but not available, falling back to application-layer");
-            Arc::new(super::traits::NoopSandbox)
+            unavailable(config, "docker", "Docker")
         }
         SandboxBackend::Auto | SandboxBackend::None => {
             // Auto-detect best available
-            detect_best_sandbox()
+            let sandbox = detect_best_sandbox();
+            // `enabled = true` with auto-detection still demands some backend
+            if sandbox.name() == "none" && config.sandbox.enabled == Some(true) {
+                return unavailable(config, "auto", "Auto-detected sandbox");
+            }
+            sandbox
         }
     }
 }
 
+/// Sandbox for shell and cron commands.
+///
+/// Commands are only wrapped when `[security.sandbox]` opts in, either with
+/// `enabled = true` or by naming a backend; the default config runs them
+/// directly. `enabled = false` turns wrapping off whatever the backend says.
+/// A `docker` runtime already isolates commands, so it is never wrapped a
+/// second time.
+pub fn create_command_sandbox(config: &Config) -> Arc<dyn Sandbox> {
+    let sandbox = &config.security.sandbox;
+    let requested = match sandbox.enabled {
+        Some(enabled) => enabled,
+        None => !matches!(sandbox.backend, SandboxBackend::Auto | SandboxBackend::None),
+    };
+    if !requested || config.runtime.kind == "docker" {
+        return Arc::new(super::traits::NoopSandbox);
+    }
+    create_sandbox(&config.security)
+}
+
+/// Fallback for a requested backend that could not be created.
+fn unavailable(config: &SecurityConfig, backend: &str, label: &str) -> Arc<dyn Sandbox> {
+    if config.sandbox.fail_closed {
+        tracing::error!(
+            "{label} requested but not available, refusing shell commands (fail_closed)"
+        );
+        return Arc::new(UnavailableSandbox {
+            backend: backend.to_string(),
+        });
+    }
+    tracing::warn!("{label} requested but not available, falling back to application-layer");
+    Arc::new(super::traits::NoopSandbox)
+}
+
+/// Stand-in for a requested backend that is unavailable while
+/// `[security.sandbox] fail_closed = true`: every command is refused.
+#[derive(Debug, Clone)]
+pub struct UnavailableSandbox {
+    backend: String,
+}
+
+impl Sandbox for UnavailableSandbox {
+    fn wrap_command(&self, _cmd: &mut std::process::Command) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!(
+                "sandbox backend '{}' is unavailable and [security.sandbox] fail_closed = true",
+                self.backend
+            ),
+        ))
+    }
+
+    fn is_available(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &str {
+        &self.backend
+    }
+
+    fn description(&self) -> &str {
+        "Requested sandbox unavailable; commands are refused (fail_closed)"
+    }
+}
+
 /// Auto-detect the best available sandbox
 fn detect_best_sandbox() -> Arc<dyn Sandbox> {
     #[cfg(target_os = "linux")]
@@ -133,6 +197,7 @@ mod tests {
                 enabled: Some(false),
                 backend: SandboxBackend::None,
                 firejail_args: Vec::new(),
+                fail_closed: false,
             },
             ..Default::default()
         };
@@ -147,6 +212,7 @@ mod tests {
                 enabled: None, // Auto-detect
                 backend: SandboxBackend::Auto,
                 firejail_args: Vec::new(),
+                fail_closed: false,
             },
             ..Default::default()
         };
@@ -154,4 +220,48 @@ mod tests {
         // Should return some sandbox (at least NoopSandbox)
         assert!(sandbox.is_available());
     }
+
+    #[test]
+    fn command_sandbox_is_opt_in() {
+        let mut config = Config::default();
+        assert_eq!(create_command_sandbox(&config).name(), "none");
+
+        config.security.sandbox.enabled = Some(false);
+        config.security.sandbox.backend = SandboxBackend::Firejail;
+        assert_eq!(create_command_sandbox(&config).name(), "none");
+    }
+
+    #[test]
+    fn command_sandbox_is_skipped_under_docker_runtime() {
+        let mut config = Config::default();
+        config.security.sandbox.enabled = Some(true);
+        config.security.sandbox.backend = SandboxBackend::Docker;
+        config.runtime.kind = "docker".into();
+        assert_eq!(create_command_sandbox(&config).name(), "none");
+    }
+
+    #[test]
+    fn fail_closed_refuses_commands_when_backend_is_missing() {
+        let config = SecurityConfig {
+            sandbox: SandboxConfig {
+                enabled: Some(true),
+                backend: SandboxBackend::Firejail,
+                firejail_args: Vec::new(),
+                fail_closed: true,
+            },
+            ..Default::default()
+        };
+        let sandbox = create_sandbox(&config);
+        if sandbox.is_available() {
+            // firejail is installed on this host; nothing to refuse
+            assert_eq!(sandbox.name(), "firejail");
+            return;
+        }
+
+        assert_eq!(sandbox.name(), "firejail");
+        let mut cmd = std::process::Command::new("echo");
+        let err = sandbox.wrap_command(&mut cmd).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+        assert!(err.to_string().contains("fail_closed"));
+    }
 }
@@ -1,6 +1,6 @@
 //! Docker sandbox (container isolation)
 
-use crate::security::traits::Sandbox;
+use crate::security::traits::{wrap_with, Sandbox, SandboxScope};
 use std::process::Command;
 
 /// Docker sandbox backend
@@ -55,12 +55,14 @@ impl DockerSandbox {
 
 impl Sandbox for DockerSandbox {
     fn wrap_command(&self, cmd: &mut Command) -> std::io::Result<()> {
-        let program = cmd.get_program().to_string_lossy().to_string();
-        let args: Vec<String> = cmd
-            .get_args()
-            .map(|s| s.to_string_lossy().to_string())
-            .collect();
+        self.wrap_command_in_scope(cmd, &SandboxScope::default())
+    }
 
+    fn wrap_command_in_scope(
+        &self,
+        cmd: &mut Command,
+        scope: &SandboxScope,
+    ) -> std::io::Result<()> {
         let mut docker_cmd = Command::new("docker");
         docker_cmd.args([
             "run",
@@ -72,11 +74,16 @@ impl Sandbox for DockerSandbox {
             "--network",
             "none",
         ]);
+        // The container only sees its image plus the workspace, so hidden
+        // host paths need no masking.
+        if let Some(workspace) = &scope.workspace_dir {
+            let workspace = workspace.display();
+            docker_cmd.args(["-v", &format!("{workspace}:{workspace}")]);
+            docker_cmd.args(["-w", &workspace.to_string()]);
+        }
         docker_cmd.arg(&self.image);
-        docker_cmd.arg(&program);
-        docker_cmd.args(&args);
 
-        *cmd = docker_cmd;
+        wrap_with(cmd, docker_cmd);
         Ok(())
     }
 
@@ -213,4 +220,27 @@ mod tests {
             "must use the custom image"
         );
     }
+
+    #[test]
+    fn docker_scope_mounts_workspace_as_working_directory() {
+        let scope = SandboxScope {
+            workspace_dir: Some("/srv/ws".into()),
+            hidden_paths: vec!["/root".into()],
+        };
+        let mut cmd = Command::new("sh");
+        DockerSandbox::default()
+            .wrap_command_in_scope(&mut cmd, &scope)
+            .unwrap();
+
+        let args: Vec<String> = cmd
+            .get_args()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect();
+        let image = args.iter().position(|a| a == "alpine:latest").unwrap();
+        let mount = args.iter().position(|a| a == "/srv/ws:/srv/ws").unwrap();
+        assert!(mount < image, "mount must precede the image");
+        assert_eq!(args[mount - 1], "-v");
+        assert!(args.windows(2).any(|w| w == ["-w", "/srv/ws"]));
+        assert_eq!(args[image + 1], "sh");
+    }
 }
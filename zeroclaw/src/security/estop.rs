@@ -1,12 +1,17 @@
-use crate::config::EstopConfig;
+use crate::config::{EstopConfig, SecurityConfig};
+use crate::security::audit::{AuditEvent, AuditEventType, AuditLogger};
 use crate::security::domain_matcher::DomainMatcher;
 use crate::security::otp::OtpValidator;
 use anyhow::{Context, Result};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+static ESTOP_GUARD: LazyLock<RwLock<Option<Arc<EstopGuard>>>> = LazyLock::new(|| RwLock::new(None));
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EstopLevel {
     KillAll,
@@ -125,6 +130,7 @@ impl EstopManager {
     }
 
     pub fn engage(&mut self, level: EstopLevel) -> Result<()> {
+        let action = format!("estop engage {level:?}");
         match level {
             EstopLevel::KillAll => {
                 self.state.kill_all = true;
@@ -149,7 +155,9 @@ impl EstopManager {
 
         self.state.updated_at = Some(now_rfc3339());
         self.state.normalize();
-        self.persist_state()
+        self.persist_state()?;
+        record_state_change(action);
+        Ok(())
     }
 
     pub fn resume(
@@ -160,6 +168,7 @@ impl EstopManager {
     ) -> Result<()> {
         self.ensure_resume_is_authorized(otp_code, otp_validator)?;
 
+        let action = format!("estop resume {selector:?}");
         match selector {
             ResumeSelector::KillAll => {
                 self.state.kill_all = false;
@@ -189,7 +198,9 @@ impl EstopManager {
 
         self.state.updated_at = Some(now_rfc3339());
         self.state.normalize();
-        self.persist_state()
+        self.persist_state()?;
+        record_state_change(action);
+        Ok(())
     }
 
     fn ensure_resume_is_authorized(
@@ -251,6 +262,210 @@ impl EstopManager {
     }
 }
 
+/// Returned when an engaged estop refuses an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EstopBlockedError {
+    pub reason: String,
+}
+
+impl std::fmt::Display for EstopBlockedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Blocked by emergency stop: {}. Resume with `zeroclaw estop resume`.",
+            self.reason
+        )
+    }
+}
+
+impl std::error::Error for EstopBlockedError {}
+
+pub fn is_estop_blocked(err: &anyhow::Error) -> bool {
+    err.chain().any(|source| source.is::<EstopBlockedError>())
+}
+
+/// Read-only view of the persisted estop state for enforcement points.
+///
+/// The state file is re-read whenever its modification time or size changes,
+/// so an estop engaged by `zeroclaw estop` in another process takes effect on
+/// the next check. Refusals are written to the audit log when one is attached.
+pub struct EstopGuard {
+    state_path: PathBuf,
+    cache: Mutex<CachedState>,
+    audit: Option<AuditLogger>,
+}
+
+#[derive(Default)]
+struct CachedState {
+    stamp: Option<(SystemTime, u64)>,
+    state: EstopState,
+}
+
+impl EstopGuard {
+    pub fn new(config: &EstopConfig, config_dir: &Path) -> Self {
+        Self {
+            state_path: resolve_state_file_path(config_dir, &config.state_file),
+            cache: Mutex::new(CachedState::default()),
+            audit: None,
+        }
+    }
+
+    /// Record every refusal in `audit`.
+    pub fn with_audit(mut self, audit: AuditLogger) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Current state, reloaded from disk if the file changed since last read.
+    pub fn state(&self) -> EstopState {
+        let mut cache = self.cache.lock();
+        let stamp = fs::metadata(&self.state_path)
+            .ok()
+            .map(|meta| (meta.modified().unwrap_or(UNIX_EPOCH), meta.len()));
+        if stamp != cache.stamp {
+            cache.state = match stamp {
+                None => EstopState::default(),
+                Some(_) => read_state(&self.state_path),
+            };
+            cache.stamp = stamp;
+        }
+        cache.state.clone()
+    }
+
+    /// Refuse all work while kill-all is engaged.
+    pub fn check_running(&self, scope: &str) -> Result<(), EstopBlockedError> {
+        if self.state().kill_all {
+            return Err(self.refuse(scope, scope, "kill-all is engaged"));
+        }
+        Ok(())
+    }
+
+    /// Refuse `tool` while kill-all is engaged or the tool is frozen.
+    pub fn check_tool(&self, tool: &str) -> Result<(), EstopBlockedError> {
+        let state = self.state();
+        let scope = format!("tool:{tool}");
+        if state.kill_all {
+            return Err(self.refuse(&scope, tool, "kill-all is engaged"));
+        }
+        let normalized = tool.trim().to_ascii_lowercase();
+        if state.frozen_tools.contains(&normalized) {
+            return Err(self.refuse(&scope, tool, &format!("tool '{tool}' is frozen")));
+        }
+        Ok(())
+    }
+
+    /// Refuse outbound network traffic for `scope` while kill-all or
+    /// network-kill is engaged.
+    pub fn check_network(&self, scope: &str) -> Result<(), EstopBlockedError> {
+        let state = self.state();
+        if state.kill_all {
+            return Err(self.refuse(scope, scope, "kill-all is engaged"));
+        }
+        if state.network_kill {
+            return Err(self.refuse(scope, scope, "network kill is engaged"));
+        }
+        Ok(())
+    }
+
+    /// [`check_network`](Self::check_network) plus the blocked-domain list.
+    pub fn check_domain(&self, scope: &str, host: &str) -> Result<(), EstopBlockedError> {
+        self.check_network(scope)?;
+        let state = self.state();
+        if state.blocked_domains.is_empty() {
+            return Ok(());
+        }
+        let blocked = DomainMatcher::new(&state.blocked_domains, &[])
+            .map(|matcher| matcher.is_gated(host))
+            // Patterns are validated on engage; an unreadable list blocks everything.
+            .unwrap_or(true);
+        if blocked {
+            return Err(self.refuse(scope, host, &format!("domain '{host}' is blocked")));
+        }
+        Ok(())
+    }
+
+    fn refuse(&self, scope: &str, target: &str, reason: &str) -> EstopBlockedError {
+        tracing::warn!(scope, target, "Emergency stop refused action: {reason}");
+        if let Some(audit) = &self.audit {
+            let event = AuditEvent::new(AuditEventType::PolicyViolation)
+                .with_actor(scope.to_string(), None, None)
+                .with_action(target.to_string(), "estop".to_string(), false, false)
+                .with_policy_violation();
+            if let Err(e) = audit.log(&event) {
+                tracing::warn!("Failed to write estop audit event: {e}");
+            }
+        }
+        EstopBlockedError {
+            reason: reason.to_string(),
+        }
+    }
+}
+
+fn read_state(path: &Path) -> EstopState {
+    match fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|raw| Ok(serde_json::from_str::<EstopState>(&raw)?))
+    {
+        Ok(mut state) => {
+            state.normalize();
+            state
+        }
+        Err(error) => {
+            tracing::warn!(
+                path = %path.display(),
+                "Failed to read estop state file; treating as kill-all: {error}"
+            );
+            EstopState::fail_closed()
+        }
+    }
+}
+
+/// Install the process-wide guard from config. Disabled estop clears it.
+pub fn init_from_config(config: &SecurityConfig, config_dir: &Path) {
+    let guard = config.estop.enabled.then(|| {
+        let guard = EstopGuard::new(&config.estop, config_dir);
+        let guard = match AuditLogger::new(config.audit.clone(), config_dir.to_path_buf()) {
+            Ok(audit) => guard.with_audit(audit),
+            Err(e) => {
+                tracing::warn!("Failed to open audit log for estop: {e}");
+                guard
+            }
+        };
+        Arc::new(guard)
+    });
+
+    let mut slot = ESTOP_GUARD.write().unwrap_or_else(|e| e.into_inner());
+    *slot = guard;
+}
+
+/// The process-wide guard, if estop is enabled.
+pub fn global_guard() -> Option<Arc<EstopGuard>> {
+    ESTOP_GUARD
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// The process-wide guard, initializing it from `config` on first use.
+pub fn shared_guard(config: &SecurityConfig, config_dir: &Path) -> Option<Arc<EstopGuard>> {
+    if !config.estop.enabled {
+        return None;
+    }
+    if let Some(guard) = global_guard() {
+        return Some(guard);
+    }
+    init_from_config(config, config_dir);
+    global_guard()
+}
+
+/// Check the shared guard before outbound traffic. See [`EstopGuard::check_network`].
+pub fn enforce_network(scope: &str) -> Result<(), EstopBlockedError> {
+    match global_guard() {
+        Some(guard) => guard.check_network(scope),
+        None => Ok(()),
+    }
+}
+
 pub fn resolve_state_file_path(config_dir: &Path, state_file: &str) -> PathBuf {
     let expanded = shellexpand::tilde(state_file).into_owned();
     let path = PathBuf::from(expanded);
@@ -287,6 +502,15 @@ fn dedup_sort(values: &[String]) -> Vec<String> {
     deduped
 }
 
+/// Record an engage/resume in the process-wide audit log.
+fn record_state_change(action: String) {
+    crate::security::audit::record(
+        AuditEvent::new(AuditEventType::SecurityEvent)
+            .with_actor("estop".into(), None, None)
+            .with_action(action, "high".into(), true, true),
+    );
+}
+
 fn now_rfc3339() -> String {
     let secs = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -419,4 +643,75 @@ mod tests {
             .unwrap();
         assert!(!manager.status().kill_all);
     }
+
+    #[test]
+    fn guard_follows_state_changed_by_another_manager() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("estop-state.json");
+        let cfg = estop_config(&state_path);
+        let guard = EstopGuard::new(&cfg, dir.path());
+        assert!(guard.check_tool("shell").is_ok());
+        assert!(guard.check_network("provider:openai").is_ok());
+
+        let mut manager = EstopManager::load(&cfg, dir.path()).unwrap();
+        manager
+            .engage(EstopLevel::ToolFreeze(vec!["shell".into()]))
+            .unwrap();
+        manager.engage(EstopLevel::NetworkKill).unwrap();
+
+        let err = guard.check_tool("shell").unwrap_err();
+        assert!(err.to_string().contains("tool 'shell' is frozen"));
+        assert!(guard.check_tool("file_read").is_ok());
+        assert!(guard.check_network("provider:openai").is_err());
+
+        manager
+            .resume(ResumeSelector::Tools(vec!["shell".into()]), None, None)
+            .unwrap();
+        manager.resume(ResumeSelector::Network, None, None).unwrap();
+        assert!(guard.check_tool("shell").is_ok());
+        assert!(guard.check_network("provider:openai").is_ok());
+    }
+
+    #[test]
+    fn guard_blocks_domains_and_audits_refusals() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("estop-state.json");
+        let cfg = estop_config(&state_path);
+        let mut manager = EstopManager::load(&cfg, dir.path()).unwrap();
+        manager
+            .engage(EstopLevel::DomainBlock(vec!["*.chase.com".into()]))
+            .unwrap();
+
+        let audit = AuditLogger::new(
+            crate::config::AuditConfig::default(),
+            dir.path().to_path_buf(),
+        )
+        .unwrap();
+        let guard = EstopGuard::new(&cfg, dir.path()).with_audit(audit);
+        assert!(guard.check_domain("http_request", "example.com").is_ok());
+        let err = guard
+            .check_domain("http_request", "secure.chase.com")
+            .unwrap_err();
+        assert!(err.to_string().contains("secure.chase.com"));
+        assert!(is_estop_blocked(&anyhow::Error::new(err)));
+
+        let log = fs::read_to_string(dir.path().join("audit.log")).unwrap();
+        let event: AuditEvent = serde_json::from_str(log.trim()).unwrap();
+        assert!(matches!(event.event_type, AuditEventType::PolicyViolation));
+        assert!(event.security.policy_violation);
+        assert_eq!(
+            event.action.unwrap().command.as_deref(),
+            Some("secure.chase.com")
+        );
+    }
+
+    #[test]
+    fn guard_treats_unreadable_state_as_kill_all() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("estop-state.json");
+        fs::write(&state_path, "{not-valid-json").unwrap();
+        let guard = EstopGuard::new(&estop_config(&state_path), dir.path());
+        assert!(guard.check_running("channel:telegram").is_err());
+        assert!(guard.check_tool("memory_recall").is_err());
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! Firejail is a SUID sandbox program that Linux applications use to sandbox themselves.
 
-use crate::security::traits::Sandbox;
+use crate::security::traits::{wrap_with, Sandbox, SandboxScope};
 use std::process::Command;
 
 /// Firejail sandbox backend for Linux
@@ -39,33 +39,46 @@ impl FirejailSandbox {
 
 impl Sandbox for FirejailSandbox {
     fn wrap_command(&self, cmd: &mut Command) -> std::io::Result<()> {
-        // Prepend firejail to the command
-        let program = cmd.get_program().to_string_lossy().to_string();
-        let args: Vec<String> = cmd
-            .get_args()
-            .map(|s| s.to_string_lossy().to_string())
-            .collect();
+        self.wrap_command_in_scope(cmd, &SandboxScope::default())
+    }
 
+    fn wrap_command_in_scope(
+        &self,
+        cmd: &mut Command,
+        scope: &SandboxScope,
+    ) -> std::io::Result<()> {
         // Build firejail wrapper with security flags
         let mut firejail_cmd = Command::new("firejail");
+        match &scope.workspace_dir {
+            // Only the workspace survives from the home/user directories
+            Some(workspace) => firejail_cmd.arg(format!("--whitelist={}", workspace.display())),
+            None => firejail_cmd.arg("--private=home"), // New home directory
+        };
         firejail_cmd.args([
-            "--private=home", // New home directory
-            "--private-dev",  // Minimal /dev
-            "--nosound",      // No audio
-            "--no3d",         // No 3D acceleration
-            "--novideo",      // No video devices
-            "--nowheel",      // No input devices
-            "--notv",         // No TV devices
-            "--noprofile",    // Skip profile loading
-            "--quiet",        // Suppress warnings
+            "--private-dev", // Minimal /dev
+            "--nosound",     // No audio
+            "--no3d",        // No 3D acceleration
+            "--novideo",     // No video devices
+            "--nowheel",     // No input devices
+            "--notv",        // No TV devices
+            "--noprofile",   // Skip profile loading
+            "--quiet",       // Suppress warnings
         ]);
 
-        // Add the original command
-        firejail_cmd.arg(&program);
-        firejail_cmd.args(&args);
+        for hidden in &scope.hidden_paths {
+            // Blacklisting an ancestor would also hide the whitelisted workspace
+            if scope
+                .workspace_dir
+                .as_ref()
+                .is_some_and(|workspace| workspace.starts_with(hidden))
+            {
+                continue;
+            }
+            firejail_cmd.arg(format!("--blacklist={}", hidden.display()));
+        }
 
-        // Replace the command
-        *cmd = firejail_cmd;
+        // Add the original command and replace it
+        wrap_with(cmd, firejail_cmd);
         Ok(())
     }
 
@@ -192,4 +205,31 @@ mod tests {
             "original args must be preserved"
         );
     }
+
+    #[test]
+    fn firejail_scope_whitelists_workspace_and_blacklists_hidden_paths() {
+        let scope = SandboxScope {
+            workspace_dir: Some("/home/user/.zeroclaw/workspace".into()),
+            hidden_paths: vec!["/home".into(), "/root".into(), "/home/user/.ssh".into()],
+        };
+        let mut cmd = Command::new("sh");
+        FirejailSandbox
+            .wrap_command_in_scope(&mut cmd, &scope)
+            .unwrap();
+
+        let args: Vec<String> = cmd
+            .get_args()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect();
+
+        assert!(args.contains(&"--whitelist=/home/user/.zeroclaw/workspace".to_string()));
+        assert!(!args.contains(&"--private=home".to_string()));
+        assert!(args.contains(&"--blacklist=/root".to_string()));
+        assert!(args.contains(&"--blacklist=/home/user/.ssh".to_string()));
+        assert!(
+            !args.contains(&"--blacklist=/home".to_string()),
+            "ancestors of the workspace must stay reachable"
+        );
+        assert_eq!(args.last().map(String::as_str), Some("sh"));
+    }
 }
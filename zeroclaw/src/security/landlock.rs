@@ -4,10 +4,17 @@
 //! This module uses the pure-Rust `landlock` crate for filesystem access control.
 
 #[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
-use landlock::{AccessFs, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreatedAttr};
+use landlock::{
+    AccessFs, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreated, RulesetCreatedAttr,
+};
+#[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
+use std::path::Path;
+#[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
+use std::sync::Mutex;
 
 use crate::security::traits::Sandbox;
-use std::path::Path;
+#[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
+use crate::security::traits::SandboxScope;
 
 /// Landlock sandbox backend for Linux
 #[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
@@ -47,89 +54,81 @@ impl LandlockSandbox {
         Self::new()
     }
 
-    /// Apply Landlock restrictions to the current process
-    fn apply_restrictions(&self) -> std::io::Result<()> {
+    /// Build the ruleset a child process will enforce: read-write access
+    /// beneath the workspace and `/tmp`, read-only access to system paths.
+    ///
+    /// Landlock is allow-list based, so paths outside these rules (including
+    /// `forbidden_paths`) are unreachable without explicit masking.
+    fn build_ruleset(&self, workspace: Option<&Path>) -> std::io::Result<RulesetCreated> {
+        let read_write = AccessFs::ReadFile
+            | AccessFs::WriteFile
+            | AccessFs::ReadDir
+            | AccessFs::RemoveDir
+            | AccessFs::RemoveFile
+            | AccessFs::MakeChar
+            | AccessFs::MakeSock
+            | AccessFs::MakeFifo
+            | AccessFs::MakeBlock
+            | AccessFs::MakeReg
+            | AccessFs::MakeSym;
         let mut ruleset = Ruleset::default()
-            .handle_access(
-                AccessFs::ReadFile
-                    | AccessFs::WriteFile
-                    | AccessFs::ReadDir
-                    | AccessFs::RemoveDir
-                    | AccessFs::RemoveFile
-                    | AccessFs::MakeChar
-                    | AccessFs::MakeSock
-                    | AccessFs::MakeFifo
-                    | AccessFs::MakeBlock
-                    | AccessFs::MakeReg
-                    | AccessFs::MakeSym,
-            )
+            .handle_access(read_write)
             .and_then(|ruleset| ruleset.create())
             .map_err(|e| std::io::Error::other(e.to_string()))?;
 
-        // Allow workspace directory (read/write)
-        if let Some(ref workspace) = self.workspace_dir {
-            if workspace.exists() {
-                let workspace_fd =
-                    PathFd::new(workspace).map_err(|e| std::io::Error::other(e.to_string()))?;
-                ruleset = ruleset
-                    .add_rule(PathBeneath::new(
-                        workspace_fd,
-                        AccessFs::ReadFile | AccessFs::WriteFile | AccessFs::ReadDir,
-                    ))
-                    .map_err(|e| std::io::Error::other(e.to_string()))?;
-            }
+        let mut rules: Vec<(&Path, _)> = vec![(Path::new("/tmp"), read_write)];
+        if let Some(workspace) = workspace.or(self.workspace_dir.as_deref()) {
+            rules.push((workspace, read_write));
+        }
+        // Allow system paths for executing commands
+        for dir in ["/usr", "/bin", "/lib", "/lib64", "/etc", "/dev", "/proc"] {
+            rules.push((Path::new(dir), AccessFs::ReadFile | AccessFs::ReadDir));
         }
 
-        // Allow /tmp for general operations
-        let tmp_fd =
-            PathFd::new(Path::new("/tmp")).map_err(|e| std::io::Error::other(e.to_string()))?;
-        ruleset = ruleset
-            .add_rule(PathBeneath::new(
-                tmp_fd,
-                AccessFs::ReadFile | AccessFs::WriteFile,
-            ))
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
-
-        // Allow /usr and /bin for executing commands
-        let usr_fd =
-            PathFd::new(Path::new("/usr")).map_err(|e| std::io::Error::other(e.to_string()))?;
-        ruleset = ruleset
-            .add_rule(PathBeneath::new(
-                usr_fd,
-                AccessFs::ReadFile | AccessFs::ReadDir,
-            ))
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
-
-        let bin_fd =
-            PathFd::new(Path::new("/bin")).map_err(|e| std::io::Error::other(e.to_string()))?;
-        ruleset = ruleset
-            .add_rule(PathBeneath::new(
-                bin_fd,
-                AccessFs::ReadFile | AccessFs::ReadDir,
-            ))
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
-
-        // Apply the ruleset
-        match ruleset.restrict_self() {
-            Ok(_) => {
-                tracing::debug!("Landlock restrictions applied successfully");
-                Ok(())
-            }
-            Err(e) => {
-                tracing::warn!("Failed to apply Landlock restrictions: {}", e);
-                Err(std::io::Error::other(e.to_string()))
+        for (path, access) in rules {
+            if !path.exists() {
+                continue;
             }
+            let fd = PathFd::new(path).map_err(|e| std::io::Error::other(e.to_string()))?;
+            ruleset = ruleset
+                .add_rule(PathBeneath::new(fd, access))
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
         }
+        Ok(ruleset)
     }
 }
 
 #[cfg(all(feature = "sandbox-landlock", target_os = "linux"))]
 impl Sandbox for LandlockSandbox {
-    fn wrap_command(&self, _cmd: &mut std::process::Command) -> std::io::Result<()> {
-        // Apply Landlock restrictions before executing the command
-        // Note: This affects the current process, not the child process
-        // Child processes inherit the Landlock restrictions
-        self.apply_restrictions()
+    fn wrap_command(&self, cmd: &mut std::process::Command) -> std::io::Result<()> {
+        self.wrap_command_in_scope(cmd, &SandboxScope::default())
+    }
+
+    fn wrap_command_in_scope(
+        &self,
+        cmd: &mut std::process::Command,
+        scope: &SandboxScope,
+    ) -> std::io::Result<()> {
+        use std::os::unix::process::CommandExt;
+
+        // Built here so only the child, never this process, is restricted.
+        let ruleset = Mutex::new(Some(self.build_ruleset(scope.workspace_dir.as_deref())?));
+        // SAFETY: the closure runs between fork and exec and only issues the
+        // prctl/landlock syscalls needed to restrict the child itself.
+        unsafe {
+            cmd.pre_exec(move || {
+                let ruleset = ruleset
+                    .lock()
+                    .ok()
+                    .and_then(|mut ruleset| ruleset.take())
+                    .ok_or_else(|| std::io::Error::other("Landlock ruleset already applied"))?;
+                ruleset
+                    .restrict_self()
+                    .map(|_| ())
+                    .map_err(|e| std::io::Error::other(e.to_string()))
+            });
+        }
+        Ok(())
     }
 
     fn is_available(&self) -> bool {
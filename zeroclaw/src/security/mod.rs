@@ -19,6 +19,7 @@
 //! change guidelines.
 
 pub mod audit;
+pub mod audit_cli;
 #[cfg(feature = "sandbox-bubblewrap")]
 pub mod bubblewrap;
 pub mod detect;
@@ -33,28 +34,36 @@ pub mod firejail;
 pub mod landlock;
 pub mod leak_detector;
 pub mod otp;
+pub mod outbound;
 pub mod pairing;
 pub mod policy;
 pub mod prompt_guard;
+pub mod resources;
 pub mod secrets;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use audit::{AuditEvent, AuditEventType, AuditLogger};
 #[allow(unused_imports)]
-pub use detect::create_sandbox;
+pub use detect::{create_command_sandbox, create_sandbox, UnavailableSandbox};
 pub use domain_matcher::DomainMatcher;
 #[allow(unused_imports)]
-pub use estop::{EstopLevel, EstopManager, EstopState, ResumeSelector};
+pub use estop::{
+    EstopBlockedError, EstopGuard, EstopLevel, EstopManager, EstopState, ResumeSelector,
+};
 #[allow(unused_imports)]
 pub use otp::OtpValidator;
 #[allow(unused_imports)]
+pub use outbound::{OutboundBlocked, OutboundGuard, OutboundSurface};
+#[allow(unused_imports)]
 pub use pairing::PairingGuard;
 pub use policy::{AutonomyLevel, SecurityPolicy};
 #[allow(unused_imports)]
+pub use resources::{LimitExceeded, ResourceLimits};
+#[allow(unused_imports)]
 pub use secrets::SecretStore;
 #[allow(unused_imports)]
-pub use traits::{NoopSandbox, Sandbox};
+pub use traits::{wrap_tokio_command, NoopSandbox, Sandbox, SandboxScope};
 // Prompt injection defense exports
 #[allow(unused_imports)]
 pub use leak_detector::{LeakDetector, LeakResult};
@@ -0,0 +1,374 @@
+//! Outbound leak guard.
+//!
+//! Screens text on its way out of the process — channel replies, gateway
+//! webhook responses and conversation content sent to the LLM provider — with
+//! [`LeakDetector`] plus the user-defined patterns in `[security.outbound]`.
+//! Every match is warned about, redacted or blocked according to its action;
+//! when several match, the strictest wins. Decisions are logged and reported
+//! to the observer as [`ObserverEvent::OutboundLeak`].
+
+use super::leak_detector::{LeakDetector, LeakResult};
+use crate::config::{OutboundAction, OutboundGuardConfig};
+use crate::observability::{Observer, ObserverEvent};
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use regex::Regex;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::{Arc, LazyLock};
+
+static OUTBOUND_GUARD: LazyLock<RwLock<Option<Arc<OutboundGuard>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Where outbound content is headed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboundSurface {
+    /// A reply or announcement sent through a channel.
+    Channel,
+    /// A gateway webhook response body.
+    Gateway,
+    /// Conversation content sent to the LLM provider.
+    Provider,
+}
+
+impl OutboundSurface {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Channel => "channel",
+            Self::Gateway => "gateway",
+            Self::Provider => "provider",
+        }
+    }
+}
+
+/// Content withheld because it matched a blocking pattern.
+#[derive(Debug, Clone)]
+pub struct OutboundBlocked {
+    pub surface: OutboundSurface,
+    pub patterns: Vec<String>,
+}
+
+impl fmt::Display for OutboundBlocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Outbound {} content withheld by leak guard (matched: {})",
+            self.surface.as_str(),
+            self.patterns.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for OutboundBlocked {}
+
+struct CustomPattern {
+    name: String,
+    regex: Regex,
+    action: OutboundAction,
+}
+
+/// Credential leak guard for outbound content.
+pub struct OutboundGuard {
+    detector: LeakDetector,
+    action: OutboundAction,
+    patterns: Vec<CustomPattern>,
+    channels: bool,
+    gateway: bool,
+    providers: bool,
+}
+
+impl OutboundGuard {
+    pub fn new(config: &OutboundGuardConfig) -> Result<Self> {
+        let patterns = config
+            .patterns
+            .iter()
+            .map(|pattern| {
+                Ok(CustomPattern {
+                    name: pattern.name.clone(),
+                    regex: Regex::new(&pattern.regex).with_context(|| {
+                        format!("Invalid outbound leak pattern '{}'", pattern.name)
+                    })?,
+                    action: pattern.action.unwrap_or(config.action),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            detector: LeakDetector::with_sensitivity(config.sensitivity),
+            action: config.action,
+            patterns,
+            channels: config.channels,
+            gateway: config.gateway,
+            providers: config.providers,
+        })
+    }
+
+    /// Whether content bound for `surface` is screened.
+    pub fn covers(&self, surface: OutboundSurface) -> bool {
+        match surface {
+            OutboundSurface::Channel => self.channels,
+            OutboundSurface::Gateway => self.gateway,
+            OutboundSurface::Provider => self.providers,
+        }
+    }
+
+    /// Screen `content` bound for `surface`.
+    ///
+    /// Returns the text to send (redacted where required), or
+    /// [`OutboundBlocked`] when a blocking pattern matched.
+    pub fn screen<'a>(
+        &self,
+        surface: OutboundSurface,
+        content: &'a str,
+        observer: &dyn Observer,
+    ) -> Result<Cow<'a, str>, OutboundBlocked> {
+        if !self.covers(surface) || content.is_empty() {
+            return Ok(Cow::Borrowed(content));
+        }
+
+        let mut matched: Vec<(String, OutboundAction)> = Vec::new();
+        let mut text = Cow::Borrowed(content);
+        if let LeakResult::Detected { patterns, redacted } = self.detector.scan(content) {
+            matched.extend(patterns.into_iter().map(|name| (name, self.action)));
+            if self.action == OutboundAction::Redact {
+                text = Cow::Owned(redacted);
+            }
+        }
+        for pattern in &self.patterns {
+            if !pattern.regex.is_match(content) {
+                continue;
+            }
+            matched.push((pattern.name.clone(), pattern.action));
+            if pattern.action == OutboundAction::Redact {
+                let placeholder = format!("[REDACTED:{}]", pattern.name);
+                text = Cow::Owned(
+                    pattern
+                        .regex
+                        .replace_all(&text, placeholder.as_str())
+                        .into_owned(),
+                );
+            }
+        }
+
+        let Some(action) = matched.iter().map(|(_, action)| *action).max() else {
+            return Ok(Cow::Borrowed(content));
+        };
+        let mut names: Vec<String> = matched.into_iter().map(|(name, _)| name).collect();
+        names.dedup();
+        let action_name = match action {
+            OutboundAction::Warn => "warn",
+            OutboundAction::Redact => "redact",
+            OutboundAction::Block => "block",
+        };
+        tracing::warn!(
+            surface = surface.as_str(),
+            action = action_name,
+            patterns = %names.join(", "),
+            "Outbound content matched the leak guard"
+        );
+        observer.record_event(&ObserverEvent::OutboundLeak {
+            surface: surface.as_str().to_string(),
+            action: action_name.to_string(),
+            patterns: names.clone(),
+        });
+
+        match action {
+            OutboundAction::Block => Err(OutboundBlocked {
+                surface,
+                patterns: names,
+            }),
+            OutboundAction::Redact => Ok(text),
+            OutboundAction::Warn => Ok(Cow::Borrowed(content)),
+        }
+    }
+}
+
+/// Install the process-wide guard. A disabled or invalid config clears it.
+pub fn init_from_config(config: &OutboundGuardConfig) {
+    let guard = config
+        .enabled
+        .then(|| OutboundGuard::new(config))
+        .and_then(|guard| match guard {
+            Ok(guard) => Some(Arc::new(guard)),
+            Err(e) => {
+                tracing::warn!("Outbound leak guard disabled: {e:#}");
+                None
+            }
+        });
+    *OUTBOUND_GUARD.write() = guard;
+}
+
+/// The process-wide guard, if one is installed.
+pub fn global_guard() -> Option<Arc<OutboundGuard>> {
+    OUTBOUND_GUARD.read().clone()
+}
+
+/// The process-wide guard, installing one from `config` when none exists yet.
+pub fn shared_guard(config: &OutboundGuardConfig) -> Option<Arc<OutboundGuard>> {
+    if !config.enabled {
+        return None;
+    }
+    if let Some(guard) = global_guard() {
+        return Some(guard);
+    }
+    init_from_config(config);
+    global_guard()
+}
+
+/// Screen `content` with the process-wide guard; passes through when none is installed.
+pub fn screen<'a>(
+    surface: OutboundSurface,
+    content: &'a str,
+    observer: &dyn Observer,
+) -> Result<Cow<'a, str>, OutboundBlocked> {
+    match global_guard() {
+        Some(guard) => guard.screen(surface, content, observer),
+        None => Ok(Cow::Borrowed(content)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OutboundPatternConfig;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct CapturingObserver {
+        events: Mutex<Vec<(String, String, Vec<String>)>>,
+    }
+
+    impl Observer for CapturingObserver {
+        fn record_event(&self, event: &ObserverEvent) {
+            if let ObserverEvent::OutboundLeak {
+                surface,
+                action,
+                patterns,
+            } = event
+            {
+                self.events
+                    .lock()
+                    .push((surface.clone(), action.clone(), patterns.clone()));
+            }
+        }
+
+        fn record_metric(&self, _metric: &crate::observability::traits::ObserverMetric) {}
+
+        fn name(&self) -> &str {
+            "capturing"
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn guard(config: OutboundGuardConfig) -> OutboundGuard {
+        OutboundGuard::new(&config).unwrap()
+    }
+
+    const KEY_REPLY: &str = "Found it in .env: sk-ant-REDACTED";
+
+    #[test]
+    fn clean_content_passes_without_events() {
+        let observer = CapturingObserver::default();
+        let out = guard(OutboundGuardConfig::default())
+            .screen(OutboundSurface::Channel, "The build is green.", &observer)
+            .unwrap();
+        assert!(matches!(out, Cow::Borrowed("The build is green.")));
+        assert!(observer.events.lock().is_empty());
+    }
+
+    #[test]
+    fn redacts_detected_credentials_by_default() {
+        let observer = CapturingObserver::default();
+        let out = guard(OutboundGuardConfig::default())
+            .screen(OutboundSurface::Channel, KEY_REPLY, &observer)
+            .unwrap();
+        assert!(!out.contains("sk-ant-"), "{out}");
+        assert!(out.contains("[REDACTED_API_KEY]"));
+
+        let events = observer.events.lock();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, "channel");
+        assert_eq!(events[0].1, "redact");
+        assert!(events[0].2.contains(&"Anthropic API key".to_string()));
+    }
+
+    #[test]
+    fn warn_sends_content_unchanged() {
+        let observer = CapturingObserver::default();
+        let out = guard(OutboundGuardConfig {
+            action: OutboundAction::Warn,
+            ..OutboundGuardConfig::default()
+        })
+        .screen(OutboundSurface::Gateway, KEY_REPLY, &observer)
+        .unwrap();
+        assert_eq!(out, KEY_REPLY);
+        assert_eq!(observer.events.lock()[0].1, "warn");
+    }
+
+    #[test]
+    fn custom_patterns_apply_their_own_action_and_strictest_wins() {
+        let observer = CapturingObserver::default();
+        let guard = guard(OutboundGuardConfig {
+            patterns: vec![
+                OutboundPatternConfig {
+                    name: "internal host".into(),
+                    regex: r"[a-z0-9-]+\.corp\.example".into(),
+                    action: None,
+                },
+                OutboundPatternConfig {
+                    name: "customer id".into(),
+                    regex: r"CUST-\d{6}".into(),
+                    action: Some(OutboundAction::Block),
+                },
+            ],
+            ..OutboundGuardConfig::default()
+        });
+
+        let out = guard
+            .screen(
+                OutboundSurface::Channel,
+                "Deploy to db-1.corp.example",
+                &observer,
+            )
+            .unwrap();
+        assert_eq!(out, "Deploy to [REDACTED:internal host]");
+
+        let blocked = guard
+            .screen(
+                OutboundSurface::Channel,
+                "db-1.corp.example belongs to CUST-123456",
+                &observer,
+            )
+            .unwrap_err();
+        assert_eq!(blocked.patterns, vec!["internal host", "customer id"]);
+        assert_eq!(observer.events.lock()[1].1, "block");
+    }
+
+    #[test]
+    fn uncovered_surfaces_are_not_screened() {
+        let observer = CapturingObserver::default();
+        let out = guard(OutboundGuardConfig {
+            providers: false,
+            ..OutboundGuardConfig::default()
+        })
+        .screen(OutboundSurface::Provider, KEY_REPLY, &observer)
+        .unwrap();
+        assert_eq!(out, KEY_REPLY);
+        assert!(observer.events.lock().is_empty());
+    }
+
+    #[test]
+    fn invalid_custom_regex_is_rejected() {
+        let config = OutboundGuardConfig {
+            patterns: vec![OutboundPatternConfig {
+                name: "broken".into(),
+                regex: "(".into(),
+                action: None,
+            }],
+            ..OutboundGuardConfig::default()
+        };
+        assert!(OutboundGuard::new(&config).is_err());
+    }
+}
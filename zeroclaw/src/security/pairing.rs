@@ -7,10 +7,16 @@
 //
 // Already-paired tokens are persisted in config so restarts don't require
 // re-pairing.
+//
+// Besides full-access paired tokens, the guard also checks named API tokens
+// (`[[gateway.api_tokens]]`) that carry scopes, an optional expiry and a
+// last-used timestamp, and can be revoked individually.
 
+use crate::config::GatewayApiToken;
 use parking_lot::Mutex;
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -24,6 +30,44 @@ const MAX_TRACKED_CLIENTS: usize = 10_000;
 const FAILED_ATTEMPT_RETENTION_SECS: u64 = 900; // 15 min
 /// Minimum interval between full sweeps of the failed-attempt map.
 const FAILED_ATTEMPT_SWEEP_INTERVAL_SECS: u64 = 300; // 5 min
+/// Granularity of API token `last_used_at` updates.
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+/// Scopes that can be granted to API tokens. `<area>:write` implies
+/// `<area>:read`; `*` grants everything.
+pub const API_TOKEN_SCOPES: &[&str] = &[
+    "*",
+    "status:read",
+    "config:read",
+    "config:write",
+    "cron:read",
+    "cron:write",
+    "memory:read",
+    "memory:write",
+    "cost:read",
+    "events:read",
+    "chat",
+    "tokens:read",
+    "tokens:write",
+    "approvals:read",
+    "approvals:write",
+];
+
+/// Outcome of checking a bearer token against a required scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenAuthorization {
+    Granted,
+    /// Unknown, expired or missing token.
+    Unauthenticated,
+    /// Valid token that lacks the required scope.
+    Forbidden,
+}
+
+impl TokenAuthorization {
+    pub fn is_granted(self) -> bool {
+        self == Self::Granted
+    }
+}
 
 /// Per-client failed attempt state with optional absolute lockout deadline.
 #[derive(Debug, Clone, Copy)]
@@ -49,6 +93,10 @@ pub struct PairingGuard {
     paired_tokens: Arc<Mutex<HashSet<String>>>,
     /// Brute-force protection: per-client failed attempt state + last sweep timestamp.
     failed_attempts: Arc<Mutex<(HashMap<String, FailedAttemptState>, Instant)>>,
+    /// Named, scoped API tokens (hashed).
+    api_tokens: Arc<Mutex<Vec<GatewayApiToken>>>,
+    /// Set when `last_used_at` changed and the token list should be persisted.
+    api_tokens_dirty: Arc<AtomicBool>,
 }
 
 impl PairingGuard {
@@ -81,9 +129,24 @@ impl PairingGuard {
             pairing_code: Arc::new(Mutex::new(code)),
             paired_tokens: Arc::new(Mutex::new(tokens)),
             failed_attempts: Arc::new(Mutex::new((HashMap::new(), Instant::now()))),
+            api_tokens: Arc::new(Mutex::new(Vec::new())),
+            api_tokens_dirty: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Attach the persisted named API tokens.
+    ///
+    /// Named tokens count as "paired", so no pairing code is offered once
+    /// any exist.
+    #[must_use]
+    pub fn with_api_tokens(self, tokens: &[GatewayApiToken]) -> Self {
+        *self.api_tokens.lock() = tokens.to_vec();
+        if !tokens.is_empty() {
+            *self.pairing_code.lock() = None;
+        }
+        self
+    }
+
     /// The one-time pairing code (only set when no tokens exist yet).
     pub fn pairing_code(&self) -> Option<String> {
         self.pairing_code.lock().clone()
@@ -205,10 +268,11 @@ impl PairingGuard {
         tokens.contains(&hashed)
     }
 
-    /// Returns true if the gateway is already paired (has at least one token).
+    /// Returns true if the gateway is already paired (has at least one paired
+    /// or named token).
     pub fn is_paired(&self) -> bool {
         let tokens = self.paired_tokens.lock();
-        !tokens.is_empty()
+        !tokens.is_empty() || !self.api_tokens.lock().is_empty()
     }
 
     /// Get all paired token hashes (for persisting to config).
@@ -216,6 +280,185 @@ impl PairingGuard {
         let tokens = self.paired_tokens.lock();
         tokens.iter().cloned().collect()
     }
+
+    /// Check `token` for `scope`. Paired tokens have full access; named
+    /// tokens must be unexpired and hold the scope. A successful named-token
+    /// check refreshes its `last_used_at`.
+    pub fn authorize(&self, token: &str, scope: &str) -> TokenAuthorization {
+        if !self.require_pairing {
+            return TokenAuthorization::Granted;
+        }
+        let hashed = hash_token(token);
+        if self.paired_tokens.lock().contains(&hashed) {
+            return TokenAuthorization::Granted;
+        }
+
+        let now = chrono::Utc::now();
+        let mut api_tokens = self.api_tokens.lock();
+        let Some(entry) = api_tokens
+            .iter_mut()
+            .find(|t| constant_time_eq(&t.token_hash, &hashed))
+        else {
+            return TokenAuthorization::Unauthenticated;
+        };
+        if is_api_token_expired(entry, now) {
+            return TokenAuthorization::Unauthenticated;
+        }
+        if !scopes_allow(&entry.scopes, scope) {
+            return TokenAuthorization::Forbidden;
+        }
+
+        let stale = entry
+            .last_used_at
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .is_none_or(|ts| {
+                (now - ts.with_timezone(&chrono::Utc)).num_seconds() >= LAST_USED_RESOLUTION_SECS
+            });
+        if stale {
+            entry.last_used_at = Some(now.to_rfc3339());
+            self.api_tokens_dirty.store(true, Ordering::Release);
+        }
+        TokenAuthorization::Granted
+    }
+
+    /// Human-readable identity behind `token`, for audit trails.
+    ///
+    /// Named API tokens report their name, paired tokens report `paired`,
+    /// and anything else (including disabled pairing) reports `None`.
+    pub fn token_label(&self, token: &str) -> Option<String> {
+        if !self.require_pairing {
+            return None;
+        }
+        let hashed = hash_token(token);
+        if self.paired_tokens.lock().contains(&hashed) {
+            return Some("paired".to_string());
+        }
+        self.api_tokens
+            .lock()
+            .iter()
+            .find(|t| constant_time_eq(&t.token_hash, &hashed))
+            .map(|t| format!("token:{}", t.name))
+    }
+
+    /// The first of `scopes` that `token` could not itself use, if any.
+    ///
+    /// A token may only mint tokens with scopes it holds, so `tokens:write`
+    /// cannot be used to escalate to `config:write` or `*`.
+    pub fn ungrantable_scope<'a>(&self, token: &str, scopes: &'a [String]) -> Option<&'a str> {
+        scopes.iter().map(|scope| scope.trim()).find(|scope| {
+            !self
+                .authorize(token, &scope.to_ascii_lowercase())
+                .is_granted()
+        })
+    }
+
+    /// Snapshot of named API tokens (for listing and persisting to config).
+    pub fn api_tokens(&self) -> Vec<GatewayApiToken> {
+        self.api_tokens.lock().clone()
+    }
+
+    /// Register a freshly minted API token.
+    pub fn add_api_token(&self, token: GatewayApiToken) {
+        self.api_tokens.lock().push(token);
+    }
+
+    /// Revoke a named API token by id. Returns whether it existed.
+    pub fn revoke_api_token(&self, id: &str) -> bool {
+        let mut tokens = self.api_tokens.lock();
+        let before = tokens.len();
+        tokens.retain(|t| t.id != id);
+        tokens.len() != before
+    }
+
+    /// Adopt the token list persisted on disk (picking up CLI creations and
+    /// revocations) while keeping newer in-memory `last_used_at` values.
+    pub fn reload_api_tokens(&self, persisted: Vec<GatewayApiToken>) {
+        let mut tokens = self.api_tokens.lock();
+        let merged = persisted
+            .into_iter()
+            .map(|mut disk| {
+                if let Some(live) = tokens.iter().find(|t| t.id == disk.id) {
+                    if live.last_used_at > disk.last_used_at {
+                        disk.last_used_at.clone_from(&live.last_used_at);
+                    }
+                }
+                disk
+            })
+            .collect();
+        *tokens = merged;
+    }
+
+    /// Returns `true` (once) if `last_used_at` timestamps changed since the last call.
+    pub fn take_api_tokens_dirty(&self) -> bool {
+        self.api_tokens_dirty.swap(false, Ordering::AcqRel)
+    }
+}
+
+/// Create a named API token. Returns the plaintext bearer secret (shown
+/// once) and the hashed record to persist.
+pub fn mint_api_token(
+    name: &str,
+    scopes: &[String],
+    expires_in: Option<chrono::Duration>,
+) -> anyhow::Result<(String, GatewayApiToken)> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("token name must not be empty");
+    }
+    if scopes.is_empty() {
+        anyhow::bail!(
+            "at least one scope is required (one of: {})",
+            API_TOKEN_SCOPES.join(", ")
+        );
+    }
+    let mut normalized: Vec<String> = Vec::with_capacity(scopes.len());
+    for scope in scopes {
+        let scope = scope.trim().to_ascii_lowercase();
+        if !API_TOKEN_SCOPES.contains(&scope.as_str()) {
+            anyhow::bail!(
+                "unknown scope '{scope}' (expected one of: {})",
+                API_TOKEN_SCOPES.join(", ")
+            );
+        }
+        if !normalized.contains(&scope) {
+            normalized.push(scope);
+        }
+    }
+
+    let now = chrono::Utc::now();
+    let token = generate_token();
+    let id_bytes: [u8; 6] = rand::random();
+    let record = GatewayApiToken {
+        id: format!("tok_{}", hex::encode(id_bytes)),
+        name: name.to_string(),
+        token_hash: hash_token(&token),
+        scopes: normalized,
+        created_at: now.to_rfc3339(),
+        expires_at: expires_in.map(|ttl| (now + ttl).to_rfc3339()),
+        last_used_at: None,
+    };
+    Ok((token, record))
+}
+
+/// Whether `token` is past its expiry. Unparseable expiries count as expired.
+pub fn is_api_token_expired(token: &GatewayApiToken, now: chrono::DateTime<chrono::Utc>) -> bool {
+    token
+        .expires_at
+        .as_deref()
+        .is_some_and(|ts| chrono::DateTime::parse_from_rfc3339(ts).map_or(true, |exp| exp <= now))
+}
+
+/// Whether `granted` covers `required`: exact match, `*`, or the matching
+/// `<area>:write` for an `<area>:read` requirement.
+fn scopes_allow(granted: &[String], required: &str) -> bool {
+    granted.iter().any(|scope| {
+        scope == "*"
+            || scope == required
+            || required
+                .strip_suffix(":read")
+                .is_some_and(|area| scope.strip_suffix(":write") == Some(area))
+    })
 }
 
 /// Normalize a client identifier: trim whitespace, map empty to `"unknown"`.
@@ -317,6 +560,136 @@ mod tests {
     use super::*;
     use tokio::test;
 
+    // ── Scoped API tokens ────────────────────────────────────
+
+    fn api_guard(scopes: &[&str], expires_in: Option<chrono::Duration>) -> (PairingGuard, String) {
+        let scopes: Vec<String> = scopes.iter().map(|s| (*s).to_string()).collect();
+        let (token, record) = mint_api_token("test", &scopes, expires_in).unwrap();
+        let guard = PairingGuard::new(true, &[]).with_api_tokens(&[record]);
+        (guard, token)
+    }
+
+    #[test]
+    async fn mint_api_token_stores_only_hash() {
+        let (token, record) = mint_api_token(" dash ", &["cost:read".into()], None).unwrap();
+        assert!(token.starts_with("zc_"));
+        assert!(record.id.starts_with("tok_"));
+        assert_eq!(record.name, "dash");
+        assert_eq!(record.token_hash, hash_token(&token));
+        assert!(is_token_hash(&record.token_hash));
+        assert!(record.expires_at.is_none());
+    }
+
+    #[test]
+    async fn mint_api_token_rejects_unknown_scopes_and_empty_names() {
+        assert!(mint_api_token("x", &["root".into()], None).is_err());
+        assert!(mint_api_token("x", &[], None).is_err());
+        assert!(mint_api_token("  ", &["*".into()], None).is_err());
+    }
+
+    #[test]
+    async fn api_token_scopes_are_enforced() {
+        let (guard, token) = api_guard(&["memory:write", "cost:read"], None);
+        assert!(guard.is_paired());
+        assert!(guard.pairing_code().is_none());
+        assert!(guard.authorize(&token, "cost:read").is_granted());
+        assert!(guard.authorize(&token, "memory:write").is_granted());
+        // write implies read
+        assert!(guard.authorize(&token, "memory:read").is_granted());
+        assert_eq!(
+            guard.authorize(&token, "config:write"),
+            TokenAuthorization::Forbidden
+        );
+        assert_eq!(
+            guard.authorize("zc_wrong", "cost:read"),
+            TokenAuthorization::Unauthenticated
+        );
+    }
+
+    #[test]
+    async fn wildcard_and_paired_tokens_have_full_access() {
+        let (guard, token) = api_guard(&["*"], None);
+        assert!(guard.authorize(&token, "tokens:write").is_granted());
+
+        let paired = PairingGuard::new(true, &["zc_paired".into()]);
+        assert!(paired.authorize("zc_paired", "config:write").is_granted());
+    }
+
+    #[test]
+    async fn tokens_cannot_grant_scopes_they_do_not_hold() {
+        let (guard, token) = api_guard(&["tokens:write"], None);
+        let scopes = |list: &[&str]| list.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            guard.ungrantable_scope(&token, &scopes(&["tokens:read", "config:write"])),
+            Some("config:write")
+        );
+        assert_eq!(guard.ungrantable_scope(&token, &scopes(&["*"])), Some("*"));
+        assert_eq!(
+            guard.ungrantable_scope(&token, &scopes(&["tokens:read", " Tokens:Write "])),
+            None
+        );
+
+        let (admin, admin_token) = api_guard(&["*"], None);
+        assert_eq!(
+            admin.ungrantable_scope(&admin_token, &scopes(&["*", "config:write"])),
+            None
+        );
+    }
+
+    #[test]
+    async fn expired_api_tokens_are_rejected() {
+        let (guard, token) = api_guard(&["*"], Some(chrono::Duration::seconds(-1)));
+        assert_eq!(
+            guard.authorize(&token, "status:read"),
+            TokenAuthorization::Unauthenticated
+        );
+    }
+
+    #[test]
+    async fn api_token_use_updates_last_used_and_marks_dirty() {
+        let (guard, token) = api_guard(&["status:read"], None);
+        assert!(!guard.take_api_tokens_dirty());
+        assert!(guard.authorize(&token, "status:read").is_granted());
+        assert!(guard.api_tokens()[0].last_used_at.is_some());
+        assert!(guard.take_api_tokens_dirty());
+        // Within the resolution window, reuse does not re-dirty.
+        assert!(guard.authorize(&token, "status:read").is_granted());
+        assert!(!guard.take_api_tokens_dirty());
+    }
+
+    #[test]
+    async fn token_label_names_the_caller() {
+        let (guard, token) = api_guard(&["approvals:write"], None);
+        assert_eq!(guard.token_label(&token).as_deref(), Some("token:test"));
+        assert!(guard.token_label("zc_unknown").is_none());
+
+        let paired = PairingGuard::new(true, &["zc_paired".into()]);
+        assert_eq!(paired.token_label("zc_paired").as_deref(), Some("paired"));
+    }
+
+    #[test]
+    async fn revoked_api_tokens_stop_working() {
+        let (guard, token) = api_guard(&["status:read"], None);
+        let id = guard.api_tokens()[0].id.clone();
+        assert!(guard.revoke_api_token(&id));
+        assert!(!guard.revoke_api_token(&id));
+        assert_eq!(
+            guard.authorize(&token, "status:read"),
+            TokenAuthorization::Unauthenticated
+        );
+    }
+
+    #[test]
+    async fn reload_api_tokens_keeps_newer_last_used() {
+        let (guard, token) = api_guard(&["status:read"], None);
+        let mut disk = guard.api_tokens();
+        assert!(guard.authorize(&token, "status:read").is_granted());
+        disk[0].last_used_at = None;
+        guard.reload_api_tokens(disk);
+        assert!(guard.api_tokens()[0].last_used_at.is_some());
+    }
+
     // ── PairingGuard ─────────────────────────────────────────
 
     #[test]
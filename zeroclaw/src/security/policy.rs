@@ -886,6 +886,24 @@ impl SecurityPolicy {
         None
     }
 
+    /// Filesystem view for OS-sandboxed commands: the workspace plus every
+    /// forbidden path except the system directories commands need to run.
+    pub fn sandbox_scope(&self) -> crate::security::SandboxScope {
+        const RUNTIME_PATHS: &[&str] = &[
+            "/usr", "/bin", "/sbin", "/lib", "/lib32", "/lib64", "/dev", "/proc", "/tmp", "/etc",
+        ];
+        let hidden_paths = self
+            .forbidden_paths
+            .iter()
+            .filter(|path| !RUNTIME_PATHS.contains(&path.as_str()))
+            .map(|path| expand_user_path(path))
+            .collect();
+        crate::security::SandboxScope {
+            workspace_dir: Some(self.workspace_dir.clone()),
+            hidden_paths,
+        }
+    }
+
     // ── Path Validation ────────────────────────────────────────────────
     // Layered checks: null-byte injection → component-level traversal →
     // URL-encoded traversal → tilde expansion → absolute-path block →
@@ -1493,6 +1511,27 @@ mod tests {
         assert!(p.shell_env_passthrough.is_empty());
     }
 
+    #[test]
+    fn sandbox_scope_hides_forbidden_paths_but_keeps_runtime_dirs() {
+        let p = SecurityPolicy {
+            workspace_dir: PathBuf::from("/srv/ws"),
+            forbidden_paths: vec![
+                "/etc".into(),
+                "/usr".into(),
+                "/root".into(),
+                "~/.ssh".into(),
+            ],
+            ..SecurityPolicy::default()
+        };
+        let scope = p.sandbox_scope();
+
+        assert_eq!(scope.workspace_dir, Some(PathBuf::from("/srv/ws")));
+        assert!(scope.hidden_paths.contains(&PathBuf::from("/root")));
+        assert!(!scope.hidden_paths.contains(&PathBuf::from("/etc")));
+        assert!(!scope.hidden_paths.contains(&PathBuf::from("/usr")));
+        assert!(scope.hidden_paths.iter().all(|path| !path.starts_with("~")));
+    }
+
     // ── ActionTracker / rate limiting ───────────────────────
 
     #[test]
@@ -0,0 +1,395 @@
+//! Per-command resource limits from `[security.resources]`.
+//!
+//! On Linux each limited command gets `RLIMIT_DATA` (memory) and `RLIMIT_CPU`
+//! (CPU time) between fork and exec, and runs in its own process group. A
+//! [`ResourceWatch`] samples that group while it runs and kills it when it
+//! holds more than `max_subprocesses` extra processes or, with
+//! `memory_monitoring`, more than `max_memory_mb` of resident memory.
+//! `RLIMIT_NPROC` is not used because it counts every thread of the user.
+//! Other platforms run commands without OS-level limits.
+
+use crate::config::ResourceLimitsConfig;
+use parking_lot::Mutex;
+use std::fmt;
+use std::process::ExitStatus;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often a running command's process group is sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Allocation failures reported by common runtimes when `RLIMIT_DATA` is hit.
+const OUT_OF_MEMORY_MARKERS: &[&str] = &[
+    "Cannot allocate memory",
+    "out of memory",
+    "Out of memory",
+    "memory allocation of",
+    "MemoryError",
+    "std::bad_alloc",
+];
+
+/// A limit a command was stopped for exceeding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Memory { max_mb: u32 },
+    CpuTime { max_seconds: u64 },
+    Subprocesses { max: u32 },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Memory { max_mb } => {
+                write!(f, "memory limit exceeded (max_memory_mb = {max_mb})")
+            }
+            Self::CpuTime { max_seconds } => write!(
+                f,
+                "CPU time limit exceeded (max_cpu_time_seconds = {max_seconds})"
+            ),
+            Self::Subprocesses { max } => {
+                write!(f, "subprocess limit exceeded (max_subprocesses = {max})")
+            }
+        }
+    }
+}
+
+/// Limits applied to one spawned command. Zero disables a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+    pub max_memory_mb: u32,
+    pub max_cpu_time_seconds: u64,
+    pub max_subprocesses: u32,
+    pub memory_monitoring: bool,
+}
+
+impl ResourceLimits {
+    pub fn from_config(config: &ResourceLimitsConfig) -> Self {
+        Self {
+            max_memory_mb: config.max_memory_mb,
+            max_cpu_time_seconds: config.max_cpu_time_seconds,
+            max_subprocesses: config.max_subprocesses,
+            memory_monitoring: config.memory_monitoring,
+        }
+    }
+
+    /// Set rlimits and a fresh process group on `cmd`.
+    ///
+    /// Call after any sandbox wrapping, which rebuilds the command.
+    pub fn apply(&self, cmd: &mut tokio::process::Command) {
+        #[cfg(target_os = "linux")]
+        {
+            let memory = u64::from(self.max_memory_mb) * 1024 * 1024;
+            let cpu = self.max_cpu_time_seconds;
+            cmd.process_group(0);
+            // SAFETY: the closure runs between fork and exec and only calls
+            // setrlimit, which is async-signal-safe.
+            unsafe {
+                cmd.pre_exec(move || {
+                    if memory > 0 {
+                        set_rlimit(libc::RLIMIT_DATA, memory, memory)?;
+                    }
+                    if cpu > 0 {
+                        // SIGXCPU at the soft limit, SIGKILL one second later
+                        set_rlimit(libc::RLIMIT_CPU, cpu, cpu.saturating_add(1))?;
+                    }
+                    Ok(())
+                });
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = cmd;
+    }
+
+    /// Start sampling the process group led by the spawned child `pid`.
+    pub fn watch(&self, pid: Option<u32>) -> ResourceWatch {
+        let verdict = Arc::new(Mutex::new(None));
+        let mut task = None;
+
+        #[cfg(target_os = "linux")]
+        if let Some(pgid) = pid.and_then(|pid| i32::try_from(pid).ok()) {
+            let monitor_memory = self.memory_monitoring && self.max_memory_mb > 0;
+            if self.max_subprocesses > 0 || monitor_memory {
+                let limits = *self;
+                let verdict = verdict.clone();
+                task = Some(tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+                    loop {
+                        interval.tick().await;
+                        let usage = group_usage(pgid);
+                        if usage.processes == 0 {
+                            break;
+                        }
+                        if let Some(hit) = limits.check(usage) {
+                            *verdict.lock() = Some(hit);
+                            kill_group(pgid);
+                            break;
+                        }
+                    }
+                }));
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = pid;
+
+        ResourceWatch {
+            limits: *self,
+            pgid: pid,
+            verdict,
+            task,
+        }
+    }
+
+    fn check(&self, usage: GroupUsage) -> Option<LimitExceeded> {
+        let children = usage.processes.saturating_sub(1);
+        if self.max_subprocesses > 0 && children > self.max_subprocesses {
+            return Some(LimitExceeded::Subprocesses {
+                max: self.max_subprocesses,
+            });
+        }
+        let max_bytes = u64::from(self.max_memory_mb) * 1024 * 1024;
+        if self.memory_monitoring && max_bytes > 0 && usage.rss_bytes > max_bytes {
+            return Some(LimitExceeded::Memory {
+                max_mb: self.max_memory_mb,
+            });
+        }
+        None
+    }
+}
+
+/// Sampler for one running command, created by [`ResourceLimits::watch`].
+pub struct ResourceWatch {
+    limits: ResourceLimits,
+    pgid: Option<u32>,
+    verdict: Arc<Mutex<Option<LimitExceeded>>>,
+    task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl ResourceWatch {
+    /// Stop sampling and report the limit the command hit, if any.
+    ///
+    /// Pass `None` when the command was abandoned (e.g. timed out); its
+    /// process group is killed so no descendants outlive it.
+    pub fn finish(self, status: Option<&ExitStatus>, stderr: &str) -> Option<LimitExceeded> {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+        if let Some(hit) = *self.verdict.lock() {
+            return Some(hit);
+        }
+        let Some(status) = status else {
+            #[cfg(target_os = "linux")]
+            if let Some(pgid) = self.pgid.and_then(|pid| i32::try_from(pid).ok()) {
+                kill_group(pgid);
+            }
+            #[cfg(not(target_os = "linux"))]
+            let _ = self.pgid;
+            return None;
+        };
+        if status.success() || !cfg!(target_os = "linux") {
+            return None;
+        }
+
+        if self.limits.max_cpu_time_seconds > 0 && killed_by_cpu_limit(*status) {
+            return Some(LimitExceeded::CpuTime {
+                max_seconds: self.limits.max_cpu_time_seconds,
+            });
+        }
+        if self.limits.max_memory_mb > 0
+            && OUT_OF_MEMORY_MARKERS
+                .iter()
+                .any(|marker| stderr.contains(marker))
+        {
+            return Some(LimitExceeded::Memory {
+                max_mb: self.limits.max_memory_mb,
+            });
+        }
+        None
+    }
+}
+
+#[cfg(unix)]
+fn killed_by_cpu_limit(status: ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    // A shell reports a child's fatal signal as 128 + signo
+    status.signal() == Some(libc::SIGXCPU) || status.code() == Some(128 + libc::SIGXCPU)
+}
+
+#[cfg(not(unix))]
+fn killed_by_cpu_limit(_status: ExitStatus) -> bool {
+    false
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct GroupUsage {
+    processes: u32,
+    rss_bytes: u64,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(target_os = "linux", not(target_env = "gnu")))]
+type RlimitResource = libc::c_int;
+
+#[cfg(target_os = "linux")]
+fn set_rlimit(resource: RlimitResource, soft: u64, hard: u64) -> std::io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: soft,
+        rlim_max: hard,
+    };
+    // SAFETY: `limit` is a valid rlimit for the duration of the call.
+    if unsafe { libc::setrlimit(resource, &raw const limit) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn kill_group(pgid: i32) {
+    // SAFETY: signalling a process group has no memory-safety requirements.
+    unsafe {
+        libc::kill(-pgid, libc::SIGKILL);
+    }
+}
+
+/// Count the processes in group `pgid` and sum their resident memory.
+#[cfg(target_os = "linux")]
+fn group_usage(pgid: i32) -> GroupUsage {
+    // SAFETY: sysconf has no memory-safety requirements.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).unwrap_or(4096);
+    let mut usage = GroupUsage::default();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return usage;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if !name.to_string_lossy().bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+        let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+            continue;
+        };
+        if let Some((group, rss_pages)) = parse_stat(&stat) {
+            if group == pgid {
+                usage.processes += 1;
+                usage.rss_bytes += rss_pages * page_size;
+            }
+        }
+    }
+    usage
+}
+
+/// Extract `(pgrp, rss pages)` from a `/proc/<pid>/stat` line.
+#[cfg(target_os = "linux")]
+fn parse_stat(stat: &str) -> Option<(i32, u64)> {
+    // The command name may contain spaces and parentheses; fields resume
+    // after the last ')', starting with field 3 (state).
+    let fields: Vec<&str> = stat
+        .get(stat.rfind(')')? + 1..)?
+        .split_whitespace()
+        .collect();
+    let pgrp = fields.get(2)?.parse().ok()?;
+    let rss = fields.get(21)?.parse().ok()?;
+    Some((pgrp, rss))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> ResourceLimits {
+        ResourceLimits::from_config(&ResourceLimitsConfig::default())
+    }
+
+    #[test]
+    fn check_flags_subprocesses_then_memory() {
+        let limits = ResourceLimits {
+            max_subprocesses: 2,
+            max_memory_mb: 1,
+            ..limits()
+        };
+        let busy = GroupUsage {
+            processes: 4,
+            rss_bytes: 0,
+        };
+        assert_eq!(
+            limits.check(busy),
+            Some(LimitExceeded::Subprocesses { max: 2 })
+        );
+        let heavy = GroupUsage {
+            processes: 1,
+            rss_bytes: 2 * 1024 * 1024,
+        };
+        assert_eq!(
+            limits.check(heavy),
+            Some(LimitExceeded::Memory { max_mb: 1 })
+        );
+        let unmonitored = ResourceLimits {
+            memory_monitoring: false,
+            ..limits
+        };
+        assert_eq!(unmonitored.check(heavy), None);
+    }
+
+    #[test]
+    fn limit_messages_name_the_config_key() {
+        let message = LimitExceeded::CpuTime { max_seconds: 5 }.to_string();
+        assert!(message.contains("max_cpu_time_seconds = 5"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parse_stat_handles_spaces_in_command_name() {
+        let stat =
+            "42 (my (odd) cmd) S 1 42 42 0 -1 4194560 100 0 0 0 0 0 0 0 20 0 1 0 100 1000 77 rest";
+        assert_eq!(parse_stat(stat), Some((42, 77)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn cpu_limit_is_reported() {
+        let limits = ResourceLimits {
+            max_cpu_time_seconds: 1,
+            max_subprocesses: 0,
+            ..limits()
+        };
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.args(["-c", "while :; do :; done"]);
+        limits.apply(&mut cmd);
+        let child = cmd.spawn().unwrap();
+        let watch = limits.watch(child.id());
+        let output = tokio::time::timeout(Duration::from_secs(20), child.wait_with_output())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            watch.finish(Some(&output.status), ""),
+            Some(LimitExceeded::CpuTime { max_seconds: 1 })
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn subprocess_limit_kills_the_group() {
+        let limits = ResourceLimits {
+            max_subprocesses: 1,
+            ..limits()
+        };
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.args(["-c", "sleep 5 & sleep 5 & sleep 5 & wait"]);
+        limits.apply(&mut cmd);
+        let child = cmd.spawn().unwrap();
+        let watch = limits.watch(child.id());
+        let output = tokio::time::timeout(Duration::from_secs(4), child.wait_with_output())
+            .await
+            .expect("group should be killed before the sleeps finish")
+            .unwrap();
+
+        assert_eq!(
+            watch.finish(Some(&output.status), ""),
+            Some(LimitExceeded::Subprocesses { max: 1 })
+        );
+    }
+}
@@ -7,8 +7,21 @@
 //! before executing any shell command.
 
 use async_trait::async_trait;
+use std::path::PathBuf;
 use std::process::Command;
 
+/// Filesystem view a sandboxed command should get.
+///
+/// Built from the active [`SecurityPolicy`](crate::security::SecurityPolicy)
+/// by [`SecurityPolicy::sandbox_scope`](crate::security::SecurityPolicy::sandbox_scope).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SandboxScope {
+    /// Mounted read-write and used as the working directory.
+    pub workspace_dir: Option<PathBuf>,
+    /// Paths masked from the command (expanded `forbidden_paths`).
+    pub hidden_paths: Vec<PathBuf>,
+}
+
 /// Sandbox backend for OS-level process isolation.
 ///
 /// Implement this trait to add a new sandboxing strategy. The runtime queries
@@ -31,6 +44,19 @@ pub trait Sandbox: Send + Sync {
     /// (e.g., missing wrapper binary, invalid policy file).
     fn wrap_command(&self, cmd: &mut Command) -> std::io::Result<()>;
 
+    /// Wrap a command so it only sees the filesystem described by `scope`:
+    /// the workspace mounted read-write and `hidden_paths` masked.
+    ///
+    /// The default ignores `scope`; backends that control mounts override it.
+    fn wrap_command_in_scope(
+        &self,
+        cmd: &mut Command,
+        scope: &SandboxScope,
+    ) -> std::io::Result<()> {
+        let _ = scope;
+        self.wrap_command(cmd)
+    }
+
     /// Check if this sandbox backend is available on the current platform.
     ///
     /// Returns `true` when all required kernel features, binaries, and
@@ -51,6 +77,36 @@ pub trait Sandbox: Send + Sync {
     fn description(&self) -> &str;
 }
 
+/// Apply `sandbox` to a Tokio command. See [`Sandbox::wrap_command_in_scope`].
+///
+/// Call this before configuring stdio or the environment: wrapper backends
+/// rebuild the command and only carry over its program, arguments, working
+/// directory and explicitly set variables.
+pub fn wrap_tokio_command(
+    sandbox: &dyn Sandbox,
+    cmd: &mut tokio::process::Command,
+    scope: &SandboxScope,
+) -> std::io::Result<()> {
+    sandbox.wrap_command_in_scope(cmd.as_std_mut(), scope)
+}
+
+/// Replace `cmd` with `wrapper` followed by the original program and
+/// arguments, keeping the working directory and explicit environment.
+pub(crate) fn wrap_with(cmd: &mut Command, mut wrapper: Command) {
+    wrapper.arg(cmd.get_program());
+    wrapper.args(cmd.get_args());
+    if let Some(dir) = cmd.get_current_dir() {
+        wrapper.current_dir(dir);
+    }
+    for (key, value) in cmd.get_envs() {
+        match value {
+            Some(value) => wrapper.env(key, value),
+            None => wrapper.env_remove(key),
+        };
+    }
+    *cmd = wrapper;
+}
+
 /// No-op sandbox that provides no additional OS-level isolation.
 ///
 /// Always reports itself as available. Use this as the fallback when no
@@ -115,4 +171,35 @@ mod tests {
             original_args
         );
     }
+
+    #[test]
+    fn wrap_with_keeps_program_cwd_and_env() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "true"])
+            .current_dir("/tmp")
+            .env("KEEP", "1");
+
+        let mut wrapper = Command::new("wrapper");
+        wrapper.arg("--flag");
+        wrap_with(&mut cmd, wrapper);
+
+        assert_eq!(cmd.get_program(), "wrapper");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["--flag", "sh", "-c", "true"]);
+        assert_eq!(cmd.get_current_dir(), Some(std::path::Path::new("/tmp")));
+        assert!(cmd
+            .get_envs()
+            .any(|(k, v)| k == "KEEP" && v == Some("1".as_ref())));
+    }
+
+    #[test]
+    fn default_scoped_wrap_delegates_to_wrap_command() {
+        let mut cmd = Command::new("echo");
+        let scope = SandboxScope {
+            workspace_dir: Some(PathBuf::from("/ws")),
+            hidden_paths: vec![PathBuf::from("/root")],
+        };
+        NoopSandbox.wrap_command_in_scope(&mut cmd, &scope).unwrap();
+        assert_eq!(cmd.get_program(), "echo");
+    }
 }
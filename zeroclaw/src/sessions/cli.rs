@@ -0,0 +1,244 @@
+use super::{history_from_records, sessions_dir, SessionStore, TranscriptEvent, TranscriptRecord};
+use crate::config::Config;
+use crate::SessionCommands;
+use anyhow::{bail, Result};
+use console::style;
+use std::fmt::Write as _;
+
+/// Handle `zeroclaw sessions <subcommand>` CLI commands.
+pub async fn handle_command(command: SessionCommands, config: &Config) -> Result<()> {
+    // Reading old transcripts works even when recording is turned off.
+    let store = SessionStore::new(
+        sessions_dir(&config.workspace_dir),
+        config.sessions.max_output_chars,
+    );
+    match command {
+        SessionCommands::List { limit } => handle_list(&store, limit),
+        SessionCommands::Show { id } => handle_show(&store, &id),
+        SessionCommands::Export { id, format, output } => {
+            let rendered = export(&store.load(&id)?, &format)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)?;
+                    println!("Exported session {id} to {}", path.display());
+                }
+                None => print!("{rendered}"),
+            }
+            Ok(())
+        }
+        SessionCommands::Resume { id, message } => {
+            Box::pin(handle_resume(config, &store, &id, message)).await
+        }
+    }
+}
+
+fn handle_list(store: &SessionStore, limit: usize) -> Result<()> {
+    let sessions = store.list()?;
+    if sessions.is_empty() {
+        println!("No sessions recorded in {}", store.dir().display());
+        return Ok(());
+    }
+
+    println!("Sessions ({} total):\n", sessions.len());
+    for session in sessions.iter().take(limit) {
+        let source = match &session.channel {
+            Some(channel) => format!("{}/{channel}", session.source),
+            None => session.source.clone(),
+        };
+        println!(
+            "- {} [{source}] {} messages, {} tool calls, updated {}",
+            style(&session.id).white().bold(),
+            session.messages,
+            session.tool_calls,
+            session.updated_at,
+        );
+    }
+    Ok(())
+}
+
+fn handle_show(store: &SessionStore, id: &str) -> Result<()> {
+    for record in store.load(id)? {
+        let ts = style(&record.timestamp).dim();
+        match &record.event {
+            TranscriptEvent::SessionStart {
+                source,
+                channel,
+                provider,
+                model,
+                ..
+            } => {
+                let via = channel.as_deref().unwrap_or(source);
+                println!("{ts} {} {via} ({provider}/{model})", style("start").cyan());
+            }
+            TranscriptEvent::Message { role, content } => {
+                println!("{ts} {}\n{content}\n", style(role).white().bold());
+            }
+            TranscriptEvent::ModelResponse {
+                model,
+                tool_calls,
+                input_tokens,
+                output_tokens,
+                duration_ms,
+                ..
+            } => {
+                println!(
+                    "{ts} {} {model} {duration_ms}ms, tokens in/out {}/{}, {tool_calls} tool call(s)",
+                    style("model").dim(),
+                    input_tokens.map_or_else(|| "?".to_string(), |n| n.to_string()),
+                    output_tokens.map_or_else(|| "?".to_string(), |n| n.to_string()),
+                );
+            }
+            TranscriptEvent::ToolCall {
+                name,
+                arguments,
+                output,
+                success,
+                duration_ms,
+            } => {
+                let status = if *success {
+                    style("ok").green()
+                } else {
+                    style("failed").red()
+                };
+                println!(
+                    "{ts} {} {name} {arguments} -> {status} ({duration_ms}ms)\n{output}\n",
+                    style("tool").yellow()
+                );
+            }
+            TranscriptEvent::Error { message } => {
+                println!("{ts} {} {message}", style("error").red().bold());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn export(records: &[TranscriptRecord], format: &str) -> Result<String> {
+    match format {
+        "json" => Ok(format!("{}\n", serde_json::to_string_pretty(records)?)),
+        "jsonl" => {
+            let mut out = String::new();
+            for record in records {
+                out.push_str(&serde_json::to_string(record)?);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+        "markdown" | "md" => Ok(export_markdown(records)),
+        other => bail!("Unknown export format '{other}' (expected json, jsonl or markdown)"),
+    }
+}
+
+fn export_markdown(records: &[TranscriptRecord]) -> String {
+    let mut out = String::new();
+    for record in records {
+        match &record.event {
+            TranscriptEvent::SessionStart {
+                source,
+                provider,
+                model,
+                ..
+            } => {
+                let _ = writeln!(
+                    out,
+                    "# Session ({source}) — {}\n\n_{provider} / {model}_\n",
+                    record.timestamp
+                );
+            }
+            TranscriptEvent::Message { role, content } => {
+                let _ = writeln!(out, "## {role}\n\n{content}\n");
+            }
+            TranscriptEvent::ToolCall {
+                name,
+                arguments,
+                output,
+                success,
+                ..
+            } => {
+                let status = if *success { "ok" } else { "failed" };
+                let _ = writeln!(
+                    out,
+                    "**tool `{name}`** ({status})\n\n```json\n{arguments}\n```\n\n```\n{output}\n```\n"
+                );
+            }
+            TranscriptEvent::Error { message } => {
+                let _ = writeln!(out, "> **error:** {message}\n");
+            }
+            TranscriptEvent::ModelResponse { .. } => {}
+        }
+    }
+    out
+}
+
+async fn handle_resume(
+    config: &Config,
+    store: &SessionStore,
+    id: &str,
+    message: Option<String>,
+) -> Result<()> {
+    let history = history_from_records(&store.load(id)?);
+    let transcript = store.open(id)?;
+    let provider = config.default_provider.as_deref().unwrap_or("openrouter");
+    let model = config
+        .default_model
+        .as_deref()
+        .unwrap_or("anthropic/claude-sonnet-4-20250514");
+    transcript.record(TranscriptEvent::SessionStart {
+        source: "resume".to_string(),
+        channel: None,
+        conversation: None,
+        provider: provider.to_string(),
+        model: model.to_string(),
+    });
+
+    println!("Resumed session {id} ({} earlier messages).", history.len());
+    let session = crate::agent::ResumedSession {
+        history,
+        transcript,
+    };
+    Box::pin(crate::agent::resume(config.clone(), session, message)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<TranscriptRecord> {
+        vec![
+            TranscriptRecord {
+                timestamp: "2026-01-01T00:00:00Z".into(),
+                event: TranscriptEvent::SessionStart {
+                    source: "cli".into(),
+                    channel: None,
+                    conversation: None,
+                    provider: "openrouter".into(),
+                    model: "m".into(),
+                },
+            },
+            TranscriptRecord {
+                timestamp: "2026-01-01T00:00:01Z".into(),
+                event: TranscriptEvent::Message {
+                    role: "user".into(),
+                    content: "hello".into(),
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn export_formats() {
+        let jsonl = export(&records(), "jsonl").unwrap();
+        assert_eq!(jsonl.lines().count(), 2);
+        assert!(jsonl.contains("\"type\":\"session_start\""));
+
+        let json: Vec<TranscriptRecord> =
+            serde_json::from_str(&export(&records(), "json").unwrap()).unwrap();
+        assert_eq!(json, records());
+
+        let markdown = export(&records(), "markdown").unwrap();
+        assert!(markdown.contains("## user\n\nhello"));
+
+        assert!(export(&records(), "xml").is_err());
+    }
+}
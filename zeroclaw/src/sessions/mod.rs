@@ -0,0 +1,535 @@
+//! Append-only session transcripts.
+//!
+//! Every CLI, channel, gateway and cron session writes one JSONL file under
+//! `<workspace>/state/sessions/<id>.jsonl`. Each line is a
+//! [`TranscriptRecord`]: user and assistant messages, model responses with
+//! token usage and timing, tool calls with their results, and errors.
+//! Unlike the runtime trace, transcripts keep enough of the conversation to
+//! rebuild it, so `zeroclaw sessions resume <id>` can continue where a
+//! session stopped.
+
+pub mod cli;
+
+use crate::config::SessionsConfig;
+use crate::providers::ChatMessage;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// One event in a session, tagged by `type`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranscriptEvent {
+    /// First line of every transcript.
+    SessionStart {
+        /// `cli`, `channel`, `gateway`, `cron`, `daemon` or `resume`.
+        source: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        channel: Option<String>,
+        /// Conversation key for channel sessions (sender/thread scope).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        conversation: Option<String>,
+        provider: String,
+        model: String,
+    },
+    /// A user message or a final assistant reply.
+    Message {
+        role: String,
+        content: String,
+    },
+    /// One provider round-trip inside the tool loop.
+    ModelResponse {
+        provider: String,
+        model: String,
+        text: String,
+        tool_calls: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        input_tokens: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output_tokens: Option<u64>,
+        duration_ms: u64,
+    },
+    /// A tool invocation together with its (possibly truncated) output.
+    ToolCall {
+        name: String,
+        arguments: serde_json::Value,
+        output: String,
+        success: bool,
+        duration_ms: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// A timestamped transcript line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptRecord {
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub event: TranscriptEvent,
+}
+
+/// Summary of one transcript file, as shown by `zeroclaw sessions list`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub source: String,
+    pub channel: Option<String>,
+    pub started_at: String,
+    pub updated_at: String,
+    pub messages: usize,
+    pub tool_calls: usize,
+}
+
+/// Directory of transcripts plus the output cap applied when writing.
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    dir: PathBuf,
+    max_output_chars: usize,
+}
+
+impl SessionStore {
+    pub fn new(dir: PathBuf, max_output_chars: usize) -> Self {
+        Self {
+            dir,
+            max_output_chars,
+        }
+    }
+
+    /// Store for `[sessions]`, or `None` when transcripts are disabled.
+    pub fn from_config(config: &SessionsConfig, workspace_dir: &Path) -> Option<Self> {
+        config
+            .enabled
+            .then(|| Self::new(sessions_dir(workspace_dir), config.max_output_chars))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Create a new transcript and write its `session_start` line.
+    pub fn start(
+        &self,
+        source: &str,
+        channel: Option<&str>,
+        conversation: Option<&str>,
+        provider: &str,
+        model: &str,
+    ) -> Result<Transcript> {
+        let id = new_session_id();
+        let transcript = Transcript {
+            path: self.path_for(&id)?,
+            id,
+            max_output_chars: self.max_output_chars,
+            write_lock: Mutex::new(()),
+        };
+        transcript.append(&TranscriptEvent::SessionStart {
+            source: source.to_string(),
+            channel: channel.map(str::to_string),
+            conversation: conversation.map(str::to_string),
+            provider: provider.to_string(),
+            model: model.to_string(),
+        })?;
+        Ok(transcript)
+    }
+
+    /// Reopen an existing transcript for appending.
+    pub fn open(&self, id: &str) -> Result<Transcript> {
+        let path = self.path_for(id)?;
+        if !path.exists() {
+            bail!("Session '{id}' not found in {}", self.dir.display());
+        }
+        Ok(Transcript {
+            id: id.to_string(),
+            path,
+            max_output_chars: self.max_output_chars,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// Read every record of a transcript, skipping malformed lines.
+    pub fn load(&self, id: &str) -> Result<Vec<TranscriptRecord>> {
+        let path = self.path_for(id)?;
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Session '{id}' not found in {}", self.dir.display()))?;
+        Ok(parse_records(&raw))
+    }
+
+    /// Summaries of all transcripts, most recently updated first.
+    pub fn list(&self) -> Result<Vec<SessionSummary>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut sessions = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let raw = match fs::read_to_string(&path) {
+                Ok(raw) => raw,
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable transcript {}: {e}", path.display());
+                    continue;
+                }
+            };
+            if let Some(summary) = summarize(id, &parse_records(&raw)) {
+                sessions.push(summary);
+            }
+        }
+
+        sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(sessions)
+    }
+
+    fn path_for(&self, id: &str) -> Result<PathBuf> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            bail!("Invalid session id '{id}'");
+        }
+        Ok(self.dir.join(format!("{id}.jsonl")))
+    }
+}
+
+/// An open transcript file. Writes are best-effort: failures are logged and
+/// never interrupt the conversation being recorded.
+#[derive(Debug)]
+pub struct Transcript {
+    id: String,
+    path: PathBuf,
+    max_output_chars: usize,
+    write_lock: Mutex<()>,
+}
+
+impl Transcript {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, event: TranscriptEvent) {
+        if let Err(e) = self.append(&event) {
+            tracing::warn!(session = %self.id, "Failed to write session transcript: {e}");
+        }
+    }
+
+    pub fn record_message(&self, role: &str, content: &str) {
+        self.record(TranscriptEvent::Message {
+            role: role.to_string(),
+            content: content.to_string(),
+        });
+    }
+
+    pub fn record_tool_call(
+        &self,
+        name: &str,
+        arguments: &serde_json::Value,
+        output: &str,
+        success: bool,
+        duration: Duration,
+    ) {
+        self.record(TranscriptEvent::ToolCall {
+            name: name.to_string(),
+            arguments: arguments.clone(),
+            output: truncate_output(output, self.max_output_chars),
+            success,
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        });
+    }
+
+    pub fn record_error(&self, message: &str) {
+        self.record(TranscriptEvent::Error {
+            message: message.to_string(),
+        });
+    }
+
+    fn append(&self, event: &TranscriptEvent) -> Result<()> {
+        let record = TranscriptRecord {
+            timestamp: Utc::now().to_rfc3339(),
+            event: event.clone(),
+        };
+        let line = serde_json::to_string(&record)?;
+
+        let _guard = self.write_lock.lock();
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&self.path)?;
+        writeln!(file, "{line}")?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+/// Channel transcripts, one per conversation key. `/new` forgets the key so
+/// the next message starts a fresh transcript.
+pub struct TranscriptRegistry {
+    store: SessionStore,
+    active: Mutex<HashMap<String, Arc<Transcript>>>,
+}
+
+impl TranscriptRegistry {
+    pub fn new(store: SessionStore) -> Self {
+        Self {
+            store,
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Transcript for `conversation`, starting one on first use.
+    pub fn transcript_for(
+        &self,
+        channel: &str,
+        conversation: &str,
+        provider: &str,
+        model: &str,
+    ) -> Option<Arc<Transcript>> {
+        let mut active = self.active.lock();
+        if let Some(existing) = active.get(conversation) {
+            return Some(Arc::clone(existing));
+        }
+        match self.store.start(
+            "channel",
+            Some(channel),
+            Some(conversation),
+            provider,
+            model,
+        ) {
+            Ok(transcript) => {
+                let transcript = Arc::new(transcript);
+                active.insert(conversation.to_string(), Arc::clone(&transcript));
+                Some(transcript)
+            }
+            Err(e) => {
+                tracing::warn!(conversation, "Failed to start session transcript: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn forget(&self, conversation: &str) {
+        self.active.lock().remove(conversation);
+    }
+}
+
+/// Start a transcript for a one-off session, logging instead of failing.
+pub fn start_session(
+    store: Option<&SessionStore>,
+    source: &str,
+    channel: Option<&str>,
+    provider: &str,
+    model: &str,
+) -> Option<Transcript> {
+    store?
+        .start(source, channel, None, provider, model)
+        .map_err(|e| tracing::warn!("Failed to start session transcript: {e}"))
+        .ok()
+}
+
+/// Rebuild the user/assistant turns of a transcript for resuming.
+///
+/// Tool calls are not replayed: their effects already happened, and the
+/// final assistant reply of each turn summarizes what they produced.
+pub fn history_from_records(records: &[TranscriptRecord]) -> Vec<ChatMessage> {
+    records
+        .iter()
+        .filter_map(|record| match &record.event {
+            TranscriptEvent::Message { role, content } if role == "user" => {
+                Some(ChatMessage::user(content))
+            }
+            TranscriptEvent::Message { role, content } if role == "assistant" => {
+                Some(ChatMessage::assistant(content))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// `<workspace>/state/sessions`.
+pub fn sessions_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join("sessions")
+}
+
+fn new_session_id() -> String {
+    let suffix = Uuid::new_v4().simple().to_string();
+    format!("{}-{}", Utc::now().format("%Y%m%d-%H%M%S"), &suffix[..8])
+}
+
+fn parse_records(raw: &str) -> Vec<TranscriptRecord> {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                tracing::warn!("Skipping malformed transcript line: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+fn summarize(id: &str, records: &[TranscriptRecord]) -> Option<SessionSummary> {
+    let first = records.first()?;
+    let (source, channel) = match &first.event {
+        TranscriptEvent::SessionStart {
+            source, channel, ..
+        } => (source.clone(), channel.clone()),
+        _ => ("unknown".to_string(), None),
+    };
+    Some(SessionSummary {
+        id: id.to_string(),
+        source,
+        channel,
+        started_at: first.timestamp.clone(),
+        updated_at: records.last()?.timestamp.clone(),
+        messages: records
+            .iter()
+            .filter(|r| matches!(r.event, TranscriptEvent::Message { .. }))
+            .count(),
+        tool_calls: records
+            .iter()
+            .filter(|r| matches!(r.event, TranscriptEvent::ToolCall { .. }))
+            .count(),
+    })
+}
+
+fn truncate_output(output: &str, max_chars: usize) -> String {
+    if max_chars == 0 || output.chars().count() <= max_chars {
+        return output.to_string();
+    }
+    let kept: String = output.chars().take(max_chars).collect();
+    format!("{kept}… [truncated]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn store(tmp: &TempDir) -> SessionStore {
+        SessionStore::new(sessions_dir(tmp.path()), 10)
+    }
+
+    #[test]
+    fn transcript_round_trips_and_rebuilds_history() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+        let transcript = store
+            .start("cli", None, None, "openrouter", "test-model")
+            .unwrap();
+        transcript.record_message("user", "list files");
+        transcript.record_tool_call(
+            "shell",
+            &serde_json::json!({"command": "ls"}),
+            "a.txt\nb.txt\nc.txt",
+            true,
+            Duration::from_millis(12),
+        );
+        transcript.record_message("assistant", "There are three files.");
+
+        let records = store.load(transcript.id()).unwrap();
+        assert_eq!(records.len(), 4);
+        assert!(matches!(
+            &records[0].event,
+            TranscriptEvent::SessionStart { source, .. } if source == "cli"
+        ));
+        match &records[2].event {
+            TranscriptEvent::ToolCall {
+                output,
+                duration_ms,
+                ..
+            } => {
+                assert_eq!(output, "a.txt\nb.tx… [truncated]");
+                assert_eq!(*duration_ms, 12);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        let history = history_from_records(&records);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].role, "user");
+        assert_eq!(history[1].content, "There are three files.");
+    }
+
+    #[test]
+    fn reopened_transcript_appends_to_same_file() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+        let id = store
+            .start(
+                "channel",
+                Some("telegram"),
+                Some("telegram_alice"),
+                "p",
+                "m",
+            )
+            .unwrap()
+            .id()
+            .to_string();
+
+        store.open(&id).unwrap().record_message("user", "again");
+
+        let sessions = store.list().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, id);
+        assert_eq!(sessions[0].channel.as_deref(), Some("telegram"));
+        assert_eq!(sessions[0].messages, 1);
+    }
+
+    #[test]
+    fn rejects_ids_that_escape_the_sessions_dir() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+        assert!(store.open("../config").is_err());
+        assert!(store.load("a/b").is_err());
+        assert!(store.open("missing").is_err());
+    }
+
+    #[test]
+    fn registry_reuses_transcript_until_forgotten() {
+        let tmp = TempDir::new().unwrap();
+        let registry = TranscriptRegistry::new(store(&tmp));
+        let first = registry
+            .transcript_for("telegram", "telegram_alice", "p", "m")
+            .unwrap();
+        let again = registry
+            .transcript_for("telegram", "telegram_alice", "p", "m")
+            .unwrap();
+        assert_eq!(first.id(), again.id());
+
+        registry.forget("telegram_alice");
+        let fresh = registry
+            .transcript_for("telegram", "telegram_alice", "p", "m")
+            .unwrap();
+        assert_ne!(first.id(), fresh.id());
+    }
+}
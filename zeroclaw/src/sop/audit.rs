@@ -3,7 +3,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use tracing::{info, warn};
 
-use super::types::{SopRun, SopStepResult};
+use super::types::{SopRun, SopRunStatus, SopStepResult};
 use crate::memory::traits::{Memory, MemoryCategory};
 
 const SOP_CATEGORY: &str = "sop";
@@ -42,6 +42,16 @@ impl SopAuditLogger {
         Ok(())
     }
 
+    /// Persist the current state of an in-flight run (step advanced, approved).
+    ///
+    /// Keeps the run record current so `load_active_runs` can resume it.
+    pub async fn log_run_progress(&self, run: &SopRun) -> Result<()> {
+        let key = run_key(&run.run_id);
+        let content = serde_json::to_string_pretty(run)?;
+        self.memory.store(&key, &content, category(), None).await?;
+        Ok(())
+    }
+
     /// Log run completion (updates the run record with final state).
     pub async fn log_run_complete(&self, run: &SopRun) -> Result<()> {
         let key = run_key(&run.run_id);
@@ -130,6 +140,32 @@ impl SopAuditLogger {
             .collect();
         Ok(run_keys)
     }
+
+    /// Load every stored run that has not reached a terminal status.
+    pub async fn load_active_runs(&self) -> Result<Vec<SopRun>> {
+        let entries = self.memory.list(Some(&category()), None).await?;
+        let mut runs = Vec::new();
+        for entry in entries {
+            if !entry.key.starts_with("sop_run_") {
+                continue;
+            }
+            match serde_json::from_str::<SopRun>(&entry.content) {
+                Ok(run)
+                    if matches!(
+                        run.status,
+                        SopRunStatus::Pending
+                            | SopRunStatus::Running
+                            | SopRunStatus::WaitingApproval
+                    ) =>
+                {
+                    runs.push(run);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("SOP audit: skipping unparseable run {}: {e}", entry.key),
+            }
+        }
+        Ok(runs)
+    }
 }
 
 fn run_key(run_id: &str) -> String {
@@ -277,4 +313,33 @@ mod tests {
         let result = logger.get_run("nonexistent").await.unwrap();
         assert!(result.is_none());
     }
+
+    #[tokio::test]
+    async fn load_active_runs_skips_finished() {
+        let mem_cfg = crate::config::MemoryConfig {
+            backend: "sqlite".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let memory: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let logger = SopAuditLogger::new(memory);
+
+        let active = test_run();
+        logger.log_run_start(&active).await.unwrap();
+
+        let mut finished = test_run();
+        finished.run_id = "run-test-002".into();
+        finished.status = SopRunStatus::Completed;
+        logger.log_run_complete(&finished).await.unwrap();
+
+        let mut progressed = active.clone();
+        progressed.current_step = 2;
+        logger.log_run_progress(&progressed).await.unwrap();
+
+        let runs = logger.load_active_runs().await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].run_id, "run-test-001");
+        assert_eq!(runs[0].current_step, 2);
+    }
 }
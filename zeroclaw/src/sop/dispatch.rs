@@ -158,7 +158,7 @@ pub async fn dispatch_sop_event(
 /// approval timeout polling in the scheduler handles progression.
 /// For `ExecuteStep` actions, the run is started in the engine but steps
 /// cannot be executed without an agent loop — this is logged as a warning.
-pub async fn process_headless_results(results: &[DispatchResult]) {
+pub fn process_headless_results(results: &[DispatchResult]) {
     for result in results {
         match result {
             DispatchResult::Started {
@@ -197,6 +197,161 @@ pub async fn process_headless_results(results: &[DispatchResult]) {
     }
 }
 
+// ── Agent-backed execution ──────────────────────────────────────
+
+/// Drive dispatch results from daemon-side trigger sources (cron, webhook,
+/// MQTT).
+///
+/// `ExecuteStep` actions are handed to a background agent turn whose tool
+/// registry shares this process's engine, so the agent reports progress via
+/// `sop_advance`. Everything else goes through [`process_headless_results`].
+pub fn execute_dispatch_results(
+    config: &crate::config::Config,
+    audit: &Arc<SopAuditLogger>,
+    results: Vec<DispatchResult>,
+) {
+    let mut headless = Vec::new();
+    for result in results {
+        match result {
+            DispatchResult::Started {
+                action: action @ SopRunAction::ExecuteStep { .. },
+                ..
+            } => spawn_step_agent(config, audit, action),
+            other => headless.push(other),
+        }
+    }
+    process_headless_results(&headless);
+}
+
+/// Run an `ExecuteStep` action in a background agent turn.
+///
+/// If the agent errors or finishes without reporting the step via
+/// `sop_advance`, the step is recorded as failed so the run does not hold
+/// a concurrency slot forever. Non-`ExecuteStep` actions are ignored.
+pub fn spawn_step_agent(
+    config: &crate::config::Config,
+    audit: &Arc<SopAuditLogger>,
+    action: SopRunAction,
+) {
+    let SopRunAction::ExecuteStep {
+        run_id,
+        step,
+        context,
+    } = action
+    else {
+        return;
+    };
+
+    let config = config.clone();
+    let audit = Arc::clone(audit);
+    tokio::spawn(async move {
+        let prompt = format!(
+            "{context}\nReport the result with the `sop_advance` tool (run_id `{run_id}`), \
+             then carry out any next step it returns until the run completes or waits for approval."
+        );
+        let temperature = config.default_temperature;
+        let outcome = Box::pin(crate::agent::run(
+            config.clone(),
+            Some(prompt),
+            None,
+            None,
+            temperature,
+            vec![],
+            false,
+        ))
+        .await;
+
+        let reason = match outcome {
+            Ok(_) => "agent finished without reporting the step result".to_string(),
+            Err(e) => {
+                warn!("SOP run {run_id}: agent step execution failed: {e}");
+                format!("agent error: {e}")
+            }
+        };
+        let engine = crate::sop::shared(&config).engine;
+        fail_stalled_step(&engine, &audit, &run_id, step.number, &reason).await;
+    });
+}
+
+/// Record a failed step result if the run is still active on `step_number`.
+async fn fail_stalled_step(
+    engine: &Arc<Mutex<SopEngine>>,
+    audit: &SopAuditLogger,
+    run_id: &str,
+    step_number: u32,
+    reason: &str,
+) {
+    let finished = {
+        let Ok(mut eng) = engine.lock() else {
+            return;
+        };
+        let stalled = eng.active_runs().get(run_id).is_some_and(|run| {
+            run.current_step == step_number && run.status == super::types::SopRunStatus::Running
+        });
+        if !stalled {
+            return;
+        }
+        let now = now_iso8601();
+        let result = super::types::SopStepResult {
+            step_number,
+            status: super::types::SopStepStatus::Failed,
+            output: reason.to_string(),
+            started_at: now.clone(),
+            completed_at: Some(now),
+        };
+        match eng.advance_step(run_id, result) {
+            Ok(_) => eng.get_run(run_id).cloned(),
+            Err(e) => {
+                warn!("SOP run {run_id}: failed to record stalled step: {e}");
+                None
+            }
+        }
+    };
+
+    if let Some(run) = finished {
+        warn!("SOP run {run_id} failed at step {step_number}: {reason}");
+        if let Err(e) = audit.log_run_complete(&run).await {
+            warn!("SOP audit log_run_complete failed for run {run_id}: {e}");
+        }
+    }
+}
+
+/// Auto-approve timed-out critical/high runs and execute their next step.
+pub async fn process_approval_timeouts(
+    config: &crate::config::Config,
+    sop: &super::SharedSop,
+    audit: &Arc<SopAuditLogger>,
+) {
+    let approved: Vec<(SopRunAction, Option<SopRun>)> = match sop.engine.lock() {
+        Ok(mut eng) => eng
+            .check_approval_timeouts()
+            .into_iter()
+            .map(|action| {
+                let run = eng.get_run(extract_run_id_from_action(&action)).cloned();
+                (action, run)
+            })
+            .collect(),
+        Err(e) => {
+            crate::health::mark_component_error("sop_dispatch", format!("lock poisoned: {e}"));
+            return;
+        }
+    };
+
+    for (action, run) in approved {
+        if let Some(run) = run {
+            if let Err(e) = audit.log_timeout_auto_approve(&run, run.current_step).await {
+                warn!("SOP audit: timeout auto-approve log failed: {e}");
+            }
+            if let Err(e) = audit.log_run_progress(&run).await {
+                warn!("SOP audit: progress log failed for run {}: {e}", run.run_id);
+            }
+            sop.collector
+                .record_timeout_auto_approve(&run.sop_name, &run.run_id);
+        }
+        spawn_step_agent(config, audit, action);
+    }
+}
+
 // ── Peripheral signal helper ────────────────────────────────────
 
 /// Convenience wrapper for peripheral hardware callbacks.
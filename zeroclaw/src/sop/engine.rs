@@ -336,6 +336,60 @@ impl SopEngine {
         actions
     }
 
+    // ── Persistence ─────────────────────────────────────────────
+
+    /// Re-insert an in-flight run loaded from the audit log (e.g. after a
+    /// restart). Returns `false` when the run is already active, is finished,
+    /// or its SOP is no longer loaded.
+    pub fn restore_run(&mut self, run: SopRun) -> bool {
+        let resumable = matches!(
+            run.status,
+            SopRunStatus::Pending | SopRunStatus::Running | SopRunStatus::WaitingApproval
+        );
+        if !resumable
+            || self.active_runs.contains_key(&run.run_id)
+            || self.get_sop(&run.sop_name).is_none()
+        {
+            return false;
+        }
+        self.run_counter += 1;
+        self.active_runs.insert(run.run_id.clone(), run);
+        true
+    }
+
+    /// Rebuild the pending action for an active run's current step, e.g. to
+    /// resume a restored run. Waiting runs yield `WaitApproval`.
+    pub fn resume_action(&self, run_id: &str) -> Result<SopRunAction> {
+        let run = self
+            .active_runs
+            .get(run_id)
+            .ok_or_else(|| anyhow::anyhow!("Active run not found: {run_id}"))?;
+        let sop = self
+            .get_sop(&run.sop_name)
+            .ok_or_else(|| anyhow::anyhow!("SOP '{}' no longer loaded", run.sop_name))?;
+        let step = sop
+            .steps
+            .get(run.current_step.saturating_sub(1) as usize)
+            .ok_or_else(|| anyhow::anyhow!("Run {run_id} points at missing step"))?
+            .clone();
+        let context = format_step_context(sop, run, &step);
+        let run_id = run_id.to_string();
+
+        Ok(if run.status == SopRunStatus::WaitingApproval {
+            SopRunAction::WaitApproval {
+                run_id,
+                step,
+                context,
+            }
+        } else {
+            SopRunAction::ExecuteStep {
+                run_id,
+                step,
+                context,
+            }
+        })
+    }
+
     // ── Test helpers ──────────────────────────────────────────────
 
     /// Replace loaded SOPs (for testing from other modules).
@@ -1628,4 +1682,77 @@ mod tests {
         assert_eq!(run.status, SopRunStatus::Running);
         assert!(run.waiting_since.is_none());
     }
+
+    #[test]
+    fn restore_run_resumes_current_step() {
+        let mut source = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        let action = source.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        source
+            .advance_step(
+                &run_id,
+                SopStepResult {
+                    step_number: 1,
+                    status: SopStepStatus::Completed,
+                    output: "ok".into(),
+                    started_at: now_iso8601(),
+                    completed_at: Some(now_iso8601()),
+                },
+            )
+            .unwrap();
+        let persisted = source.get_run(&run_id).unwrap().clone();
+
+        let mut restarted = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        assert!(restarted.restore_run(persisted.clone()));
+        assert!(!restarted.restore_run(persisted), "duplicate restore");
+
+        match restarted.resume_action(&run_id).unwrap() {
+            SopRunAction::ExecuteStep { step, .. } => assert_eq!(step.number, 2),
+            other => panic!("expected ExecuteStep, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn restore_run_rejects_finished_or_unknown() {
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        let action = engine.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        let mut run = engine.get_run(&run_id).unwrap().clone();
+        engine.cancel_run(&run_id).unwrap();
+
+        let mut unknown = run.clone();
+        unknown.sop_name = "gone".into();
+        assert!(!engine.restore_run(unknown));
+
+        run.status = SopRunStatus::Completed;
+        assert!(!engine.restore_run(run));
+        assert!(engine.active_runs().is_empty());
+    }
+
+    #[test]
+    fn resume_action_keeps_waiting_runs_waiting() {
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Supervised,
+            SopPriority::Normal,
+        )]);
+        let action = engine.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        assert!(matches!(
+            engine.resume_action(&run_id).unwrap(),
+            SopRunAction::WaitApproval { .. }
+        ));
+    }
 }
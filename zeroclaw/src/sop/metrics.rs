@@ -645,7 +645,8 @@ mod tests {
             current_step: total_steps,
             total_steps,
             started_at: "2026-02-19T12:00:00Z".into(),
-            completed_at: Some("2026-02-19T12:05:00Z".into()),
+            // Relative to now so windowed (7d/30d) metrics include the run
+            completed_at: Some(crate::sop::engine::now_iso8601()),
             step_results,
             waiting_since: None,
         }
@@ -19,11 +19,77 @@ pub use types::{
 };
 
 use anyhow::Result;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tracing::warn;
+use std::sync::{Arc, LazyLock, Mutex};
+use tracing::{info, warn};
 
 use types::{SopManifest, SopMeta};
 
+// ── Shared runtime ──────────────────────────────────────────────
+
+/// Engine and metrics shared by every SOP consumer in one process.
+///
+/// The daemon's trigger sources, the gateway and every agent tool registry
+/// resolve the same instance, so a run started by a cron trigger can be
+/// advanced by the agent's `sop_advance` tool.
+#[derive(Clone)]
+pub struct SharedSop {
+    pub engine: Arc<Mutex<SopEngine>>,
+    pub collector: Arc<SopMetricsCollector>,
+}
+
+/// Process-wide SOP runtimes keyed by resolved SOPs directory.
+static SHARED_SOPS: LazyLock<Mutex<HashMap<PathBuf, SharedSop>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Return the shared SOP runtime for this config, loading SOPs on first use.
+pub fn shared(config: &crate::config::Config) -> SharedSop {
+    let dir = resolve_sops_dir(&config.workspace_dir, config.sop.sops_dir.as_deref());
+    let mut registry = SHARED_SOPS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    registry
+        .entry(dir)
+        .or_insert_with(|| {
+            let mut engine = SopEngine::new(config.sop.clone());
+            engine.reload(&config.workspace_dir);
+            SharedSop {
+                engine: Arc::new(Mutex::new(engine)),
+                collector: Arc::new(SopMetricsCollector::new()),
+            }
+        })
+        .clone()
+}
+
+/// Re-register in-flight runs persisted by the audit logger so they survive
+/// a daemon restart. Returns the pending action of each restored run.
+pub async fn restore_active_runs(
+    engine: &Arc<Mutex<SopEngine>>,
+    audit: &SopAuditLogger,
+) -> Result<Vec<SopRunAction>> {
+    let runs = audit.load_active_runs().await?;
+    let mut eng = engine
+        .lock()
+        .map_err(|e| anyhow::anyhow!("Engine lock poisoned: {e}"))?;
+
+    let mut actions = Vec::new();
+    for run in runs {
+        let run_id = run.run_id.clone();
+        if !eng.restore_run(run) {
+            continue;
+        }
+        match eng.resume_action(&run_id) {
+            Ok(action) => actions.push(action),
+            Err(e) => warn!("SOP restore: cannot resume run {run_id}: {e}"),
+        }
+    }
+    if !actions.is_empty() {
+        info!("SOP engine restored {} in-flight run(s)", actions.len());
+    }
+    Ok(actions)
+}
+
 // ── SOP directory helpers ───────────────────────────────────────
 
 /// Return the default SOPs directory: `<workspace>/sops`.
@@ -6,7 +6,7 @@
 //! Computer-use (OS-level) actions are supported via an optional sidecar endpoint.
 
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{EstopGuard, SecurityPolicy};
 use anyhow::Context;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -67,6 +67,7 @@ pub struct BrowserTool {
     native_webdriver_url: String,
     native_chrome_path: Option<String>,
     computer_use: ComputerUseConfig,
+    estop: Option<Arc<EstopGuard>>,
     #[cfg(feature = "browser-native")]
     native_state: tokio::sync::Mutex<native_backend::NativeBrowserState>,
 }
@@ -234,11 +235,18 @@ impl BrowserTool {
             native_webdriver_url,
             native_chrome_path,
             computer_use,
+            estop: None,
             #[cfg(feature = "browser-native")]
             native_state: tokio::sync::Mutex::new(native_backend::NativeBrowserState::default()),
         }
     }
 
+    /// Refuse navigation to hosts blocked by an engaged estop.
+    pub fn with_estop(mut self, estop: Option<Arc<EstopGuard>>) -> Self {
+        self.estop = estop;
+        self
+    }
+
     /// Check if agent-browser CLI is available
     pub async fn is_agent_browser_available() -> bool {
         Command::new("agent-browser")
@@ -433,6 +441,10 @@ impl BrowserTool {
             anyhow::bail!("Host '{host}' not in browser.allowed_domains");
         }
 
+        if let Some(estop) = &self.estop {
+            estop.check_domain("tool:browser", &host)?;
+        }
+
         Ok(())
     }
 
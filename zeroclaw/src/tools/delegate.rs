@@ -2,7 +2,7 @@ use super::traits::{Tool, ToolResult};
 use crate::agent::loop_::run_tool_call_loop;
 use crate::config::DelegateAgentConfig;
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
-use crate::providers::{self, ChatMessage, Provider};
+use crate::providers::{self, ChatMessage, ChatRequest, Provider};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -288,12 +288,32 @@ impl Tool for DelegateTool {
                 .await;
         }
 
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system_prompt) = agent_config.system_prompt.as_deref() {
+            messages.push(ChatMessage::system(system_prompt));
+        }
+        messages.push(ChatMessage::user(full_prompt.clone()));
+
+        if let Err(e) = crate::cost::budget::precheck(
+            &agent_config.provider,
+            &agent_config.model,
+            crate::cost::budget::estimate_input_tokens(&messages),
+        ) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Agent '{agent_name}' failed: {e}")),
+            });
+        }
+
         // Wrap the provider call in a timeout to prevent indefinite blocking
         let result = tokio::time::timeout(
             Duration::from_secs(DELEGATE_TIMEOUT_SECS),
-            provider.chat_with_system(
-                agent_config.system_prompt.as_deref(),
-                &full_prompt,
+            provider.chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
                 &agent_config.model,
                 temperature,
             ),
@@ -315,7 +335,13 @@ impl Tool for DelegateTool {
 
         match result {
             Ok(response) => {
-                let mut rendered = response;
+                crate::cost::budget::record(
+                    "delegate",
+                    &agent_config.provider,
+                    &agent_config.model,
+                    response.usage.as_ref(),
+                );
+                let mut rendered = response.text_or_empty().to_string();
                 if rendered.trim().is_empty() {
                     rendered = "[Empty response]".to_string();
                 }
@@ -411,6 +437,7 @@ impl DelegateTool {
                 None,
                 None,
                 &[],
+                None,
             ),
         )
         .await;
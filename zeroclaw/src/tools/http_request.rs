@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{EstopGuard, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -12,6 +12,7 @@ pub struct HttpRequestTool {
     allowed_domains: Vec<String>,
     max_response_size: usize,
     timeout_secs: u64,
+    estop: Option<Arc<EstopGuard>>,
 }
 
 impl HttpRequestTool {
@@ -26,9 +27,16 @@ impl HttpRequestTool {
             allowed_domains: normalize_allowed_domains(allowed_domains),
             max_response_size,
             timeout_secs,
+            estop: None,
         }
     }
 
+    /// Refuse requests to hosts blocked by an engaged estop.
+    pub fn with_estop(mut self, estop: Option<Arc<EstopGuard>>) -> Self {
+        self.estop = estop;
+        self
+    }
+
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
         let url = raw_url.trim();
 
@@ -60,6 +68,10 @@ impl HttpRequestTool {
             anyhow::bail!("Host '{host}' is not in http_request.allowed_domains");
         }
 
+        if let Some(estop) = &self.estop {
+            estop.check_domain("tool:http_request", &host)?;
+        }
+
         Ok(url.to_string())
     }
 
@@ -680,6 +692,36 @@ mod tests {
         assert!(!is_private_or_local_host("100.128.0.1")); // Just above range
     }
 
+    #[test]
+    fn validate_url_rejects_estop_blocked_domain() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let estop_config = crate::config::EstopConfig {
+            enabled: true,
+            state_file: "estop-state.json".into(),
+            require_otp_to_resume: false,
+        };
+        crate::security::EstopManager::load(&estop_config, tmp.path())
+            .unwrap()
+            .engage(crate::security::EstopLevel::DomainBlock(vec![
+                "api.example.com".into(),
+            ]))
+            .unwrap();
+        let tool = HttpRequestTool::new(
+            Arc::new(SecurityPolicy::default()),
+            vec!["example.com".into()],
+            1_000_000,
+            30,
+        )
+        .with_estop(Some(Arc::new(EstopGuard::new(&estop_config, tmp.path()))));
+
+        assert!(tool.validate_url("https://www.example.com/ok").is_ok());
+        let err = tool
+            .validate_url("https://api.example.com/v1")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("emergency stop"), "{err}");
+    }
+
     #[tokio::test]
     async fn execute_blocks_readonly_mode() {
         let security = Arc::new(SecurityPolicy {
@@ -51,7 +51,14 @@ pub mod schedule;
 pub mod schema;
 pub mod screenshot;
 pub mod shell;
+pub mod sop_advance;
+pub mod sop_approve;
+pub mod sop_execute;
+pub mod sop_list;
+pub mod sop_status;
 pub mod traits;
+#[cfg(feature = "runtime-wasm")]
+pub mod wasm_tool;
 pub mod web_fetch;
 pub mod web_search_tool;
 
@@ -91,6 +98,11 @@ pub use schedule::ScheduleTool;
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
 pub use shell::ShellTool;
+pub use sop_advance::SopAdvanceTool;
+pub use sop_approve::SopApproveTool;
+pub use sop_execute::SopExecuteTool;
+pub use sop_list::SopListTool;
+pub use sop_status::SopStatusTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
 pub use traits::{ToolResult, ToolSpec};
@@ -100,7 +112,9 @@ pub use web_search_tool::WebSearchTool;
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
 use crate::runtime::{NativeRuntime, RuntimeAdapter};
-use crate::security::SecurityPolicy;
+use crate::security::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::security::policy::CommandRiskLevel;
+use crate::security::{EstopGuard, SecurityPolicy};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -135,6 +149,226 @@ impl Tool for ArcDelegatingTool {
     }
 }
 
+/// Tools that reach the network and stop under an estop network kill, on top
+/// of the per-domain checks `http_request`, `web_fetch` and `browser` do.
+const NETWORK_TOOL_NAMES: &[&str] = &[
+    "http_request",
+    "web_fetch",
+    "web_search_tool",
+    "browser",
+    "browser_open",
+    "composio",
+    "pushover",
+];
+
+/// Consults live estop state before every call to the wrapped tool.
+struct EstopGatedTool {
+    inner: Arc<dyn Tool>,
+    guard: Arc<EstopGuard>,
+}
+
+impl EstopGatedTool {
+    fn gate(tools: Vec<Arc<dyn Tool>>, guard: &Arc<EstopGuard>) -> Vec<Arc<dyn Tool>> {
+        tools
+            .into_iter()
+            .map(|inner| {
+                Arc::new(Self {
+                    inner,
+                    guard: guard.clone(),
+                }) as Arc<dyn Tool>
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Tool for EstopGatedTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = self.inner.name();
+        let mut verdict = self.guard.check_tool(name);
+        if verdict.is_ok() && NETWORK_TOOL_NAMES.contains(&name) {
+            verdict = self.guard.check_network(&format!("tool:{name}"));
+        }
+        if let Err(blocked) = verdict {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(blocked.to_string()),
+            });
+        }
+        self.inner.execute(args).await
+    }
+}
+
+/// Tools whose calls are written to the tamper-evident audit log.
+const AUDITED_TOOL_NAMES: &[&str] = &[
+    "shell",
+    "file_read",
+    "file_write",
+    "file_edit",
+    "git_operations",
+];
+
+/// Records every call to the wrapped tool, with its outcome and whether the
+/// estop and security policy allowed it, in the audit log.
+struct AuditedTool {
+    inner: Arc<dyn Tool>,
+    logger: Arc<AuditLogger>,
+    security: Arc<SecurityPolicy>,
+    estop: Option<Arc<EstopGuard>>,
+}
+
+impl AuditedTool {
+    fn wrap(
+        tools: Vec<Arc<dyn Tool>>,
+        logger: &Arc<AuditLogger>,
+        security: &Arc<SecurityPolicy>,
+        estop: Option<&Arc<EstopGuard>>,
+    ) -> Vec<Arc<dyn Tool>> {
+        tools
+            .into_iter()
+            .map(|inner| {
+                if !AUDITED_TOOL_NAMES.contains(&inner.name()) {
+                    return inner;
+                }
+                Arc::new(Self {
+                    inner,
+                    logger: logger.clone(),
+                    security: security.clone(),
+                    estop: estop.cloned(),
+                }) as Arc<dyn Tool>
+            })
+            .collect()
+    }
+
+    /// Event type, action, risk and approval for a call, plus whether the
+    /// security policy will let it through. The policy checks mirror the
+    /// wrapped tool's own, without recording an action against the rate limit.
+    fn describe(&self, args: &serde_json::Value) -> (AuditEventType, String, String, bool, bool) {
+        let name = self.inner.name();
+        let arg = |key: &str| {
+            args.get(key)
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let security = &self.security;
+        let within_budget = !security.is_rate_limited();
+        match name {
+            "shell" => {
+                let command = arg("command");
+                let risk = match security.command_risk_level(&command) {
+                    CommandRiskLevel::Low => "low",
+                    CommandRiskLevel::Medium => "medium",
+                    CommandRiskLevel::High => "high",
+                };
+                let allowed = within_budget
+                    && security
+                        .validate_command_execution(&command, approved)
+                        .is_ok()
+                    && security.forbidden_path_argument(&command).is_none();
+                (
+                    AuditEventType::CommandExecution,
+                    command,
+                    risk.into(),
+                    approved,
+                    allowed,
+                )
+            }
+            "git_operations" => {
+                let operation = arg("operation");
+                let writes = matches!(
+                    operation.as_str(),
+                    "commit" | "add" | "checkout" | "stash" | "reset" | "revert"
+                );
+                (
+                    AuditEventType::CommandExecution,
+                    format!("git {operation}"),
+                    "medium".into(),
+                    approved,
+                    within_budget && (!writes || security.can_act()),
+                )
+            }
+            _ => {
+                let path = arg("path");
+                let reads = name == "file_read";
+                let allowed = within_budget
+                    && security.is_path_allowed(&path)
+                    && (reads || security.can_act());
+                (
+                    AuditEventType::FileAccess,
+                    format!("{name} {path}"),
+                    if reads { "low" } else { "medium" }.into(),
+                    approved,
+                    allowed,
+                )
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AuditedTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let (event_type, command, risk, approved, allowed) = self.describe(&args);
+        let started = std::time::Instant::now();
+        let stopped = self
+            .estop
+            .as_ref()
+            .and_then(|guard| guard.check_tool(self.inner.name()).err());
+        let allowed = allowed && stopped.is_none();
+        let outcome = match stopped {
+            Some(blocked) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(blocked.to_string()),
+            }),
+            None => self.inner.execute(args).await,
+        };
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let (success, error) = match &outcome {
+            Ok(result) => (result.success, result.error.clone()),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        let event = AuditEvent::new(event_type)
+            .with_actor("tool".into(), None, Some(self.inner.name().into()))
+            .with_action(command, risk, approved, allowed)
+            .with_result(success, None, duration_ms, error);
+        if let Err(e) = self.logger.log(&event) {
+            tracing::warn!("Failed to write audit event: {e}");
+        }
+        outcome
+    }
+}
+
 fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
     tools.into_iter().map(ArcDelegatingTool::boxed).collect()
 }
@@ -209,8 +443,19 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    let estop = root_config
+        .config_path
+        .parent()
+        .and_then(|dir| crate::security::estop::shared_guard(&root_config.security, dir));
+
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(
+            ShellTool::new(security.clone(), runtime)
+                .with_sandbox(crate::security::create_command_sandbox(root_config))
+                .with_resource_limits(crate::security::ResourceLimits::from_config(
+                    &root_config.security.resources,
+                )),
+        ),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
@@ -224,7 +469,7 @@ pub fn all_tools_with_runtime(
         Arc::new(CronRunsTool::new(config.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
-        Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(MemoryForgetTool::new(memory.clone(), security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(ModelRoutingConfigTool::new(
             config.clone(),
@@ -248,43 +493,52 @@ pub fn all_tools_with_runtime(
             browser_config.allowed_domains.clone(),
         )));
         // Add full browser automation tool (pluggable backend)
-        tool_arcs.push(Arc::new(BrowserTool::new_with_backend(
-            security.clone(),
-            browser_config.allowed_domains.clone(),
-            browser_config.session_name.clone(),
-            browser_config.backend.clone(),
-            browser_config.native_headless,
-            browser_config.native_webdriver_url.clone(),
-            browser_config.native_chrome_path.clone(),
-            ComputerUseConfig {
-                endpoint: browser_config.computer_use.endpoint.clone(),
-                api_key: browser_config.computer_use.api_key.clone(),
-                timeout_ms: browser_config.computer_use.timeout_ms,
-                allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
-                window_allowlist: browser_config.computer_use.window_allowlist.clone(),
-                max_coordinate_x: browser_config.computer_use.max_coordinate_x,
-                max_coordinate_y: browser_config.computer_use.max_coordinate_y,
-            },
-        )));
+        tool_arcs.push(Arc::new(
+            BrowserTool::new_with_backend(
+                security.clone(),
+                browser_config.allowed_domains.clone(),
+                browser_config.session_name.clone(),
+                browser_config.backend.clone(),
+                browser_config.native_headless,
+                browser_config.native_webdriver_url.clone(),
+                browser_config.native_chrome_path.clone(),
+                ComputerUseConfig {
+                    endpoint: browser_config.computer_use.endpoint.clone(),
+                    api_key: browser_config.computer_use.api_key.clone(),
+                    timeout_ms: browser_config.computer_use.timeout_ms,
+                    allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
+                    window_allowlist: browser_config.computer_use.window_allowlist.clone(),
+                    max_coordinate_x: browser_config.computer_use.max_coordinate_x,
+                    max_coordinate_y: browser_config.computer_use.max_coordinate_y,
+                },
+            )
+            .with_estop(estop.clone()),
+        ));
     }
 
     if http_config.enabled {
-        tool_arcs.push(Arc::new(HttpRequestTool::new(
-            security.clone(),
-            http_config.allowed_domains.clone(),
-            http_config.max_response_size,
-            http_config.timeout_secs,
-        )));
+        tool_arcs.push(Arc::new(
+            HttpRequestTool::new(
+                security.clone(),
+                http_config.allowed_domains.clone(),
+                http_config.max_response_size,
+                http_config.timeout_secs,
+            )
+            .with_estop(estop.clone()),
+        ));
     }
 
     if web_fetch_config.enabled {
-        tool_arcs.push(Arc::new(WebFetchTool::new(
-            security.clone(),
-            web_fetch_config.allowed_domains.clone(),
-            web_fetch_config.blocked_domains.clone(),
-            web_fetch_config.max_response_size,
-            web_fetch_config.timeout_secs,
-        )));
+        tool_arcs.push(Arc::new(
+            WebFetchTool::new(
+                security.clone(),
+                web_fetch_config.allowed_domains.clone(),
+                web_fetch_config.blocked_domains.clone(),
+                web_fetch_config.max_response_size,
+                web_fetch_config.timeout_secs,
+            )
+            .with_estop(estop.clone()),
+        ));
     }
 
     // Web search tool (enabled by default for GLM and other models)
@@ -297,6 +551,35 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    // SOP tools share the process-wide engine with the daemon's trigger sources
+    if root_config.sop.enabled {
+        let sop = crate::sop::shared(root_config);
+        let has_sops = sop
+            .engine
+            .lock()
+            .is_ok_and(|engine| !engine.sops().is_empty());
+        if has_sops {
+            let audit = Arc::new(crate::sop::SopAuditLogger::new(memory.clone()));
+            tool_arcs.push(Arc::new(SopListTool::new(sop.engine.clone())));
+            tool_arcs.push(Arc::new(
+                SopExecuteTool::new(sop.engine.clone()).with_audit(audit.clone()),
+            ));
+            tool_arcs.push(Arc::new(
+                SopAdvanceTool::new(sop.engine.clone())
+                    .with_audit(audit.clone())
+                    .with_collector(sop.collector.clone()),
+            ));
+            tool_arcs.push(Arc::new(
+                SopApproveTool::new(sop.engine.clone())
+                    .with_audit(audit)
+                    .with_collector(sop.collector.clone()),
+            ));
+            tool_arcs.push(Arc::new(
+                SopStatusTool::new(sop.engine).with_collector(sop.collector),
+            ));
+        }
+    }
+
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
@@ -314,6 +597,39 @@ pub fn all_tools_with_runtime(
         }
     }
 
+    // Sandboxed WASM tools from `runtime.wasm.tools_dir`, loaded after every
+    // built-in so none of them can be shadowed
+    #[cfg(feature = "runtime-wasm")]
+    for tool in wasm_tool::load_wasm_tools(&root_config.runtime.wasm, workspace_dir, security) {
+        let reserved = !agents.is_empty() && tool.name() == "delegate";
+        if reserved
+            || tool_arcs
+                .iter()
+                .any(|existing| existing.name() == tool.name())
+        {
+            tracing::warn!(
+                "Skipping WASM tool '{}': name collides with a built-in tool",
+                tool.name()
+            );
+            continue;
+        }
+        tool_arcs.push(Arc::new(tool));
+    }
+
+    // Gate before delegation so sub-agents inherit the estop checks too
+    if let Some(guard) = &estop {
+        tool_arcs = EstopGatedTool::gate(tool_arcs, guard);
+    }
+
+    // Audit outside the estop gate so refused calls are recorded as denied
+    if let Some(logger) = root_config
+        .config_path
+        .parent()
+        .and_then(|dir| crate::security::audit::shared_logger(&root_config.security.audit, dir))
+    {
+        tool_arcs = AuditedTool::wrap(tool_arcs, &logger, security, estop.as_ref());
+    }
+
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
@@ -342,7 +658,11 @@ pub fn all_tools_with_runtime(
         )
         .with_parent_tools(parent_tools)
         .with_multimodal_config(root_config.multimodal.clone());
-        tool_arcs.push(Arc::new(delegate_tool));
+        let delegate_tool: Arc<dyn Tool> = Arc::new(delegate_tool);
+        match &estop {
+            Some(guard) => tool_arcs.extend(EstopGatedTool::gate(vec![delegate_tool], guard)),
+            None => tool_arcs.push(delegate_tool),
+        }
     }
 
     boxed_registry_from_arcs(tool_arcs)
@@ -453,6 +773,137 @@ mod tests {
         assert!(names.contains(&"proxy_config"));
     }
 
+    #[cfg(feature = "runtime-wasm")]
+    #[test]
+    fn wasm_tools_cannot_shadow_built_in_tools() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let wasm_dir = tmp.path().join("tools/wasm");
+        std::fs::create_dir_all(&wasm_dir).unwrap();
+        for name in ["pdf_read", "image_info", "delegate", "custom"] {
+            std::fs::write(wasm_dir.join(format!("{name}.wasm")), b"\0asm\x01\0\0\0").unwrap();
+        }
+        let mut agents = HashMap::new();
+        agents.insert(
+            "helper".to_string(),
+            DelegateAgentConfig {
+                provider: "openrouter".into(),
+                model: "test-model".into(),
+                system_prompt: None,
+                api_key: None,
+                temperature: None,
+                max_depth: 3,
+                agentic: false,
+                allowed_tools: Vec::new(),
+                max_iterations: 10,
+            },
+        );
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            &crate::config::WebFetchConfig::default(),
+            tmp.path(),
+            &agents,
+            None,
+            &test_config(&tmp),
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        for name in ["pdf_read", "image_info", "delegate", "custom"] {
+            assert_eq!(
+                names.iter().filter(|n| **n == name).count(),
+                1,
+                "{name} in {names:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn all_tools_registers_sop_tools_when_sops_exist() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let cfg = test_config(&tmp);
+        let sop_dir = cfg.workspace_dir.join("sops").join("demo");
+        std::fs::create_dir_all(&sop_dir).unwrap();
+        std::fs::write(
+            sop_dir.join("SOP.toml"),
+            "[sop]\nname = \"demo\"\ndescription = \"Demo\"\n\n[[triggers]]\ntype = \"manual\"\n",
+        )
+        .unwrap();
+        std::fs::write(sop_dir.join("SOP.md"), "## Steps\n\n1. **Check** — Look.\n").unwrap();
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            &crate::config::WebFetchConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        for name in [
+            "sop_list",
+            "sop_execute",
+            "sop_advance",
+            "sop_approve",
+            "sop_status",
+        ] {
+            assert!(names.contains(&name), "missing {name}");
+        }
+    }
+
+    #[test]
+    fn all_tools_omits_sop_tools_without_sops() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let cfg = test_config(&tmp);
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            &crate::config::WebFetchConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        assert!(!tools.iter().any(|t| t.name().starts_with("sop_")));
+    }
+
     #[test]
     fn default_tools_names() {
         let security = Arc::new(SecurityPolicy::default());
@@ -631,4 +1082,147 @@ mod tests {
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"delegate"));
     }
+
+    struct EchoTool(&'static str);
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "echo"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: "ran".into(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn estop_gate_refuses_frozen_and_network_tools() {
+        let tmp = TempDir::new().unwrap();
+        let estop_config = crate::config::EstopConfig {
+            enabled: true,
+            state_file: "estop-state.json".into(),
+            require_otp_to_resume: false,
+        };
+        let guard = Arc::new(EstopGuard::new(&estop_config, tmp.path()));
+        let tools = EstopGatedTool::gate(
+            vec![
+                Arc::new(EchoTool("shell")),
+                Arc::new(EchoTool("web_fetch")),
+                Arc::new(EchoTool("file_read")),
+            ],
+            &guard,
+        );
+        assert!(
+            tools[0]
+                .execute(serde_json::json!({}))
+                .await
+                .unwrap()
+                .success
+        );
+
+        let mut manager = crate::security::EstopManager::load(&estop_config, tmp.path()).unwrap();
+        manager
+            .engage(crate::security::EstopLevel::ToolFreeze(
+                vec!["shell".into()],
+            ))
+            .unwrap();
+        manager
+            .engage(crate::security::EstopLevel::NetworkKill)
+            .unwrap();
+
+        let results: Vec<ToolResult> = futures_util::future::join_all(
+            tools.iter().map(|tool| tool.execute(serde_json::json!({}))),
+        )
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+        assert!(!results[0].success);
+        assert!(results[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("tool 'shell' is frozen"));
+        assert!(results[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("network kill"));
+        assert_eq!(results[2].output, "ran");
+    }
+
+    #[tokio::test]
+    async fn audited_tools_record_policy_and_estop_denials() {
+        let tmp = TempDir::new().unwrap();
+        let logger = Arc::new(
+            AuditLogger::new(
+                crate::config::AuditConfig {
+                    enabled: true,
+                    ..Default::default()
+                },
+                tmp.path().to_path_buf(),
+            )
+            .unwrap(),
+        );
+        let security = Arc::new(SecurityPolicy {
+            autonomy: crate::security::AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let estop_config = crate::config::EstopConfig {
+            enabled: true,
+            state_file: "estop-state.json".into(),
+            require_otp_to_resume: false,
+        };
+        let guard = Arc::new(EstopGuard::new(&estop_config, tmp.path()));
+        let tools = AuditedTool::wrap(
+            vec![
+                Arc::new(EchoTool("file_write")),
+                Arc::new(EchoTool("file_read")),
+            ],
+            &logger,
+            &security,
+            Some(&guard),
+        );
+
+        tools[0]
+            .execute(serde_json::json!({ "path": "notes.txt" }))
+            .await
+            .unwrap();
+        tools[1]
+            .execute(serde_json::json!({ "path": "notes.txt" }))
+            .await
+            .unwrap();
+
+        let mut manager = crate::security::EstopManager::load(&estop_config, tmp.path()).unwrap();
+        manager
+            .engage(crate::security::EstopLevel::ToolFreeze(vec![
+                "file_read".into()
+            ]))
+            .unwrap();
+        let refused = tools[1]
+            .execute(serde_json::json!({ "path": "notes.txt" }))
+            .await
+            .unwrap();
+        assert!(!refused.success);
+
+        let allowed: Vec<bool> = crate::security::audit::read_events(logger.log_path())
+            .unwrap()
+            .iter()
+            .map(|event| event.action.as_ref().unwrap().allowed)
+            .collect();
+        assert_eq!(allowed, vec![false, true, false]);
+    }
 }
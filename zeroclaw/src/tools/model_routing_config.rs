@@ -467,6 +467,8 @@ impl ModelRoutingConfigTool {
             provider: provider.clone(),
             model: model.clone(),
             api_key: None,
+            max_context_tokens: None,
+            vision: None,
         });
 
         next_route.hint = hint.clone();
@@ -1,9 +1,10 @@
 use super::traits::{Tool, ToolResult};
 use crate::runtime::RuntimeAdapter;
-use crate::security::SecurityPolicy;
+use crate::security::{NoopSandbox, ResourceLimits, Sandbox, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashSet;
+use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -21,11 +22,31 @@ const SAFE_ENV_VARS: &[&str] = &[
 pub struct ShellTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
+    sandbox: Arc<dyn Sandbox>,
+    limits: Option<ResourceLimits>,
 }
 
 impl ShellTool {
     pub fn new(security: Arc<SecurityPolicy>, runtime: Arc<dyn RuntimeAdapter>) -> Self {
-        Self { security, runtime }
+        Self {
+            security,
+            runtime,
+            sandbox: Arc::new(NoopSandbox),
+            limits: None,
+        }
+    }
+
+    /// Run every command inside `sandbox`, scoped to the policy's workspace
+    /// and forbidden paths.
+    pub fn with_sandbox(mut self, sandbox: Arc<dyn Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Enforce `[security.resources]` limits on every command.
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = Some(limits);
+        self
     }
 }
 
@@ -146,6 +167,21 @@ impl Tool for ShellTool {
                 });
             }
         };
+        // Wrap first: sandbox backends rebuild the command around the original.
+        if let Err(e) = crate::security::wrap_tokio_command(
+            self.sandbox.as_ref(),
+            &mut cmd,
+            &self.security.sandbox_scope(),
+        ) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Sandbox ({}) refused command: {e}",
+                    self.sandbox.name()
+                )),
+            });
+        }
         cmd.env_clear();
 
         for var in collect_allowed_shell_env_vars(&self.security) {
@@ -154,8 +190,30 @@ impl Tool for ShellTool {
             }
         }
 
-        let result =
-            tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
+        if let Some(limits) = &self.limits {
+            limits.apply(&mut cmd);
+        }
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to execute command: {e}")),
+                });
+            }
+        };
+        let watch = self.limits.map(|limits| limits.watch(child.id()));
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(SHELL_TIMEOUT_SECS),
+            child.wait_with_output(),
+        )
+        .await;
 
         match result {
             Ok(Ok(output)) => {
@@ -172,6 +230,16 @@ impl Tool for ShellTool {
                     stderr.push_str("\n... [stderr truncated at 1MB]");
                 }
 
+                if let Some(limit) =
+                    watch.and_then(|watch| watch.finish(Some(&output.status), &stderr))
+                {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: stdout,
+                        error: Some(format!("Command killed: {limit}\n{stderr}")),
+                    });
+                }
+
                 Ok(ToolResult {
                     success: output.status.success(),
                     output: stdout,
@@ -187,13 +255,18 @@ impl Tool for ShellTool {
                 output: String::new(),
                 error: Some(format!("Failed to execute command: {e}")),
             }),
-            Err(_) => Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!(
-                    "Command timed out after {SHELL_TIMEOUT_SECS}s and was killed"
-                )),
-            }),
+            Err(_) => {
+                if let Some(watch) = watch {
+                    watch.finish(None, "");
+                }
+                Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Command timed out after {SHELL_TIMEOUT_SECS}s and was killed"
+                    )),
+                })
+            }
         }
     }
 }
@@ -252,6 +325,81 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    /// Sandbox that records the scope it was asked to apply, then refuses.
+    struct RefusingSandbox(std::sync::Mutex<Option<crate::security::SandboxScope>>);
+
+    impl Sandbox for RefusingSandbox {
+        fn wrap_command(&self, _cmd: &mut std::process::Command) -> std::io::Result<()> {
+            unreachable!("shell tool must use the scoped wrapper")
+        }
+
+        fn wrap_command_in_scope(
+            &self,
+            _cmd: &mut std::process::Command,
+            scope: &crate::security::SandboxScope,
+        ) -> std::io::Result<()> {
+            *self.0.lock().unwrap() = Some(scope.clone());
+            Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "unavailable",
+            ))
+        }
+
+        fn is_available(&self) -> bool {
+            false
+        }
+
+        fn name(&self) -> &str {
+            "refusing"
+        }
+
+        fn description(&self) -> &str {
+            "test"
+        }
+    }
+
+    #[tokio::test]
+    async fn shell_applies_sandbox_with_workspace_scope() {
+        let sandbox = Arc::new(RefusingSandbox(std::sync::Mutex::new(None)));
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime())
+            .with_sandbox(sandbox.clone());
+        let result = tool
+            .execute(json!({"command": "echo hello"}))
+            .await
+            .expect("sandbox refusal should be a tool result");
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Sandbox (refusing)"));
+        let scope = sandbox.0.lock().unwrap().clone().expect("scope recorded");
+        assert_eq!(scope.workspace_dir, Some(std::env::temp_dir()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn shell_reports_exceeded_subprocess_limit() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: std::env::temp_dir(),
+            allowed_commands: vec!["sleep".into()],
+            ..SecurityPolicy::default()
+        });
+        let limits = ResourceLimits {
+            max_memory_mb: 0,
+            max_cpu_time_seconds: 0,
+            max_subprocesses: 1,
+            memory_monitoring: false,
+        };
+        let tool = ShellTool::new(security, test_runtime()).with_resource_limits(limits);
+        let result = tool
+            .execute(json!({"command": "sleep 5 | sleep 5 | sleep 5"}))
+            .await
+            .expect("limit breach should be a tool result");
+
+        assert!(!result.success);
+        let error = result.error.unwrap_or_default();
+        assert!(error.contains("max_subprocesses = 1"), "{error}");
+    }
+
     #[tokio::test]
     async fn shell_blocks_disallowed_command() {
         let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
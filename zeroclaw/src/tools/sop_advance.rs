@@ -99,7 +99,7 @@ impl Tool for SopAdvanceTool {
         };
 
         // Lock engine, advance step, snapshot data for audit, then drop lock
-        let (action, step_result_ok, finished_run) = {
+        let (action, step_result_ok, finished_run, progressed_run) = {
             let mut engine = self
                 .engine
                 .lock()
@@ -122,16 +122,19 @@ impl Tool for SopAdvanceTool {
 
             match engine.advance_step(run_id, step_result) {
                 Ok(action) => {
-                    // Snapshot finished run for audit (Completed/Failed/Cancelled)
-                    let finished = match &action {
-                        SopRunAction::Completed { run_id, .. }
-                        | SopRunAction::Failed { run_id, .. } => engine.get_run(run_id).cloned(),
-                        _ => None,
+                    // Snapshot the run for audit: finished runs close the record,
+                    // in-flight runs refresh it so they can resume after restart
+                    let snapshot = engine.get_run(run_id).cloned();
+                    let (finished, progressed) = match &action {
+                        SopRunAction::Completed { .. } | SopRunAction::Failed { .. } => {
+                            (snapshot, None)
+                        }
+                        _ => (None, snapshot),
                     };
                     // Only audit step result when advance succeeded
-                    (Ok(action), Some(step_result_clone), finished)
+                    (Ok(action), Some(step_result_clone), finished, progressed)
                 }
-                Err(e) => (Err(e), None, None),
+                Err(e) => (Err(e), None, None, None),
             }
         };
 
@@ -147,6 +150,11 @@ impl Tool for SopAdvanceTool {
                     warn!("SOP audit log_run_complete failed: {e}");
                 }
             }
+            if let Some(ref run) = progressed_run {
+                if let Err(e) = audit.log_run_progress(run).await {
+                    warn!("SOP audit log_run_progress failed: {e}");
+                }
+            }
         }
 
         // Metrics collector (independent of audit)
@@ -86,6 +86,9 @@ impl Tool for SopApproveTool {
                 if let Err(e) = audit.log_approval(run, run.current_step).await {
                     warn!("SOP audit log after approve failed: {e}");
                 }
+                if let Err(e) = audit.log_run_progress(run).await {
+                    warn!("SOP audit log_run_progress after approve failed: {e}");
+                }
             }
         }
 
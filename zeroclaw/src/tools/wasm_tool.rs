@@ -0,0 +1,407 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::WasmRuntimeConfig;
+use crate::runtime::wasm::{WasmCapabilities, WasmRuntime};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Optional `<name>.json` sidecar describing a WASM tool to the LLM.
+#[derive(Debug, Default, Deserialize)]
+struct WasmToolManifest {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    parameters: Option<Value>,
+}
+
+/// Agent tool backed by a sandboxed WASM module in `runtime.wasm.tools_dir`.
+///
+/// Arguments are passed as JSON through the module's `alloc`/`call` exports
+/// (see `runtime::wasm` for the ABI). Each invocation gets a fresh instance
+/// with fuel and memory limits, and is abandoned after `timeout_secs`.
+pub struct WasmTool {
+    name: String,
+    description: String,
+    parameters: Value,
+    runtime: Arc<WasmRuntime>,
+    caps: WasmCapabilities,
+    workspace_dir: PathBuf,
+    timeout: Duration,
+    security: Arc<SecurityPolicy>,
+}
+
+impl WasmTool {
+    pub fn new(
+        name: String,
+        runtime: Arc<WasmRuntime>,
+        workspace_dir: PathBuf,
+        timeout: Duration,
+        security: Arc<SecurityPolicy>,
+    ) -> Self {
+        let manifest = load_manifest(&runtime.tools_dir(&workspace_dir), &name);
+        let description = manifest
+            .description
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or_else(|| format!("Sandboxed WASM tool '{name}'."));
+        let parameters = manifest
+            .parameters
+            .unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+        let caps = runtime.default_capabilities();
+        Self {
+            name,
+            description,
+            parameters,
+            runtime,
+            caps,
+            workspace_dir,
+            timeout,
+            security,
+        }
+    }
+}
+
+/// Discover WASM tool modules for the given workspace.
+///
+/// Returns no tools (with a warning) when the config is invalid, fuel
+/// metering is disabled, or the tools directory cannot be read.
+pub fn load_wasm_tools(
+    config: &WasmRuntimeConfig,
+    workspace_dir: &Path,
+    security: &Arc<SecurityPolicy>,
+) -> Vec<WasmTool> {
+    let runtime = Arc::new(WasmRuntime::with_workspace(
+        config.clone(),
+        workspace_dir.to_path_buf(),
+    ));
+    if let Err(e) = runtime.validate_config() {
+        tracing::warn!("Skipping WASM tools: {e}");
+        return Vec::new();
+    }
+    // Without fuel a timed-out module would spin its blocking thread forever.
+    if config.fuel_limit == 0 {
+        tracing::warn!("Skipping WASM tools: runtime.wasm.fuel_limit must be greater than 0");
+        return Vec::new();
+    }
+    let modules = match runtime.list_modules(workspace_dir) {
+        Ok(modules) => modules,
+        Err(e) => {
+            tracing::warn!("Skipping WASM tools: {e}");
+            return Vec::new();
+        }
+    };
+
+    let timeout = Duration::from_secs(config.timeout_secs.max(1));
+    modules
+        .into_iter()
+        .map(|name| {
+            WasmTool::new(
+                name,
+                runtime.clone(),
+                workspace_dir.to_path_buf(),
+                timeout,
+                security.clone(),
+            )
+        })
+        .collect()
+}
+
+fn load_manifest(tools_dir: &Path, name: &str) -> WasmToolManifest {
+    let path = tools_dir.join(format!("{name}.json"));
+    let Ok(raw) = std::fs::read_to_string(&path) else {
+        return WasmToolManifest::default();
+    };
+    serde_json::from_str(&raw).unwrap_or_else(|e| {
+        tracing::warn!(
+            "Ignoring invalid WASM tool manifest {}: {e}",
+            path.display()
+        );
+        WasmToolManifest::default()
+    })
+}
+
+/// Map the module's JSON output onto a `ToolResult`.
+fn parse_tool_output(raw: &str) -> ToolResult {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Object(map)) if map.contains_key("success") || map.contains_key("output") => {
+            let output = match map.get("output") {
+                Some(Value::String(text)) => text.clone(),
+                Some(Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            };
+            ToolResult {
+                success: map.get("success").and_then(Value::as_bool).unwrap_or(true),
+                output,
+                error: map.get("error").and_then(Value::as_str).map(str::to_string),
+            }
+        }
+        Ok(other) => ToolResult {
+            success: true,
+            output: other.to_string(),
+            error: None,
+        },
+        Err(e) => ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("WASM tool returned invalid JSON: {e}")),
+        },
+    }
+}
+
+#[async_trait]
+impl Tool for WasmTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.parameters.clone()
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let operation = if self.caps.write_workspace {
+            ToolOperation::Act
+        } else {
+            ToolOperation::Read
+        };
+        if let Err(error) = self.security.enforce_tool_operation(operation, &self.name) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        let input = serde_json::to_string(&args)?;
+        let runtime = self.runtime.clone();
+        let name = self.name.clone();
+        let workspace_dir = self.workspace_dir.clone();
+        let caps = self.caps.clone();
+        // Fuel (always nonzero for loaded tools) bounds how long an
+        // abandoned invocation keeps its thread.
+        let task = tokio::task::spawn_blocking(move || {
+            runtime.call_tool(&name, &workspace_dir, &caps, &input)
+        });
+
+        let result = match tokio::time::timeout(self.timeout, task).await {
+            Ok(joined) => joined.map_err(|e| anyhow::anyhow!("WASM tool task failed: {e}"))?,
+            Err(_) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "WASM tool '{}' timed out after {}s",
+                        self.name,
+                        self.timeout.as_secs()
+                    )),
+                });
+            }
+        };
+
+        match result {
+            Ok(execution) if execution.exit_code != 0 => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(execution.stderr),
+            }),
+            Ok(execution) => Ok(parse_tool_output(&execution.stdout)),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    /// Echo tool: returns `{"output": <input json>}` built in guest memory.
+    const ECHO_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (func (export "alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (data (i32.const 0) "{\22output\22:")
+          (func (export "call") (param $ptr i32) (param $len i32) (result i64)
+            (memory.copy (i32.const 10) (local.get $ptr) (local.get $len))
+            (i32.store8 (i32.add (i32.const 10) (local.get $len)) (i32.const 125))
+            (i64.or
+              (i64.shl (i64.const 0) (i64.const 32))
+              (i64.extend_i32_u (i32.add (local.get $len) (i32.const 11))))))
+    "#;
+
+    /// Spins forever; only fuel or the timeout stops it.
+    const LOOP_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "call") (param i32 i32) (result i64)
+            (loop $l (br $l))
+            (i64.const 0)))
+    "#;
+
+    fn install(workspace: &Path, name: &str, wat: &str) {
+        let dir = workspace.join("tools/wasm");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(format!("{name}.wasm")),
+            wat::parse_str(wat).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn security(workspace: &Path, autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn parse_tool_output_maps_result_object() {
+        let result = parse_tool_output(r#"{"success": false, "error": "bad input"}"#);
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("bad input"));
+
+        let result = parse_tool_output(r#"{"output": {"sum": 3}}"#);
+        assert!(result.success);
+        assert_eq!(result.output, r#"{"sum":3}"#);
+    }
+
+    #[test]
+    fn parse_tool_output_passes_plain_json_through() {
+        let result = parse_tool_output("[1,2,3]");
+        assert!(result.success);
+        assert_eq!(result.output, "[1,2,3]");
+
+        let result = parse_tool_output("not json");
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("invalid JSON"));
+    }
+
+    #[test]
+    fn load_wasm_tools_reads_sidecar_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        install(tmp.path(), "echo", ECHO_WAT);
+        std::fs::write(
+            tmp.path().join("tools/wasm/echo.json"),
+            r#"{"description": "Echo arguments", "parameters": {"type": "object", "properties": {"text": {"type": "string"}}}}"#,
+        )
+        .unwrap();
+
+        let tools = load_wasm_tools(
+            &WasmRuntimeConfig::default(),
+            tmp.path(),
+            &security(tmp.path(), AutonomyLevel::Supervised),
+        );
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "echo");
+        assert_eq!(tools[0].description(), "Echo arguments");
+        assert!(tools[0].parameters_schema()["properties"]["text"].is_object());
+    }
+
+    #[tokio::test]
+    async fn wasm_tool_round_trips_json_arguments() {
+        let tmp = tempfile::tempdir().unwrap();
+        install(tmp.path(), "echo", ECHO_WAT);
+        let tools = load_wasm_tools(
+            &WasmRuntimeConfig::default(),
+            tmp.path(),
+            &security(tmp.path(), AutonomyLevel::Supervised),
+        );
+
+        let result = tools[0].execute(json!({"text": "hi"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, r#"{"text":"hi"}"#);
+    }
+
+    #[tokio::test]
+    async fn wasm_tool_reports_fuel_exhaustion() {
+        let tmp = tempfile::tempdir().unwrap();
+        install(tmp.path(), "spin", LOOP_WAT);
+        let tools = load_wasm_tools(
+            &WasmRuntimeConfig::default(),
+            tmp.path(),
+            &security(tmp.path(), AutonomyLevel::Supervised),
+        );
+
+        let result = tools[0].execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("fuel limit"));
+    }
+
+    #[tokio::test]
+    async fn wasm_tool_times_out() {
+        let tmp = tempfile::tempdir().unwrap();
+        install(tmp.path(), "spin", LOOP_WAT);
+        // Enough fuel to outlast the timeout without leaving a thread spinning forever
+        let config = WasmRuntimeConfig {
+            fuel_limit: 300_000_000,
+            ..WasmRuntimeConfig::default()
+        };
+        let runtime = Arc::new(WasmRuntime::new(config));
+        let tool = WasmTool::new(
+            "spin".into(),
+            runtime,
+            tmp.path().to_path_buf(),
+            Duration::from_millis(100),
+            security(tmp.path(), AutonomyLevel::Supervised),
+        );
+
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("timed out"));
+    }
+
+    #[test]
+    fn load_wasm_tools_refuses_unmetered_modules() {
+        let tmp = tempfile::tempdir().unwrap();
+        install(tmp.path(), "echo", ECHO_WAT);
+        let config = WasmRuntimeConfig {
+            fuel_limit: 0,
+            ..WasmRuntimeConfig::default()
+        };
+        let tools = load_wasm_tools(
+            &config,
+            tmp.path(),
+            &security(tmp.path(), AutonomyLevel::Supervised),
+        );
+        assert!(tools.is_empty());
+    }
+
+    #[tokio::test]
+    async fn wasm_tool_with_write_capability_blocked_in_read_only_mode() {
+        let tmp = tempfile::tempdir().unwrap();
+        install(tmp.path(), "echo", ECHO_WAT);
+        let config = WasmRuntimeConfig {
+            allow_workspace_write: true,
+            ..WasmRuntimeConfig::default()
+        };
+        let tools = load_wasm_tools(
+            &config,
+            tmp.path(),
+            &security(tmp.path(), AutonomyLevel::ReadOnly),
+        );
+
+        let result = tools[0].execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
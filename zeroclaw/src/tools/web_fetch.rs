@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{EstopGuard, SecurityPolicy};
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use serde_json::json;
@@ -20,6 +20,7 @@ pub struct WebFetchTool {
     blocked_domains: Vec<String>,
     max_response_size: usize,
     timeout_secs: u64,
+    estop: Option<Arc<EstopGuard>>,
 }
 
 impl WebFetchTool {
@@ -36,16 +37,25 @@ impl WebFetchTool {
             blocked_domains: normalize_allowed_domains(blocked_domains),
             max_response_size,
             timeout_secs,
+            estop: None,
         }
     }
 
+    /// Refuse requests to hosts blocked by an engaged estop.
+    pub fn with_estop(mut self, estop: Option<Arc<EstopGuard>>) -> Self {
+        self.estop = estop;
+        self
+    }
+
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
-        validate_target_url(
+        let url = validate_target_url(
             raw_url,
             &self.allowed_domains,
             &self.blocked_domains,
             "web_fetch",
-        )
+        )?;
+        check_estop(self.estop.as_deref(), &url)?;
+        Ok(url)
     }
 
     fn truncate_response(&self, text: &str) -> String {
@@ -150,6 +160,7 @@ impl Tool for WebFetchTool {
 
         let allowed_domains = self.allowed_domains.clone();
         let blocked_domains = self.blocked_domains.clone();
+        let estop = self.estop.clone();
         let redirect_policy = reqwest::redirect::Policy::custom(move |attempt| {
             if attempt.previous().len() >= 10 {
                 return attempt.error(std::io::Error::other("Too many redirects (max 10)"));
@@ -160,7 +171,9 @@ impl Tool for WebFetchTool {
                 &allowed_domains,
                 &blocked_domains,
                 "web_fetch",
-            ) {
+            )
+            .and_then(|url| check_estop(estop.as_deref(), &url))
+            {
                 return attempt.error(std::io::Error::new(
                     std::io::ErrorKind::PermissionDenied,
                     format!("Blocked redirect target: {err}"),
@@ -366,6 +379,13 @@ fn normalize_domain(raw: &str) -> Option<String> {
     Some(d)
 }
 
+fn check_estop(estop: Option<&EstopGuard>, url: &str) -> anyhow::Result<()> {
+    if let Some(estop) = estop {
+        estop.check_domain("tool:web_fetch", &extract_host(url)?)?;
+    }
+    Ok(())
+}
+
 fn extract_host(url: &str) -> anyhow::Result<String> {
     let rest = url
         .strip_prefix("http://")